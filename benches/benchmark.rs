@@ -65,6 +65,45 @@ fn phaser_bench(_dummy: usize) -> Wave32 {
     )
 }
 
+fn pink_bench(_dummy: usize) -> Wave32 {
+    Wave32::render(44100.0, 1.0, &mut (noise() >> pinkpass()))
+}
+
+fn pink_block_bench(_dummy: usize) -> [f32; 64] {
+    // Filter one second of a 64-sample block through the pinking filter.
+    let mut filter = pinkpass();
+    let input = [0.5; 64];
+    let mut output = [0.0; 64];
+    for _ in 0..44100 / 64 {
+        filter.process(64, &[&input[..]], &mut [&mut output[..]]);
+    }
+    output
+}
+
+fn biquad_tick_bench(_dummy: usize) -> [f32; 64] {
+    // Filter one second of 64-sample blocks through a biquad one sample at a time.
+    let mut filter = biquad(-1.8, 0.81, 0.01, 0.02, 0.01);
+    let input = [0.5; 64];
+    let mut output = [0.0; 64];
+    for _ in 0..44100 / 64 {
+        for (x, y) in input.iter().zip(output.iter_mut()) {
+            *y = filter.filter_mono(*x);
+        }
+    }
+    output
+}
+
+fn biquad_block_bench(_dummy: usize) -> [f32; 64] {
+    // Filter one second of 64-sample blocks through a biquad using block processing.
+    let mut filter = biquad(-1.8, 0.81, 0.01, 0.02, 0.01);
+    let input = [0.5; 64];
+    let mut output = [0.0; 64];
+    for _ in 0..44100 / 64 {
+        filter.process(64, &[&input[..]], &mut [&mut output[..]]);
+    }
+    output
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("pass", |b| b.iter(|| pass_bench(black_box(0))));
     c.bench_function("wavetable", |b| b.iter(|| wavetable_bench(black_box(0))));
@@ -75,6 +114,14 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("reverb", |b| b.iter(|| reverb_bench(black_box(0))));
     c.bench_function("limiter", |b| b.iter(|| limiter_bench(black_box(0))));
     c.bench_function("phaser", |b| b.iter(|| phaser_bench(black_box(0))));
+    c.bench_function("pink", |b| b.iter(|| pink_bench(black_box(0))));
+    c.bench_function("pink_block", |b| b.iter(|| pink_block_bench(black_box(0))));
+    c.bench_function("biquad_tick", |b| {
+        b.iter(|| biquad_tick_bench(black_box(0)))
+    });
+    c.bench_function("biquad_block", |b| {
+        b.iter(|| biquad_block_bench(black_box(0)))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -31,6 +31,12 @@ enum Filter {
     Peak,
 }
 
+#[derive(Debug, PartialEq)]
+enum Temperament {
+    Equal,
+    Just,
+}
+
 #[allow(dead_code)]
 struct State {
     /// Random number generator.
@@ -45,6 +51,8 @@ struct State {
     waveform: Waveform,
     /// Selected filter.
     filter: Filter,
+    /// Selected tuning.
+    temperament: Temperament,
     /// Chorus amount.
     chorus_amount: Shared<f64>,
     /// Reverb amount.
@@ -172,6 +180,7 @@ where
         net,
         waveform: Waveform::Saw,
         filter: Filter::None,
+        temperament: Temperament::Equal,
         chorus_amount,
         reverb_amount,
         room_size,
@@ -247,6 +256,14 @@ impl eframe::App for State {
             ui.separator();
             ui.end_row();
 
+            ui.label("Tuning");
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.temperament, Temperament::Equal, "Equal");
+                ui.selectable_value(&mut self.temperament, Temperament::Just, "Just");
+            });
+            ui.separator();
+            ui.end_row();
+
             ui.label("Chorus Amount");
             let mut chorus = self.chorus_amount.value() * 100.0;
             ui.add(egui::Slider::new(&mut chorus, 0.0..=100.0).suffix("%"));
@@ -324,7 +341,11 @@ impl eframe::App for State {
                     }
                 }
                 if ctx.input(|c| c.key_down(KEYS[i])) && self.id[i].is_none() {
-                    let pitch = midi_hz(40.0 + i as f64);
+                    let tuning = match self.temperament {
+                        Temperament::Equal => Tuning::equal_temperament(),
+                        Temperament::Just => Tuning::just_intonation(),
+                    };
+                    let pitch = tuning.midi_hz(40.0 + i as f64);
                     let waveform = match self.waveform {
                         Waveform::Sine => Net64::wrap(Box::new(sine_hz(pitch) * 0.1)),
                         Waveform::Saw => Net64::wrap(Box::new(saw_hz(pitch) * 0.5)),
@@ -0,0 +1,475 @@
+//! Arpeggiator and tuning.
+
+use super::audionode::*;
+use super::math::*;
+use super::signal::*;
+use super::*;
+use numeric_array::typenum::*;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Maximum number of notes an arpeggiator can hold.
+pub const ARP_MAX_NOTES: usize = 16;
+
+/// Order in which an arpeggiator cycles through held notes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ArpPattern {
+    /// Lowest to highest note.
+    #[default]
+    Up,
+    /// Highest to lowest note.
+    Down,
+    /// Lowest to highest and back, without repeating the end notes.
+    UpDown,
+    /// Pseudorandom held note on each step.
+    Random,
+}
+
+/// Held notes shared between frontend and backend.
+struct ArpState {
+    /// MIDI note numbers stored as bits.
+    notes: [AtomicU64; ARP_MAX_NOTES],
+    /// Number of held notes.
+    count: AtomicUsize,
+}
+
+impl Default for ArpState {
+    fn default() -> Self {
+        Self {
+            notes: std::array::from_fn(|_| AtomicU64::new(0)),
+            count: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// Frontend for an arpeggiator. Holds and releases notes.
+/// Notes are MIDI note numbers; fractional values are allowed.
+/// Updates should be made from one thread at a time.
+#[derive(Clone)]
+pub struct ArpNotes {
+    state: Arc<ArpState>,
+}
+
+impl ArpNotes {
+    /// Replace held notes with `notes`. At most `ARP_MAX_NOTES` notes can be held.
+    pub fn set_notes(&self, notes: &[f64]) {
+        assert!(notes.len() <= ARP_MAX_NOTES);
+        // Hide notes from the backend while they are being written.
+        self.state.count.store(0, Ordering::Release);
+        for (slot, note) in self.state.notes.iter().zip(notes.iter()) {
+            slot.store(note.to_bits(), Ordering::Relaxed);
+        }
+        self.state.count.store(notes.len(), Ordering::Release);
+    }
+
+    /// Hold `note`. Does nothing if the note is already held or all slots are in use.
+    pub fn hold(&self, note: f64) {
+        let count = self.state.count.load(Ordering::Acquire);
+        if count < ARP_MAX_NOTES && !self.notes().contains(&note) {
+            self.state.notes[count].store(note.to_bits(), Ordering::Relaxed);
+            self.state.count.store(count + 1, Ordering::Release);
+        }
+    }
+
+    /// Release `note`. Does nothing if the note is not held.
+    pub fn release(&self, note: f64) {
+        let mut notes = self.notes();
+        if let Some(i) = notes.iter().position(|x| *x == note) {
+            notes.remove(i);
+            self.set_notes(&notes);
+        }
+    }
+
+    /// Release all notes.
+    pub fn clear(&self) {
+        self.state.count.store(0, Ordering::Release);
+    }
+
+    /// Currently held notes in the order they were added.
+    pub fn notes(&self) -> Vec<f64> {
+        let count = self.state.count.load(Ordering::Acquire);
+        self.state.notes[..count]
+            .iter()
+            .map(|x| f64::from_bits(x.load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+/// Arpeggiator. Cycles through notes held in an `ArpNotes` frontend
+/// at a fixed rate, outputting the frequency of the current note in Hz.
+/// Held notes are read at the start of each step.
+/// - Output 0: frequency in Hz, or zero if no notes are held.
+#[derive(Clone)]
+pub struct Arpeggiator<T: Real> {
+    pattern: ArpPattern,
+    /// Steps per second.
+    rate: f64,
+    sample_rate: f64,
+    /// Samples remaining in the current step.
+    remaining: f64,
+    /// Step number since notes were last held.
+    step: u64,
+    /// Current output frequency.
+    value: T,
+    hash: u64,
+    state: Arc<ArpState>,
+}
+
+impl<T: Real> Arpeggiator<T> {
+    /// Create new arpeggiator with `pattern`, advancing `rate` steps per second.
+    /// The rate must be positive.
+    /// Returns (frontend, backend).
+    pub fn new(sample_rate: f64, pattern: ArpPattern, rate: f64) -> (ArpNotes, Self) {
+        assert!(rate > 0.0);
+        let state = Arc::new(ArpState::default());
+        let mut node = Self {
+            pattern,
+            rate,
+            sample_rate,
+            remaining: 0.0,
+            step: 0,
+            value: T::zero(),
+            hash: 0,
+            state: Arc::clone(&state),
+        };
+        node.reset();
+        (ArpNotes { state }, node)
+    }
+
+    /// Arpeggiation pattern.
+    pub fn pattern(&self) -> ArpPattern {
+        self.pattern
+    }
+
+    /// Set arpeggiation pattern.
+    pub fn set_pattern(&mut self, pattern: ArpPattern) {
+        self.pattern = pattern;
+    }
+
+    /// Rate in steps per second.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Set rate in steps per second. Takes effect from the next step.
+    pub fn set_rate(&mut self, rate: f64) {
+        assert!(rate > 0.0);
+        self.rate = rate;
+    }
+
+    /// Begin a new step.
+    fn advance(&mut self) {
+        let count = self.state.count.load(Ordering::Acquire);
+        if count == 0 {
+            // Start from the beginning of the pattern when notes are held again.
+            self.step = 0;
+            self.value = T::zero();
+            return;
+        }
+        let mut notes = [0.0; ARP_MAX_NOTES];
+        for (note, slot) in notes.iter_mut().zip(self.state.notes[..count].iter()) {
+            *note = f64::from_bits(slot.load(Ordering::Relaxed));
+        }
+        let notes = &mut notes[..count];
+        notes.sort_unstable_by(|a, b| a.total_cmp(b));
+        let step = self.step as usize;
+        let index = match self.pattern {
+            ArpPattern::Up => step % count,
+            ArpPattern::Down => count - 1 - step % count,
+            ArpPattern::UpDown => {
+                if count == 1 {
+                    0
+                } else {
+                    let period = 2 * count - 2;
+                    let i = step % period;
+                    if i < count {
+                        i
+                    } else {
+                        period - i
+                    }
+                }
+            }
+            ArpPattern::Random => {
+                let x = rnd(self.hash.wrapping_add(self.step) as i64);
+                min((x * count as f64) as usize, count - 1)
+            }
+        };
+        self.value = T::from_f64(midi_hz(notes[index]));
+        self.step += 1;
+    }
+}
+
+impl<T: Real> AudioNode for Arpeggiator<T> {
+    const ID: u64 = 100;
+    type Sample = T;
+    type Inputs = U0;
+    type Outputs = U1;
+    type Setting = T;
+
+    fn set(&mut self, setting: Self::Setting) {
+        self.set_rate(setting.to_f64());
+    }
+
+    fn reset(&mut self) {
+        self.remaining = 0.0;
+        self.step = 0;
+        self.value = T::zero();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.remaining *= sample_rate / self.sample_rate;
+        self.sample_rate = sample_rate;
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        _input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        if self.remaining <= 0.0 {
+            self.advance();
+            self.remaining += self.sample_rate / self.rate;
+        }
+        self.remaining -= 1.0;
+        [self.value].into()
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+    }
+
+    fn heap_footprint(&self) -> usize {
+        // The held notes are shared with `ArpNotes`, but the arpeggiator allocates them.
+        std::mem::size_of::<ArpState>()
+    }
+}
+
+/// Scale degree ratios of 5-limit just intonation.
+const JUST_RATIOS: [f64; 12] = [
+    1.0,
+    16.0 / 15.0,
+    9.0 / 8.0,
+    6.0 / 5.0,
+    5.0 / 4.0,
+    4.0 / 3.0,
+    45.0 / 32.0,
+    3.0 / 2.0,
+    8.0 / 5.0,
+    5.0 / 3.0,
+    9.0 / 5.0,
+    15.0 / 8.0,
+];
+
+/// Tuning table. Maps MIDI note numbers to frequencies through a table
+/// of frequency ratios that repeats every period (usually an octave).
+/// Each table entry is one note; the table may have any number of notes.
+/// Fractional note numbers are interpolated exponentially between table entries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tuning {
+    /// Ratios of scale degrees to the root, starting from 1 and ascending below the period.
+    ratios: Vec<f64>,
+    /// Ratio of one period, 2 for an octave.
+    period: f64,
+    /// MIDI note number of the root.
+    root: f64,
+    /// Frequency of the root in Hz.
+    reference: f64,
+}
+
+impl Tuning {
+    /// Create new tuning from a table of `ratios` to the root. The first ratio must be 1
+    /// and ratios must ascend below `period`, which is 2 for an octave.
+    /// MIDI note `root` sounds at `reference` Hz.
+    pub fn new(ratios: &[f64], period: f64, root: f64, reference: f64) -> Self {
+        assert!(!ratios.is_empty() && ratios[0] == 1.0);
+        assert!(ratios.windows(2).all(|x| x[0] < x[1]));
+        assert!(ratios[ratios.len() - 1] < period);
+        assert!(reference > 0.0);
+        Self {
+            ratios: ratios.to_vec(),
+            period,
+            root,
+            reference,
+        }
+    }
+
+    /// 12 tone equal temperament with A4 at 440 Hz. Agrees with `midi_hz`.
+    pub fn equal_temperament() -> Self {
+        let ratios: Vec<f64> = (0..12).map(|i| exp2(i as f64 / 12.0)).collect();
+        Self::new(&ratios, 2.0, 69.0, 440.0)
+    }
+
+    /// 5-limit just intonation rooted at C4, which is tuned to its equal tempered frequency.
+    pub fn just_intonation() -> Self {
+        Self::new(&JUST_RATIOS, 2.0, 60.0, midi_hz(60.0))
+    }
+
+    /// Ratios of scale degrees to the root.
+    pub fn ratios(&self) -> &[f64] {
+        &self.ratios
+    }
+
+    /// Ratio of one period.
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+
+    /// MIDI note number of the root.
+    pub fn root(&self) -> f64 {
+        self.root
+    }
+
+    /// Frequency of the root in Hz.
+    pub fn reference(&self) -> f64 {
+        self.reference
+    }
+
+    /// Set root note and its frequency in Hz. For example,
+    /// `set_reference(69.0, 415.0)` tunes A4 to 415 Hz.
+    pub fn set_reference(&mut self, root: f64, reference: f64) {
+        assert!(reference > 0.0);
+        self.root = root;
+        self.reference = reference;
+    }
+
+    /// Ratio of the table entry following degree `i`.
+    #[inline]
+    fn next_ratio(&self, i: usize) -> f64 {
+        if i + 1 < self.ratios.len() {
+            self.ratios[i + 1]
+        } else {
+            self.period
+        }
+    }
+
+    /// Frequency in Hz of MIDI note `note`.
+    pub fn midi_hz(&self, note: f64) -> f64 {
+        let n = self.ratios.len() as f64;
+        let x = note - self.root;
+        let period = floor(x / n);
+        let degree = x - period * n;
+        let i = min(degree as usize, self.ratios.len() - 1);
+        let t = degree - i as f64;
+        let ratio = self.ratios[i] * pow(self.next_ratio(i) / self.ratios[i], t);
+        self.reference * pow(self.period, period) * ratio
+    }
+
+    /// MIDI note number of `frequency` in Hz. Inverse of `midi_hz`.
+    pub fn hz_midi(&self, frequency: f64) -> f64 {
+        assert!(frequency > 0.0);
+        let x = frequency / self.reference;
+        let period = floor(log(x) / log(self.period));
+        let ratio = x / pow(self.period, period);
+        let i = self
+            .ratios
+            .partition_point(|r| *r <= ratio)
+            .saturating_sub(1);
+        let t = log(ratio / self.ratios[i]) / log(self.next_ratio(i) / self.ratios[i]);
+        self.root + period * self.ratios.len() as f64 + i as f64 + t
+    }
+}
+
+/// Pitch to frequency converter. Converts pitch in MIDI semitones
+/// to frequency in equal temperament relative to a reference A4 (MIDI note 69).
+/// Optionally quantizes pitch to the nearest semitone and stretches octaves.
+/// - Input 0: pitch in semitones
+/// - Output 0: frequency in Hz
+#[derive(Clone)]
+pub struct Tune<T: Real> {
+    /// Frequency of A4 in Hz.
+    reference: T,
+    /// Whether to round pitch to the nearest semitone.
+    quantize: bool,
+    /// Octave size in semitones. Equals 12 without stretch.
+    octave: T,
+}
+
+impl<T: Real> Tune<T> {
+    /// Create new converter with A4 at `reference` Hz.
+    pub fn new(reference: T) -> Self {
+        assert!(reference > T::zero());
+        Self {
+            reference,
+            quantize: false,
+            octave: T::new(12),
+        }
+    }
+
+    /// Frequency of A4 in Hz.
+    pub fn reference(&self) -> T {
+        self.reference
+    }
+
+    /// Set frequency of A4 in Hz.
+    pub fn set_reference(&mut self, reference: T) {
+        assert!(reference > T::zero());
+        self.reference = reference;
+    }
+
+    /// Whether pitch is quantized to the nearest semitone.
+    pub fn quantize(&self) -> bool {
+        self.quantize
+    }
+
+    /// Enable or disable quantization of pitch to the nearest semitone.
+    pub fn set_quantize(&mut self, quantize: bool) {
+        self.quantize = quantize;
+    }
+
+    /// Octave stretch in cents.
+    pub fn stretch(&self) -> T {
+        (T::new(12) - self.octave) / self.octave * T::new(1200)
+    }
+
+    /// Set octave stretch in cents. Each octave spans 1200 + `cents` cents.
+    /// Positive values widen octaves, as in stretch tuned pianos.
+    /// The reference A4 is unaffected.
+    pub fn set_stretch(&mut self, cents: T) {
+        assert!(cents > T::new(-1200));
+        self.octave = T::new(12) * T::new(1200) / (T::new(1200) + cents);
+    }
+
+    /// Frequency in Hz of `pitch` in semitones.
+    #[inline]
+    pub fn frequency(&self, pitch: T) -> T {
+        let pitch = if self.quantize { round(pitch) } else { pitch };
+        self.reference * exp2((pitch - T::new(69)) / self.octave)
+    }
+}
+
+impl<T: Real> AudioNode for Tune<T> {
+    const ID: u64 = 106;
+    type Sample = T;
+    type Inputs = U1;
+    type Outputs = U1;
+    type Setting = T;
+
+    fn set(&mut self, setting: Self::Setting) {
+        self.set_reference(setting);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        [self.frequency(input[0])].into()
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        for (o, i) in output[0][..size].iter_mut().zip(input[0][..size].iter()) {
+            *o = self.frequency(*i);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = input[0].distort(0.0);
+        output
+    }
+}
@@ -17,6 +17,108 @@ impl<T, A: numeric_array::ArrayLength<T> + Sync + Send + Clone> Size<T> for A {}
 /// Frames are used to transport audio data between `AudioNode` instances.
 pub type Frame<T, Size> = numeric_array::NumericArray<T, Size>;
 
+/// Attempt to convert a slice, such as the contents of a `Vec`, into a frame.
+/// Returns `None` if the length of the slice does not match the size of the frame.
+/// Arrays convert into frames with `into()` and tuples with `ConstantFrame::convert`.
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// let v = vec![1.0, 2.0];
+/// let frame: Frame<f64, U2> = try_frame(&v).unwrap();
+/// assert_eq!(frame.to_vec(), v);
+/// assert!(try_frame::<f64, U3>(&v).is_none());
+/// ```
+pub fn try_frame<T: Clone, N: Size<T>>(slice: &[T]) -> Option<Frame<T, N>> {
+    if slice.len() == N::USIZE {
+        Some(Frame::from(generic_array::GenericArray::clone_from_slice(
+            slice,
+        )))
+    } else {
+        None
+    }
+}
+
+/// Version of the state snapshot format written by `save_state`.
+const STATE_VERSION: u8 = 1;
+
+/// Errors from restoring a state snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateError {
+    /// The snapshot does not have the size expected by the node.
+    Size,
+    /// The snapshot was saved in an unsupported format version.
+    Version,
+    /// The node does not support state snapshots.
+    Unsupported,
+}
+
+impl std::fmt::Display for StateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StateError::Size => write!(f, "State snapshot has the wrong size"),
+            StateError::Version => write!(f, "State snapshot has an unsupported version"),
+            StateError::Unsupported => write!(f, "Node does not support state snapshots"),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+/// Save state `values` into a snapshot. Helper for implementing `save_state`.
+pub fn save_state_values(values: &[f64]) -> Vec<u8> {
+    let mut state = Vec::with_capacity(1 + values.len() * 8);
+    state.push(STATE_VERSION);
+    for value in values {
+        state.extend_from_slice(&value.to_le_bytes());
+    }
+    state
+}
+
+/// Restore state `values` from a snapshot made with `save_state_values`.
+/// Helper for implementing `restore_state`. The values are not modified on error.
+pub fn restore_state_values(state: &[u8], values: &mut [f64]) -> Result<(), StateError> {
+    match state.first() {
+        None => return Err(StateError::Size),
+        Some(&STATE_VERSION) => (),
+        Some(_) => return Err(StateError::Version),
+    }
+    if state.len() != 1 + values.len() * 8 {
+        return Err(StateError::Size);
+    }
+    for (value, bytes) in values.iter_mut().zip(state[1..].chunks_exact(8)) {
+        *value = f64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    Ok(())
+}
+
+/// Combine snapshots of two contained nodes into one.
+fn save_state_pair(x: Vec<u8>, y: Vec<u8>) -> Vec<u8> {
+    let mut state = Vec::with_capacity(5 + x.len() + y.len());
+    state.push(STATE_VERSION);
+    state.extend_from_slice(&(x.len() as u32).to_le_bytes());
+    state.extend_from_slice(&x);
+    state.extend_from_slice(&y);
+    state
+}
+
+/// Split a snapshot made with `save_state_pair`.
+fn split_state_pair(state: &[u8]) -> Result<(&[u8], &[u8]), StateError> {
+    match state.first() {
+        None => return Err(StateError::Size),
+        Some(&STATE_VERSION) => (),
+        Some(_) => return Err(StateError::Version),
+    }
+    if state.len() < 5 {
+        return Err(StateError::Size);
+    }
+    let length = u32::from_le_bytes(state[1..5].try_into().unwrap()) as usize;
+    if state.len() < 5 + length {
+        return Err(StateError::Size);
+    }
+    Ok((&state[5..5 + length], &state[5 + length..]))
+}
+
 /*
 Order of type arguments in nodes:
 1. Basic input and output arities excepting filter input selector arities.
@@ -174,6 +276,53 @@ pub trait AudioNode: Clone + Sync + Send {
         // The default implementation does nothing.
     }
 
+    /// Whether all memory needed for processing has been preallocated,
+    /// so that processing will not allocate.
+    fn is_allocated(&self) -> bool {
+        // The default implementation has nothing to allocate.
+        true
+    }
+
+    /// Size in bytes of heap allocations owned by this node and its children,
+    /// such as delay lines and block processing buffers.
+    /// Nodes that allocate should override this.
+    fn heap_footprint(&self) -> usize {
+        // The default implementation reports no allocations.
+        0
+    }
+
+    /// Whether this node and its children support `save_state` and `restore_state`.
+    /// Stateless nodes should override this to return true.
+    /// Nodes with processing state should override all three methods.
+    fn supports_state(&self) -> bool {
+        false
+    }
+
+    /// Save a snapshot of the processing state, such as sample history,
+    /// which can be restored later with `restore_state`. Settings are not included.
+    /// Returns `StateError::Unsupported` if the node does not support snapshots.
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        // The default implementation has no state to save.
+        if self.supports_state() {
+            Ok(Vec::new())
+        } else {
+            Err(StateError::Unsupported)
+        }
+    }
+
+    /// Restore processing state from a snapshot made with `save_state`
+    /// by a node of the same type. On error, the state is unchanged.
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        // The default implementation accepts only an empty snapshot.
+        if !self.supports_state() {
+            Err(StateError::Unsupported)
+        } else if state.is_empty() {
+            Ok(())
+        } else {
+            Err(StateError::Size)
+        }
+    }
+
     // End of interface. There is no need to override the following.
 
     /// Number of inputs.
@@ -273,6 +422,30 @@ pub trait AudioNode: Clone + Sync + Send {
         result
     }
 
+    /// Group delay of `output` in seconds at `frequency` Hz.
+    /// The delay is measured numerically from the slope of the phase response.
+    /// Return `None` if there is no response or it could not be calculated.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// assert!(pass().group_delay(0, 440.0).unwrap().abs() < 1.0e-9);
+    /// let delay = tick().group_delay(0, 440.0).unwrap();
+    /// assert!((delay * DEFAULT_SR - 1.0).abs() < 1.0e-6);
+    /// ```
+    fn group_delay(&mut self, output: usize, frequency: f64) -> Option<f64> {
+        assert!(output < self.outputs());
+        // Central difference with a small frequency step in Hz.
+        let delta = 0.01;
+        let r0 = self.response(output, frequency - delta)?;
+        let r1 = self.response(output, frequency + delta)?;
+        if r0.norm() == 0.0 || r1.norm() == 0.0 {
+            return None;
+        }
+        // The phase difference of the ratio is unwrapped as long as it stays below pi.
+        Some(-(r1 / r0).arg() / (TAU * 2.0 * delta))
+    }
+
     /// Retrieve the next mono sample from a generator.
     /// The node must have no inputs and 1 or 2 outputs.
     /// If there are two outputs, average the channels.
@@ -365,6 +538,10 @@ impl<N: Size<T>, T: Float> AudioNode for MultiPass<N, T> {
     type Outputs = N;
     type Setting = ();
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn tick(
         &mut self,
@@ -408,6 +585,10 @@ impl<T: Float> AudioNode for Pass<T> {
     type Outputs = U1;
     type Setting = ();
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn tick(
         &mut self,
@@ -447,6 +628,10 @@ impl<N: Size<T>, T: Float> AudioNode for Sink<N, T> {
     type Outputs = U0;
     type Setting = ();
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn tick(
         &mut self,
@@ -498,6 +683,10 @@ impl<N: Size<T>, T: Float> AudioNode for Constant<N, T> {
     type Outputs = N;
     type Setting = Frame<T, N>;
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn set(&mut self, setting: Self::Setting) {
         self.output = setting;
@@ -563,6 +752,10 @@ where
     type Outputs = N;
     type Setting = ();
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn tick(
         &mut self,
@@ -619,6 +812,10 @@ where
     type Outputs = numeric_array::typenum::Prod<M, N>;
     type Setting = ();
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn tick(
         &mut self,
@@ -671,6 +868,10 @@ where
     type Outputs = U1;
     type Setting = ();
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn tick(
         &mut self,
@@ -706,6 +907,86 @@ where
     }
 }
 
+/// Sum `N` channels into one with power-preserving scaling `1 / sqrt(N)`.
+/// Uncorrelated inputs of equal level keep their level in the sum,
+/// while fully correlated inputs gain `sqrt(N)`.
+#[derive(Clone)]
+pub struct JoinPower<N, T> {
+    _marker: PhantomData<(N, T)>,
+}
+
+impl<N, T> JoinPower<N, T>
+where
+    N: Size<T>,
+    T: Float,
+{
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData::default(),
+        }
+    }
+
+    /// Gain applied to each input.
+    #[inline]
+    pub fn gain() -> f64 {
+        1.0 / (N::USIZE as f64).sqrt()
+    }
+}
+
+impl<N, T> AudioNode for JoinPower<N, T>
+where
+    N: Size<T>,
+    T: Float,
+{
+    const ID: u64 = 92;
+    type Sample = T;
+    type Inputs = N;
+    type Outputs = U1;
+    type Setting = ();
+
+    fn supports_state(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let mut output = input[0];
+        for i in 1..N::USIZE {
+            output += input[i];
+        }
+        [output * T::from_f64(Self::gain())].into()
+    }
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        let z = T::from_f64(Self::gain());
+        for (o, i) in output[0][..size].iter_mut().zip(input[0][..size].iter()) {
+            *o = *i * z;
+        }
+        for channel in 1..N::USIZE {
+            for (o, i) in output[0][..size]
+                .iter_mut()
+                .zip(input[channel][..size].iter())
+            {
+                *o += *i * z;
+            }
+        }
+    }
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        // Join routing averages, so rescale the average to 1 / sqrt(N).
+        let mut output = Routing::Join.propagate(input, self.outputs());
+        output[0] = output[0].scale((N::USIZE as f64).sqrt());
+        output
+    }
+}
+
 /// Average `N` branches of `M` channels into one branch with `M` channels.
 /// The input has `M` * `N` channels. Inverse of `MultiSplit<M, N, T>`.
 #[derive(Clone)]
@@ -741,6 +1022,10 @@ where
     type Outputs = M;
     type Setting = ();
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn tick(
         &mut self,
@@ -1045,6 +1330,37 @@ where
         self.x.allocate();
         self.y.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.buffer.channels() == self.outputs() && self.x.is_allocated() && self.y.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer.footprint() + self.x.heap_footprint() + self.y.heap_footprint()
+    }
+
+    fn supports_state(&self) -> bool {
+        self.x.supports_state() && self.y.supports_state()
+    }
+
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        Ok(save_state_pair(self.x.save_state()?, self.y.save_state()?))
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        if !self.supports_state() {
+            return Err(StateError::Unsupported);
+        }
+        let (x_state, y_state) = split_state_pair(state)?;
+        // Restore into copies so that the state is unchanged on error.
+        let mut x = self.x.clone();
+        let mut y = self.y.clone();
+        x.restore_state(x_state)?;
+        y.restore_state(y_state)?;
+        self.x = x;
+        self.y = y;
+        Ok(())
+    }
 }
 
 /// Provides unary operator implementations to the `Unop` node.
@@ -1275,6 +1591,26 @@ where
     fn allocate(&mut self) {
         self.x.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.x.heap_footprint()
+    }
+
+    fn supports_state(&self) -> bool {
+        self.x.supports_state()
+    }
+
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        self.x.save_state()
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        self.x.restore_state(state)
+    }
 }
 
 /// Map any number of channels.
@@ -1316,6 +1652,10 @@ where
     type Outputs = O::Size;
     type Setting = ();
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn tick(
         &mut self,
@@ -1331,6 +1671,7 @@ where
 
 /// Use setting from left or right side of a binary operation.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Side<L: Clone + Default, R: Clone + Default> {
     Left(L),
     Right(R),
@@ -1474,6 +1815,37 @@ where
         self.x.allocate();
         self.y.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.buffer.channels() == self.x.outputs() && self.x.is_allocated() && self.y.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer.footprint() + self.x.heap_footprint() + self.y.heap_footprint()
+    }
+
+    fn supports_state(&self) -> bool {
+        self.x.supports_state() && self.y.supports_state()
+    }
+
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        Ok(save_state_pair(self.x.save_state()?, self.y.save_state()?))
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        if !self.supports_state() {
+            return Err(StateError::Unsupported);
+        }
+        let (x_state, y_state) = split_state_pair(state)?;
+        // Restore into copies so that the state is unchanged on error.
+        let mut x = self.x.clone();
+        let mut y = self.y.clone();
+        x.restore_state(x_state)?;
+        y.restore_state(y_state)?;
+        self.x = x;
+        self.y = y;
+        Ok(())
+    }
 }
 
 /// Stack `X` and `Y` in parallel.
@@ -1622,6 +1994,37 @@ where
         self.x.allocate();
         self.y.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.is_allocated() && self.y.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.x.heap_footprint() + self.y.heap_footprint()
+    }
+
+    fn supports_state(&self) -> bool {
+        self.x.supports_state() && self.y.supports_state()
+    }
+
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        Ok(save_state_pair(self.x.save_state()?, self.y.save_state()?))
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        if !self.supports_state() {
+            return Err(StateError::Unsupported);
+        }
+        let (x_state, y_state) = split_state_pair(state)?;
+        // Restore into copies so that the state is unchanged on error.
+        let mut x = self.x.clone();
+        let mut y = self.y.clone();
+        x.restore_state(x_state)?;
+        y.restore_state(y_state)?;
+        self.x = x;
+        self.y = y;
+        Ok(())
+    }
 }
 
 /// Send the same input to `X` and `Y`. Concatenate outputs.
@@ -1754,6 +2157,37 @@ where
         self.x.allocate();
         self.y.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.is_allocated() && self.y.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.x.heap_footprint() + self.y.heap_footprint()
+    }
+
+    fn supports_state(&self) -> bool {
+        self.x.supports_state() && self.y.supports_state()
+    }
+
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        Ok(save_state_pair(self.x.save_state()?, self.y.save_state()?))
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        if !self.supports_state() {
+            return Err(StateError::Unsupported);
+        }
+        let (x_state, y_state) = split_state_pair(state)?;
+        // Restore into copies so that the state is unchanged on error.
+        let mut x = self.x.clone();
+        let mut y = self.y.clone();
+        x.restore_state(x_state)?;
+        y.restore_state(y_state)?;
+        self.x = x;
+        self.y = y;
+        Ok(())
+    }
 }
 
 /// Mix together `X` and `Y` sourcing from the same inputs.
@@ -1895,6 +2329,37 @@ where
         self.x.allocate();
         self.y.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.buffer.channels() == self.outputs() && self.x.is_allocated() && self.y.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer.footprint() + self.x.heap_footprint() + self.y.heap_footprint()
+    }
+
+    fn supports_state(&self) -> bool {
+        self.x.supports_state() && self.y.supports_state()
+    }
+
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        Ok(save_state_pair(self.x.save_state()?, self.y.save_state()?))
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        if !self.supports_state() {
+            return Err(StateError::Unsupported);
+        }
+        let (x_state, y_state) = split_state_pair(state)?;
+        // Restore into copies so that the state is unchanged on error.
+        let mut x = self.x.clone();
+        let mut y = self.y.clone();
+        x.restore_state(x_state)?;
+        y.restore_state(y_state)?;
+        self.x = x;
+        self.y = y;
+        Ok(())
+    }
 }
 
 /// Pass through inputs without matching outputs.
@@ -1994,6 +2459,27 @@ impl<X: AudioNode> AudioNode for Thru<X> {
         }
         self.x.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        (X::Inputs::USIZE >= X::Outputs::USIZE || self.buffer.channels() == X::Outputs::USIZE)
+            && self.x.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer.footprint() + self.x.heap_footprint()
+    }
+
+    fn supports_state(&self) -> bool {
+        self.x.supports_state()
+    }
+
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        self.x.save_state()
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        self.x.restore_state(state)
+    }
 }
 
 /// Mix together a bunch of similar nodes sourcing from the same inputs.
@@ -2138,6 +2624,14 @@ where
             x.allocate();
         }
     }
+
+    fn is_allocated(&self) -> bool {
+        self.buffer.channels() == X::Outputs::USIZE && self.x.iter().all(|x| x.is_allocated())
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer.footprint() + self.x.iter().map(|x| x.heap_footprint()).sum::<usize>()
+    }
 }
 
 /// Stack a bunch of similar nodes in parallel.
@@ -2288,6 +2782,14 @@ where
             x.allocate();
         }
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.iter().all(|x| x.is_allocated())
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.x.iter().map(|x| x.heap_footprint()).sum::<usize>()
+    }
 }
 
 /// Combine outputs of a bunch of similar nodes with a binary operation.
@@ -2450,6 +2952,14 @@ where
             x.allocate();
         }
     }
+
+    fn is_allocated(&self) -> bool {
+        self.buffer.channels() == X::Outputs::USIZE && self.x.iter().all(|x| x.is_allocated())
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer.footprint() + self.x.iter().map(|x| x.heap_footprint()).sum::<usize>()
+    }
 }
 
 /// Branch into a bunch of similar nodes in parallel.
@@ -2586,6 +3096,14 @@ where
             x.allocate();
         }
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.iter().all(|x| x.is_allocated())
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.x.iter().map(|x| x.heap_footprint()).sum::<usize>()
+    }
 }
 
 /// Chain together a bunch of similar nodes.
@@ -2732,6 +3250,18 @@ where
             x.allocate();
         }
     }
+
+    fn is_allocated(&self) -> bool {
+        self.buffer_a.channels() == X::Outputs::USIZE
+            && self.buffer_b.channels() == X::Outputs::USIZE
+            && self.x.iter().all(|x| x.is_allocated())
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer_a.footprint()
+            + self.buffer_b.footprint()
+            + self.x.iter().map(|x| x.heap_footprint()).sum::<usize>()
+    }
 }
 
 /// Reverse channel order.
@@ -2753,6 +3283,10 @@ impl<N: Size<T>, T: Float> AudioNode for Reverse<N, T> {
     type Outputs = N;
     type Setting = ();
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn tick(
         &mut self,
@@ -4,6 +4,7 @@ use super::audionode::*;
 use super::buffer::*;
 use super::combinator::*;
 use super::math::*;
+use super::shared::*;
 use super::signal::*;
 use super::*;
 use duplicate::duplicate_item;
@@ -80,10 +81,82 @@ pub trait AudioUnit48: Send + Sync + DynClone {
     /// Memory footprint of this unit in bytes, without counting buffers and other allocations.
     fn footprint(&self) -> usize;
 
+    /// Memory footprint of this unit in bytes, including buffers and other heap allocations
+    /// owned by the unit and its children, such as delay lines and reverb tanks.
+    /// The default implementation equals `footprint`.
+    fn total_footprint(&self) -> usize {
+        self.footprint()
+    }
+
     /// Preallocate all needed memory, including buffers for block processing.
+    /// Calling this again after memory has been allocated does nothing.
     /// The default implementation does nothing.
     fn allocate(&mut self) {}
 
+    /// Whether all memory needed for processing has been preallocated,
+    /// so that processing will not allocate. Hosts can check this before going realtime.
+    /// The default implementation returns true.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::wrap(Box::new(saw_hz(110.0) >> lowpass_hz(1000.0, 1.0)));
+    /// assert!(!net.is_allocated());
+    /// net.allocate();
+    /// assert!(net.is_allocated());
+    /// ```
+    fn is_allocated(&self) -> bool {
+        true
+    }
+
+    /// Whether this unit is an effect that processes a signal into a signal of the same shape,
+    /// so that mixing in its dry input is meaningful. Generators and units that change
+    /// the number of channels are not effects.
+    /// The default implementation returns true if there are as many inputs as outputs
+    /// and at least one of each.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// assert!(lowpass_hz(1000.0, 1.0).is_effect());
+    /// assert!(!sine_hz(440.0).is_effect());
+    /// ```
+    fn is_effect(&self) -> bool {
+        self.inputs() > 0 && self.inputs() == self.outputs()
+    }
+
+    /// Whether this unit supports `save_state` and `restore_state`.
+    /// The default implementation returns false.
+    fn supports_state(&self) -> bool {
+        false
+    }
+
+    /// Save a snapshot of the processing state, such as sample history.
+    /// Together with `restore_state`, this lets a host seek in a timeline
+    /// and resume deterministic playback. Settings are not included.
+    /// Returns `StateError::Unsupported` if the unit does not support snapshots.
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        Err(StateError::Unsupported)
+    }
+
+    /// Restore processing state from a snapshot made with `save_state`
+    /// by a unit of the same type. On error, the state is unchanged.
+    /// Returns `StateError::Unsupported` if the unit does not support snapshots.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut filter = butterpass_hz(1000.0);
+    /// filter.filter_mono(1.0);
+    /// let state = filter.save_state().unwrap();
+    /// let y = filter.filter_mono(0.0);
+    /// filter.restore_state(&state).unwrap();
+    /// assert_eq!(filter.filter_mono(0.0), y);
+    /// ```
+    fn restore_state(&mut self, _state: &[u8]) -> Result<(), StateError> {
+        Err(StateError::Unsupported)
+    }
+
     // End of interface. There is no need to override the following.
 
     /// Evaluate frequency response of `output` at `frequency` Hz.
@@ -158,6 +231,30 @@ pub trait AudioUnit48: Send + Sync + DynClone {
         result
     }
 
+    /// Group delay of `output` in seconds at `frequency` Hz.
+    /// The delay is measured numerically from the slope of the phase response.
+    /// Return `None` if there is no response or it could not be calculated.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// assert!(pass().group_delay(0, 440.0).unwrap().abs() < 1.0e-9);
+    /// let delay = tick().group_delay(0, 440.0).unwrap();
+    /// assert!((delay * DEFAULT_SR - 1.0).abs() < 1.0e-6);
+    /// ```
+    fn group_delay(&mut self, output: usize, frequency: f64) -> Option<f64> {
+        assert!(output < self.outputs());
+        // Central difference with a small frequency step in Hz.
+        let delta = 0.01;
+        let r0 = self.response(output, frequency - delta)?;
+        let r1 = self.response(output, frequency + delta)?;
+        if r0.norm() == 0.0 || r1.norm() == 0.0 {
+            return None;
+        }
+        // The phase difference of the ratio is unwrapped as long as it stays below pi.
+        Some(-(r1 / r0).arg() / (TAU * 2.0 * delta))
+    }
+
     /// Retrieve the next mono sample from a generator.
     /// The node must have no inputs and 1 or 2 outputs.
     /// If there are two outputs, average them.
@@ -246,6 +343,63 @@ pub trait AudioUnit48: Send + Sync + DynClone {
         (output[0], output[1])
     }
 
+    /// Process `size` frames of interleaved data, as supplied by most audio APIs.
+    /// The length of `input` and `output` must be `size * inputs()` and `size * outputs()`,
+    /// respectively. Any `size` is permitted: data is processed in blocks of up to
+    /// `MAX_BUFFER_SIZE` samples with `process`. Scratch buffers are allocated lazily,
+    /// once per thread.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut unit: Box<dyn AudioUnit64> = Box::new(pan(0.0));
+    /// let mut output = [0.0; 4];
+    /// unit.process_interleaved(2, &[1.0, 0.5], &mut output);
+    /// assert!((output[0] - output[1]).abs() < 1.0e-12);
+    /// assert!((output[2] - output[3]).abs() < 1.0e-12);
+    /// ```
+    fn process_interleaved(&mut self, size: usize, input: &[f48], output: &mut [f48]) {
+        let inputs = self.inputs();
+        let outputs = self.outputs();
+        assert_eq!(input.len(), size * inputs);
+        assert_eq!(output.len(), size * outputs);
+        thread_local! {
+            static SCRATCH: std::cell::RefCell<(Buffer<f48>, Buffer<f48>)> =
+                std::cell::RefCell::new((Buffer::new(), Buffer::new()));
+        }
+        SCRATCH.with(|scratch| {
+            // If a unit processes interleaved data from inside this call,
+            // then the inner call gets temporary buffers.
+            let mut temporary = None;
+            let mut borrowed = scratch.try_borrow_mut().ok();
+            let (input_buffer, output_buffer) = match borrowed.as_deref_mut() {
+                Some(buffers) => buffers,
+                None => temporary.insert((Buffer::new(), Buffer::new())),
+            };
+            let mut i = 0;
+            while i < size {
+                let n = min(size - i, MAX_BUFFER_SIZE);
+                input_buffer.resize(inputs);
+                for channel in 0..inputs {
+                    for (j, x) in input_buffer.mut_at(channel)[..n].iter_mut().enumerate() {
+                        *x = input[(i + j) * inputs + channel];
+                    }
+                }
+                self.process(
+                    n,
+                    input_buffer.get_ref(inputs),
+                    output_buffer.get_mut(outputs),
+                );
+                for channel in 0..outputs {
+                    for (j, y) in output_buffer.at(channel)[..n].iter().enumerate() {
+                        output[(i + j) * outputs + channel] = *y;
+                    }
+                }
+                i += n;
+            }
+        });
+    }
+
     /// Print information about this unit into a string.
     fn display(&mut self) -> String {
         let mut string = String::new();
@@ -411,9 +565,24 @@ where
     fn footprint(&self) -> usize {
         std::mem::size_of::<X>()
     }
+    fn total_footprint(&self) -> usize {
+        std::mem::size_of::<X>() + self.0.heap_footprint()
+    }
     fn allocate(&mut self) {
         self.0.allocate();
     }
+    fn is_allocated(&self) -> bool {
+        self.0.is_allocated()
+    }
+    fn supports_state(&self) -> bool {
+        self.0.supports_state()
+    }
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        self.0.save_state()
+    }
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        self.0.restore_state(state)
+    }
 }
 
 /// A big block adapter.
@@ -494,20 +663,16 @@ impl AudioUnit48 for BigBlockAdapter48 {
             let mut i = 0;
             while i < size {
                 let n = min(size - i, MAX_BUFFER_SIZE);
-                for input_i in 0..self.input.len() {
-                    for j in 0..n {
-                        self.input[input_i][j] = input[input_i][i + j];
-                    }
+                for (buffer, input) in self.input.iter_mut().zip(input.iter()) {
+                    buffer[..n].clone_from_slice(&input[i..i + n]);
                 }
                 self.source.process(
                     n,
                     self.input_slice.from_refs(&self.input),
                     self.output_slice.from_muts(&mut self.output),
                 );
-                for output_i in 0..self.output.len() {
-                    for j in 0..n {
-                        output[output_i][i + j] = self.output[output_i][j];
-                    }
+                for (output, buffer) in output.iter_mut().zip(self.output.iter()) {
+                    output[i..i + n].clone_from_slice(&buffer[..n]);
                 }
                 i += n;
             }
@@ -533,6 +698,11 @@ impl AudioUnit48 for BigBlockAdapter48 {
     fn footprint(&self) -> usize {
         self.source.footprint()
     }
+    fn total_footprint(&self) -> usize {
+        let buffers = self.input.iter().chain(self.output.iter());
+        self.source.total_footprint()
+            + buffers.map(|v| v.capacity()).sum::<usize>() * std::mem::size_of::<f48>()
+    }
     fn allocate(&mut self) {
         for input_buffer in self.input.iter_mut() {
             input_buffer.resize(MAX_BUFFER_SIZE, 0.0);
@@ -542,11 +712,17 @@ impl AudioUnit48 for BigBlockAdapter48 {
         }
         self.source.allocate();
     }
+    fn is_allocated(&self) -> bool {
+        let mut buffers = self.input.iter().chain(self.output.iter());
+        buffers.all(|v| v.len() == MAX_BUFFER_SIZE) && self.source.is_allocated()
+    }
 }
 
 /// Block rate adapter converts processing calls to maximum length block processing.
 /// Maximizes performance at the expense of latency.
-/// The unit must have no inputs.
+/// If the unit has inputs, then they are buffered until a full block is available,
+/// which delays the output by one block (`MAX_BUFFER_SIZE` samples,
+/// or less with a host block size hint). Units without inputs incur no delay.
 #[duplicate_item(
     f48       BlockRateAdapter48       AudioUnit48;
     [ f64 ]   [ BlockRateAdapter64 ]   [ AudioUnit64 ];
@@ -555,9 +731,17 @@ impl AudioUnit48 for BigBlockAdapter48 {
 #[derive(Clone)]
 pub struct BlockRateAdapter48 {
     unit: Box<dyn AudioUnit48>,
+    inputs: usize,
     channels: usize,
+    /// Input block being collected. Used only if the unit has inputs.
+    input: Buffer<f48>,
     buffer: Buffer<f48>,
     index: usize,
+    /// Host block size hint, if any.
+    host_block: Option<usize>,
+    /// Length of internally processed blocks.
+    block: usize,
+    sample_rate: f64,
 }
 
 #[duplicate_item(
@@ -568,15 +752,91 @@ pub struct BlockRateAdapter48 {
 impl BlockRateAdapter48 {
     /// Create new block rate adapter for the unit.
     pub fn new(unit: Box<dyn AudioUnit48>) -> Self {
-        assert_eq!(unit.inputs(), 0);
+        let inputs = unit.inputs();
         let channels = unit.outputs();
-        Self {
+        let mut adapter = Self {
             unit,
+            inputs,
             channels,
+            input: Buffer::new(),
             buffer: Buffer::new(),
-            index: MAX_BUFFER_SIZE,
+            index: 0,
+            host_block: None,
+            block: MAX_BUFFER_SIZE,
+            sample_rate: DEFAULT_SR,
+        };
+        adapter.rewind();
+        adapter
+    }
+
+    /// Tell the adapter the block size the host processes with, if known.
+    /// With a hint, internal blocks are shortened to the largest multiple of `host_block`
+    /// that fits in `MAX_BUFFER_SIZE`, so a block is only ever computed at the start
+    /// of a host block and never in the middle of one. The first block is also prefilled
+    /// here and on `reset`, so the first call in the audio thread does not pay for it.
+    /// The tradeoff is lower efficiency if `host_block` does not divide `MAX_BUFFER_SIZE`:
+    /// for example, a hint of 48 samples processes 48 sample blocks instead of 64.
+    /// The default is no hint, which always processes blocks of `MAX_BUFFER_SIZE` samples.
+    /// The adapter is reset.
+    pub fn set_host_block(&mut self, host_block: Option<usize>) {
+        if let Some(size) = host_block {
+            assert!(size > 0 && size <= MAX_BUFFER_SIZE);
+            self.block = MAX_BUFFER_SIZE / size * size;
+        } else {
+            self.block = MAX_BUFFER_SIZE;
+        }
+        self.host_block = host_block;
+        self.reset();
+    }
+
+    /// Host block size hint, if any.
+    pub fn host_block(&self) -> Option<usize> {
+        self.host_block
+    }
+
+    /// Latency in samples introduced by the adapter.
+    /// This is one block if the unit has inputs and zero otherwise.
+    pub fn block_latency(&self) -> usize {
+        if self.inputs > 0 {
+            self.block
+        } else {
+            0
+        }
+    }
+
+    /// Return to the start of a block.
+    fn rewind(&mut self) {
+        if self.inputs > 0 {
+            // Output silence until the first input block has been collected.
+            self.index = 0;
+            self.input.resize(self.inputs);
+            self.buffer.resize(self.channels);
+            for channel in self.buffer.vec_mut().iter_mut() {
+                channel.fill(0.0);
+            }
+        } else {
+            self.index = self.block;
         }
     }
+
+    /// Compute the next block.
+    #[inline]
+    fn fill(&mut self, input: &[&[f48]]) {
+        self.unit
+            .process(self.block, input, self.buffer.get_mut(self.channels));
+        self.index = 0;
+    }
+
+    /// Compute the next block from collected input.
+    #[inline]
+    fn fill_input(&mut self) {
+        self.unit.process(
+            self.block,
+            self.input.get_ref(self.inputs),
+            self.buffer.get_mut(self.channels),
+        );
+        self.index = 0;
+    }
 }
 
 #[duplicate_item(
@@ -587,16 +847,31 @@ impl BlockRateAdapter48 {
 impl AudioUnit48 for BlockRateAdapter48 {
     fn reset(&mut self) {
         self.unit.reset();
-        self.index = MAX_BUFFER_SIZE;
+        self.rewind();
+        if self.inputs == 0 && self.host_block.is_some() {
+            self.fill(&[]);
+        }
     }
     fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
         self.unit.set_sample_rate(sample_rate);
     }
-    fn tick(&mut self, _input: &[f48], output: &mut [f48]) {
-        if self.index == MAX_BUFFER_SIZE {
-            self.unit
-                .process(MAX_BUFFER_SIZE, &[], self.buffer.get_mut(self.channels));
-            self.index = 0;
+    fn tick(&mut self, input: &[f48], output: &mut [f48]) {
+        if self.inputs > 0 {
+            for channel in 0..self.channels {
+                output[channel] = self.buffer.at(channel)[self.index];
+            }
+            for channel in 0..self.inputs {
+                self.input.mut_at(channel)[self.index] = input[channel];
+            }
+            self.index += 1;
+            if self.index == self.block {
+                self.fill_input();
+            }
+            return;
+        }
+        if self.index == self.block {
+            self.fill(&[]);
         }
         for channel in 0..self.channels {
             output[channel] = self.buffer.at(channel)[self.index];
@@ -606,22 +881,27 @@ impl AudioUnit48 for BlockRateAdapter48 {
     fn process(&mut self, size: usize, input: &[&[f48]], output: &mut [&mut [f48]]) {
         let mut i = 0;
         while i < size {
-            if self.index == MAX_BUFFER_SIZE {
-                self.unit
-                    .process(MAX_BUFFER_SIZE, input, self.buffer.get_mut(self.channels));
-                self.index = 0;
+            if self.inputs == 0 && self.index == self.block {
+                self.fill(input);
             }
-            let n = min(size - i, MAX_BUFFER_SIZE - self.index);
+            let n = min(size - i, self.block - self.index);
             for channel in 0..self.channels {
                 output[channel][i..i + n]
                     .clone_from_slice(&self.buffer.at(channel)[self.index..self.index + n]);
             }
+            for channel in 0..self.inputs {
+                self.input.mut_at(channel)[self.index..self.index + n]
+                    .clone_from_slice(&input[channel][i..i + n]);
+            }
             i += n;
             self.index += n;
+            if self.inputs > 0 && self.index == self.block {
+                self.fill_input();
+            }
         }
     }
     fn inputs(&self) -> usize {
-        0
+        self.inputs
     }
     fn outputs(&self) -> usize {
         self.channels
@@ -633,13 +913,380 @@ impl AudioUnit48 for BlockRateAdapter48 {
         self.unit.ping(probe, hash)
     }
     fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
-        self.unit.route(input, frequency)
+        let mut output = self.unit.route(input, frequency);
+        let latency = self.block_latency() as f64;
+        if latency > 0.0 {
+            let delay = Complex64::from_polar(1.0, -TAU * frequency * latency / self.sample_rate);
+            for signal in output.iter_mut() {
+                *signal = match *signal {
+                    Signal::Response(..) => signal.filter(latency, |r| r * delay),
+                    _ => signal.delay(latency),
+                };
+            }
+        }
+        output
     }
     fn footprint(&self) -> usize {
         self.unit.footprint()
     }
+    fn total_footprint(&self) -> usize {
+        self.unit.total_footprint() + self.input.footprint() + self.buffer.footprint()
+    }
     fn allocate(&mut self) {
+        self.input.resize(self.inputs);
         self.buffer.resize(self.channels);
         self.unit.allocate();
     }
+    fn is_allocated(&self) -> bool {
+        self.input.channels() == self.inputs
+            && self.buffer.channels() == self.channels
+            && self.unit.is_allocated()
+    }
+}
+
+/// Unit defined by a closure, for prototyping without writing an `AudioNode`.
+/// The closure is called once per sample with input and output slices
+/// of `inputs` and `outputs` samples, respectively.
+/// Resetting the unit restores the closure to the state it had when the unit was created,
+/// so any state it captures is reset, too.
+/// Sample rate changes are not passed on to the closure.
+/// Frequency responses are unknown.
+#[duplicate_item(
+    f48       CustomUnit48;
+    [ f64 ]   [ CustomUnit64 ];
+    [ f32 ]   [ CustomUnit32 ];
+)]
+#[derive(Clone)]
+pub struct CustomUnit48<F> {
+    inputs: usize,
+    outputs: usize,
+    /// Closure in its initial state.
+    initial: F,
+    f: F,
+    /// Frames for block processing.
+    input: Vec<f48>,
+    output: Vec<f48>,
+}
+
+#[duplicate_item(
+    f48       CustomUnit48;
+    [ f64 ]   [ CustomUnit64 ];
+    [ f32 ]   [ CustomUnit32 ];
+)]
+impl<F: FnMut(&[f48], &mut [f48]) + Clone + Send + Sync> CustomUnit48<F> {
+    /// Create new unit with `inputs` inputs and `outputs` outputs
+    /// that processes samples with closure `f`.
+    pub fn new(inputs: usize, outputs: usize, f: F) -> Self {
+        Self {
+            inputs,
+            outputs,
+            initial: f.clone(),
+            f,
+            input: vec![0.0; inputs],
+            output: vec![0.0; outputs],
+        }
+    }
+}
+
+#[duplicate_item(
+    f48       CustomUnit48       AudioUnit48;
+    [ f64 ]   [ CustomUnit64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ CustomUnit32 ]   [ AudioUnit32 ];
+)]
+impl<F: FnMut(&[f48], &mut [f48]) + Clone + Send + Sync> AudioUnit48 for CustomUnit48<F> {
+    fn reset(&mut self) {
+        self.f = self.initial.clone();
+    }
+    fn set_sample_rate(&mut self, _sample_rate: f64) {}
+    #[inline]
+    fn tick(&mut self, input: &[f48], output: &mut [f48]) {
+        (self.f)(input, output);
+    }
+    fn process(&mut self, size: usize, input: &[&[f48]], output: &mut [&mut [f48]]) {
+        for i in 0..size {
+            for (x, channel) in self.input.iter_mut().zip(input.iter()) {
+                *x = channel[i];
+            }
+            (self.f)(&self.input, &mut self.output);
+            for (channel, y) in output.iter_mut().zip(self.output.iter()) {
+                channel[i] = *y;
+            }
+        }
+    }
+    fn inputs(&self) -> usize {
+        self.inputs
+    }
+    fn outputs(&self) -> usize {
+        self.outputs
+    }
+    fn get_id(&self) -> u64 {
+        const ID: u64 = 120;
+        ID
+    }
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        new_signal_frame(self.outputs)
+    }
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+    fn total_footprint(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + (self.input.capacity() + self.output.capacity()) * std::mem::size_of::<f48>()
+    }
+}
+
+/// Source of the wet amount of a dry/wet mixer.
+#[derive(Clone)]
+enum DryWetAmount<T: Atomic> {
+    Value(T),
+    Shared(Shared<T>),
+    /// Amount is read from the last input.
+    Input,
+}
+
+/// Dry/wet mixer. Mixes the output of the contained unit with its input
+/// by a wet amount in 0...1, which can be fixed, shared or read from an extra input.
+/// The unit must have equally many inputs and outputs.
+/// The dry path is delayed by the latency of the unit, rounded to whole samples,
+/// so the paths stay aligned. An amount of 0 outputs the delayed dry signal exactly
+/// and an amount of 1 outputs the unit exactly.
+/// - Inputs: from the unit, plus the wet amount as the last input if read from input.
+/// - Outputs: from the unit.
+#[duplicate_item(
+    f48       DryWet48       AudioUnit48;
+    [ f64 ]   [ DryWet64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ DryWet32 ]   [ AudioUnit32 ];
+)]
+#[derive(Clone)]
+pub struct DryWet48 {
+    unit: Box<dyn AudioUnit48>,
+    amount: DryWetAmount<f48>,
+    channels: usize,
+    /// Dry path delay lines, one per channel.
+    dry: Vec<Vec<f48>>,
+    /// Position in the dry path delay lines.
+    index: usize,
+    sample_rate: f64,
+}
+
+#[duplicate_item(
+    f48       DryWet48       AudioUnit48;
+    [ f64 ]   [ DryWet64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ DryWet32 ]   [ AudioUnit32 ];
+)]
+impl DryWet48 {
+    /// Create new dry/wet mixer for `unit` with wet `amount` in 0...1.
+    pub fn new(amount: f48, unit: Box<dyn AudioUnit48>) -> Self {
+        Self::with_amount(DryWetAmount::Value(amount), unit)
+    }
+
+    /// Create new dry/wet mixer for `unit` with wet amount in 0...1 read from a shared variable.
+    pub fn with_shared(amount: &Shared<f48>, unit: Box<dyn AudioUnit48>) -> Self {
+        Self::with_amount(DryWetAmount::Shared(amount.clone()), unit)
+    }
+
+    /// Create new dry/wet mixer for `unit` with wet amount in 0...1 read from an extra input,
+    /// which is placed after the inputs of the unit.
+    pub fn with_input(unit: Box<dyn AudioUnit48>) -> Self {
+        Self::with_amount(DryWetAmount::Input, unit)
+    }
+
+    fn with_amount(amount: DryWetAmount<f48>, unit: Box<dyn AudioUnit48>) -> Self {
+        let channels = unit.inputs();
+        assert_eq!(unit.outputs(), channels);
+        let mut node = Self {
+            unit,
+            amount,
+            channels,
+            dry: vec![Vec::new(); channels],
+            index: 0,
+            sample_rate: DEFAULT_SR,
+        };
+        node.update_dry_latency();
+        node
+    }
+
+    /// Resize the dry path delay lines to the current latency of the unit.
+    fn update_dry_latency(&mut self) {
+        let latency = self
+            .unit
+            .latency()
+            .map(|x| max(0.0, round(x)))
+            .unwrap_or(0.0) as usize;
+        if latency != self.dry_latency() {
+            for buffer in self.dry.iter_mut() {
+                buffer.clear();
+                buffer.resize(latency, 0.0);
+            }
+            self.index = 0;
+        }
+    }
+
+    /// Fixed wet amount, or the current value of the shared variable.
+    /// Returns `None` if the amount is read from input.
+    pub fn amount(&self) -> Option<f48> {
+        match &self.amount {
+            DryWetAmount::Value(amount) => Some(*amount),
+            DryWetAmount::Shared(amount) => Some(amount.value()),
+            DryWetAmount::Input => None,
+        }
+    }
+
+    /// Set fixed wet amount in 0...1.
+    pub fn set_amount(&mut self, amount: f48) {
+        self.amount = DryWetAmount::Value(amount);
+    }
+
+    /// Delay of the dry path in samples.
+    pub fn dry_latency(&self) -> usize {
+        self.dry.first().map(|x| x.len()).unwrap_or(0)
+    }
+
+    /// Access the contained unit.
+    pub fn unit(&self) -> &dyn AudioUnit48 {
+        &*self.unit
+    }
+
+    /// Access the contained unit mutably.
+    pub fn unit_mut(&mut self) -> &mut dyn AudioUnit48 {
+        &mut *self.unit
+    }
+
+    /// Mix `dry` and `wet` samples by `amount`.
+    #[inline]
+    fn mix(dry: f48, wet: f48, amount: f48) -> f48 {
+        if amount <= 0.0 {
+            dry
+        } else if amount >= 1.0 {
+            wet
+        } else {
+            dry + (wet - dry) * amount
+        }
+    }
+
+    /// Store the next dry sample of `channel` and return the delayed one.
+    /// The position is advanced separately.
+    #[inline]
+    fn delay_dry(&mut self, channel: usize, x: f48) -> f48 {
+        match self.dry[channel].get_mut(self.index) {
+            Some(y) => std::mem::replace(y, x),
+            None => x,
+        }
+    }
+
+    /// Advance the position in the dry path delay lines.
+    #[inline]
+    fn advance_dry(&mut self) {
+        self.index += 1;
+        if self.index >= self.dry_latency() {
+            self.index = 0;
+        }
+    }
+}
+
+#[allow(clippy::unnecessary_cast)]
+#[duplicate_item(
+    f48       DryWet48       AudioUnit48;
+    [ f64 ]   [ DryWet64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ DryWet32 ]   [ AudioUnit32 ];
+)]
+impl AudioUnit48 for DryWet48 {
+    fn reset(&mut self) {
+        self.unit.reset();
+        for buffer in self.dry.iter_mut() {
+            buffer.fill(0.0);
+        }
+        self.index = 0;
+    }
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        self.unit.set_sample_rate(sample_rate);
+        self.update_dry_latency();
+    }
+    fn tick(&mut self, input: &[f48], output: &mut [f48]) {
+        self.unit.tick(&input[..self.channels], output);
+        let amount = match &self.amount {
+            DryWetAmount::Value(amount) => *amount,
+            DryWetAmount::Shared(amount) => amount.value(),
+            DryWetAmount::Input => input[self.channels],
+        };
+        for channel in 0..self.channels {
+            let dry = self.delay_dry(channel, input[channel]);
+            output[channel] = Self::mix(dry, output[channel], amount);
+        }
+        self.advance_dry();
+    }
+    fn process(&mut self, size: usize, input: &[&[f48]], output: &mut [&mut [f48]]) {
+        self.unit.process(size, &input[..self.channels], output);
+        let amount = match &self.amount {
+            DryWetAmount::Value(amount) => Some(*amount),
+            DryWetAmount::Shared(amount) => Some(amount.value()),
+            DryWetAmount::Input => None,
+        };
+        for i in 0..size {
+            let amount = amount.unwrap_or_else(|| input[self.channels][i]);
+            for channel in 0..self.channels {
+                let dry = self.delay_dry(channel, input[channel][i]);
+                output[channel][i] = Self::mix(dry, output[channel][i], amount);
+            }
+            self.advance_dry();
+        }
+    }
+    fn inputs(&self) -> usize {
+        match self.amount {
+            DryWetAmount::Input => self.channels + 1,
+            _ => self.channels,
+        }
+    }
+    fn outputs(&self) -> usize {
+        self.channels
+    }
+    fn get_id(&self) -> u64 {
+        const ID: u64 = 129;
+        ID
+    }
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.unit.ping(probe, hash.hash(self.get_id()))
+    }
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let wet = self
+            .unit
+            .route(&copy_signal_frame(input, 0, self.channels), frequency);
+        let latency = self.dry_latency() as f64;
+        let delay = Complex64::from_polar(1.0, -TAU * frequency * latency / self.sample_rate);
+        let mut output = new_signal_frame(self.channels);
+        for channel in 0..self.channels {
+            let dry = match input[channel] {
+                Signal::Response(..) => input[channel].filter(latency, |r| r * delay),
+                _ => input[channel].delay(latency),
+            };
+            output[channel] = match self.amount() {
+                Some(amount) => {
+                    let amount = clamp01(amount) as f64;
+                    dry.combine_linear(
+                        wet[channel],
+                        0.0,
+                        |x, y| x + (y - x) * amount,
+                        |x, y| x + (y - x) * amount,
+                    )
+                }
+                None => dry.combine_nonlinear(wet[channel], 0.0),
+            };
+        }
+        output
+    }
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+    fn total_footprint(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.unit.total_footprint()
+            + self.dry.iter().map(|x| x.capacity()).sum::<usize>() * std::mem::size_of::<f48>()
+    }
+    fn allocate(&mut self) {
+        self.unit.allocate();
+    }
+    fn is_allocated(&self) -> bool {
+        self.unit.is_allocated()
+    }
 }
@@ -0,0 +1,419 @@
+//! Binaural spatialization by HRIR convolution.
+
+use super::audionode::*;
+use super::math::*;
+use super::signal::*;
+use super::*;
+use num_complex::Complex64;
+use numeric_array::typenum::*;
+use std::sync::Arc;
+
+/// Head radius in meters of the built-in spherical head model.
+const HEAD_RADIUS: f64 = 0.0875;
+
+/// Speed of sound in meters per second.
+const SPEED_OF_SOUND: f64 = 343.0;
+
+/// Duration in seconds of the crossfade between directions.
+const FADE_TIME: f64 = 0.01;
+
+/// Head-related impulse response pair measured at one direction.
+#[derive(Clone, Debug)]
+pub struct Hrir {
+    /// Azimuth in radians: 0 is straight ahead, positive angles are to the right.
+    pub azimuth: f64,
+    /// Elevation in radians: 0 is level, positive angles are up.
+    pub elevation: f64,
+    /// Impulse response of the left ear.
+    pub left: Vec<f32>,
+    /// Impulse response of the right ear.
+    pub right: Vec<f32>,
+}
+
+/// Unit vector pointing at a direction, with x to the right, y ahead and z up.
+fn direction(azimuth: f64, elevation: f64) -> (f64, f64, f64) {
+    (
+        sin(azimuth) * cos(elevation),
+        cos(azimuth) * cos(elevation),
+        sin(elevation),
+    )
+}
+
+/// Angle in radians between two directions.
+fn angle_between(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64 {
+    clamp11(a.0 * b.0 + a.1 * b.1 + a.2 * b.2).acos()
+}
+
+/// A set of HRIR pairs measured at a common sample rate,
+/// in the spirit of a SOFA dataset flattened to a list of directions.
+/// Each entry has an azimuth and elevation in radians and
+/// one impulse response per ear. Shorter responses are padded with zeros
+/// to the length of the longest one.
+/// Clones share the responses.
+#[derive(Clone)]
+pub struct HrirSet {
+    sample_rate: f64,
+    length: usize,
+    hrirs: Arc<Vec<Hrir>>,
+}
+
+impl HrirSet {
+    /// Create a set from measured HRIR pairs at `sample_rate` Hz.
+    /// There must be at least one direction.
+    pub fn new(sample_rate: f64, mut hrirs: Vec<Hrir>) -> Self {
+        assert!(!hrirs.is_empty());
+        let length = max(
+            1,
+            hrirs
+                .iter()
+                .map(|hrir| max(hrir.left.len(), hrir.right.len()))
+                .max()
+                .unwrap_or(0),
+        );
+        for hrir in hrirs.iter_mut() {
+            hrir.left.resize(length, 0.0);
+            hrir.right.resize(length, 0.0);
+        }
+        Self {
+            sample_rate,
+            length,
+            hrirs: Arc::new(hrirs),
+        }
+    }
+
+    /// Built-in minimal set synthesized from a spherical head model at `sample_rate` Hz.
+    /// Interaural time differences follow Woodworth's formula and the head shadow
+    /// is the one-pole, one-zero model of Brown and Duda. Directions are every 30 degrees
+    /// in azimuth at elevations -45, 0 and 45 degrees, plus straight up.
+    /// There are no pinna cues, so elevation is only weakly conveyed.
+    pub fn spherical_head(sample_rate: f64) -> Self {
+        let length = (sample_rate * 0.0025).ceil() as usize;
+        let mut hrirs = Vec::new();
+        for elevation in [-45.0, 0.0, 45.0] {
+            for azimuth in (0..12).map(|i| -180.0 + 30.0 * i as f64) {
+                hrirs.push(Self::model(sample_rate, length, azimuth, elevation));
+            }
+        }
+        hrirs.push(Self::model(sample_rate, length, 0.0, 90.0));
+        Self::new(sample_rate, hrirs)
+    }
+
+    /// Spherical head model HRIR pair for a direction given in degrees.
+    fn model(sample_rate: f64, length: usize, azimuth: f64, elevation: f64) -> Hrir {
+        let azimuth = azimuth.to_radians();
+        let elevation = elevation.to_radians();
+        let source = direction(azimuth, elevation);
+        let ear = |side: f64| {
+            // Angle between the source and the ear axis.
+            let theta = angle_between(source, (side, 0.0, 0.0));
+            let a = HEAD_RADIUS / SPEED_OF_SOUND;
+            // Arrival time relative to the center of the head, offset to be non-negative.
+            let delay = if theta < PI * 0.5 {
+                a - a * cos(theta)
+            } else {
+                a + a * (theta - PI * 0.5)
+            } * sample_rate;
+            // Head shadow filter, discretized with the bilinear transform.
+            let alpha = 1.05 + 0.95 * cos(theta * 180.0 / 150.0);
+            let beta = a * 0.5;
+            let k = 2.0 * sample_rate;
+            let b0 = (1.0 + alpha * beta * k) / (1.0 + beta * k);
+            let b1 = (1.0 - alpha * beta * k) / (1.0 + beta * k);
+            let a1 = (1.0 - beta * k) / (1.0 + beta * k);
+            let mut shadow = vec![0.0; length];
+            let mut y1 = 0.0;
+            for (n, y) in shadow.iter_mut().enumerate() {
+                let x0 = if n == 0 { 1.0 } else { 0.0 };
+                let x1 = if n == 1 { 1.0 } else { 0.0 };
+                *y = b0 * x0 + b1 * x1 - a1 * y1;
+                y1 = *y;
+            }
+            // Apply the delay with linear interpolation.
+            let whole = delay as usize;
+            let fraction = delay - whole as f64;
+            let mut response = vec![0.0f32; length];
+            for (n, &h) in shadow.iter().enumerate() {
+                if n + whole < length {
+                    response[n + whole] += ((1.0 - fraction) * h) as f32;
+                }
+                if n + whole + 1 < length {
+                    response[n + whole + 1] += (fraction * h) as f32;
+                }
+            }
+            response
+        };
+        Hrir {
+            azimuth,
+            elevation,
+            left: ear(-1.0),
+            right: ear(1.0),
+        }
+    }
+
+    /// Sample rate of the set in Hz.
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Length of the impulse responses in samples.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// The measured HRIR pairs.
+    pub fn hrirs(&self) -> &[Hrir] {
+        &self.hrirs
+    }
+
+    /// Whether this set shares its responses with `other`.
+    pub fn shares_hrirs(&self, other: &HrirSet) -> bool {
+        Arc::ptr_eq(&self.hrirs, &other.hrirs)
+    }
+
+    /// Write the HRIR pair for a direction into `left` and `right`.
+    /// The pair is interpolated from the three nearest measured directions,
+    /// weighted by inverse angular distance. A measured direction is returned as is.
+    /// Does not allocate, so it can be called from the audio thread.
+    pub fn interpolate(&self, azimuth: f64, elevation: f64, left: &mut [f32], right: &mut [f32]) {
+        let target = direction(azimuth, elevation);
+        // Angular distances and indices of the nearest directions in ascending order.
+        let mut nearest = [(f64::INFINITY, 0); 3];
+        let mut count = 0;
+        for (i, hrir) in self.hrirs.iter().enumerate() {
+            let d = angle_between(target, direction(hrir.azimuth, hrir.elevation));
+            if count < nearest.len() {
+                count += 1;
+            } else if d >= nearest[count - 1].0 {
+                continue;
+            }
+            // Insert after any direction at an equal distance, keeping the earlier one first.
+            let mut j = count - 1;
+            while j > 0 && nearest[j - 1].0 > d {
+                nearest[j] = nearest[j - 1];
+                j -= 1;
+            }
+            nearest[j] = (d, i);
+        }
+        let nearest = &nearest[..count];
+        left[..self.length].fill(0.0);
+        right[..self.length].fill(0.0);
+        if nearest[0].0 < 1.0e-6 {
+            let hrir = &self.hrirs[nearest[0].1];
+            left[..self.length].copy_from_slice(&hrir.left);
+            right[..self.length].copy_from_slice(&hrir.right);
+            return;
+        }
+        let total: f64 = nearest.iter().map(|(d, _)| 1.0 / d).sum();
+        for &(d, i) in nearest.iter() {
+            let weight = (1.0 / d / total) as f32;
+            let hrir = &self.hrirs[i];
+            for (y, x) in left.iter_mut().zip(hrir.left.iter()) {
+                *y += weight * *x;
+            }
+            for (y, x) in right.iter_mut().zip(hrir.right.iter()) {
+                *y += weight * *x;
+            }
+        }
+    }
+}
+
+/// Binaural spatializer. Convolves a mono source with HRIR pairs
+/// interpolated from an `HrirSet` to position it in 3D around the listener.
+/// Direction changes crossfade over 10 ms between the old and new responses
+/// to avoid clicks. The set should be measured at the processing sample rate;
+/// the built-in spherical head set is regenerated when the sample rate changes.
+/// Setting: (azimuth, elevation) in radians.
+/// - Input 0: mono audio
+/// - Output 0: left output
+/// - Output 1: right output
+#[derive(Clone)]
+pub struct Binaural<T: Float> {
+    _marker: std::marker::PhantomData<T>,
+    set: HrirSet,
+    builtin: bool,
+    azimuth: f64,
+    elevation: f64,
+    /// Input history, most recent sample at `index`.
+    history: Vec<f32>,
+    index: usize,
+    left: Vec<f32>,
+    right: Vec<f32>,
+    /// Responses faded out after a direction change.
+    old_left: Vec<f32>,
+    old_right: Vec<f32>,
+    /// Remaining crossfade samples.
+    fade: usize,
+    fade_length: usize,
+}
+
+impl<T: Float> Binaural<T> {
+    /// Create new binaural spatializer from an HRIR set.
+    /// Source is at `azimuth` and `elevation` radians.
+    pub fn new(set: &HrirSet, azimuth: f64, elevation: f64) -> Self {
+        let mut node = Self {
+            _marker: std::marker::PhantomData::default(),
+            set: set.clone(),
+            builtin: false,
+            azimuth,
+            elevation,
+            history: Vec::new(),
+            index: 0,
+            left: Vec::new(),
+            right: Vec::new(),
+            old_left: Vec::new(),
+            old_right: Vec::new(),
+            fade: 0,
+            fade_length: 0,
+        };
+        node.allocate_responses();
+        node
+    }
+
+    /// Create new binaural spatializer using the built-in spherical head set.
+    pub fn spherical_head(sample_rate: f64, azimuth: f64, elevation: f64) -> Self {
+        let mut node = Self::new(&HrirSet::spherical_head(sample_rate), azimuth, elevation);
+        node.builtin = true;
+        node
+    }
+
+    fn allocate_responses(&mut self) {
+        let length = self.set.length();
+        self.history = vec![0.0; length];
+        self.index = 0;
+        self.left = vec![0.0; length];
+        self.right = vec![0.0; length];
+        self.old_left = vec![0.0; length];
+        self.old_right = vec![0.0; length];
+        self.fade = 0;
+        self.fade_length = max(1, (FADE_TIME * self.set.sample_rate()) as usize);
+        self.set.interpolate(
+            self.azimuth,
+            self.elevation,
+            &mut self.left,
+            &mut self.right,
+        );
+    }
+
+    /// The HRIR set in use.
+    pub fn hrir_set(&self) -> &HrirSet {
+        &self.set
+    }
+
+    /// Azimuth in radians.
+    pub fn azimuth(&self) -> f64 {
+        self.azimuth
+    }
+
+    /// Elevation in radians.
+    pub fn elevation(&self) -> f64 {
+        self.elevation
+    }
+
+    /// Move the source to `azimuth` and `elevation` radians.
+    /// The responses crossfade to the new direction.
+    pub fn set_direction(&mut self, azimuth: f64, elevation: f64) {
+        if azimuth == self.azimuth && elevation == self.elevation {
+            return;
+        }
+        self.azimuth = azimuth;
+        self.elevation = elevation;
+        if self.fade == 0 {
+            std::mem::swap(&mut self.left, &mut self.old_left);
+            std::mem::swap(&mut self.right, &mut self.old_right);
+        }
+        // If a crossfade is already in progress, the intermediate target is dropped
+        // and the fade restarts from the older responses.
+        self.set
+            .interpolate(azimuth, elevation, &mut self.left, &mut self.right);
+        self.fade = self.fade_length;
+    }
+
+    /// Convolve the history with a response pair.
+    #[inline]
+    fn convolve(&self, left: &[f32], right: &[f32]) -> (f32, f32) {
+        let (recent, older) = self.history.split_at(self.index + 1);
+        let mut l = 0.0;
+        let mut r = 0.0;
+        // History runs backwards in time from `index`.
+        for ((x, hl), hr) in recent
+            .iter()
+            .rev()
+            .chain(older.iter().rev())
+            .zip(left.iter())
+            .zip(right.iter())
+        {
+            l += x * hl;
+            r += x * hr;
+        }
+        (l, r)
+    }
+}
+
+impl<T: Float> AudioNode for Binaural<T> {
+    const ID: u64 = 96;
+    type Sample = T;
+    type Inputs = U1;
+    type Outputs = U2;
+    type Setting = (T, T);
+
+    fn set(&mut self, (azimuth, elevation): Self::Setting) {
+        self.set_direction(azimuth.to_f64(), elevation.to_f64());
+    }
+
+    fn reset(&mut self) {
+        self.history.fill(0.0);
+        self.index = 0;
+        self.fade = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.builtin && sample_rate != self.set.sample_rate() {
+            self.set = HrirSet::spherical_head(sample_rate);
+            self.allocate_responses();
+        }
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        self.index = (self.index + 1) % self.history.len();
+        self.history[self.index] = input[0].to_f32();
+        let (mut l, mut r) = self.convolve(&self.left, &self.right);
+        if self.fade > 0 {
+            let (old_l, old_r) = self.convolve(&self.old_left, &self.old_right);
+            let mix = self.fade as f32 / self.fade_length as f32;
+            l += (old_l - l) * mix;
+            r += (old_r - r) * mix;
+            self.fade -= 1;
+        }
+        [T::from_f64(l as f64), T::from_f64(r as f64)].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        // Pretend the direction is constant.
+        let omega = -TAU * frequency / self.set.sample_rate();
+        let response = |h: &[f32]| {
+            h.iter()
+                .enumerate()
+                .map(|(n, x)| Complex64::from_polar(*x as f64, omega * n as f64))
+                .sum::<Complex64>()
+        };
+        let left = response(&self.left);
+        let right = response(&self.right);
+        output[0] = input[0].filter(0.0, |r| r * left);
+        output[1] = input[0].filter(0.0, |r| r * right);
+        output
+    }
+
+    fn heap_footprint(&self) -> usize {
+        (self.history.capacity()
+            + self.left.capacity()
+            + self.right.capacity()
+            + self.old_left.capacity()
+            + self.old_right.capacity())
+            * std::mem::size_of::<f32>()
+    }
+}
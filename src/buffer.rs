@@ -107,6 +107,12 @@ impl<T: Float> Buffer<T> {
         self.slice.from_muts(&mut self.buffer)
     }
 
+    /// Size in bytes of the sample data allocated for this buffer.
+    #[inline]
+    pub fn footprint(&self) -> usize {
+        self.buffer.iter().map(|v| v.capacity()).sum::<usize>() * std::mem::size_of::<T>()
+    }
+
     /// Get reference to the vector of vectors.
     #[inline]
     pub fn vec(&self) -> &Vec<Vec<T>> {
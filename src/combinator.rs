@@ -11,12 +11,28 @@ use duplicate::duplicate_item;
 use numeric_array::typenum::*;
 
 /// Trait for multi-channel constants.
+/// Scalars and tuples of up to 10 elements can be converted to frames
+/// and back, via `FromFrame`.
+///
+/// ### Example
+/// ```
+/// use fundsp::hacker::*;
+/// let frame: Frame<f64, U3> = (1.0, 2.0, 3.0).convert();
+/// assert_eq!(<(f64, f64, f64)>::from_frame(&frame), (1.0, 2.0, 3.0));
+/// ```
 pub trait ConstantFrame: Clone + Sync + Send {
     type Sample: Float;
     type Size: Size<Self::Sample>;
+    /// Convert into a frame.
     fn convert(self) -> Frame<Self::Sample, Self::Size>;
 }
 
+/// Trait for multi-channel constants that can be converted back from frames.
+pub trait FromFrame: ConstantFrame {
+    /// Convert a frame into this type.
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self;
+}
+
 impl<T: Float, N: Size<T>> ConstantFrame for Frame<T, N> {
     type Sample = T;
     type Size = N;
@@ -25,6 +41,12 @@ impl<T: Float, N: Size<T>> ConstantFrame for Frame<T, N> {
     }
 }
 
+impl<T: Float, N: Size<T>> FromFrame for Frame<T, N> {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        frame.clone()
+    }
+}
+
 impl<T: Float> ConstantFrame for T {
     type Sample = T;
     type Size = U1;
@@ -33,6 +55,12 @@ impl<T: Float> ConstantFrame for T {
     }
 }
 
+impl<T: Float> FromFrame for T {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        frame[0]
+    }
+}
+
 impl<T: Float> ConstantFrame for (T, T) {
     type Sample = T;
     type Size = U2;
@@ -41,6 +69,12 @@ impl<T: Float> ConstantFrame for (T, T) {
     }
 }
 
+impl<T: Float> FromFrame for (T, T) {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        (frame[0], frame[1])
+    }
+}
+
 impl<T: Float> ConstantFrame for (T, T, T) {
     type Sample = T;
     type Size = U3;
@@ -49,6 +83,12 @@ impl<T: Float> ConstantFrame for (T, T, T) {
     }
 }
 
+impl<T: Float> FromFrame for (T, T, T) {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        (frame[0], frame[1], frame[2])
+    }
+}
+
 impl<T: Float> ConstantFrame for (T, T, T, T) {
     type Sample = T;
     type Size = U4;
@@ -57,6 +97,12 @@ impl<T: Float> ConstantFrame for (T, T, T, T) {
     }
 }
 
+impl<T: Float> FromFrame for (T, T, T, T) {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        (frame[0], frame[1], frame[2], frame[3])
+    }
+}
+
 impl<T: Float> ConstantFrame for (T, T, T, T, T) {
     type Sample = T;
     type Size = U5;
@@ -65,6 +111,12 @@ impl<T: Float> ConstantFrame for (T, T, T, T, T) {
     }
 }
 
+impl<T: Float> FromFrame for (T, T, T, T, T) {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        (frame[0], frame[1], frame[2], frame[3], frame[4])
+    }
+}
+
 impl<T: Float> ConstantFrame for (T, T, T, T, T, T) {
     type Sample = T;
     type Size = U6;
@@ -73,6 +125,12 @@ impl<T: Float> ConstantFrame for (T, T, T, T, T, T) {
     }
 }
 
+impl<T: Float> FromFrame for (T, T, T, T, T, T) {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        (frame[0], frame[1], frame[2], frame[3], frame[4], frame[5])
+    }
+}
+
 impl<T: Float> ConstantFrame for (T, T, T, T, T, T, T) {
     type Sample = T;
     type Size = U7;
@@ -81,6 +139,14 @@ impl<T: Float> ConstantFrame for (T, T, T, T, T, T, T) {
     }
 }
 
+impl<T: Float> FromFrame for (T, T, T, T, T, T, T) {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        (
+            frame[0], frame[1], frame[2], frame[3], frame[4], frame[5], frame[6],
+        )
+    }
+}
+
 impl<T: Float> ConstantFrame for (T, T, T, T, T, T, T, T) {
     type Sample = T;
     type Size = U8;
@@ -92,6 +158,14 @@ impl<T: Float> ConstantFrame for (T, T, T, T, T, T, T, T) {
     }
 }
 
+impl<T: Float> FromFrame for (T, T, T, T, T, T, T, T) {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        (
+            frame[0], frame[1], frame[2], frame[3], frame[4], frame[5], frame[6], frame[7],
+        )
+    }
+}
+
 impl<T: Float> ConstantFrame for (T, T, T, T, T, T, T, T, T) {
     type Sample = T;
     type Size = U9;
@@ -103,6 +177,15 @@ impl<T: Float> ConstantFrame for (T, T, T, T, T, T, T, T, T) {
     }
 }
 
+impl<T: Float> FromFrame for (T, T, T, T, T, T, T, T, T) {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        (
+            frame[0], frame[1], frame[2], frame[3], frame[4], frame[5], frame[6], frame[7],
+            frame[8],
+        )
+    }
+}
+
 impl<T: Float> ConstantFrame for (T, T, T, T, T, T, T, T, T, T) {
     type Sample = T;
     type Size = U10;
@@ -114,6 +197,15 @@ impl<T: Float> ConstantFrame for (T, T, T, T, T, T, T, T, T, T) {
     }
 }
 
+impl<T: Float> FromFrame for (T, T, T, T, T, T, T, T, T, T) {
+    fn from_frame(frame: &Frame<Self::Sample, Self::Size>) -> Self {
+        (
+            frame[0], frame[1], frame[2], frame[3], frame[4], frame[5], frame[6], frame[7],
+            frame[8], frame[9],
+        )
+    }
+}
+
 /// Trait for 1-way/2-way distinctions, such as symmetric/asymmetric response times.
 pub trait ScalarOrPair: Clone + Default + Send + Sync {
     type Sample: Float;
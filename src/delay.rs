@@ -132,6 +132,136 @@ impl<T: Float> AudioNode for Delay<T> {
         });
         output
     }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer.capacity() * std::mem::size_of::<T>()
+    }
+}
+
+/// Stereo feedback delay with independent channel delay times.
+/// Each delayed channel is fed back into itself with gain `feedback`
+/// and into the opposite channel with gain `cross_feedback`.
+/// Only the delayed signal is output.
+/// Allocates: the delay lines.
+/// - Input 0: left input
+/// - Input 1: right input
+/// - Output 0: left delayed signal
+/// - Output 1: right delayed signal
+#[derive(Clone)]
+pub struct StereoDelay<T: Float> {
+    left: Vec<T>,
+    right: Vec<T>,
+    left_i: usize,
+    right_i: usize,
+    left_time: f64,
+    right_time: f64,
+    feedback: T,
+    cross_feedback: T,
+    sample_rate: f64,
+}
+
+impl<T: Float> StereoDelay<T> {
+    /// Create a new stereo delay. Delay times are in seconds and are
+    /// rounded to the nearest sample. The minimum delay is one sample.
+    pub fn new(left_time: f64, right_time: f64, feedback: T, cross_feedback: T) -> Self {
+        let mut node = StereoDelay {
+            left: vec![],
+            right: vec![],
+            left_i: 0,
+            right_i: 0,
+            left_time,
+            right_time,
+            feedback,
+            cross_feedback,
+            sample_rate: 0.0,
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Feedback amount into the same channel.
+    pub fn feedback(&self) -> T {
+        self.feedback
+    }
+
+    /// Set feedback amount into the same channel.
+    pub fn set_feedback(&mut self, feedback: T) {
+        self.feedback = feedback;
+    }
+
+    /// Feedback amount into the opposite channel.
+    pub fn cross_feedback(&self) -> T {
+        self.cross_feedback
+    }
+
+    /// Set feedback amount into the opposite channel.
+    pub fn set_cross_feedback(&mut self, cross_feedback: T) {
+        self.cross_feedback = cross_feedback;
+    }
+}
+
+impl<T: Float> AudioNode for StereoDelay<T> {
+    const ID: u64 = 82;
+    type Sample = T;
+    type Inputs = U2;
+    type Outputs = U2;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.left_i = 0;
+        self.right_i = 0;
+        self.left.fill(T::zero());
+        self.right.fill(T::zero());
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            let left_length = max(1.0, round(self.left_time * sample_rate));
+            let right_length = max(1.0, round(self.right_time * sample_rate));
+            self.left.resize(left_length as usize, T::zero());
+            self.right.resize(right_length as usize, T::zero());
+            self.reset();
+        }
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let left = self.left[self.left_i];
+        let right = self.right[self.right_i];
+        self.left[self.left_i] = input[0] + left * self.feedback + right * self.cross_feedback;
+        self.right[self.right_i] = input[1] + right * self.feedback + left * self.cross_feedback;
+        self.left_i += 1;
+        if self.left_i >= self.left.len() {
+            self.left_i = 0;
+        }
+        self.right_i += 1;
+        if self.right_i >= self.right.len() {
+            self.right_i = 0;
+        }
+        [left, right].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        let left_latency = self.left.len() as f64;
+        let right_latency = self.right.len() as f64;
+        if self.cross_feedback == T::zero() {
+            output[0] = input[0].distort(left_latency);
+            output[1] = input[1].distort(right_latency);
+        } else {
+            output[0] = input[0].combine_nonlinear(input[1], left_latency);
+            output[1] = input[1].combine_nonlinear(input[0], right_latency);
+        }
+        output
+    }
+
+    fn heap_footprint(&self) -> usize {
+        (self.left.capacity() + self.right.capacity()) * std::mem::size_of::<T>()
+    }
 }
 
 /// Variable delay line using cubic interpolation.
@@ -241,4 +371,122 @@ where
         output[0] = input[0].distort(self.min_delay.to_f64() * self.sample_rate.to_f64());
         output
     }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer.capacity() * std::mem::size_of::<T>()
+    }
+}
+
+/// Variable delay line using cubic Hermite interpolation.
+/// The delay time is clamped between one sample and `max_delay` seconds.
+/// Allocates: the delay line.
+/// - Input 0: input
+/// - Input 1: delay amount in seconds.
+/// - Output 0: delayed input
+#[derive(Clone)]
+pub struct DelayVar<T: Float> {
+    buffer: Vec<T>,
+    i: usize,
+    sample_rate: f64,
+    max_delay: f64,
+    /// Maximum delay in samples.
+    max_samples: T,
+}
+
+impl<T: Float> DelayVar<T> {
+    /// Create a variable delay line with maximum delay `max_delay` seconds.
+    pub fn new(max_delay: f64) -> Self {
+        assert!(max_delay >= 0.0);
+        let mut node = DelayVar {
+            buffer: vec![],
+            i: 0,
+            sample_rate: 0.0,
+            max_delay,
+            max_samples: T::one(),
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Maximum delay in seconds.
+    pub fn max_delay(&self) -> f64 {
+        self.max_delay
+    }
+
+    /// Length of the delay line in samples at the current sample rate.
+    /// Leaves room for the interpolation points past the maximum delay.
+    fn buffer_length(&self) -> usize {
+        let length = ceil(self.max_delay * self.sample_rate) as usize + 3;
+        length.next_power_of_two()
+    }
+}
+
+impl<T: Float> AudioNode for DelayVar<T> {
+    const ID: u64 = 126;
+    type Sample = T;
+    type Inputs = U2;
+    type Outputs = U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.i = 0;
+        self.buffer.fill(T::zero());
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            self.max_samples = T::from_f64(max(1.0, self.max_delay * sample_rate));
+            self.allocate();
+            self.reset();
+        }
+    }
+
+    fn allocate(&mut self) {
+        let length = self.buffer_length();
+        if self.buffer.len() != length {
+            self.buffer.resize(length, T::zero());
+        }
+    }
+
+    fn is_allocated(&self) -> bool {
+        self.buffer.len() == self.buffer_length()
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let mask = self.buffer.len() - 1;
+        self.buffer[self.i] = input[0];
+        let delay = clamp(
+            T::one(),
+            self.max_samples,
+            input[1] * T::from_f64(self.sample_rate),
+        );
+        let delay_floor = delay.to_f64() as usize;
+        let delay_d = delay - T::new(delay_floor as i64);
+        // Samples are indexed backwards in time: `i1` is delayed by `delay_floor` samples.
+        let i1 = self.i + self.buffer.len() - delay_floor;
+        let output = spline(
+            self.buffer[(i1 + 1) & mask],
+            self.buffer[i1 & mask],
+            self.buffer[(i1 - 1) & mask],
+            self.buffer[(i1 - 2) & mask],
+            delay_d,
+        );
+        self.i = (self.i + 1) & mask;
+        [output].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = input[0].distort(1.0);
+        output
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer.capacity() * std::mem::size_of::<T>()
+    }
 }
@@ -8,6 +8,7 @@ use super::shared::*;
 use super::signal::*;
 use super::*;
 use numeric_array::typenum::*;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// Binary operation for the monoidal reducer.
@@ -95,6 +96,11 @@ where
         self.length
     }
 
+    /// Size of the buffer in bytes.
+    pub fn footprint(&self) -> usize {
+        self.buffer.capacity() * std::mem::size_of::<T>()
+    }
+
     #[inline]
     pub fn total(&self) -> T {
         self.buffer[1]
@@ -117,7 +123,29 @@ where
     }
 }
 
-/// Look-ahead limiter.
+/// Static gain curve of a compressor: gain in dB to apply to a signal
+/// at `level_db` for the given `threshold_db` and `ratio`.
+/// Levels above threshold are reduced by `(level - threshold) * (1 - 1 / ratio)`.
+/// The transition is smoothed over a soft knee `knee_db` wide, centered at threshold,
+/// using quadratic interpolation. A knee of 0 is a hard knee.
+/// The returned gain is always non-positive.
+#[inline]
+pub fn compressor_gain<T: Real>(level_db: T, threshold_db: T, ratio: T, knee_db: T) -> T {
+    let over = level_db - threshold_db;
+    let slope = T::one() / ratio - T::one();
+    let half_knee = knee_db * T::from_f64(0.5);
+    if over <= -half_knee {
+        T::zero()
+    } else if over < half_knee {
+        slope * squared(over + half_knee) / (knee_db * T::new(2))
+    } else {
+        slope * over
+    }
+}
+
+/// Look-ahead limiter. The signal is delayed by the look-ahead time
+/// and gain reduction is derived from the peak in the look-ahead window.
+/// Latency is equal to the look-ahead.
 #[derive(Clone)]
 pub struct Limiter<T, N, S>
 where
@@ -149,6 +177,17 @@ where
         }
     }
 
+    /// Look-ahead time in seconds.
+    pub fn lookahead(&self) -> f64 {
+        self.lookahead
+    }
+
+    /// Look-ahead in samples at the current sample rate.
+    /// This is also the latency of the limiter.
+    pub fn lookahead_samples(&self) -> usize {
+        self.reducer.length()
+    }
+
     fn buffer_length(sample_rate: f64, lookahead: f64) -> usize {
         max(1, round(sample_rate * lookahead) as usize)
     }
@@ -157,6 +196,8 @@ where
         ReduceBuffer::new(Self::buffer_length(sample_rate, lookahead), Maximum::new())
     }
 
+    /// Create new limiter. `time` is either a single time in seconds
+    /// or an `(attack, release)` pair. Look-ahead is equal to the attack time.
     pub fn new(sample_rate: f64, time: S) -> Self {
         let (lookahead, release) = time.broadcast();
         Limiter {
@@ -228,7 +269,10 @@ where
             self.follower
                 .filter_mono(max(T::one(), self.reducer.total() * T::from_f64(1.10)));
             self.advance();
-            let limit = self.follower.value();
+            // The outgoing frame has just left the look-ahead window.
+            // Never apply less reduction than it needs, so peaks cannot overshoot.
+            let peak = output.iter().fold(T::zero(), |amp, &x| max(amp, abs(x)));
+            let limit = max(self.follower.value(), peak);
             output * Frame::splat(T::from_f64(1.0) / limit)
         }
     }
@@ -248,6 +292,172 @@ where
                 .reserve(self.reducer.length() - self.buffer.capacity());
         }
     }
+
+    fn is_allocated(&self) -> bool {
+        self.buffer.capacity() >= self.reducer.length()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.reducer.footprint() + self.buffer.capacity() * std::mem::size_of::<Frame<T, N>>()
+    }
+}
+
+/// Default RMS averaging time of `Compressor` in seconds.
+const COMPRESSOR_WINDOW: f64 = 0.01;
+
+/// RMS compressor with `N` linked channels. Levels above threshold are reduced
+/// by `(level - threshold) * (1 - 1 / ratio)` dB, as in `compressor_gain`.
+/// The detector measures the mean square of the loudest channel,
+/// averaged over a configurable window. Gain changes follow attack and release ballistics.
+/// The number of outputs `M` is either `N`, or `N + 1` to output gain reduction in dB
+/// (a non-positive number) after the signal channels for metering.
+/// Setting: (threshold in dB, ratio).
+/// - Input(s): signal
+/// - Output(s): compressed signal
+/// - Output N (optional): gain reduction in dB
+#[derive(Clone)]
+pub struct Compressor<T: Real, N: Size<T>, M: Size<T>> {
+    _marker: std::marker::PhantomData<(N, M)>,
+    threshold_db: T,
+    ratio: T,
+    attack: f64,
+    release: f64,
+    window: f64,
+    sample_rate: f64,
+    attack_coeff: T,
+    release_coeff: T,
+    window_coeff: T,
+    /// Averaged mean square level.
+    mean_square: T,
+    /// Current gain in dB.
+    gain_db: T,
+}
+
+impl<T: Real, N: Size<T>, M: Size<T>> Compressor<T, N, M> {
+    /// Create new compressor. Levels above `threshold_db` are compressed by `ratio`.
+    /// `attack` and `release` are the times in seconds for gain reduction
+    /// to increase and decrease, respectively.
+    pub fn new(sample_rate: f64, threshold_db: T, ratio: T, attack: f64, release: f64) -> Self {
+        assert!(M::USIZE == N::USIZE || M::USIZE == N::USIZE + 1);
+        assert!(ratio >= T::one());
+        let mut node = Self {
+            _marker: std::marker::PhantomData,
+            threshold_db,
+            ratio,
+            attack,
+            release,
+            window: COMPRESSOR_WINDOW,
+            sample_rate,
+            attack_coeff: T::zero(),
+            release_coeff: T::zero(),
+            window_coeff: T::zero(),
+            mean_square: T::zero(),
+            gain_db: T::zero(),
+        };
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Threshold in dB.
+    pub fn threshold_db(&self) -> T {
+        self.threshold_db
+    }
+
+    /// Compression ratio.
+    pub fn ratio(&self) -> T {
+        self.ratio
+    }
+
+    /// Set threshold in dB and compression ratio (`ratio` >= 1).
+    pub fn set_threshold_ratio(&mut self, threshold_db: T, ratio: T) {
+        assert!(ratio >= T::one());
+        self.threshold_db = threshold_db;
+        self.ratio = ratio;
+    }
+
+    /// RMS averaging time in seconds. The default is 10 ms.
+    pub fn window(&self) -> f64 {
+        self.window
+    }
+
+    /// Set RMS averaging time in seconds. Longer windows respond
+    /// more slowly to transients but reduce ripple from low frequencies.
+    pub fn set_window(&mut self, window: f64) {
+        assert!(window > 0.0);
+        self.window = window;
+        self.set_sample_rate(self.sample_rate);
+    }
+
+    /// Current gain reduction in dB. This is zero or negative.
+    pub fn gain_db(&self) -> T {
+        self.gain_db
+    }
+
+    /// Smoothing coefficient for time constant `time` in seconds.
+    fn coeff(&self, time: f64) -> T {
+        if time > 0.0 {
+            T::from_f64(1.0 - exp(-1.0 / (time * self.sample_rate)))
+        } else {
+            T::one()
+        }
+    }
+}
+
+impl<T: Real, N: Size<T>, M: Size<T>> AudioNode for Compressor<T, N, M> {
+    const ID: u64 = 124;
+    type Sample = T;
+    type Inputs = N;
+    type Outputs = M;
+    type Setting = (T, T);
+
+    fn set(&mut self, (threshold_db, ratio): Self::Setting) {
+        self.set_threshold_ratio(threshold_db, ratio);
+    }
+
+    fn reset(&mut self) {
+        self.mean_square = T::zero();
+        self.gain_db = T::zero();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        self.attack_coeff = self.coeff(self.attack);
+        self.release_coeff = self.coeff(self.release);
+        self.window_coeff = self.coeff(self.window);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let square = input.iter().fold(T::zero(), |acc, &x| max(acc, x * x));
+        self.mean_square += (square - self.mean_square) * self.window_coeff;
+        let level_db = amp_db(sqrt(self.mean_square));
+        let target = compressor_gain(level_db, self.threshold_db, self.ratio, T::zero());
+        let coeff = if target < self.gain_db {
+            self.attack_coeff
+        } else {
+            self.release_coeff
+        };
+        self.gain_db += (target - self.gain_db) * coeff;
+        let gain = db_amp(self.gain_db);
+        Frame::generate(|i| {
+            if i < N::USIZE {
+                input[i] * gain
+            } else {
+                self.gain_db
+            }
+        })
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        for i in 0..N::USIZE {
+            output[i] = input[i].distort(0.0);
+        }
+        output
+    }
 }
 
 /// Transient filter. Multiply the signal with a fade-in curve.
@@ -558,3 +768,141 @@ impl<T: Real + Atomic> AudioNode for Monitor<T> {
         input.clone()
     }
 }
+
+/// Over counter state shared between frontend and backend.
+#[derive(Default)]
+struct OversState {
+    /// Number of overs.
+    count: AtomicU64,
+    /// Number of samples since the last over, or `u64::MAX` if there have been none.
+    since: AtomicU64,
+    /// Sample rate stored as bits.
+    sample_rate: AtomicU64,
+}
+
+/// Frontend for an over counter. Reads the number of samples that exceeded
+/// the threshold and the time since the last one.
+#[derive(Clone)]
+pub struct OversMeter {
+    state: Arc<OversState>,
+}
+
+impl OversMeter {
+    /// Number of samples so far whose absolute value exceeded the threshold.
+    pub fn count(&self) -> u64 {
+        self.state.count.load(Ordering::Relaxed)
+    }
+
+    /// Time in seconds since the last over, or `None` if there have been no overs.
+    pub fn since_last(&self) -> Option<f64> {
+        let since = self.state.since.load(Ordering::Relaxed);
+        if since == u64::MAX {
+            None
+        } else {
+            let sample_rate = f64::from_bits(self.state.sample_rate.load(Ordering::Relaxed));
+            Some(since as f64 / sample_rate)
+        }
+    }
+}
+
+/// Pass through input unchanged.
+/// Counts samples whose absolute value exceeds a threshold
+/// and publishes the count to an `OversMeter` frontend.
+#[derive(Clone)]
+pub struct Overs<T: Float> {
+    threshold: T,
+    count: u64,
+    since: u64,
+    state: Arc<OversState>,
+}
+
+impl<T: Float> Overs<T> {
+    /// Create a new over counter with amplitude `threshold`.
+    /// Returns (frontend, backend).
+    pub fn new(sample_rate: f64, threshold: T) -> (OversMeter, Self) {
+        let state = Arc::new(OversState::default());
+        let mut node = Self {
+            threshold,
+            count: 0,
+            since: u64::MAX,
+            state: Arc::clone(&state),
+        };
+        node.set_sample_rate(sample_rate);
+        node.publish();
+        (OversMeter { state }, node)
+    }
+
+    /// Amplitude threshold.
+    pub fn threshold(&self) -> T {
+        self.threshold
+    }
+
+    #[inline]
+    fn count_sample(&mut self, x: T) {
+        if abs(x) > self.threshold {
+            self.count += 1;
+            self.since = 0;
+        } else {
+            self.since = self.since.saturating_add(1);
+        }
+    }
+
+    #[inline]
+    fn publish(&self) {
+        self.state.count.store(self.count, Ordering::Relaxed);
+        self.state.since.store(self.since, Ordering::Relaxed);
+    }
+}
+
+impl<T: Float> AudioNode for Overs<T> {
+    const ID: u64 = 86;
+    type Sample = T;
+    type Inputs = U1;
+    type Outputs = U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.count = 0;
+        self.since = u64::MAX;
+        self.publish();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.state
+            .sample_rate
+            .store(sample_rate.to_bits(), Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        self.count_sample(input[0]);
+        self.publish();
+        *input
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        for x in input[0][..size].iter() {
+            self.count_sample(*x);
+        }
+        // For efficiency, publish only once per block.
+        self.publish();
+        output[0][..size].clone_from_slice(&input[0][..size]);
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        input.clone()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        // The counters are shared with `OversMeter`, but the node allocates them.
+        std::mem::size_of::<OversState>()
+    }
+}
@@ -1,8 +1,9 @@
-//! Subsampled control node.
+//! Control nodes: subsampled envelopes, linear ramps and test signals.
 
 use super::audionode::*;
 use super::combinator::*;
 use super::math::*;
+use super::signal::*;
 use super::*;
 use numeric_array::*;
 use std::marker::PhantomData;
@@ -365,3 +366,234 @@ where
         self.t_hash = hash;
     }
 }
+
+/// Linear ramp from one value to another over a fixed duration.
+/// The output then holds the final value until the node is reset.
+/// - Output 0: ramp value
+#[derive(Clone)]
+pub struct Ramp<T: Float> {
+    from: T,
+    to: T,
+    /// Ramp duration in seconds.
+    duration: f64,
+    /// Ramp duration in samples.
+    samples: f64,
+    /// Current position in samples.
+    position: f64,
+    sample_rate: f64,
+}
+
+impl<T: Float> Ramp<T> {
+    /// Create new ramp from `from` to `to` in `duration` seconds.
+    pub fn new(sample_rate: f64, from: T, to: T, duration: f64) -> Self {
+        assert!(duration >= 0.0);
+        let mut node = Self {
+            from,
+            to,
+            duration,
+            samples: 0.0,
+            position: 0.0,
+            sample_rate,
+        };
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Start value.
+    pub fn from(&self) -> T {
+        self.from
+    }
+
+    /// End value.
+    pub fn to(&self) -> T {
+        self.to
+    }
+
+    /// Ramp duration in seconds.
+    pub fn duration(&self) -> f64 {
+        self.duration
+    }
+
+    /// Current ramp value.
+    #[inline]
+    fn value(&self) -> T {
+        if self.position >= self.samples {
+            self.to
+        } else {
+            self.from + (self.to - self.from) * T::from_f64(self.position / self.samples)
+        }
+    }
+}
+
+impl<T: Float> AudioNode for Ramp<T> {
+    const ID: u64 = 102;
+    type Sample = T;
+    type Inputs = typenum::U0;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.position = 0.0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        // Keep the position in time.
+        if self.samples > 0.0 {
+            self.position *= sample_rate / self.sample_rate;
+        }
+        self.sample_rate = sample_rate;
+        self.samples = self.duration * sample_rate;
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        _input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let value = self.value();
+        if self.position < self.samples {
+            self.position += 1.0;
+        }
+        [value].into()
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        _input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        if self.position >= self.samples {
+            output[0][..size].fill(self.to);
+        } else {
+            for x in output[0][..size].iter_mut() {
+                *x = self.value();
+                if self.position < self.samples {
+                    self.position += 1.0;
+                }
+            }
+        }
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = Signal::Latency(0.0);
+        output
+    }
+}
+
+/// Unit impulse: a single sample of 1.0 after reset, followed by zeros.
+/// - Output 0: impulse
+#[derive(Clone)]
+pub struct Impulse<T: Float> {
+    value: T,
+}
+
+impl<T: Float> Impulse<T> {
+    /// Create new impulse generator.
+    pub fn new() -> Self {
+        Self { value: T::one() }
+    }
+}
+
+impl<T: Float> Default for Impulse<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> AudioNode for Impulse<T> {
+    const ID: u64 = 104;
+    type Sample = T;
+    type Inputs = typenum::U0;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.value = T::one();
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        _input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let value = self.value;
+        self.value = T::zero();
+        [value].into()
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        _input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        if size > 0 {
+            output[0][..size].fill(T::zero());
+            output[0][0] = self.value;
+            self.value = T::zero();
+        }
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = Signal::Latency(0.0);
+        output
+    }
+}
+
+/// Unit step: 0.0 on the first sample after reset, then 1.0 from there on.
+/// - Output 0: step
+#[derive(Default, Clone)]
+pub struct Step<T: Float> {
+    value: T,
+}
+
+impl<T: Float> Step<T> {
+    /// Create new step generator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T: Float> AudioNode for Step<T> {
+    const ID: u64 = 105;
+    type Sample = T;
+    type Inputs = typenum::U0;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.value = T::zero();
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        _input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let value = self.value;
+        self.value = T::one();
+        [value].into()
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        _input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        if size > 0 {
+            output[0][..size].fill(T::one());
+            output[0][0] = self.value;
+            self.value = T::one();
+        }
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = Signal::Latency(0.0);
+        output
+    }
+}
@@ -7,6 +7,7 @@ use super::math::*;
 use super::signal::*;
 use super::*;
 use duplicate::duplicate_item;
+use num_complex::Complex64;
 use std::marker::PhantomData;
 
 /// Diffusive Hadamard feedback matrix. The number of channels must be a power of two.
@@ -168,6 +169,14 @@ where
     fn allocate(&mut self) {
         self.x.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.x.heap_footprint()
+    }
 }
 
 /// Mix back output of contained node `X` to its input, with extra feedback processing `Y`.
@@ -274,6 +283,14 @@ where
     fn allocate(&mut self) {
         self.x.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.x.heap_footprint() + self.y.heap_footprint()
+    }
 }
 
 #[duplicate_item(
@@ -453,7 +470,292 @@ impl AudioUnit48 for Feedback48 {
         std::mem::size_of::<Self>()
     }
 
+    fn total_footprint(&self) -> usize {
+        let samples = self.feedback.iter().map(|v| v.capacity()).sum::<usize>()
+            + self.tick_buffer.capacity()
+            + self.tick_buffer2.capacity();
+        std::mem::size_of::<Self>()
+            + samples * std::mem::size_of::<f48>()
+            + self.buffer.footprint()
+            + self.x.total_footprint()
+    }
+
+    fn allocate(&mut self) {
+        self.x.allocate();
+    }
+
+    fn is_allocated(&self) -> bool {
+        self.x.is_allocated()
+    }
+}
+
+/// Amplitude of the dither injected by `LoopStabilizer` (-360 dB).
+const STABILIZER_DITHER: f64 = 1.0e-18;
+
+/// Output magnitude below which `Stabilizer` flushes samples to zero (-200 dB).
+const STABILIZER_FLUSH: f64 = 1.0e-10;
+
+/// Cutoff frequency (in Hz) of the `LoopStabilizer` DC blocking filters.
+const STABILIZER_CUTOFF: f64 = 5.0;
+
+/// Feedback loop processing that keeps a recursive structure out of
+/// the denormal range. Each channel is highpassed to prevent DC buildup
+/// and receives a tiny amount of decorrelated dither.
+/// Place it in the loopback path of `Feedback2`, where it touches
+/// only the fed-back signal.
+#[derive(Clone)]
+pub struct LoopStabilizer<N, T>
+where
+    N: Size<T>,
+    T: Float,
+{
+    /// Previous DC blocker inputs.
+    x1: Frame<T, N>,
+    /// Previous DC blocker outputs.
+    y1: Frame<T, N>,
+    /// DC blocker feedback coefficient.
+    coeff: T,
+    /// Dither generator seed.
+    seed: u64,
+    /// Dither generator state.
+    state: u64,
+    sample_rate: f64,
+}
+
+impl<N, T> LoopStabilizer<N, T>
+where
+    N: Size<T>,
+    T: Float,
+{
+    /// Create new loop stabilizer.
+    pub fn new() -> Self {
+        let mut node = LoopStabilizer {
+            x1: Frame::default(),
+            y1: Frame::default(),
+            coeff: T::zero(),
+            seed: 0,
+            state: 0,
+            sample_rate: DEFAULT_SR,
+        };
+        let hash = node.ping(true, AttoHash::new(Self::ID));
+        node.ping(false, hash);
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Next dither sample. Successive calls are decorrelated,
+    /// so each channel receives an independent sequence.
+    #[inline]
+    fn dither(&mut self) -> T {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        if self.state >> 63 == 0 {
+            T::from_f64(STABILIZER_DITHER)
+        } else {
+            T::from_f64(-STABILIZER_DITHER)
+        }
+    }
+}
+
+impl<N, T> Default for LoopStabilizer<N, T>
+where
+    N: Size<T>,
+    T: Float,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, T> AudioNode for LoopStabilizer<N, T>
+where
+    N: Size<T>,
+    T: Float,
+{
+    const ID: u64 = 131;
+    type Sample = T;
+    type Inputs = N;
+    type Outputs = N;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.x1 = Frame::default();
+        self.y1 = Frame::default();
+        self.state = self.seed;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        self.coeff = T::from_f64(1.0 - TAU * STABILIZER_CUTOFF / sample_rate);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let mut output = Frame::default();
+        for i in 0..N::USIZE {
+            // Dither is applied first so it keeps the DC blocker out of the denormal range, too.
+            let x = input[i] + self.dither();
+            let y = x - self.x1[i] + self.coeff * self.y1[i];
+            self.x1[i] = x;
+            self.y1[i] = y;
+            output[i] = y;
+        }
+        output
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        for channel in 0..N::USIZE {
+            let mut x1 = self.x1[channel];
+            let mut y1 = self.y1[channel];
+            for i in 0..size {
+                let x = input[channel][i] + self.dither();
+                y1 = x - x1 + self.coeff * y1;
+                x1 = x;
+                output[channel][i] = y1;
+            }
+            self.x1[channel] = x1;
+            self.y1[channel] = y1;
+        }
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.seed = hash;
+        self.state = hash;
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        let c = self.coeff.to_f64();
+        let f = frequency * TAU / self.sample_rate;
+        for i in 0..N::USIZE {
+            output[i] = input[i].filter(0.0, |r| {
+                let z1 = Complex64::from_polar(1.0, -f);
+                r * ((1.0 - z1) / (1.0 - c * z1))
+            });
+        }
+        output
+    }
+}
+
+/// Flushes outputs of node `X` with a magnitude below -200 dB to exactly zero,
+/// so a decaying tail ends in silence. Inputs are passed to `X` untouched.
+/// To keep the feedback paths of `X` out of the denormal range,
+/// build them with a `LoopStabilizer` in the loopback path.
+#[derive(Clone)]
+pub struct Stabilizer<T, X>
+where
+    T: Float,
+    X: AudioNode<Sample = T>,
+    X::Inputs: Size<T>,
+    X::Outputs: Size<T>,
+{
+    x: X,
+}
+
+impl<T, X> Stabilizer<T, X>
+where
+    T: Float,
+    X: AudioNode<Sample = T>,
+    X::Inputs: Size<T>,
+    X::Outputs: Size<T>,
+{
+    /// Create new stabilizer around node `x`.
+    pub fn new(x: X) -> Self {
+        let mut node = Stabilizer { x };
+        let hash = node.ping(true, AttoHash::new(Self::ID));
+        node.ping(false, hash);
+        node
+    }
+
+    /// Flush a tiny output sample to zero.
+    #[inline]
+    fn flush(x: T) -> T {
+        if abs(x) < T::from_f64(STABILIZER_FLUSH) {
+            T::zero()
+        } else {
+            x
+        }
+    }
+}
+
+impl<T, X> AudioNode for Stabilizer<T, X>
+where
+    T: Float,
+    X: AudioNode<Sample = T>,
+    X::Inputs: Size<T>,
+    X::Outputs: Size<T>,
+{
+    const ID: u64 = 99;
+    type Sample = T;
+    type Inputs = X::Inputs;
+    type Outputs = X::Outputs;
+    type Setting = X::Setting;
+
+    fn set(&mut self, setting: Self::Setting) {
+        self.x.set(setting);
+    }
+
+    fn reset(&mut self) {
+        self.x.reset();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.x.set_sample_rate(sample_rate);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let mut output = self.x.tick(input);
+        for y in output.iter_mut() {
+            *y = Self::flush(*y);
+        }
+        output
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        self.x.process(size, input, output);
+        for channel in output.iter_mut() {
+            for y in channel[..size].iter_mut() {
+                *y = Self::flush(*y);
+            }
+        }
+    }
+
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.x.ping(probe, hash.hash(Self::ID))
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        self.x.route(input, frequency)
+    }
+
     fn allocate(&mut self) {
         self.x.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.x.heap_footprint()
+    }
 }
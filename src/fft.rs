@@ -0,0 +1,188 @@
+//! Short-time Fourier transform (STFT) based spectral processing.
+
+use super::audionode::*;
+use super::math::*;
+use super::signal::*;
+use super::*;
+use num_complex::Complex64;
+use numeric_array::typenum::*;
+use rustfft::{Fft, FftPlanner};
+use std::sync::Arc;
+
+/// Windowed overlap-add framework for spectral processing.
+/// Input is split into Hann windowed frames of length `window`, spaced `hop` samples apart.
+/// Each frame is transformed to the frequency domain and handed to the spectral callback,
+/// which may modify the spectrum in place. The frame is then transformed back,
+/// windowed again and overlap-added to form the output.
+/// The callback receives all `window` bins, from DC up to (but excluding) the sample rate.
+/// Latency is `window` samples.
+/// - Input 0: audio
+/// - Output 0: processed audio
+#[derive(Clone)]
+pub struct Stft<T, S>
+where
+    T: Float,
+    S: FnMut(&mut [Complex64]) + Clone + Send + Sync,
+{
+    _marker: std::marker::PhantomData<T>,
+    /// Window length in samples.
+    window: usize,
+    /// Hop length in samples.
+    hop: usize,
+    /// Analysis and synthesis window. Immutable tables are shared between clones.
+    shape: Arc<Vec<f64>>,
+    /// Overlap-add normalization for each position within a hop.
+    normalize: Arc<Vec<f64>>,
+    /// Last `window` input samples, oldest first.
+    input: Vec<f64>,
+    /// Overlap-add accumulator.
+    accumulator: Vec<f64>,
+    /// Finished output samples for the current hop.
+    output: Vec<f64>,
+    /// Spectral work buffer.
+    spectrum: Vec<Complex64>,
+    /// FFT scratch buffer.
+    scratch: Vec<Complex64>,
+    /// Position within the current hop.
+    index: usize,
+    forward: Arc<dyn Fft<f64>>,
+    inverse: Arc<dyn Fft<f64>>,
+    spectral: S,
+}
+
+impl<T, S> Stft<T, S>
+where
+    T: Float,
+    S: FnMut(&mut [Complex64]) + Clone + Send + Sync,
+{
+    /// Create a new STFT processor with frame length `window` and frame spacing `hop`
+    /// (in samples). The hop length must divide the window length and be shorter than it.
+    /// A hop of a quarter window is typical.
+    pub fn new(window: usize, hop: usize, spectral: S) -> Self {
+        assert!(hop > 0 && hop < window && window % hop == 0);
+        let mut planner = FftPlanner::new();
+        let forward = planner.plan_fft_forward(window);
+        let inverse = planner.plan_fft_inverse(window);
+        let scratch_length = max(
+            forward.get_inplace_scratch_len(),
+            inverse.get_inplace_scratch_len(),
+        );
+        // Periodic Hann window.
+        let shape: Vec<f64> = (0..window)
+            .map(|i| 0.5 - 0.5 * cos(TAU * i as f64 / window as f64))
+            .collect();
+        // The window is applied twice, so overlapping frames sum to the squared window.
+        let normalize: Vec<f64> = (0..hop)
+            .map(|i| {
+                let total = (i..window)
+                    .step_by(hop)
+                    .fold(0.0, |acc, j| acc + squared(shape[j]));
+                if total > 0.0 {
+                    1.0 / total
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        Self {
+            _marker: std::marker::PhantomData,
+            window,
+            hop,
+            shape: Arc::new(shape),
+            normalize: Arc::new(normalize),
+            input: vec![0.0; window],
+            accumulator: vec![0.0; window],
+            output: vec![0.0; hop],
+            spectrum: vec![Complex64::new(0.0, 0.0); window],
+            scratch: vec![Complex64::new(0.0, 0.0); scratch_length],
+            index: 0,
+            forward,
+            inverse,
+            spectral,
+        }
+    }
+
+    /// Window length in samples.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Hop length in samples.
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// Process a complete frame and fill the output buffer.
+    fn process_frame(&mut self) {
+        for i in 0..self.window {
+            self.spectrum[i] = Complex64::new(self.input[i] * self.shape[i], 0.0);
+        }
+        self.forward
+            .process_with_scratch(&mut self.spectrum, &mut self.scratch);
+        (self.spectral)(&mut self.spectrum);
+        self.inverse
+            .process_with_scratch(&mut self.spectrum, &mut self.scratch);
+        // The inverse transform is not normalized.
+        let z = 1.0 / self.window as f64;
+        for i in 0..self.window {
+            self.accumulator[i] += self.spectrum[i].re * self.shape[i] * z;
+        }
+        for i in 0..self.hop {
+            self.output[i] = self.accumulator[i] * self.normalize[i];
+        }
+        self.accumulator.copy_within(self.hop.., 0);
+        self.accumulator[self.window - self.hop..].fill(0.0);
+        self.input.copy_within(self.hop.., 0);
+    }
+}
+
+impl<T, S> AudioNode for Stft<T, S>
+where
+    T: Float,
+    S: FnMut(&mut [Complex64]) + Clone + Send + Sync,
+{
+    const ID: u64 = 80;
+    type Sample = T;
+    type Inputs = U1;
+    type Outputs = U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.input.fill(0.0);
+        self.accumulator.fill(0.0);
+        self.output.fill(0.0);
+        self.index = 0;
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        self.input[self.window - self.hop + self.index] = input[0].to_f64();
+        let output = self.output[self.index];
+        self.index += 1;
+        if self.index == self.hop {
+            self.index = 0;
+            self.process_frame();
+        }
+        [convert(output)].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        // The spectral callback can be arbitrary, so we report only latency.
+        output[0] = input[0].distort(self.window as f64);
+        output
+    }
+
+    fn heap_footprint(&self) -> usize {
+        let reals = self.shape.capacity()
+            + self.normalize.capacity()
+            + self.input.capacity()
+            + self.accumulator.capacity()
+            + self.output.capacity();
+        let complexes = self.spectrum.capacity() + self.scratch.capacity();
+        reals * std::mem::size_of::<f64>() + complexes * std::mem::size_of::<Complex64>()
+    }
+}
@@ -9,6 +9,7 @@ use numeric_array::typenum::*;
 use numeric_array::*;
 
 #[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BiquadCoefs<F> {
     pub a1: F,
     pub a2: F,
@@ -20,6 +21,9 @@ pub struct BiquadCoefs<F> {
 impl<F: Real> BiquadCoefs<F> {
     /// Returns settings for a Butterworth lowpass filter.
     /// Cutoff is the -3 dB point of the filter in Hz.
+    /// The cutoff is prewarped for the bilinear transform, so the -3 dB point
+    /// is exact up to rounding at any cutoff below Nyquist. The rest of the response
+    /// is compressed toward Nyquist, where the filter has a double zero.
     pub fn butter_lowpass(sample_rate: F, cutoff: F) -> Self {
         let c = F::from_f64;
         let f: F = tan(cutoff * c(PI) / sample_rate);
@@ -32,21 +36,154 @@ impl<F: Real> BiquadCoefs<F> {
         Self { a1, a2, b0, b1, b2 }
     }
 
+    /// Returns settings for a Butterworth highpass filter.
+    /// Cutoff is the -3 dB point of the filter in Hz.
+    /// Like the lowpass, the cutoff is prewarped for the bilinear transform.
+    /// The filter has a double zero at DC.
+    pub fn butter_highpass(sample_rate: F, cutoff: F) -> Self {
+        let c = F::from_f64;
+        let f: F = tan(cutoff * c(PI) / sample_rate);
+        let a0r: F = c(1.0) / (c(1.0) + c(SQRT_2) * f + f * f);
+        let a1: F = (c(2.0) * f * f - c(2.0)) * a0r;
+        let a2: F = (c(1.0) - c(SQRT_2) * f + f * f) * a0r;
+        let b0: F = a0r;
+        let b1: F = c(-2.0) * b0;
+        let b2: F = b0;
+        Self { a1, a2, b0, b1, b2 }
+    }
+
     /// Returns settings for a constant-gain bandpass resonator.
     /// The center frequency is given in Hz.
     /// Bandwidth is the difference in Hz between -3 dB points of the filter response.
     /// The overall gain of the filter is independent of bandwidth.
+    /// The design is prewarped for the bilinear transform: the peak is exactly at the center
+    /// and the -3 dB points are exactly `bandwidth` Hz apart anywhere below Nyquist.
+    /// Near DC and Nyquist, the -3 dB points are no longer symmetric around the center.
     pub fn resonator(sample_rate: F, center: F, bandwidth: F) -> Self {
         let c = F::from_f64;
-        let r: F = exp(c(-PI) * bandwidth / sample_rate);
-        let a1: F = c(-2.0) * r * cos(c(TAU) * center / sample_rate);
-        let a2: F = r * r;
-        let b0: F = sqrt(c(1.0) - r * r) * c(0.5);
+        let t: F = tan(c(PI) * bandwidth / sample_rate);
+        let a2: F = (c(1.0) - t) / (c(1.0) + t);
+        let a1: F = -(c(1.0) + a2) * cos(c(TAU) * center / sample_rate);
+        let b0: F = sqrt(c(1.0) - a2) * c(0.5);
         let b1: F = c(0.0);
         let b2: F = -b0;
         Self { a1, a2, b0, b1, b2 }
     }
 
+    /// Returns settings for a bandpass resonator with unity gain at the center frequency.
+    /// The poles are the same as in `resonator`, but the gain is scaled using
+    /// the analytic response at `center`, so the peak stays at 0 dB
+    /// wherever the center is between DC and Nyquist.
+    pub fn resonator_normalized(sample_rate: F, center: F, bandwidth: F) -> Self {
+        let mut coefs = Self::resonator(sample_rate, center, bandwidth);
+        coefs.b0 = F::one();
+        coefs.b2 = -F::one();
+        let gain = coefs.response((center / sample_rate).to_f64()).norm();
+        coefs.b0 = F::from_f64(1.0 / gain);
+        coefs.b2 = -coefs.b0;
+        coefs
+    }
+
+    /// Returns settings for a low shelf from the Audio EQ Cookbook.
+    /// The shelf midpoint frequency is given in Hz and the gain at DC in dB.
+    /// `slope` in 0...1 sets the steepness of the transition;
+    /// 1 is the steepest slope that keeps the response monotonic.
+    pub fn lowshelf_slope(sample_rate: F, frequency: F, gain_db: F, slope: F) -> Self {
+        let c = F::from_f64;
+        let a: F = db_amp(gain_db * c(0.5));
+        let omega: F = c(TAU) * frequency / sample_rate;
+        let alpha: F =
+            sin(omega) * c(0.5) * sqrt((a + c(1.0) / a) * (c(1.0) / slope - c(1.0)) + c(2.0));
+        Self::lowshelf_alpha(a, omega, alpha)
+    }
+
+    /// Returns settings for a high shelf from the Audio EQ Cookbook.
+    /// The shelf midpoint frequency is given in Hz and the gain at Nyquist in dB.
+    /// `slope` in 0...1 sets the steepness of the transition;
+    /// 1 is the steepest slope that keeps the response monotonic.
+    pub fn highshelf_slope(sample_rate: F, frequency: F, gain_db: F, slope: F) -> Self {
+        let c = F::from_f64;
+        let a: F = db_amp(gain_db * c(0.5));
+        let omega: F = c(TAU) * frequency / sample_rate;
+        let alpha: F =
+            sin(omega) * c(0.5) * sqrt((a + c(1.0) / a) * (c(1.0) / slope - c(1.0)) + c(2.0));
+        Self::highshelf_alpha(a, omega, alpha)
+    }
+
+    /// Returns settings for a low shelf from the Audio EQ Cookbook, parameterized by Q.
+    /// The shelf midpoint frequency `cutoff` is given in Hz and the gain at DC in dB.
+    /// The response is unity well above the cutoff.
+    /// A Q of 1/sqrt(2) is the steepest transition that keeps the response monotonic;
+    /// higher values add a resonant bump near the cutoff.
+    pub fn lowshelf_q(sample_rate: F, cutoff: F, q: F, gain_db: F) -> Self {
+        let c = F::from_f64;
+        let a: F = db_amp(gain_db * c(0.5));
+        let omega: F = c(TAU) * cutoff / sample_rate;
+        Self::lowshelf_alpha(a, omega, sin(omega) / (c(2.0) * q))
+    }
+
+    /// Returns settings for a high shelf from the Audio EQ Cookbook, parameterized by Q.
+    /// The shelf midpoint frequency `cutoff` is given in Hz and the gain at Nyquist in dB.
+    /// The response is unity well below the cutoff.
+    /// A Q of 1/sqrt(2) is the steepest transition that keeps the response monotonic;
+    /// higher values add a resonant bump near the cutoff.
+    pub fn highshelf_q(sample_rate: F, cutoff: F, q: F, gain_db: F) -> Self {
+        let c = F::from_f64;
+        let a: F = db_amp(gain_db * c(0.5));
+        let omega: F = c(TAU) * cutoff / sample_rate;
+        Self::highshelf_alpha(a, omega, sin(omega) / (c(2.0) * q))
+    }
+
+    /// Low shelf from square root gain `a`, normalized frequency `omega` and bandwidth term `alpha`.
+    fn lowshelf_alpha(a: F, omega: F, alpha: F) -> Self {
+        let c = F::from_f64;
+        let cos_omega: F = cos(omega);
+        let beta: F = c(2.0) * sqrt(a) * alpha;
+        let a0r: F = c(1.0) / ((a + c(1.0)) + (a - c(1.0)) * cos_omega + beta);
+        let a1: F = c(-2.0) * ((a - c(1.0)) + (a + c(1.0)) * cos_omega) * a0r;
+        let a2: F = ((a + c(1.0)) + (a - c(1.0)) * cos_omega - beta) * a0r;
+        let b0: F = a * ((a + c(1.0)) - (a - c(1.0)) * cos_omega + beta) * a0r;
+        let b1: F = c(2.0) * a * ((a - c(1.0)) - (a + c(1.0)) * cos_omega) * a0r;
+        let b2: F = a * ((a + c(1.0)) - (a - c(1.0)) * cos_omega - beta) * a0r;
+        Self { a1, a2, b0, b1, b2 }
+    }
+
+    /// High shelf from square root gain `a`, normalized frequency `omega` and bandwidth term `alpha`.
+    fn highshelf_alpha(a: F, omega: F, alpha: F) -> Self {
+        let c = F::from_f64;
+        let cos_omega: F = cos(omega);
+        let beta: F = c(2.0) * sqrt(a) * alpha;
+        let a0r: F = c(1.0) / ((a + c(1.0)) - (a - c(1.0)) * cos_omega + beta);
+        let a1: F = c(2.0) * ((a - c(1.0)) - (a + c(1.0)) * cos_omega) * a0r;
+        let a2: F = ((a + c(1.0)) - (a - c(1.0)) * cos_omega - beta) * a0r;
+        let b0: F = a * ((a + c(1.0)) + (a - c(1.0)) * cos_omega + beta) * a0r;
+        let b1: F = c(-2.0) * a * ((a - c(1.0)) + (a + c(1.0)) * cos_omega) * a0r;
+        let b2: F = a * ((a + c(1.0)) + (a - c(1.0)) * cos_omega - beta) * a0r;
+        Self { a1, a2, b0, b1, b2 }
+    }
+
+    /// Returns settings for a peaking equalizer from the Audio EQ Cookbook.
+    /// The center frequency is given in Hz and the gain at the center in dB.
+    /// Frequencies far away from the center are left untouched,
+    /// and a gain of 0 dB results in a flat response.
+    /// Centers at or above Nyquist frequency result in a pass-through filter.
+    pub fn peak_eq(sample_rate: F, center: F, q: F, gain_db: F) -> Self {
+        let c = F::from_f64;
+        if center >= sample_rate * c(0.5) {
+            return Self::arbitrary(c(0.0), c(0.0), c(1.0), c(0.0), c(0.0));
+        }
+        let a: F = db_amp(gain_db * c(0.5));
+        let omega: F = c(TAU) * center / sample_rate;
+        let alpha: F = sin(omega) / (c(2.0) * q);
+        let a0r: F = c(1.0) / (c(1.0) + alpha / a);
+        let a1: F = c(-2.0) * cos(omega) * a0r;
+        let a2: F = (c(1.0) - alpha / a) * a0r;
+        let b0: F = (c(1.0) + alpha * a) * a0r;
+        let b1: F = a1;
+        let b2: F = (c(1.0) - alpha * a) * a0r;
+        Self { a1, a2, b0, b1, b2 }
+    }
+
     /// Arbitrary biquad.
     pub fn arbitrary(a1: F, a2: F, b0: F, b1: F, b2: F) -> Self {
         Self { a1, a2, b0, b1, b2 }
@@ -65,7 +202,12 @@ impl<F: Real> BiquadCoefs<F> {
     }
 }
 
-/// 2nd order IIR filter implemented in normalized Direct Form I.
+/// Magnitude below which transposed Direct Form II states are flushed to zero.
+/// This is well above the denormal range of `f32`.
+const TDF2_FLUSH: f64 = 1.0e-30;
+
+/// 2nd order IIR filter implemented in normalized Direct Form I
+/// or, optionally, transposed Direct Form II.
 /// Setting: coefficients as tuple (a1, a2, b0, b1, b2).
 /// - Input 0: input signal.
 /// - Output 0: filtered signal.
@@ -77,6 +219,10 @@ pub struct Biquad<T, F> {
     x2: F,
     y1: F,
     y2: F,
+    /// Whether we use transposed Direct Form II with states `s1` and `s2`.
+    tdf2: bool,
+    s1: F,
+    s2: F,
     sample_rate: f64,
 }
 
@@ -94,12 +240,47 @@ impl<T: Float, F: Real> Biquad<T, F> {
             ..Default::default()
         }
     }
+    /// Create new biquad in transposed Direct Form II.
+    /// The frequency response is the same as in Direct Form I, but numerical behavior differs:
+    /// there are only two state variables, and they are flushed to zero when
+    /// they become tiny, which keeps decaying tails out of the slow denormal range.
+    pub fn new_tdf2() -> Self {
+        Self {
+            tdf2: true,
+            ..Self::new()
+        }
+    }
+    /// Create new biquad with `coefs` in transposed Direct Form II. See `new_tdf2`.
+    pub fn with_coefs_tdf2(coefs: BiquadCoefs<F>) -> Self {
+        Self {
+            tdf2: true,
+            ..Self::with_coefs(coefs)
+        }
+    }
+    /// Whether the filter is implemented in transposed Direct Form II.
+    pub fn is_tdf2(&self) -> bool {
+        self.tdf2
+    }
     pub fn coefs(&self) -> &BiquadCoefs<F> {
         &self.coefs
     }
     pub fn set_coefs(&mut self, coefs: BiquadCoefs<F>) {
         self.coefs = coefs;
     }
+    /// Reset the filter to the given state instead of silence.
+    /// `x1` and `x2` are the previous two inputs and `y1` and `y2` the previous two outputs.
+    /// For example, to start from the steady state of a constant input `x`,
+    /// set the inputs to `x` and the outputs to `x` times the DC gain of the filter.
+    /// In transposed Direct Form II, the equivalent state is derived from the current coefficients.
+    pub fn reset_with(&mut self, x1: F, x2: F, y1: F, y2: F) {
+        self.x1 = x1;
+        self.x2 = x2;
+        self.y1 = y1;
+        self.y2 = y2;
+        let c = &self.coefs;
+        self.s1 = c.b1 * x1 - c.a1 * y1 + c.b2 * x2 - c.a2 * y2;
+        self.s2 = c.b2 * x1 - c.a2 * y1;
+    }
 }
 
 impl<T: Float, F: Real> AudioNode for Biquad<T, F> {
@@ -118,18 +299,50 @@ impl<T: Float, F: Real> AudioNode for Biquad<T, F> {
         self.x2 = F::zero();
         self.y1 = F::zero();
         self.y2 = F::zero();
+        self.s1 = F::zero();
+        self.s2 = F::zero();
     }
 
     fn set_sample_rate(&mut self, sample_rate: f64) {
         self.sample_rate = sample_rate;
     }
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        Ok(save_state_values(
+            &[self.x1, self.x2, self.y1, self.y2, self.s1, self.s2].map(|x| x.to_f64()),
+        ))
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        let mut values = [0.0; 6];
+        restore_state_values(state, &mut values)?;
+        [self.x1, self.x2, self.y1, self.y2, self.s1, self.s2] = values.map(F::from_f64);
+        Ok(())
+    }
+
     #[inline]
     fn tick(
         &mut self,
         input: &Frame<Self::Sample, Self::Inputs>,
     ) -> Frame<Self::Sample, Self::Outputs> {
         let x0 = convert(input[0]);
+        if self.tdf2 {
+            let y0 = self.coefs.b0 * x0 + self.s1;
+            self.s1 = self.s2 + self.coefs.b1 * x0 - self.coefs.a1 * y0;
+            self.s2 = self.coefs.b2 * x0 - self.coefs.a2 * y0;
+            // The states are flushed together: flushing one of them alone
+            // would disturb a decaying oscillation and sustain it.
+            let flush = F::from_f64(TDF2_FLUSH);
+            if abs(self.s1) < flush && abs(self.s2) < flush {
+                self.s1 = F::zero();
+                self.s2 = F::zero();
+            }
+            return [convert(y0)].into();
+        }
         let y0 = self.coefs.b0 * x0 + self.coefs.b1 * self.x1 + self.coefs.b2 * self.x2
             - self.coefs.a1 * self.y1
             - self.coefs.a2 * self.y2;
@@ -138,11 +351,71 @@ impl<T: Float, F: Real> AudioNode for Biquad<T, F> {
         self.y2 = self.y1;
         self.y1 = y0;
         [convert(y0)].into()
+    }
 
-        // Transposed Direct Form II would be:
-        //   y0 = b0 * x0 + s1
-        //   s1 = s2 + b1 * x0 - a1 * y0
-        //   s2 = b2 * x0 - a2 * y0
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        // Transposed Direct Form II keeps per-sample processing.
+        if self.tdf2 {
+            for (o, i) in output[0][..size].iter_mut().zip(input[0][..size].iter()) {
+                *o = self.tick(&Frame::splat(*i))[0];
+            }
+            return;
+        }
+        let c = self.coefs;
+        // Input history followed by the block.
+        let mut x = [F::zero(); MAX_BUFFER_SIZE + 2];
+        x[0] = self.x2;
+        x[1] = self.x1;
+        for (x, i) in x[2..size + 2].iter_mut().zip(input[0][..size].iter()) {
+            *x = convert(*i);
+        }
+        // The feedforward part has no dependencies between samples and is computed with SIMD.
+        let mut y = [F::zero(); MAX_BUFFER_SIZE];
+        F::biquad_feedforward(c.b0, c.b1, c.b2, &x[..size + 2], &mut y[..size]);
+        // The recursive part is unrolled by four with look-ahead: the response to the block
+        // does not depend on the state, and the state contributes to each output independently.
+        // This shortens the dependency chain between samples. Results agree with `tick` within rounding.
+        // The contribution of the previous output `y1` is given by the impulse response
+        // of the recursive part and that of `y2` by the impulse response delayed and scaled by -`a2`.
+        let h1 = -c.a1;
+        let h2 = -c.a1 * h1 - c.a2;
+        let h3 = -c.a1 * h2 - c.a2 * h1;
+        let h4 = -c.a1 * h3 - c.a2 * h2;
+        let (k0, k1, k2, k3) = (-c.a2, -c.a2 * h1, -c.a2 * h2, -c.a2 * h3);
+        let (mut y1, mut y2) = (self.y1, self.y2);
+        let mut i = 0;
+        while i + 4 <= size {
+            let g0 = y[i];
+            let g1 = y[i + 1] - c.a1 * g0;
+            let g2 = y[i + 2] - c.a1 * g1 - c.a2 * g0;
+            let g3 = y[i + 3] - c.a1 * g2 - c.a2 * g1;
+            y[i] = g0 + (h1 * y1 + k0 * y2);
+            y[i + 1] = g1 + (h2 * y1 + k1 * y2);
+            y[i + 2] = g2 + (h3 * y1 + k2 * y2);
+            y[i + 3] = g3 + (h4 * y1 + k3 * y2);
+            y2 = y[i + 2];
+            y1 = y[i + 3];
+            i += 4;
+        }
+        while i < size {
+            let y0 = y[i] - c.a1 * y1 - c.a2 * y2;
+            y[i] = y0;
+            y2 = y1;
+            y1 = y0;
+            i += 1;
+        }
+        for (o, y) in output[0][..size].iter_mut().zip(y[..size].iter()) {
+            *o = convert(*y);
+        }
+        self.x2 = x[size];
+        self.x1 = x[size + 1];
+        self.y1 = y1;
+        self.y2 = y2;
     }
 
     fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
@@ -166,6 +439,12 @@ pub struct ButterLowpass<T: Float, F: Real, N: Size<T>> {
     biquad: Biquad<T, F>,
     sample_rate: F,
     cutoff: F,
+    /// Coefficients for the current cutoff.
+    target: BiquadCoefs<F>,
+    /// Coefficient smoothing time constant in seconds. Zero disables smoothing.
+    smoothing: f64,
+    /// Per-sample coefficient smoothing factor.
+    smooth: F,
 }
 
 impl<T: Float, F: Real, N: Size<T>> ButterLowpass<T, F, N> {
@@ -176,15 +455,57 @@ impl<T: Float, F: Real, N: Size<T>> ButterLowpass<T, F, N> {
             biquad: Biquad::new(),
             sample_rate: F::from_f64(DEFAULT_SR),
             cutoff: F::zero(),
+            target: BiquadCoefs::default(),
+            smoothing: 0.0,
+            smooth: F::one(),
         };
         node.biquad.reset();
         node.set_cutoff(cutoff);
         node
     }
+
+    /// Set cutoff frequency in Hz. If smoothing is enabled,
+    /// then the coefficients move toward the new cutoff gradually.
     pub fn set_cutoff(&mut self, cutoff: F) {
-        self.biquad
-            .set_coefs(BiquadCoefs::butter_lowpass(self.sample_rate, cutoff));
+        self.target = BiquadCoefs::butter_lowpass(self.sample_rate, cutoff);
         self.cutoff = cutoff;
+        if self.smoothing == 0.0 {
+            self.biquad.set_coefs(self.target);
+        }
+    }
+
+    /// Coefficient smoothing time constant in seconds.
+    pub fn smoothing(&self) -> f64 {
+        self.smoothing
+    }
+
+    /// Smooth coefficient changes with time constant `seconds`.
+    /// Each of the five biquad coefficients approaches its target exponentially.
+    /// This avoids zipper noise when the cutoff is modulated quickly,
+    /// at the cost of the response lagging behind the cutoff.
+    /// While the coefficients are in transit, the response is not exactly Butterworth.
+    /// Stability is preserved because the coefficients of two stable biquads
+    /// can be interpolated without leaving the stability region.
+    /// Zero disables smoothing, so cutoff changes take effect immediately. This is the default.
+    pub fn set_smoothing(&mut self, seconds: f64) {
+        assert!(seconds >= 0.0);
+        self.smoothing = seconds;
+        self.smooth = if seconds > 0.0 {
+            F::from_f64(1.0 - exp(-1.0 / (seconds * self.sample_rate.to_f64())))
+        } else {
+            self.biquad.set_coefs(self.target);
+            F::one()
+        };
+    }
+
+    /// Current filter coefficients.
+    pub fn coefs(&self) -> &BiquadCoefs<F> {
+        self.biquad.coefs()
+    }
+
+    /// Reset the filter to the steady state of a constant input `x`.
+    pub fn reset_with(&mut self, x: F) {
+        self.biquad.reset_with(x, x, x, x);
     }
 }
 
@@ -199,6 +520,119 @@ impl<T: Float, F: Real, N: Size<T>> AudioNode for ButterLowpass<T, F, N> {
         self.set_cutoff(setting);
     }
 
+    fn reset(&mut self) {
+        self.biquad.reset();
+        self.biquad.set_coefs(self.target);
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        self.biquad.set_sample_rate(sample_rate);
+        self.set_cutoff(self.cutoff);
+        // Coefficients for the previous sample rate are not meaningful, so jump to the target.
+        self.biquad.set_coefs(self.target);
+        self.set_smoothing(self.smoothing);
+    }
+
+    fn supports_state(&self) -> bool {
+        true
+    }
+
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        self.biquad.save_state()
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        self.biquad.restore_state(state)
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        if N::USIZE > 1 {
+            let cutoff: F = convert(input[1]);
+            if cutoff != self.cutoff {
+                self.set_cutoff(cutoff);
+            }
+        }
+        if self.smoothing > 0.0 {
+            let c = *self.biquad.coefs();
+            let t = self.target;
+            let k = self.smooth;
+            self.biquad.set_coefs(BiquadCoefs {
+                a1: c.a1 + (t.a1 - c.a1) * k,
+                a2: c.a2 + (t.a2 - c.a2) * k,
+                b0: c.b0 + (t.b0 - c.b0) * k,
+                b1: c.b1 + (t.b1 - c.b1) * k,
+                b2: c.b2 + (t.b2 - c.b2) * k,
+            });
+        }
+        self.biquad.tick(&[input[0]].into())
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = input[0].filter(0.0, |r| {
+            r * self.target.response(frequency / self.sample_rate.to_f64())
+        });
+        output
+    }
+}
+
+/// Butterworth highpass filter.
+/// Setting: cutoff.
+/// Number of inputs is `N`, either `U1` or `U2`.
+/// - Input 0: input signal
+/// - Input 1 (optional): cutoff frequency (Hz)
+/// - Output 0: filtered signal
+#[derive(Clone)]
+pub struct ButterHighpass<T: Float, F: Real, N: Size<T>> {
+    _marker: std::marker::PhantomData<N>,
+    biquad: Biquad<T, F>,
+    sample_rate: F,
+    cutoff: F,
+}
+
+impl<T: Float, F: Real, N: Size<T>> ButterHighpass<T, F, N> {
+    /// Create new Butterworth highpass filter with initial `cutoff` frequency in Hz.
+    pub fn new(cutoff: F) -> Self {
+        let mut node = ButterHighpass {
+            _marker: std::marker::PhantomData::default(),
+            biquad: Biquad::new(),
+            sample_rate: F::from_f64(DEFAULT_SR),
+            cutoff: F::zero(),
+        };
+        node.biquad.reset();
+        node.set_cutoff(cutoff);
+        node
+    }
+
+    /// Set cutoff frequency in Hz.
+    pub fn set_cutoff(&mut self, cutoff: F) {
+        self.biquad
+            .set_coefs(BiquadCoefs::butter_highpass(self.sample_rate, cutoff));
+        self.cutoff = cutoff;
+    }
+
+    /// Reset the filter to the steady state of a constant input `x`, where the output is zero.
+    pub fn reset_with(&mut self, x: F) {
+        self.biquad.reset_with(x, x, F::zero(), F::zero());
+    }
+}
+
+impl<T: Float, F: Real, N: Size<T>> AudioNode for ButterHighpass<T, F, N> {
+    const ID: u64 = 117;
+    type Sample = T;
+    type Inputs = N;
+    type Outputs = typenum::U1;
+    type Setting = F;
+
+    fn set(&mut self, setting: F) {
+        self.set_cutoff(setting);
+    }
+
     fn reset(&mut self) {
         self.biquad.reset();
     }
@@ -209,6 +643,18 @@ impl<T: Float, F: Real, N: Size<T>> AudioNode for ButterLowpass<T, F, N> {
         self.set_cutoff(self.cutoff);
     }
 
+    fn supports_state(&self) -> bool {
+        true
+    }
+
+    fn save_state(&self) -> Result<Vec<u8>, StateError> {
+        self.biquad.save_state()
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        self.biquad.restore_state(state)
+    }
+
     #[inline]
     fn tick(
         &mut self,
@@ -235,63 +681,753 @@ impl<T: Float, F: Real, N: Size<T>> AudioNode for ButterLowpass<T, F, N> {
     }
 }
 
-/// Constant-gain bandpass filter (resonator).
-/// Filter gain is (nearly) independent of bandwidth.
-/// Setting: (center, bandwidth).
-/// Number of inputs is `N`, either `U1` or `U3`.
+/// 4th order Linkwitz-Riley crossover for splitting a signal into two bands.
+/// Each band is a cascade of two Butterworth filters, so the bands are -6 dB
+/// at the crossover frequency and in phase with each other.
+/// Their sum reconstructs the input with a flat magnitude response (as a 2nd order allpass).
+/// Setting: crossover frequency.
+/// - Input 0: input signal
+/// - Output 0: low band
+/// - Output 1: high band
+#[derive(Clone)]
+pub struct Crossover2<T: Float, F: Real> {
+    low: [Biquad<T, F>; 2],
+    high: [Biquad<T, F>; 2],
+    sample_rate: F,
+    frequency: F,
+}
+
+impl<T: Float, F: Real> Crossover2<T, F> {
+    /// Create new crossover at `frequency` Hz.
+    pub fn new(frequency: F) -> Self {
+        let mut node = Crossover2 {
+            low: [Biquad::new(), Biquad::new()],
+            high: [Biquad::new(), Biquad::new()],
+            sample_rate: F::from_f64(DEFAULT_SR),
+            frequency: F::zero(),
+        };
+        node.set_frequency(frequency);
+        node
+    }
+
+    /// Crossover frequency in Hz.
+    pub fn frequency(&self) -> F {
+        self.frequency
+    }
+
+    /// Set crossover frequency in Hz.
+    pub fn set_frequency(&mut self, frequency: F) {
+        let low = BiquadCoefs::butter_lowpass(self.sample_rate, frequency);
+        let high = BiquadCoefs::butter_highpass(self.sample_rate, frequency);
+        for biquad in self.low.iter_mut() {
+            biquad.set_coefs(low);
+        }
+        for biquad in self.high.iter_mut() {
+            biquad.set_coefs(high);
+        }
+        self.frequency = frequency;
+    }
+}
+
+impl<T: Float, F: Real> AudioNode for Crossover2<T, F> {
+    const ID: u64 = 118;
+    type Sample = T;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U2;
+    type Setting = F;
+
+    fn set(&mut self, setting: F) {
+        self.set_frequency(setting);
+    }
+
+    fn reset(&mut self) {
+        for biquad in self.low.iter_mut().chain(self.high.iter_mut()) {
+            biquad.reset();
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        for biquad in self.low.iter_mut().chain(self.high.iter_mut()) {
+            biquad.set_sample_rate(sample_rate);
+        }
+        self.set_frequency(self.frequency);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let low = self.low[0].tick(input);
+        let low = self.low[1].tick(&low);
+        let high = self.high[0].tick(input);
+        let high = self.high[1].tick(&high);
+        [low[0], high[0]].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        let omega = frequency / self.sample_rate.to_f64();
+        let low = self.low[0].coefs().response(omega);
+        let high = self.high[0].coefs().response(omega);
+        output[0] = input[0].filter(0.0, |r| r * low * low);
+        output[1] = input[0].filter(0.0, |r| r * high * high);
+        output
+    }
+}
+
+/// Constant-gain bandpass filter (resonator).
+/// Filter gain is (nearly) independent of bandwidth.
+/// Setting: (center, bandwidth).
+/// Number of inputs is `N`, either `U1` or `U3`.
+/// - Input 0: input signal
+/// - Input 1 (optional): filter center frequency (peak) (Hz)
+/// - Input 2 (optional): filter bandwidth (distance) between -3 dB points (Hz)
+/// - Output 0: filtered signal
+#[derive(Clone)]
+pub struct Resonator<T: Float, F: Real, N: Size<T>> {
+    _marker: std::marker::PhantomData<N>,
+    biquad: Biquad<T, F>,
+    sample_rate: F,
+    center: F,
+    bandwidth: F,
+}
+
+impl<T: Float, F: Real, N: Size<T>> Resonator<T, F, N> {
+    /// Create new resonator bandpass. Initial `center` frequency and `bandwidth` are specified in Hz.
+    pub fn new(center: F, bandwidth: F) -> Self {
+        let mut node = Resonator {
+            _marker: std::marker::PhantomData::default(),
+            biquad: Biquad::new(),
+            sample_rate: F::from_f64(DEFAULT_SR),
+            center,
+            bandwidth,
+        };
+        node.biquad.reset();
+        node.set_center_bandwidth(center, bandwidth);
+        node
+    }
+    pub fn set_center_bandwidth(&mut self, center: F, bandwidth: F) {
+        self.biquad
+            .set_coefs(BiquadCoefs::resonator(self.sample_rate, center, bandwidth));
+        self.center = center;
+        self.bandwidth = bandwidth;
+    }
+}
+
+impl<T: Float, F: Real, N: Size<T>> AudioNode for Resonator<T, F, N> {
+    const ID: u64 = 17;
+    type Sample = T;
+    type Inputs = N;
+    type Outputs = typenum::U1;
+    type Setting = (F, F);
+
+    fn set(&mut self, (center, bandwidth): Self::Setting) {
+        self.set_center_bandwidth(center, bandwidth);
+    }
+
+    fn reset(&mut self) {
+        self.biquad.reset();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        self.set_center_bandwidth(self.center, self.bandwidth);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        if N::USIZE >= 3 {
+            let center: F = convert(input[1]);
+            let bandwidth: F = convert(input[2]);
+            if center != self.center || bandwidth != self.bandwidth {
+                self.biquad
+                    .set_coefs(BiquadCoefs::resonator(self.sample_rate, center, bandwidth));
+                self.center = center;
+                self.bandwidth = bandwidth;
+            }
+        }
+        self.biquad.tick(&[input[0]].into())
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = input[0].filter(0.0, |r| {
+            r * self
+                .biquad
+                .coefs()
+                .response(frequency / self.sample_rate.to_f64())
+        });
+        output
+    }
+}
+
+/// Multimode state variable filter with simultaneous lowpass, highpass,
+/// bandpass and notch outputs from one shared resonant structure.
+/// This is Andrew Simper's trapezoidal integrator formulation.
+/// The bandpass output has a peak gain of Q.
+/// Setting: (cutoff, Q).
+/// Number of inputs is `N`, either `U1` or `U3`.
+/// - Input 0: input signal
+/// - Input 1 (optional): cutoff frequency (Hz)
+/// - Input 2 (optional): Q
+/// - Output 0: lowpass
+/// - Output 1: highpass
+/// - Output 2: bandpass
+/// - Output 3: notch
+#[derive(Clone)]
+pub struct MultiSvf<T: Float, F: Real, N: Size<T>> {
+    _marker: std::marker::PhantomData<(T, N)>,
+    sample_rate: F,
+    cutoff: F,
+    q: F,
+    /// Prewarped cutoff.
+    g: F,
+    /// Damping, the reciprocal of Q.
+    k: F,
+    a1: F,
+    a2: F,
+    a3: F,
+    ic1eq: F,
+    ic2eq: F,
+}
+
+impl<T: Float, F: Real, N: Size<T>> MultiSvf<T, F, N> {
+    /// Create new filter. Initial `cutoff` is specified in Hz.
+    pub fn new(cutoff: F, q: F) -> Self {
+        let mut node = MultiSvf {
+            _marker: std::marker::PhantomData::default(),
+            sample_rate: F::from_f64(DEFAULT_SR),
+            cutoff,
+            q,
+            g: F::zero(),
+            k: F::zero(),
+            a1: F::zero(),
+            a2: F::zero(),
+            a3: F::zero(),
+            ic1eq: F::zero(),
+            ic2eq: F::zero(),
+        };
+        node.set_cutoff_q(cutoff, q);
+        node
+    }
+
+    /// Cutoff frequency in Hz.
+    pub fn cutoff(&self) -> F {
+        self.cutoff
+    }
+
+    /// Filter Q.
+    pub fn q(&self) -> F {
+        self.q
+    }
+
+    /// Set cutoff frequency (in Hz) and Q.
+    pub fn set_cutoff_q(&mut self, cutoff: F, q: F) {
+        self.cutoff = cutoff;
+        self.q = q;
+        self.g = tan(F::from_f64(PI) * cutoff / self.sample_rate);
+        self.k = F::one() / q;
+        self.a1 = F::one() / (F::one() + self.g * (self.g + self.k));
+        self.a2 = self.g * self.a1;
+        self.a3 = self.g * self.a2;
+    }
+
+    /// Lowpass, highpass, bandpass and notch responses at `frequency` Hz.
+    fn transfer(&self, frequency: f64) -> [Complex64; 4] {
+        let g = self.g.to_f64();
+        let k = self.k.to_f64();
+        let z = Complex64::from_polar(1.0, frequency * TAU / self.sample_rate.to_f64());
+        let low = g * g * (1.0 + z) * (1.0 + z);
+        let high = (z - 1.0) * (z - 1.0);
+        let band = g * (z * z - 1.0);
+        let denominator = high + low + k * band;
+        [low, high, band, low + high].map(|numerator| numerator / denominator)
+    }
+}
+
+impl<T: Float, F: Real, N: Size<T>> AudioNode for MultiSvf<T, F, N> {
+    const ID: u64 = 116;
+    type Sample = T;
+    type Inputs = N;
+    type Outputs = typenum::U4;
+    type Setting = (F, F);
+
+    fn set(&mut self, (cutoff, q): Self::Setting) {
+        self.set_cutoff_q(cutoff, q);
+    }
+
+    fn reset(&mut self) {
+        self.ic1eq = F::zero();
+        self.ic2eq = F::zero();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        self.set_cutoff_q(self.cutoff, self.q);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        if N::USIZE >= 3 {
+            let cutoff: F = convert(input[1]);
+            let q: F = convert(input[2]);
+            if cutoff != self.cutoff || q != self.q {
+                self.set_cutoff_q(cutoff, q);
+            }
+        }
+        let v0: F = convert(input[0]);
+        let v3 = v0 - self.ic2eq;
+        let v1 = self.a1 * self.ic1eq + self.a2 * v3;
+        let v2 = self.ic2eq + self.a2 * self.ic1eq + self.a3 * v3;
+        self.ic1eq = F::new(2) * v1 - self.ic1eq;
+        self.ic2eq = F::new(2) * v2 - self.ic2eq;
+        let high = v0 - self.k * v1 - v2;
+        [convert(v2), convert(high), convert(v1), convert(v2 + high)].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        for (i, response) in self.transfer(frequency).into_iter().enumerate() {
+            output[i] = input[0].filter(0.0, |r| r * response);
+        }
+        output
+    }
+}
+
+/// Smoothing time in seconds for morph changes made via settings.
+const MORPH_SMOOTHING: f64 = 0.005;
+
+/// Morphing state variable filter. Blends continuously from lowpass (morph -1)
+/// through bandpass (morph 0) to highpass (morph 1).
+/// The blend is a crossfade of the outputs of one shared `MultiSvf`,
+/// so changing morph never disturbs filter state.
+/// Morph changes made via settings are smoothed; the morph input is used as is.
+/// Setting: (cutoff, Q, morph).
+/// Number of inputs is `N`, either `U1` or `U4`.
+/// - Input 0: input signal
+/// - Input 1 (optional): cutoff frequency (Hz)
+/// - Input 2 (optional): Q
+/// - Input 3 (optional): morph in -1...1
+/// - Output 0: filtered signal
+#[derive(Clone)]
+pub struct SvfMorph<T: Float, F: Real, N: Size<T>> {
+    svf: MultiSvf<T, F, U1>,
+    _marker: std::marker::PhantomData<N>,
+    /// Current morph.
+    current: F,
+    /// Morph set via settings.
+    target: F,
+    /// Per-sample smoothing coefficient for morph.
+    coeff: F,
+}
+
+impl<T: Float, F: Real, N: Size<T>> SvfMorph<T, F, N> {
+    /// Create new filter. Initial `cutoff` is specified in Hz.
+    /// `morph` is clamped to -1...1.
+    pub fn new(cutoff: F, q: F, morph: F) -> Self {
+        let morph = clamp11(morph);
+        let mut node = SvfMorph {
+            svf: MultiSvf::new(cutoff, q),
+            _marker: std::marker::PhantomData::default(),
+            current: morph,
+            target: morph,
+            coeff: F::zero(),
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Cutoff frequency in Hz.
+    pub fn cutoff(&self) -> F {
+        self.svf.cutoff()
+    }
+
+    /// Filter Q.
+    pub fn q(&self) -> F {
+        self.svf.q()
+    }
+
+    /// Morph in -1...1.
+    pub fn morph(&self) -> F {
+        self.target
+    }
+
+    /// Set cutoff frequency (in Hz), Q and morph. The morph transition is smoothed.
+    pub fn set_cutoff_q_morph(&mut self, cutoff: F, q: F, morph: F) {
+        self.svf.set_cutoff_q(cutoff, q);
+        self.target = clamp11(morph);
+    }
+
+    /// Lowpass, bandpass and highpass weights of `morph`.
+    #[inline]
+    fn weights(morph: F) -> (F, F, F) {
+        let low = max(-morph, F::zero());
+        let high = max(morph, F::zero());
+        (low, F::one() - low - high, high)
+    }
+}
+
+impl<T: Float, F: Real, N: Size<T>> AudioNode for SvfMorph<T, F, N> {
+    const ID: u64 = 119;
+    type Sample = T;
+    type Inputs = N;
+    type Outputs = typenum::U1;
+    type Setting = (F, F, F);
+
+    fn set(&mut self, (cutoff, q, morph): Self::Setting) {
+        self.set_cutoff_q_morph(cutoff, q, morph);
+    }
+
+    fn reset(&mut self) {
+        self.svf.reset();
+        self.current = self.target;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.svf.set_sample_rate(sample_rate);
+        self.coeff = F::from_f64(1.0 - exp(-1.0 / (MORPH_SMOOTHING * sample_rate)));
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        if N::USIZE >= 4 {
+            let cutoff: F = convert(input[1]);
+            let q: F = convert(input[2]);
+            if cutoff != self.svf.cutoff() || q != self.svf.q() {
+                self.svf.set_cutoff_q(cutoff, q);
+            }
+            self.current = clamp11(convert(input[3]));
+        } else {
+            self.current += (self.target - self.current) * self.coeff;
+        }
+        let y = self.svf.tick(&[input[0]].into());
+        let (low, band, high) = Self::weights(self.current);
+        let output: F = low * convert(y[0]) + band * convert(y[2]) + high * convert(y[1]);
+        [convert(output)].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        let (low, band, high) = Self::weights(self.target);
+        let [lowpass, highpass, bandpass, _] = self.svf.transfer(frequency);
+        let response = lowpass * low.to_f64() + bandpass * band.to_f64() + highpass * high.to_f64();
+        output[0] = input[0].filter(0.0, |r| r * response);
+        output
+    }
+}
+
+/// Shelf type of a `BiquadShelf`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShelfType {
+    /// Gain applies below the shelf frequency.
+    Low,
+    /// Gain applies above the shelf frequency.
+    High,
+}
+
+/// Biquad shelving filter with adjustable slope.
+/// Setting: (frequency, gain in dB, slope).
+/// - Input 0: input signal
+/// - Output 0: filtered signal
+#[derive(Clone)]
+pub struct BiquadShelf<T: Float, F: Real> {
+    biquad: Biquad<T, F>,
+    shelf: ShelfType,
+    sample_rate: F,
+    frequency: F,
+    gain_db: F,
+    slope: F,
+}
+
+impl<T: Float, F: Real> BiquadShelf<T, F> {
+    /// Create new shelf at `frequency` Hz with `gain_db` dB of gain
+    /// on the shelved side and transition `slope` in 0...1.
+    pub fn new(shelf: ShelfType, frequency: F, gain_db: F, slope: F) -> Self {
+        let mut node = BiquadShelf {
+            biquad: Biquad::new(),
+            shelf,
+            sample_rate: F::from_f64(DEFAULT_SR),
+            frequency,
+            gain_db,
+            slope,
+        };
+        node.set_shelf(frequency, gain_db, slope);
+        node
+    }
+
+    /// Shelf type.
+    pub fn shelf(&self) -> ShelfType {
+        self.shelf
+    }
+
+    /// Set shelf `frequency` in Hz, `gain_db` in dB and `slope` in 0...1.
+    pub fn set_shelf(&mut self, frequency: F, gain_db: F, slope: F) {
+        self.frequency = frequency;
+        self.gain_db = gain_db;
+        self.slope = slope;
+        self.biquad.set_coefs(match self.shelf {
+            ShelfType::Low => {
+                BiquadCoefs::lowshelf_slope(self.sample_rate, frequency, gain_db, slope)
+            }
+            ShelfType::High => {
+                BiquadCoefs::highshelf_slope(self.sample_rate, frequency, gain_db, slope)
+            }
+        });
+    }
+}
+
+impl<T: Float, F: Real> AudioNode for BiquadShelf<T, F> {
+    const ID: u64 = 97;
+    type Sample = T;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+    type Setting = (F, F, F);
+
+    fn set(&mut self, (frequency, gain_db, slope): Self::Setting) {
+        self.set_shelf(frequency, gain_db, slope);
+    }
+
+    fn reset(&mut self) {
+        self.biquad.reset();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        self.biquad.set_sample_rate(sample_rate);
+        self.set_shelf(self.frequency, self.gain_db, self.slope);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        self.biquad.tick(input)
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        self.biquad.route(input, frequency)
+    }
+}
+
+/// Slope of the complementary shelves in the tilt equalizer.
+const TILT_SLOPE: f64 = 0.5;
+
+/// Tilt equalizer: complementary low and high shelves around a pivot frequency.
+/// Positive gain brightens: frequencies above the pivot are boosted
+/// and frequencies below are cut, each approaching half the gain in dB.
+/// The response at the pivot is unity.
+/// Setting: gain in dB.
+/// - Input 0: input signal
+/// - Output 0: filtered signal
+#[derive(Clone)]
+pub struct TiltEq<T: Float, F: Real> {
+    low: BiquadShelf<T, F>,
+    high: BiquadShelf<T, F>,
+    gain_db: F,
+    pivot: F,
+}
+
+impl<T: Float, F: Real> TiltEq<T, F> {
+    /// Create new tilt equalizer with total tilt `gain_db` in dB around `pivot` Hz.
+    pub fn new(gain_db: F, pivot: F) -> Self {
+        let half = gain_db * F::from_f64(0.5);
+        let slope = F::from_f64(TILT_SLOPE);
+        Self {
+            low: BiquadShelf::new(ShelfType::Low, pivot, -half, slope),
+            high: BiquadShelf::new(ShelfType::High, pivot, half, slope),
+            gain_db,
+            pivot,
+        }
+    }
+
+    /// Tilt in dB.
+    pub fn gain_db(&self) -> F {
+        self.gain_db
+    }
+
+    /// Pivot frequency in Hz.
+    pub fn pivot(&self) -> F {
+        self.pivot
+    }
+
+    /// Set tilt `gain_db` in dB.
+    pub fn set_gain_db(&mut self, gain_db: F) {
+        self.set_tilt(gain_db, self.pivot);
+    }
+
+    /// Set tilt `gain_db` in dB and `pivot` frequency in Hz.
+    pub fn set_tilt(&mut self, gain_db: F, pivot: F) {
+        let half = gain_db * F::from_f64(0.5);
+        let slope = F::from_f64(TILT_SLOPE);
+        self.gain_db = gain_db;
+        self.pivot = pivot;
+        self.low.set_shelf(pivot, -half, slope);
+        self.high.set_shelf(pivot, half, slope);
+    }
+}
+
+impl<T: Float, F: Real> AudioNode for TiltEq<T, F> {
+    const ID: u64 = 98;
+    type Sample = T;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+    type Setting = F;
+
+    fn set(&mut self, gain_db: Self::Setting) {
+        self.set_gain_db(gain_db);
+    }
+
+    fn reset(&mut self) {
+        self.low.reset();
+        self.high.reset();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.low.set_sample_rate(sample_rate);
+        self.high.set_sample_rate(sample_rate);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let x = self.low.tick(input);
+        self.high.tick(&x)
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let x = self.low.route(input, frequency);
+        self.high.route(&x, frequency)
+    }
+}
+
+/// ISO 266 nominal octave band center frequencies in Hz.
+const ISO_OCTAVE: [f64; 10] = [
+    31.5, 63.0, 125.0, 250.0, 500.0, 1000.0, 2000.0, 4000.0, 8000.0, 16000.0,
+];
+
+/// ISO 266 nominal third-octave band center frequencies in Hz.
+const ISO_THIRD_OCTAVE: [f64; 31] = [
+    20.0, 25.0, 31.5, 40.0, 50.0, 63.0, 80.0, 100.0, 125.0, 160.0, 200.0, 250.0, 315.0, 400.0,
+    500.0, 630.0, 800.0, 1000.0, 1250.0, 1600.0, 2000.0, 2500.0, 3150.0, 4000.0, 5000.0, 6300.0,
+    8000.0, 10000.0, 12500.0, 16000.0, 20000.0,
+];
+
+/// Graphic equalizer with fixed ISO octave (10 bands) or third-octave (31 bands)
+/// center frequencies. Each band is a peaking biquad with adjustable gain.
+/// Setting: (band, gain in dB).
 /// - Input 0: input signal
-/// - Input 1 (optional): filter center frequency (peak) (Hz)
-/// - Input 2 (optional): filter bandwidth (distance) between -3 dB points (Hz)
-/// - Output 0: filtered signal
+/// - Output 0: equalized signal
 #[derive(Clone)]
-pub struct Resonator<T: Float, F: Real, N: Size<T>> {
-    _marker: std::marker::PhantomData<N>,
-    biquad: Biquad<T, F>,
+pub struct GraphicEq<T: Float, F: Real> {
+    bands: Vec<Biquad<T, F>>,
+    center: Vec<F>,
+    gain: Vec<F>,
+    q: F,
     sample_rate: F,
-    center: F,
-    bandwidth: F,
 }
 
-impl<T: Float, F: Real, N: Size<T>> Resonator<T, F, N> {
-    /// Create new resonator bandpass. Initial `center` frequency and `bandwidth` are specified in Hz.
-    pub fn new(center: F, bandwidth: F) -> Self {
-        let mut node = Resonator {
-            _marker: std::marker::PhantomData::default(),
-            biquad: Biquad::new(),
+impl<T: Float, F: Real> GraphicEq<T, F> {
+    /// Create new graphic equalizer with `bands` bands, either 10 (octave bands)
+    /// or 31 (third-octave bands). All bands start at 0 dB.
+    pub fn new(bands: usize) -> Self {
+        let (center, octaves): (&[f64], f64) = match bands {
+            10 => (&ISO_OCTAVE, 1.0),
+            31 => (&ISO_THIRD_OCTAVE, 1.0 / 3.0),
+            _ => panic!("GraphicEq: number of bands must be 10 or 31."),
+        };
+        // Q of a band that is `octaves` wide.
+        let q = sqrt(pow(2.0, octaves)) / (pow(2.0, octaves) - 1.0);
+        let mut node = Self {
+            bands: vec![Biquad::new(); bands],
+            center: center.iter().map(|f| F::from_f64(*f)).collect(),
+            gain: vec![F::zero(); bands],
+            q: F::from_f64(q),
             sample_rate: F::from_f64(DEFAULT_SR),
-            center,
-            bandwidth,
         };
-        node.biquad.reset();
-        node.set_center_bandwidth(center, bandwidth);
+        node.update_coefs();
         node
     }
-    pub fn set_center_bandwidth(&mut self, center: F, bandwidth: F) {
-        self.biquad
-            .set_coefs(BiquadCoefs::resonator(self.sample_rate, center, bandwidth));
-        self.center = center;
-        self.bandwidth = bandwidth;
+
+    /// Number of bands.
+    pub fn bands(&self) -> usize {
+        self.bands.len()
+    }
+
+    /// Center frequency of `band` in Hz.
+    pub fn center(&self, band: usize) -> F {
+        self.center[band]
+    }
+
+    /// Gain of `band` in dB.
+    pub fn gain_db(&self, band: usize) -> F {
+        self.gain[band]
+    }
+
+    /// Set gain of `band` in dB.
+    pub fn set_gain_db(&mut self, band: usize, gain_db: F) {
+        self.gain[band] = gain_db;
+        self.bands[band].set_coefs(BiquadCoefs::peak_eq(
+            self.sample_rate,
+            self.center[band],
+            self.q,
+            gain_db,
+        ));
+    }
+
+    fn update_coefs(&mut self) {
+        for ((biquad, center), gain_db) in self
+            .bands
+            .iter_mut()
+            .zip(self.center.iter())
+            .zip(self.gain.iter())
+        {
+            biquad.set_coefs(BiquadCoefs::peak_eq(
+                self.sample_rate,
+                *center,
+                self.q,
+                *gain_db,
+            ));
+        }
     }
 }
 
-impl<T: Float, F: Real, N: Size<T>> AudioNode for Resonator<T, F, N> {
-    const ID: u64 = 17;
+impl<T: Float, F: Real> AudioNode for GraphicEq<T, F> {
+    const ID: u64 = 84;
     type Sample = T;
-    type Inputs = N;
+    type Inputs = typenum::U1;
     type Outputs = typenum::U1;
-    type Setting = (F, F);
+    type Setting = (usize, F);
 
-    fn set(&mut self, (center, bandwidth): Self::Setting) {
-        self.set_center_bandwidth(center, bandwidth);
+    fn set(&mut self, (band, gain_db): Self::Setting) {
+        self.set_gain_db(band, gain_db);
     }
 
     fn reset(&mut self) {
-        self.biquad.reset();
+        for band in self.bands.iter_mut() {
+            band.reset();
+        }
     }
 
     fn set_sample_rate(&mut self, sample_rate: f64) {
         self.sample_rate = convert(sample_rate);
-        self.set_center_bandwidth(self.center, self.bandwidth);
+        for band in self.bands.iter_mut() {
+            band.set_sample_rate(sample_rate);
+        }
+        self.update_coefs();
     }
 
     #[inline]
@@ -299,29 +1435,28 @@ impl<T: Float, F: Real, N: Size<T>> AudioNode for Resonator<T, F, N> {
         &mut self,
         input: &Frame<Self::Sample, Self::Inputs>,
     ) -> Frame<Self::Sample, Self::Outputs> {
-        if N::USIZE >= 3 {
-            let center: F = convert(input[1]);
-            let bandwidth: F = convert(input[2]);
-            if center != self.center || bandwidth != self.bandwidth {
-                self.biquad
-                    .set_coefs(BiquadCoefs::resonator(self.sample_rate, center, bandwidth));
-                self.center = center;
-                self.bandwidth = bandwidth;
-            }
+        let mut output = input[0];
+        for band in self.bands.iter_mut() {
+            output = band.tick(&[output].into())[0];
         }
-        self.biquad.tick(&[input[0]].into())
+        [output].into()
     }
 
     fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
         let mut output = new_signal_frame(self.outputs());
+        let omega = frequency / self.sample_rate.to_f64();
         output[0] = input[0].filter(0.0, |r| {
-            r * self
-                .biquad
-                .coefs()
-                .response(frequency / self.sample_rate.to_f64())
+            self.bands
+                .iter()
+                .fold(r, |r, band| r * band.coefs().response(omega))
         });
         output
     }
+
+    fn heap_footprint(&self) -> usize {
+        self.bands.capacity() * std::mem::size_of::<Biquad<T, F>>()
+            + (self.center.capacity() + self.gain.capacity()) * std::mem::size_of::<F>()
+    }
 }
 
 /// One-pole lowpass filter.
@@ -354,11 +1489,20 @@ impl<T: Float, F: Real, N: Size<T>> Lowpole<T, F, N> {
     }
 
     /// Set the cutoff frequency (in Hz).
+    /// The cutoff is clamped to 0...Nyquist, which keeps the filter stable
+    /// when it is modulated with arbitrary signals.
     /// This has no effect if the filter has a cutoff frequency input.
     pub fn set_cutoff(&mut self, cutoff: F) {
         self.cutoff = cutoff;
+        let cutoff = clamp(F::zero(), self.sample_rate * F::from_f64(0.5), cutoff);
         self.coeff = exp(F::from_f64(-TAU) * cutoff / self.sample_rate);
     }
+
+    /// Reset the filter to the given output `value` instead of zero.
+    /// The steady state of a constant input is the input itself.
+    pub fn reset_with(&mut self, value: F) {
+        self.value = value;
+    }
 }
 
 impl<T: Float, F: Real, N: Size<T>> AudioNode for Lowpole<T, F, N> {
@@ -409,6 +1553,117 @@ impl<T: Float, F: Real, N: Size<T>> AudioNode for Lowpole<T, F, N> {
     }
 }
 
+/// Leaky integrator with time constant in seconds.
+/// Unlike `Lowpole`, which has unity gain at DC, the integrator has a DC gain
+/// equal to its time constant, like an analog RC integrator.
+/// In the saturating variant the integrator state passes through `tanh`
+/// on every sample, which confines it to -1...1 like the rails of an analog circuit.
+/// Setting: time constant.
+/// The number of inputs is `N`, either `U1` or `U2`.
+/// - Input 0: input signal
+/// - Input 1 (optional): time constant (seconds)
+/// - Output 0: integrated signal
+#[derive(Default, Clone)]
+pub struct LeakyIntegrator<T: Float, F: Real, N: Size<T>> {
+    _marker: std::marker::PhantomData<(T, N)>,
+    value: F,
+    coeff: F,
+    time: F,
+    saturate: bool,
+    sample_rate: F,
+}
+
+impl<T: Float, F: Real, N: Size<T>> LeakyIntegrator<T, F, N> {
+    /// Create new leaky integrator with time constant `time` seconds.
+    /// If `saturate` is set, then the integrator state passes through `tanh`.
+    pub fn new(time: F, saturate: bool) -> Self {
+        let mut node = LeakyIntegrator {
+            _marker: std::marker::PhantomData::default(),
+            value: F::zero(),
+            coeff: F::zero(),
+            time,
+            saturate,
+            sample_rate: convert(DEFAULT_SR),
+        };
+        node.set_time(time);
+        node
+    }
+
+    /// Time constant in seconds.
+    pub fn time(&self) -> F {
+        self.time
+    }
+
+    /// Set the time constant (in seconds).
+    /// This has no effect if the integrator has a time constant input.
+    pub fn set_time(&mut self, time: F) {
+        self.time = time;
+        self.coeff = exp(-F::one() / (max(time, F::from_f64(1.0e-9)) * self.sample_rate));
+    }
+
+    /// Whether the integrator state saturates.
+    pub fn saturate(&self) -> bool {
+        self.saturate
+    }
+}
+
+impl<T: Float, F: Real, N: Size<T>> AudioNode for LeakyIntegrator<T, F, N> {
+    const ID: u64 = 94;
+    type Sample = T;
+    type Inputs = N;
+    type Outputs = typenum::U1;
+    type Setting = F;
+
+    fn set(&mut self, setting: Self::Setting) {
+        self.set_time(setting);
+    }
+
+    fn reset(&mut self) {
+        self.value = F::zero();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = convert(sample_rate);
+        self.set_time(self.time);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        if N::USIZE > 1 {
+            let time: F = convert(input[1]);
+            if time != self.time {
+                self.set_time(time);
+            }
+        }
+        let x: F = convert(input[0]);
+        self.value = self.coeff * self.value + x / self.sample_rate;
+        if self.saturate {
+            // Saturate the feedback state so it cannot wind up past the rails.
+            self.value = tanh(self.value);
+        }
+        [convert(self.value)].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        if self.saturate {
+            output[0] = input[0].distort(0.0);
+        } else {
+            output[0] = input[0].filter(0.0, |r| {
+                let c = self.coeff.to_f64();
+                let sr = self.sample_rate.to_f64();
+                let f = frequency * TAU / sr;
+                let z1 = Complex64::from_polar(1.0, -f);
+                r * (1.0 / sr / (1.0 - c * z1))
+            });
+        }
+        output
+    }
+}
+
 /// DC blocking filter with cutoff frequency in Hz.
 /// Setting: cutoff.
 /// - Input 0: signal
@@ -435,10 +1690,20 @@ impl<T: Float, F: Real> DCBlock<T, F> {
         node
     }
 
-    /// Set the cutoff frequency (in Hz).
+    /// Set the cutoff frequency (in Hz). Any nonnegative cutoff is valid:
+    /// the pole stays inside the unit circle and moves toward the origin
+    /// as the cutoff approaches and exceeds Nyquist, where the filter becomes a differentiator.
+    /// Cutoffs well below Nyquist are where the filter is useful as a DC blocker.
     pub fn set_cutoff(&mut self, cutoff: F) {
         self.cutoff = cutoff;
-        self.coeff = F::one() - F::from_f64(TAU) / self.sample_rate * cutoff;
+        self.coeff = exp(F::from_f64(-TAU) * cutoff / self.sample_rate);
+    }
+
+    /// Reset the filter to the given previous input `x1` and output `y1` instead of zero.
+    /// The steady state of a constant input `x` is `x1` = `x`, `y1` = 0.
+    pub fn reset_with(&mut self, x1: F, y1: F) {
+        self.x1 = x1;
+        self.y1 = y1;
     }
 }
 
@@ -560,6 +1825,37 @@ impl<T: Float, F: Float> AudioNode for Pinkpass<T, F> {
         [convert(out)].into()
     }
 
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        // Keep the state in locals for the duration of the block.
+        let (mut b0, mut b1, mut b2, mut b3) = (self.b0, self.b1, self.b2, self.b3);
+        let (mut b4, mut b5, mut b6) = (self.b4, self.b5, self.b6);
+        for (o, i) in output[0][..size].iter_mut().zip(input[0][..size].iter()) {
+            let x: F = convert(*i);
+            b0 = F::from_f64(0.99886) * b0 + x * F::from_f64(0.0555179);
+            b1 = F::from_f64(0.99332) * b1 + x * F::from_f64(0.0750759);
+            b2 = F::from_f64(0.96900) * b2 + x * F::from_f64(0.1538520);
+            b3 = F::from_f64(0.86650) * b3 + x * F::from_f64(0.3104856);
+            b4 = F::from_f64(0.55000) * b4 + x * F::from_f64(0.5329522);
+            b5 = F::from_f64(-0.7616) * b5 - x * F::from_f64(0.0168980);
+            let out = (b0 + b1 + b2 + b3 + b4 + b5 + b6 + x * F::from_f64(0.5362))
+                * F::from_f64(0.115830421);
+            b6 = x * F::from_f64(0.115926);
+            *o = convert(out);
+        }
+        self.b0 = b0;
+        self.b1 = b1;
+        self.b2 = b2;
+        self.b3 = b3;
+        self.b4 = b4;
+        self.b5 = b5;
+        self.b6 = b6;
+    }
+
     fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
         let mut output = new_signal_frame(self.outputs());
         output[0] = input[0].filter(0.0, |r| {
@@ -613,6 +1909,13 @@ impl<T: Float, F: Float, N: Size<T>> Allpole<T, F, N> {
     pub fn set_delay(&mut self, delay: F) {
         self.eta = (F::one() - delay) / (F::one() + delay);
     }
+
+    /// Reset the filter to the given previous input `x1` and output `y1` instead of zero.
+    /// The steady state of a constant input `x` is `x1` = `y1` = `x`.
+    pub fn reset_with(&mut self, x1: F, y1: F) {
+        self.x1 = x1;
+        self.y1 = y1;
+    }
 }
 
 impl<T: Float, F: Float, N: Size<T>> AudioNode for Allpole<T, F, N> {
@@ -661,6 +1964,258 @@ impl<T: Float, F: Float, N: Size<T>> AudioNode for Allpole<T, F, N> {
     }
 }
 
+/// Number of allpass stages in the dispersion filter.
+const DISPERSION_STAGES: usize = 8;
+
+/// Maximum stiffness of the dispersion filter, which keeps it away from instability.
+const DISPERSION_MAX: f64 = 0.99;
+
+/// Dispersion filter: a cascade of 8 identical 1st order allpass filters
+/// that delays low frequencies more than high frequencies,
+/// like the stiffness of a real string or plate.
+/// Stiffness `s` is in 0...1. The group delay of each stage is
+/// (1 + `s`) / (1 - `s`) samples at DC and (1 - `s`) / (1 + `s`) samples at Nyquist,
+/// so zero stiffness is a plain delay of 8 samples.
+/// Setting: stiffness.
+/// - Input 0: input signal
+/// - Output 0: filtered signal
+#[derive(Clone)]
+pub struct Dispersion<T: Real> {
+    stage: [Allpole<T, T, U1>; DISPERSION_STAGES],
+    stiffness: T,
+}
+
+impl<T: Real> Dispersion<T> {
+    /// Create new dispersion filter with `stiffness` in 0...1.
+    pub fn new(stiffness: T) -> Self {
+        let mut node = Self {
+            stage: std::array::from_fn(|_| Allpole::new(T::one())),
+            stiffness: T::zero(),
+        };
+        node.set_stiffness(stiffness);
+        node
+    }
+
+    /// Stiffness in 0...1.
+    pub fn stiffness(&self) -> T {
+        self.stiffness
+    }
+
+    /// Set stiffness in 0...1. Values are clamped to 0...0.99.
+    pub fn set_stiffness(&mut self, stiffness: T) {
+        self.stiffness = clamp(T::zero(), T::from_f64(DISPERSION_MAX), stiffness);
+        let delay = (T::one() + self.stiffness) / (T::one() - self.stiffness);
+        for stage in self.stage.iter_mut() {
+            stage.set_delay(delay);
+        }
+    }
+}
+
+impl<T: Real> AudioNode for Dispersion<T> {
+    const ID: u64 = 115;
+    type Sample = T;
+    type Inputs = U1;
+    type Outputs = U1;
+    type Setting = T;
+
+    fn set(&mut self, setting: Self::Setting) {
+        self.set_stiffness(setting);
+    }
+
+    fn reset(&mut self) {
+        for stage in self.stage.iter_mut() {
+            stage.reset();
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        for stage in self.stage.iter_mut() {
+            stage.set_sample_rate(sample_rate);
+        }
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let mut x = input[0];
+        for stage in self.stage.iter_mut() {
+            x = stage.tick(&[x].into())[0];
+        }
+        [x].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = input.clone();
+        for stage in self.stage.iter_mut() {
+            output = stage.route(&output, frequency);
+        }
+        output
+    }
+}
+
+/// Lowest break frequency of the phaser allpass sections in Hz.
+const PHASER_MIN_HZ: f64 = 200.0;
+
+/// Phaser sweep range in octaves at full depth.
+const PHASER_OCTAVES: f64 = 6.0;
+
+/// Largest feedback amount of the phaser, which keeps it stable.
+const PHASER_MAX_FEEDBACK: f64 = 0.99;
+
+/// Phaser: a chain of 1st order allpass sections swept by a sine LFO,
+/// mixed equally with the dry signal. The output of the last section is fed back
+/// to the first section with a delay of one sample.
+/// The break frequency of the sections, where each shifts phase by 90 degrees,
+/// sweeps exponentially from 200 Hz up to `depth` times 6 octaves higher.
+/// Each pair of sections makes one notch in the response.
+/// - Input 0: audio
+/// - Output 0: phased audio
+#[derive(Clone)]
+pub struct Phaser<T: Real> {
+    stage: Vec<Allpole<T, T, U1>>,
+    feedback: T,
+    /// LFO rate in Hz.
+    rate: f64,
+    /// Sweep depth in 0...1.
+    depth: f64,
+    /// LFO phase in 0...1.
+    phase: f64,
+    /// Output of the last section from the previous sample.
+    y1: T,
+    sample_rate: f64,
+}
+
+impl<T: Real> Phaser<T> {
+    /// Create new phaser with `stages` allpass sections (for example, 8),
+    /// `feedback` amount in -0.99...0.99, LFO `rate` in Hz and sweep `depth` in 0...1.
+    pub fn new(stages: usize, feedback: T, rate: f64, depth: f64) -> Self {
+        assert!(stages > 0);
+        let mut node = Self {
+            stage: vec![Allpole::new(T::one()); stages],
+            feedback: T::zero(),
+            rate,
+            depth: clamp01(depth),
+            phase: 0.0,
+            y1: T::zero(),
+            sample_rate: DEFAULT_SR,
+        };
+        node.set_feedback(feedback);
+        node.sweep();
+        node
+    }
+
+    /// Number of allpass sections.
+    pub fn stages(&self) -> usize {
+        self.stage.len()
+    }
+
+    /// Feedback amount.
+    pub fn feedback(&self) -> T {
+        self.feedback
+    }
+
+    /// Set feedback amount. Values are clamped to -0.99...0.99.
+    pub fn set_feedback(&mut self, feedback: T) {
+        let max = T::from_f64(PHASER_MAX_FEEDBACK);
+        self.feedback = clamp(-max, max, feedback);
+    }
+
+    /// LFO rate in Hz.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Set LFO rate in Hz.
+    pub fn set_rate(&mut self, rate: f64) {
+        self.rate = rate;
+    }
+
+    /// Sweep depth in 0...1.
+    pub fn depth(&self) -> f64 {
+        self.depth
+    }
+
+    /// Set sweep depth in 0...1.
+    pub fn set_depth(&mut self, depth: f64) {
+        self.depth = clamp01(depth);
+    }
+
+    /// Set allpass section delays from the current LFO phase.
+    #[inline]
+    fn sweep(&mut self) {
+        let lfo = 0.5 + 0.5 * sin(TAU * self.phase);
+        let frequency = min(
+            PHASER_MIN_HZ * exp2(PHASER_OCTAVES * self.depth * lfo),
+            0.45 * self.sample_rate,
+        );
+        let delay = T::from_f64(1.0 / tan(PI * frequency / self.sample_rate));
+        for stage in self.stage.iter_mut() {
+            stage.set_delay(delay);
+        }
+    }
+}
+
+impl<T: Real> AudioNode for Phaser<T> {
+    const ID: u64 = 128;
+    type Sample = T;
+    type Inputs = U1;
+    type Outputs = U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        for stage in self.stage.iter_mut() {
+            stage.reset();
+        }
+        self.phase = 0.0;
+        self.y1 = T::zero();
+        self.sweep();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        for stage in self.stage.iter_mut() {
+            stage.set_sample_rate(sample_rate);
+        }
+        self.sweep();
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        self.sweep();
+        self.phase += self.rate / self.sample_rate;
+        self.phase -= floor(self.phase);
+        let x = input[0];
+        let mut y = x + self.feedback * self.y1;
+        for stage in self.stage.iter_mut() {
+            y = stage.tick(&[y].into())[0];
+        }
+        self.y1 = y;
+        [(x + y) * T::from_f64(0.5)].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = input[0].filter(0.0, |r| {
+            // Response with the section delays of the next sample.
+            let eta = self.stage[0].eta.to_f64();
+            let z1 = Complex64::from_polar(1.0, -frequency * TAU / self.sample_rate);
+            let chain = ((eta + z1) / (1.0 + eta * z1)).powi(self.stage.len() as i32);
+            let wet = chain / (1.0 - self.feedback.to_f64() * z1 * chain);
+            r * 0.5 * (1.0 + wet)
+        });
+        output
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.stage.capacity() * std::mem::size_of::<Allpole<T, T, U1>>()
+    }
+}
+
 /// One-pole, one-zero highpass filter.
 /// Setting: cutoff.
 /// The number of inputs is `N`, either `U1` or `U2`.
@@ -691,10 +2246,23 @@ impl<T: Float, F: Real, N: Size<T>> Highpole<T, F, N> {
         node.set_cutoff(cutoff);
         node
     }
+
+    /// Set the cutoff frequency (in Hz).
+    /// The cutoff is clamped to 0...Nyquist, which keeps the filter stable
+    /// when it is modulated with arbitrary signals.
+    /// This has no effect if the filter has a cutoff frequency input.
     pub fn set_cutoff(&mut self, cutoff: F) {
         self.cutoff = cutoff;
+        let cutoff = clamp(F::zero(), self.sample_rate * F::from_f64(0.5), cutoff);
         self.coeff = exp(F::from_f64(-TAU) * cutoff / self.sample_rate);
     }
+
+    /// Reset the filter to the given previous input `x1` and output `y1` instead of zero.
+    /// The steady state of a constant input `x` is `x1` = `x`, `y1` = 0.
+    pub fn reset_with(&mut self, x1: F, y1: F) {
+        self.x1 = x1;
+        self.y1 = y1;
+    }
 }
 
 impl<T: Float, F: Real, N: Size<T>> AudioNode for Highpole<T, F, N> {
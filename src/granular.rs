@@ -248,4 +248,9 @@ impl<
     fn footprint(&self) -> usize {
         std::mem::size_of::<Self>()
     }
+
+    fn total_footprint(&self) -> usize {
+        // The sequencer is stored inline, so count only its allocations here.
+        std::mem::size_of::<Self>() + self.sequencer.total_footprint() - self.sequencer.footprint()
+    }
 }
@@ -1,19 +1,23 @@
 //! The 32-bit hacker prelude, a 32-bit environment for audio processing.
 
+pub use super::arp::*;
 pub use super::audionode::*;
 pub use super::audiounit::*;
+pub use super::binaural::*;
 pub use super::buffer::*;
 pub use super::combinator::*;
 pub use super::delay::*;
 pub use super::dynamics::*;
 pub use super::envelope::*;
 pub use super::feedback::*;
+pub use super::fft::*;
 pub use super::filter::*;
 pub use super::fir::*;
 pub use super::follow::*;
 pub use super::gen::*;
 pub use super::granular::*;
 pub use super::math::*;
+pub use super::mixer::*;
 pub use super::moog::*;
 pub use super::net::*;
 pub use super::noise::*;
@@ -23,6 +27,7 @@ pub use super::pan::*;
 pub use super::realnet::*;
 pub use super::realseq::*;
 pub use super::resample::*;
+pub use super::reverb::*;
 pub use super::rez::*;
 pub use super::sequencer::*;
 pub use super::setting::*;
@@ -299,6 +304,27 @@ pub fn meter(meter: Meter) -> An<MeterNode<f32>> {
     An(MeterNode::new(meter))
 }
 
+/// Over counter. Passes through input and counts samples whose absolute value
+/// exceeds amplitude `threshold`. Returns (frontend, backend).
+/// The frontend reports the over count and the time since the last over.
+/// - Input 0: signal
+/// - Output 0: signal passed through
+///
+/// ### Example: Count Overs At 0 dBFS
+/// ```
+/// use fundsp::hacker32::*;
+/// let (overs, backend) = overs(1.0);
+/// let mut node = sine_hz(110.0) * 1.5 >> backend;
+/// for _ in 0..1000 {
+///     node.get_mono();
+/// }
+/// assert!(overs.count() > 0);
+/// ```
+pub fn overs(threshold: f32) -> (OversMeter, An<Overs<f32>>) {
+    let (meter, backend) = Overs::new(DEFAULT_SR, threshold);
+    (meter, An(backend))
+}
+
 /// Mono sink. Input is discarded.
 /// -Input 0: signal
 pub fn sink() -> An<Sink<U1, f32>> {
@@ -349,6 +375,72 @@ pub fn sine_hz(f: f32) -> An<Pipe<f32, Constant<U1, f32>, Sine<f32>>> {
     super::prelude::sine_hz(f)
 }
 
+/// Unison of `N` copies of oscillator `voice` detuned by up to `spread` semitones.
+/// Detunes and initial phases are derived from the node hash,
+/// so identically seeded graphs sound the same.
+/// Output is normalized by the square root of `N`.
+/// - Input 0: frequency (Hz)
+/// - Output 0: unison
+///
+/// ### Example: Detuned Sine Pad
+/// ```
+/// use fundsp::hacker32::*;
+/// dc(220.0) >> unison::<U7, _>(0.15, sine());
+/// ```
+pub fn unison<N, X>(spread: f32, voice: An<X>) -> An<Unison<N, f32, X>>
+where
+    N: Size<f32>,
+    X: AudioNode<Sample = f32, Inputs = U1, Outputs = U1>,
+{
+    An(Unison::new(spread, voice.0))
+}
+
+/// FM operator: sine oscillator at `ratio` times the pitch input
+/// with its phase modulated by the modulation input scaled by `index` (in radians).
+/// - Input 0: pitch in Hz
+/// - Input 1: modulation signal
+/// - Output 0: operator output
+pub fn fm_op(ratio: f32, index: f32) -> An<FmOp<f32>> {
+    super::prelude::fm_op(ratio, index)
+}
+
+/// Two operator FM voice. The modulator runs at `modulator_ratio` and
+/// the carrier at `carrier_ratio` times the pitch input.
+/// The modulator drives the carrier phase with modulation `index`
+/// and itself with `feedback`, both in radians.
+/// - Input 0: pitch in Hz
+/// - Output 0: FM voice
+pub fn fm2(carrier_ratio: f32, modulator_ratio: f32, index: f32, feedback: f32) -> An<Fm2<f32>> {
+    super::prelude::fm2(carrier_ratio, modulator_ratio, index, feedback)
+}
+
+/// Phase distortion oscillator. The phase ramp of a sine is bent at a breakpoint
+/// that moves towards the start of the cycle as distortion increases.
+/// - Input 0: frequency in Hz
+/// - Input 1: distortion amount in 0...1 (0 = pure sine)
+/// - Output 0: phase distorted wave
+pub fn phase_distort() -> An<PhaseDistort<f32>> {
+    super::prelude::phase_distort()
+}
+
+/// Fixed phase distortion oscillator at `f` Hz with distortion `amount` in 0...1.
+/// - Output 0: phase distorted wave
+pub fn phase_distort_hz(
+    f: f32,
+    amount: f32,
+) -> An<Pipe<f32, Constant<U2, f32>, PhaseDistort<f32>>> {
+    super::prelude::phase_distort_hz(f, amount)
+}
+
+/// Vocal synthesizer: a glottal pulse source filtered by a bank of formant resonators.
+/// Vowel is continuous in 0...4, morphing through "a", "e", "i", "o" and "u".
+/// - Input 0: pitch in Hz
+/// - Input 1: vowel in 0...4
+/// - Output 0: voice
+pub fn voice() -> An<Voice<f32>> {
+    super::prelude::voice()
+}
+
 /// Rossler dynamical system oscillator.
 /// - Input 0: frequency. The Rossler oscillator exhibits peaks at multiples of this frequency.
 /// - Output 0: system output
@@ -435,6 +527,43 @@ pub fn butterpass_hz(f: f32) -> An<ButterLowpass<f32, f32, U1>> {
     super::prelude::butterpass_hz(f)
 }
 
+/// Butterworth highpass filter (2nd order).
+/// - Input 0: audio
+/// - Input 1: cutoff frequency (Hz)
+/// - Output 0: filtered audio
+///
+/// ### Example: Filtered Noise
+/// ```
+/// use fundsp::hacker32::*;
+/// (noise() | dc(1000.0)) >> butterhighpass();
+/// ```
+pub fn butterhighpass() -> An<ButterHighpass<f32, f32, U2>> {
+    An(ButterHighpass::new(440.0))
+}
+
+/// Butterworth highpass filter (2nd order) with fixed cutoff frequency `f` Hz.
+/// - Input 0: audio
+/// - Output 0: filtered audio
+pub fn butterhighpass_hz(f: f32) -> An<ButterHighpass<f32, f32, U1>> {
+    super::prelude::butterhighpass_hz(f)
+}
+
+/// 4th order Linkwitz-Riley crossover at `frequency` Hz.
+/// The sum of the two bands has a flat magnitude response.
+/// Setting: crossover frequency.
+/// - Input 0: audio
+/// - Output 0: low band
+/// - Output 1: high band
+///
+/// ### Example: Two-Band Distortion
+/// ```
+/// use fundsp::hacker32::*;
+/// crossover2(200.0) >> (pass() + shape(Shape::Tanh(2.0)));
+/// ```
+pub fn crossover2(frequency: f32) -> An<Crossover2<f32, f32>> {
+    super::prelude::crossover2(frequency)
+}
+
 /// One-pole lowpass filter (1st order).
 /// - Input 0: audio
 /// - Input 1: cutoff frequency (Hz)
@@ -462,6 +591,52 @@ pub fn lowpole_hz(f: f32) -> An<Lowpole<f32, f32, U1>> {
     super::prelude::lowpole_hz(f)
 }
 
+/// Leaky integrator with time constant `time` seconds.
+/// The DC gain equals the time constant.
+/// - Input 0: audio
+/// - Output 0: integrated audio
+pub fn leaky_integrator(time: f32) -> An<LeakyIntegrator<f32, f32, U1>> {
+    super::prelude::leaky_integrator(time)
+}
+
+/// Saturating leaky integrator with time constant `time` seconds.
+/// The integrator state passes through `tanh`, so it stays in -1...1.
+/// - Input 0: audio
+/// - Output 0: integrated audio
+///
+/// ### Example: Soft Limited Envelope
+/// ```
+/// use fundsp::hacker32::*;
+/// noise() >> shape_fn(abs) >> leaky_integrator_tanh(0.01);
+/// ```
+pub fn leaky_integrator_tanh(time: f32) -> An<LeakyIntegrator<f32, f32, U1>> {
+    super::prelude::leaky_integrator_tanh(time)
+}
+
+/// Leaky integrator with a time constant input.
+/// The DC gain equals the time constant.
+/// - Input 0: audio
+/// - Input 1: time constant (seconds)
+/// - Output 0: integrated audio
+///
+/// ### Example: Modulated Integrator
+/// ```
+/// use fundsp::hacker32::*;
+/// (noise() | lfo(|t| xerp11(0.1, 1.0, sin_hz(0.1, t)))) >> leaky_integrator_var();
+/// ```
+pub fn leaky_integrator_var() -> An<LeakyIntegrator<f32, f32, U2>> {
+    super::prelude::leaky_integrator_var()
+}
+
+/// Saturating leaky integrator with a time constant input.
+/// The integrator state passes through `tanh`, so it stays in -1...1.
+/// - Input 0: audio
+/// - Input 1: time constant (seconds)
+/// - Output 0: integrated audio
+pub fn leaky_integrator_tanh_var() -> An<LeakyIntegrator<f32, f32, U2>> {
+    super::prelude::leaky_integrator_tanh_var()
+}
+
 /// Allpass filter (1st order) with adjustable delay (delay > 0) in samples at DC.
 /// - Input 0: audio
 /// - Input 1: delay in samples
@@ -477,6 +652,22 @@ pub fn allpole_delay(delay: f32) -> An<Allpole<f32, f32, U1>> {
     An(Allpole::new(delay))
 }
 
+/// Dispersion filter with `stiffness` in 0...1: a cascade of allpass filters
+/// that delays low frequencies more than high frequencies.
+/// Each of the 8 stages delays DC by (1 + `stiffness`) / (1 - `stiffness`) samples.
+/// Setting: stiffness.
+/// - Input 0: audio
+/// - Output 0: filtered audio
+///
+/// ### Example: Stiff Plucked String
+/// ```
+/// use fundsp::hacker32::*;
+/// pluck(220.0, 0.8, 0.5) >> dispersion(0.3);
+/// ```
+pub fn dispersion(stiffness: f32) -> An<Dispersion<f32>> {
+    super::prelude::dispersion(stiffness)
+}
+
 /// One-pole, one-zero highpass filter (1st order).
 /// - Input 0: audio
 /// - Input 1: cutoff frequency (Hz)
@@ -520,6 +711,77 @@ pub fn resonator_hz(center: f32, bandwidth: f32) -> An<Resonator<f32, f32, U1>>
     super::prelude::resonator_hz(center, bandwidth)
 }
 
+/// Multimode state variable filter with simultaneous lowpass, highpass, bandpass and notch outputs.
+/// The bandpass output has a peak gain of Q.
+/// - Input 0: audio
+/// - Input 1: cutoff frequency (Hz)
+/// - Input 2: Q
+/// - Output 0: lowpass
+/// - Output 1: highpass
+/// - Output 2: bandpass
+/// - Output 3: notch
+///
+/// ### Example: Swept Multimode Filter
+/// ```
+/// use fundsp::hacker32::*;
+/// (noise() | lfo(|t| (xerp11(200.0, 2000.0, sin_hz(0.5, t)), 2.0))) >> svf_multi();
+/// ```
+pub fn svf_multi() -> An<MultiSvf<f32, f32, U3>> {
+    super::prelude::svf_multi()
+}
+
+/// Multimode state variable filter with fixed `cutoff` frequency (Hz) and `q`.
+/// The bandpass output has a peak gain of `q`.
+/// Setting: (cutoff, Q).
+/// - Input 0: audio
+/// - Output 0: lowpass
+/// - Output 1: highpass
+/// - Output 2: bandpass
+/// - Output 3: notch
+///
+/// ### Example: Lowpass And Highpass Bands
+/// ```
+/// use fundsp::hacker32::*;
+/// noise() >> svf_multi_hz(1000.0, 0.7) >> (pass() | pass() | sink() | sink());
+/// ```
+pub fn svf_multi_hz(cutoff: f32, q: f32) -> An<MultiSvf<f32, f32, U1>> {
+    super::prelude::svf_multi_hz(cutoff, q)
+}
+
+/// Morphing state variable filter with modulated cutoff, Q and morph.
+/// Morph blends lowpass (-1), bandpass (0) and highpass (1) responses.
+/// The bandpass response has a peak gain of Q.
+/// - Input 0: audio
+/// - Input 1: cutoff frequency (Hz)
+/// - Input 2: Q
+/// - Input 3: morph in -1...1
+/// - Output 0: filtered audio
+///
+/// ### Example: Morph Sweep
+/// ```
+/// use fundsp::hacker32::*;
+/// (noise() | lfo(|t| (1000.0, 2.0, sin_hz(0.25, t)))) >> svf_morph();
+/// ```
+pub fn svf_morph() -> An<SvfMorph<f32, f32, U4>> {
+    super::prelude::svf_morph()
+}
+
+/// Morphing state variable filter with fixed `cutoff` frequency (Hz), `q` and `morph`.
+/// Morph blends lowpass (-1), bandpass (0) and highpass (1) responses.
+/// Morph changes made via settings are smoothed.
+/// Setting: (cutoff, Q, morph).
+/// - Input 0: audio
+/// - Output 0: filtered audio
+///
+/// ### Example: Lowpass Leaning Filter
+/// ```
+/// use fundsp::hacker32::*;
+/// noise() >> svf_morph_hz(800.0, 1.0, -0.5);
+/// ```
+pub fn svf_morph_hz(cutoff: f32, q: f32, morph: f32) -> An<SvfMorph<f32, f32, U1>> {
+    super::prelude::svf_morph_hz(cutoff, q, morph)
+}
+
 /// An arbitrary biquad filter with coefficients in normalized form.
 /// - Input 0: signal
 /// - Output 0: filtered signal
@@ -529,6 +791,24 @@ pub fn biquad(a1: f32, a2: f32, b0: f32, b1: f32, b2: f32) -> An<Biquad<f32, f32
     )))
 }
 
+/// Graphic equalizer with `bands` fixed ISO bands: 10 for octave bands
+/// or 31 for third-octave bands. Each band is a peaking biquad.
+/// Band gains start at 0 dB and can be adjusted with `set_gain_db`.
+/// Setting: (band, gain in dB).
+/// - Input 0: signal
+/// - Output 0: equalized signal
+///
+/// ### Example: Boost 1 kHz Octave Band
+/// ```
+/// use fundsp::hacker32::*;
+/// let mut eq = graphic_eq(10);
+/// eq.set_gain_db(5, 6.0);
+/// assert_eq!(eq.center(5), 1000.0);
+/// ```
+pub fn graphic_eq(bands: usize) -> An<GraphicEq<f32, f32>> {
+    An(GraphicEq::new(bands))
+}
+
 /// Moog resonant lowpass filter.
 /// - Input 0: input signal
 /// - Input 1: cutoff frequency (Hz)
@@ -754,6 +1034,45 @@ where
     An(EnvelopeIn::new(0.002, DEFAULT_SR, f))
 }
 
+/// Linear ramp from `from` to `to` in `duration` seconds, after which the output holds at `to`.
+/// The ramp starts over when reset.
+/// - Output 0: ramp value
+///
+/// ### Example: Filter Cutoff Sweep
+/// ```
+/// use fundsp::hacker32::*;
+/// (noise() | ramp(100.0, 10_000.0, 5.0) | constant(1.0)) >> lowpass();
+/// ```
+pub fn ramp(from: f32, to: f32, duration: f64) -> An<Ramp<f32>> {
+    An(Ramp::new(DEFAULT_SR, from, to, duration))
+}
+
+/// Unit impulse test signal: 1.0 on the first sample after reset, then zeros.
+/// - Output 0: impulse
+///
+/// ### Example: Impulse Response
+/// ```
+/// use fundsp::hacker32::*;
+/// let mut node = impulse() >> lowpole_hz(1000.0);
+/// let response: Vec<f32> = (0..100).map(|_| node.get_mono()).collect();
+/// ```
+pub fn impulse() -> An<Impulse<f32>> {
+    An(Impulse::new())
+}
+
+/// Unit step test signal: 0.0 on the first sample after reset, then 1.0.
+/// - Output 0: step
+///
+/// ### Example: Step Response
+/// ```
+/// use fundsp::hacker32::*;
+/// let mut node = step() >> lowpole_hz(1000.0);
+/// let response: Vec<f32> = (0..100).map(|_| node.get_mono()).collect();
+/// ```
+pub fn step() -> An<Step<f32>> {
+    An(Step::new())
+}
+
 /// ADSR envelope.
 ///
 /// When a positive value is given by the input, its output increases from 0.0 to 1.0 in the time
@@ -776,6 +1095,38 @@ pub fn adsr_live(
     super::adsr::adsr_live(attack, decay, sustain, release)
 }
 
+/// Arpeggiator cycling through held notes in `pattern` at `rate` steps per second.
+/// Notes are held and released via the returned frontend as MIDI note numbers.
+/// Returns (frontend, backend).
+/// - Output 0: frequency in Hz, or zero if no notes are held.
+///
+/// ### Example: Arpeggiated C Major Chord
+/// ```
+/// use fundsp::hacker32::*;
+/// let (notes, arp) = arpeggiator(ArpPattern::UpDown, 8.0);
+/// notes.set_notes(&[60.0, 64.0, 67.0]);
+/// let synth = arp >> saw();
+/// ```
+pub fn arpeggiator(pattern: ArpPattern, rate: f64) -> (ArpNotes, An<Arpeggiator<f32>>) {
+    super::prelude::arpeggiator(pattern, rate)
+}
+
+/// Pitch to frequency converter with A4 (MIDI note 69) at `reference` Hz.
+/// Quantization to semitones and octave stretch can be configured on the node.
+/// - Input 0: pitch in semitones
+/// - Output 0: frequency in Hz
+///
+/// ### Example: Baroque Tuning With Quantized Pitch Bend
+/// ```
+/// use fundsp::hacker32::*;
+/// let mut converter = tune(415.0);
+/// converter.set_quantize(true);
+/// let synth = (dc(60.0) + lfo(|t| sin_hz(0.5, t))) >> converter >> saw();
+/// ```
+pub fn tune(reference: f32) -> An<Tune<f32>> {
+    An(Tune::new(reference))
+}
+
 /// Maximum Length Sequence noise generator from an `n`-bit sequence (1 <= `n` <= 31).
 /// - Output 0: repeating white noise sequence of only -1 and 1 values.
 ///
@@ -912,6 +1263,35 @@ pub fn delay(t: f32) -> An<Delay<f32>> {
     An(Delay::new(t as f64))
 }
 
+/// Stereo feedback delay with delay times `left_time` and `right_time` in seconds.
+/// Delayed signal feeds back into the same channel with gain `feedback`
+/// and into the opposite channel with gain `cross_feedback`.
+/// Outputs the delayed signal only. Delay times are rounded to the nearest sample.
+/// Allocates: the delay lines.
+/// - Input 0: left signal.
+/// - Input 1: right signal.
+/// - Output 0: left delayed signal.
+/// - Output 1: right delayed signal.
+///
+/// ### Example: Dotted Eighth And Quarter Note Echoes At 120 BPM
+/// ```
+/// use fundsp::hacker32::*;
+/// multipass() & 0.3 * stereo_delay(0.375, 0.5, 0.4, 0.2);
+/// ```
+pub fn stereo_delay(
+    left_time: f32,
+    right_time: f32,
+    feedback: f32,
+    cross_feedback: f32,
+) -> An<StereoDelay<f32>> {
+    An(StereoDelay::new(
+        left_time as f64,
+        right_time as f64,
+        feedback,
+        cross_feedback,
+    ))
+}
+
 /// Tapped delay line with cubic interpolation.
 /// Minimum and maximum delay times are in seconds.
 /// Allocates: the delay line.
@@ -928,6 +1308,22 @@ pub fn tap(min_delay: f32, max_delay: f32) -> An<Tap<U1, f32>> {
     An(Tap::new(min_delay, max_delay))
 }
 
+/// Variable delay line with cubic Hermite interpolation.
+/// Delay time is clamped between one sample and `max_delay` seconds.
+/// Allocates: the delay line.
+/// - Input 0: signal.
+/// - Input 1: delay time in seconds.
+/// - Output 0: delayed signal.
+///
+/// ### Example: Chorus Voice
+/// ```
+/// use fundsp::hacker32::*;
+/// pass() & (pass() | lfo(|t| lerp11(0.01, 0.02, sin_hz(0.5, t)))) >> delay_var(0.02);
+/// ```
+pub fn delay_var(max_delay: f32) -> An<DelayVar<f32>> {
+    An(DelayVar::new(max_delay as f64))
+}
+
 /// Tapped delay line with cubic interpolation.
 /// The number of taps is `N`.
 /// Minimum and maximum delay times are in seconds.
@@ -970,6 +1366,66 @@ where
     An(Oversampler::new(DEFAULT_SR, node.0))
 }
 
+/// Oversample `unit` by `factor` (typically 2 or 4) using polyphase FIR filters.
+/// Unlike `oversample`, this works with any unit, including networks,
+/// and supports higher factors. The filters add a latency of 32 samples.
+/// - Inputs and outputs: from `unit`.
+///
+/// ### Example: Oversampled Waveshaper
+/// ```
+/// use fundsp::hacker32::*;
+/// let mut unit = oversample_unit(4, Box::new(shape(Shape::Tanh(4.0))));
+/// assert_eq!(unit.latency(), Some(32.0));
+/// ```
+pub fn oversample_unit(factor: usize, unit: Box<dyn AudioUnit32>) -> Oversample32 {
+    Oversample32::new(factor, unit)
+}
+
+/// Mix `unit` with its input by wet `amount` in 0...1.
+/// The dry path is delayed to match the latency of `unit`.
+/// Amount 0 outputs the dry signal and amount 1 the output of `unit` without mixing.
+/// - Inputs and outputs: from `unit`, which must have equally many of each.
+///
+/// ### Example: Half Wet Reverb
+/// ```
+/// use fundsp::hacker32::*;
+/// let mut unit = dry_wet(0.5, Box::new(reverb_stereo(10.0, 2.0)));
+/// assert_eq!(unit.inputs(), 2);
+/// ```
+pub fn dry_wet(amount: f32, unit: Box<dyn AudioUnit32>) -> DryWet32 {
+    DryWet32::new(amount, unit)
+}
+
+/// Mix `unit` with its input by a wet amount in 0...1 read from shared variable `amount`.
+/// The dry path is delayed to match the latency of `unit`.
+/// - Inputs and outputs: from `unit`, which must have equally many of each.
+///
+/// ### Example: Chorus With Wetness Control
+/// ```
+/// use fundsp::hacker32::*;
+/// let wet = shared(0.2);
+/// let mut unit = dry_wet_var(&wet, Box::new(chorus(0, 0.015, 0.005, 0.5)));
+/// wet.set_value(0.8);
+/// ```
+pub fn dry_wet_var(amount: &Shared<f32>, unit: Box<dyn AudioUnit32>) -> DryWet32 {
+    DryWet32::with_shared(amount, unit)
+}
+
+/// Mix `unit` with its input by a wet amount in 0...1 read from an extra input.
+/// The dry path is delayed to match the latency of `unit`.
+/// - Inputs: from `unit`, followed by the wet amount.
+/// - Outputs: from `unit`, which must have equally many inputs and outputs.
+///
+/// ### Example: Automated Lowpass
+/// ```
+/// use fundsp::hacker32::*;
+/// let mut unit = dry_wet_input(Box::new(lowpass_hz(1000.0, 1.0)));
+/// assert_eq!(unit.inputs(), 2);
+/// ```
+pub fn dry_wet_input(unit: Box<dyn AudioUnit32>) -> DryWet32 {
+    DryWet32::with_input(unit)
+}
+
 /// Resample enclosed generator `node` using cubic interpolation
 /// at speed obtained from input 0, where 1 is the original speed.
 /// Input 0: Sampling speed.
@@ -1036,6 +1492,41 @@ where
     An(Feedback2::new(node.0, loopback.0, FrameId::new()))
 }
 
+/// Flush outputs of `node`, such as a reverb or a feedback delay, to zero below -200 dB,
+/// so a decaying tail ends in silence. Inputs are passed to `node` untouched.
+/// To keep feedback paths out of the denormal range, see `loop_stabilizer`.
+/// - Input(s): input of `node`.
+/// - Output(s): output of `node`.
+///
+/// ### Example: Stabilized Reverb
+/// ```
+/// use fundsp::hacker32::*;
+/// stabilize(reverb_stereo(10.0, 5.0));
+/// ```
+pub fn stabilize<X>(node: An<X>) -> An<Stabilizer<f32, X>>
+where
+    X: AudioNode<Sample = f32>,
+    X::Inputs: Size<f32>,
+    X::Outputs: Size<f32>,
+{
+    An(Stabilizer::new(node.0))
+}
+
+/// Feedback loop processing that keeps a recursive structure out of the denormal range.
+/// Each channel is DC blocked and receives inaudible decorrelated dither.
+/// Use it as the loopback of `feedback2` or `fdn2` to touch only the fed-back signal.
+/// - Input(s): fed-back signal.
+/// - Output(s): stabilized signal.
+///
+/// ### Example: Stabilized Feedback Delay
+/// ```
+/// use fundsp::hacker32::*;
+/// stabilize(pass() & feedback2(delay(0.1) * db_amp(-3.0), loop_stabilizer()));
+/// ```
+pub fn loop_stabilizer<N: Size<f32>>() -> An<LoopStabilizer<N, f32>> {
+    An(LoopStabilizer::new())
+}
+
 /// Transform channels freely. Accounted as non-linear processing for signal flow.
 ///
 /// ### Example: Max Operator
@@ -1053,6 +1544,29 @@ where
     An(Map::new(f, Routing::Arbitrary))
 }
 
+/// Custom unit with `inputs` inputs and `outputs` outputs defined by closure `f`,
+/// which is called once per sample with input and output slices.
+/// This is handy for prototyping before writing an `AudioNode`.
+/// Resetting the unit restores the closure to its initial state.
+/// Frequency responses are unknown.
+///
+/// ### Example: One-Pole Lowpass With Captured State
+/// ```
+/// use fundsp::hacker32::*;
+/// let mut value = 0.0;
+/// let mut unit = custom_unit(1, 1, move |input: &[f32], output: &mut [f32]| {
+///     value += (input[0] - value) * 0.1;
+///     output[0] = value;
+/// });
+/// assert_eq!(unit.filter_mono(1.0), 0.1);
+/// ```
+pub fn custom_unit<F>(inputs: usize, outputs: usize, f: F) -> Box<dyn AudioUnit32>
+where
+    F: FnMut(&[f32], &mut [f32]) + Clone + Send + Sync + 'static,
+{
+    Box::new(CustomUnit32::new(inputs, outputs, f))
+}
+
 /// Keeps a signal zero centered.
 /// Filter `cutoff` (in Hz) is usually somewhere below the audible range.
 /// The default blocker cutoff is 10 Hz.
@@ -1115,6 +1629,15 @@ pub fn shape(mode: Shape<f32>) -> An<Shaper<f32>> {
     super::prelude::shape(mode)
 }
 
+/// Shape signal with a per-sample `drive` input using the curve `mode`.
+/// The signal is multiplied by the drive before shaping.
+/// - Input 0: input signal
+/// - Input 1: drive amount (1 = unity)
+/// - Output 0: shaped signal
+pub fn shape_drive(mode: DriveShape) -> An<DriveShaper<f32>> {
+    super::prelude::shape_drive(mode)
+}
+
 /// Clip signal to -1...1.
 /// - Input 0: input signal
 /// - Output 0: clipped signal
@@ -1129,6 +1652,30 @@ pub fn clip_to(minimum: f32, maximum: f32) -> An<Shaper<f32>> {
     super::prelude::clip_to(minimum, maximum)
 }
 
+/// Hard clip signal to -`threshold`...`threshold` with no lookahead.
+/// Setting: threshold.
+/// - Input 0: input signal
+/// - Output 0: clipped signal
+///
+/// ### Example: Safety Clipper
+/// ```
+/// use fundsp::hacker32::*;
+/// let safe = (sine_hz(110.0) * 2.0) >> clipper(0.9);
+/// ```
+pub fn clipper(threshold: f32) -> An<Clipper<f32>> {
+    super::prelude::clipper(threshold)
+}
+
+/// Soft clip signal to -`threshold`...`threshold` with no lookahead.
+/// Signals within `threshold` - `knee` pass unchanged;
+/// the knee bends the curve into the threshold over a width of 2 * `knee`
+/// (0 <= `knee` <= `threshold`). Setting: threshold.
+/// - Input 0: input signal
+/// - Output 0: clipped signal
+pub fn clipper_soft(threshold: f32, knee: f32) -> An<Clipper<f32>> {
+    super::prelude::clipper_soft(threshold, knee)
+}
+
 /// Equal power mono-to-stereo panner.
 /// - Input 0: input signal
 /// - Input 1: pan in -1...1 (left to right).
@@ -1158,6 +1705,190 @@ pub fn pan(pan: f32) -> An<Panner<f32, U1>> {
     An(Panner::new(pan))
 }
 
+/// Fixed mono-to-stereo panner with panning `law` and `pan` value in -1...1 (left to right).
+/// `PanLaw::ConstantPower` is the law used by `pan`.
+/// - Input 0: input signal
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example (Linear Panning)
+/// ```
+/// use fundsp::hacker32::*;
+/// saw_hz(440.0) >> pan_law(PanLaw::Linear, 0.5);
+/// ```
+pub fn pan_law(law: PanLaw, pan: f32) -> An<Panner<f32, U1>> {
+    An(Panner::with_law(law, pan))
+}
+
+/// Mono-to-stereo panner with panning `law`. The pan value is an input.
+/// - Input 0: input signal
+/// - Input 1: pan in -1...1 (left to right).
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example: Panning Noise Linearly
+/// ```
+/// use fundsp::hacker32::*;
+/// (noise() | sine_hz(0.5)) >> panner_law(PanLaw::Linear);
+/// ```
+pub fn panner_law(law: PanLaw) -> An<Panner<f32, U2>> {
+    An(Panner::with_law(law, 0.0))
+}
+
+/// Mono-to-stereo spatializer with azimuth and distance inputs.
+/// Applies level and time differences for azimuth,
+/// inverse distance attenuation and a gentle lowpass for far sources.
+/// Azimuth is 0 straight ahead and `PI / 2` hard right.
+/// - Input 0: input signal
+/// - Input 1: azimuth in radians
+/// - Input 2: distance in meters
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example: Source Circling The Listener
+/// ```
+/// use fundsp::hacker32::*;
+/// (noise() | lfo(|t| t) | dc(2.0)) >> spatial_panner();
+/// ```
+pub fn spatial_panner() -> An<SpatialPanner<f32, U3>> {
+    An(SpatialPanner::new(DEFAULT_SR, 0.0, 1.0))
+}
+
+/// Stereo master section with smoothed output `gain` (in amplitude), `balance` in -1...1
+/// and stereo `width` (`width` >= 0). Width 0 is mono, 1 is neutral and above 1 widens.
+/// Balance -1 mutes the right channel and 1 mutes the left channel.
+/// Setting: (gain, balance, width).
+/// - Input 0: left input
+/// - Input 1: right input
+/// - Output 0: left output
+/// - Output 1: right output
+///
+/// ### Example: Narrowed Final Mix
+/// ```
+/// use fundsp::hacker32::*;
+/// (saw_hz(110.0) | square_hz(110.5)) >> master(db_amp(-3.0), 0.0, 0.5);
+/// ```
+pub fn master(gain: f32, balance: f32, width: f32) -> An<Master<f32>> {
+    super::prelude::master(gain, balance, width)
+}
+
+/// Mid-side encoder. Converts left and right channels into
+/// mid `(L + R) / 2` and side `(L - R) / 2` channels.
+/// - Input 0: left input
+/// - Input 1: right input
+/// - Output 0: mid output
+/// - Output 1: side output
+///
+/// ### Example: Processing The Side Channel
+/// ```
+/// use fundsp::hacker32::*;
+/// midside() >> (pass() | highpass_hz(200.0, 0.7)) >> sidemid();
+/// ```
+pub fn midside() -> An<MidSide<f32>> {
+    super::prelude::midside()
+}
+
+/// Mid-side decoder. Converts mid and side channels
+/// into left `M + S` and right `M - S` channels. This is the inverse of `midside`.
+/// - Input 0: mid input
+/// - Input 1: side input
+/// - Output 0: left output
+/// - Output 1: right output
+pub fn sidemid() -> An<SideMid<f32>> {
+    super::prelude::sidemid()
+}
+
+/// Stereo width control. Scales the side channel by `amount` while passing the mid channel.
+/// Width 0 is mono, 1 is an exact identity and values above 1 widen the image.
+/// Setting: width.
+/// - Input 0: left input
+/// - Input 1: right input
+/// - Output 0: left output
+/// - Output 1: right output
+///
+/// ### Example: Wider Stereo Image
+/// ```
+/// use fundsp::hacker32::*;
+/// (saw_hz(110.0) | saw_hz(110.5)) >> width(1.5);
+/// ```
+pub fn width(amount: f32) -> An<Width<f32>> {
+    super::prelude::width(amount)
+}
+
+/// First order ambisonic encoder. Places the input at `azimuth` and `elevation` radians
+/// in a traditional B-format sound field. Azimuth is 0 straight ahead
+/// and `PI / 2` hard right. Elevation is 0 level and `PI / 2` straight up.
+/// Setting: (azimuth, elevation).
+/// - Input 0: input signal
+/// - Output 0: W (omnidirectional, scaled by 1/sqrt(2))
+/// - Output 1: X (front)
+/// - Output 2: Y (left)
+/// - Output 3: Z (up)
+///
+/// ### Example: Noise Front Right
+/// ```
+/// use fundsp::hacker32::*;
+/// noise() >> ambi_encode(0.25 * PI as f32, 0.0);
+/// ```
+pub fn ambi_encode(azimuth: f32, elevation: f32) -> An<AmbiEncoder<f32>> {
+    super::prelude::ambi_encode(azimuth, elevation)
+}
+
+/// First order ambisonic decoder for `N` speakers at (azimuth, elevation) positions
+/// in `layout`, using the same conventions as `ambi_encode`.
+/// Layouts with all speakers at zero elevation are decoded horizontally.
+/// - Input 0: W
+/// - Input 1: X
+/// - Input 2: Y
+/// - Input 3: Z
+/// - Output(s): `N` speaker signals
+///
+/// ### Example: Square Speaker Layout
+/// ```
+/// use fundsp::hacker32::*;
+/// let layout = [(-0.25 * PI as f32, 0.0), (0.25 * PI as f32, 0.0), (0.75 * PI as f32, 0.0), (-0.75 * PI as f32, 0.0)];
+/// noise() >> ambi_encode(0.25 * PI as f32, 0.0) >> ambi_decode::<U4>(&layout);
+/// ```
+pub fn ambi_decode<N: Size<f32>>(layout: &[(f32, f32)]) -> An<AmbiDecoder<f32, N>> {
+    super::prelude::ambi_decode(layout)
+}
+
+/// Binaural spatializer using the built-in spherical head HRIR set.
+/// Source is at `azimuth` and `elevation` radians. Azimuth is 0 straight ahead
+/// and `PI / 2` hard right. Elevation is 0 level and `PI / 2` straight up.
+/// Setting: (azimuth, elevation).
+/// - Input 0: input signal
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example: Noise To The Left
+/// ```
+/// use fundsp::hacker32::*;
+/// noise() >> binaural(-0.5 * PI as f32, 0.0);
+/// ```
+pub fn binaural(azimuth: f32, elevation: f32) -> An<Binaural<f32>> {
+    super::prelude::binaural(azimuth, elevation)
+}
+
+/// Binaural spatializer convolving with HRIR pairs from `set`.
+/// Source is at `azimuth` and `elevation` radians.
+/// Setting: (azimuth, elevation).
+/// - Input 0: input signal
+/// - Output 0: left channel
+/// - Output 1: right channel
+pub fn binaural_hrir(set: &HrirSet, azimuth: f32, elevation: f32) -> An<Binaural<f32>> {
+    super::prelude::binaural_hrir(set, azimuth, elevation)
+}
+
+/// Fixed mono-to-stereo spatializer with source at `azimuth` radians and `distance` meters.
+/// Azimuth is 0 straight ahead and `PI / 2` hard right.
+/// - Input 0: input signal
+/// - Output 0: left channel
+/// - Output 1: right channel
+pub fn spatial_pan(azimuth: f32, distance: f32) -> An<SpatialPanner<f32, U1>> {
+    An(SpatialPanner::new(DEFAULT_SR, azimuth, distance))
+}
+
 /// Parameter follower filter with halfway response time `t` seconds.
 /// - Input 0: input signal
 /// - Output 0: smoothed signal
@@ -1192,6 +1923,59 @@ pub fn limiter_stereo<S: ScalarOrPair<Sample = f32>>(time: S) -> An<Limiter<f32,
     An(Limiter::new(DEFAULT_SR, time))
 }
 
+/// RMS compressor. Levels above `threshold_db` are compressed by `ratio`
+/// with `attack` and `release` times in seconds.
+/// The RMS window is 10 ms by default; it can be changed with `Compressor::set_window`.
+/// Setting: (threshold in dB, ratio).
+/// - Input 0: signal
+/// - Output 0: compressed signal
+///
+/// ### Example: Vocal Compression
+/// ```
+/// use fundsp::hacker32::*;
+/// noise() >> compressor(-18.0, 3.0, 0.005, 0.1);
+/// ```
+pub fn compressor(
+    threshold_db: f32,
+    ratio: f32,
+    attack: f32,
+    release: f32,
+) -> An<Compressor<f32, U1, U1>> {
+    super::prelude::compressor(threshold_db, ratio, attack, release)
+}
+
+/// Stereo RMS compressor with linked detection. Levels above `threshold_db`
+/// are compressed by `ratio` with `attack` and `release` times in seconds.
+/// Setting: (threshold in dB, ratio).
+/// - Input 0: left signal
+/// - Input 1: right signal
+/// - Output 0: compressed left signal
+/// - Output 1: compressed right signal
+pub fn compressor_stereo(
+    threshold_db: f32,
+    ratio: f32,
+    attack: f32,
+    release: f32,
+) -> An<Compressor<f32, U2, U2>> {
+    super::prelude::compressor_stereo(threshold_db, ratio, attack, release)
+}
+
+/// RMS compressor with a gain reduction output for metering.
+/// Levels above `threshold_db` are compressed by `ratio`
+/// with `attack` and `release` times in seconds.
+/// Setting: (threshold in dB, ratio).
+/// - Input 0: signal
+/// - Output 0: compressed signal
+/// - Output 1: gain reduction in dB (zero or negative)
+pub fn compressor_meter(
+    threshold_db: f32,
+    ratio: f32,
+    attack: f32,
+    release: f32,
+) -> An<Compressor<f32, U1, U2>> {
+    super::prelude::compressor_meter(threshold_db, ratio, attack, release)
+}
+
 /// Pinking filter.
 /// - Input 0: input signal
 /// - Output 0: filtered signal
@@ -1199,12 +1983,22 @@ pub fn pinkpass() -> An<Pinkpass<f32, f32>> {
     An(Pinkpass::new())
 }
 
-/// Pink noise.
+/// Pink noise: white noise filtered with `pinkpass`.
+/// Energy is roughly equal in each octave. The RMS level is approximately 0.2.
 /// - Output 0: pink noise
 pub fn pink() -> An<Pipe<f32, Noise<f32>, Pinkpass<f32, f32>>> {
     super::prelude::pink()
 }
 
+/// Pink noise normalized to an RMS level of approximately 1.
+/// Energy is roughly equal in each octave.
+/// - Output 0: pink noise
+pub fn pink_normalized(
+) -> An<Pipe<f32, Noise<f32>, Binop<f32, FrameMul<U1, f32>, Pinkpass<f32, f32>, Constant<U1, f32>>>>
+{
+    super::prelude::pink_normalized()
+}
+
 /// Brown noise.
 /// - Output 0: brown noise
 pub fn brown() -> An<
@@ -1214,6 +2008,18 @@ pub fn brown() -> An<
     white() >> lowpole_hz(10.0) * dc(13.7)
 }
 
+/// Blue noise (+3 dB/octave): differentiated pink noise.
+/// - Output 0: blue noise
+pub fn blue_noise() -> An<Pipe<f32, Pipe<f32, Noise<f32>, Pinkpass<f32, f32>>, Fir<f32, U2>>> {
+    super::prelude::blue_noise()
+}
+
+/// Violet noise (+6 dB/octave): differentiated white noise.
+/// - Output 0: violet noise
+pub fn violet_noise() -> An<Pipe<f32, Noise<f32>, Fir<f32, U2>>> {
+    super::prelude::violet_noise()
+}
+
 /// Feedback delay network.
 /// Mix output of enclosed circuit `x` back to its input.
 /// The output is diffused with a Hadamard matrix for feedback.
@@ -1464,6 +2270,44 @@ where
     super::prelude::join::<N, f32>()
 }
 
+/// Sum `N` channels into one with power-preserving gain `1 / sqrt(N)`.
+/// Uncorrelated inputs of equal level keep their level in the sum.
+/// - Input(s): `N`.
+/// - Output 0: scaled sum.
+///
+/// ### Example: Mix Four Noise Sources
+/// ```
+/// use fundsp::hacker32::*;
+/// let mix = (noise() | noise() | noise() | noise()) >> join_power::<U4>();
+/// ```
+pub fn join_power<N>() -> An<JoinPower<N, f32>>
+where
+    N: Size<f32>,
+{
+    super::prelude::join_power::<N, f32>()
+}
+
+/// Mixer with per-input gain, mute and solo controls. Mixes `N` inputs into one.
+/// Controls can be set live via the node or any of its clones.
+/// Gain transitions are smoothed with a 10 ms time constant.
+/// - Input(s): `N` signals.
+/// - Output 0: mix.
+///
+/// ### Example: Solo The Second Input
+/// ```
+/// use fundsp::hacker32::*;
+/// let mix = mixer::<U3>();
+/// let control = mix.clone();
+/// let graph = (sine_hz(110.0) | sine_hz(220.0) | sine_hz(440.0)) >> mix;
+/// control.set_solo(1, true);
+/// ```
+pub fn mixer<N>() -> An<Mixer<N, f32>>
+where
+    N: Size<f32>,
+{
+    An(Mixer::new(DEFAULT_SR, 0.01))
+}
+
 /// Average `N` branches of `M` channels into one branch with `M` channels.
 /// The input has `N` * `M` channels. Inverse of `multisplit::<M, N>`.
 /// - Input(s): `N` * `M`.
@@ -1497,6 +2341,31 @@ pub fn reverb_stereo(
     super::prelude::reverb_stereo::<f32>(room_size, time)
 }
 
+/// Dattorro plate reverb.
+/// `decay` is reverberation time to -60 dB in seconds.
+/// `damping` in 0...1 is the amount of high frequency absorption.
+/// `predelay` is in seconds. Outputs the reverberated signal only.
+/// Modulation depth can be adjusted with `set_modulation`.
+/// Allocates: the delay lines.
+/// - Input 0: left signal
+/// - Input 1: right signal
+/// - Output 0: reverberated left signal
+/// - Output 1: reverberated right signal
+///
+/// ### Example: Add 20% Plate Reverb
+/// ```
+/// use fundsp::hacker32::*;
+/// multipass() & 0.2 * plate_reverb(2.0, 0.3, 0.02);
+/// ```
+pub fn plate_reverb(decay: f32, damping: f32, predelay: f32) -> An<PlateReverb<f32>> {
+    An(PlateReverb::new(
+        DEFAULT_SR,
+        decay as f64,
+        damping as f64,
+        predelay as f64,
+    ))
+}
+
 /// Saw-like discrete summation formula oscillator.
 /// - Input 0: frequency in Hz
 /// - Input 1: roughness in 0...1 is the attenuation of successive partials.
@@ -1548,6 +2417,25 @@ pub fn pluck(frequency: f32, gain_per_second: f32, high_frequency_damping: f32)
     ))
 }
 
+/// Karplus-Strong plucked string oscillator with variable pitch and damping.
+/// The pluck buffer is sized for frequencies down to `min_frequency` Hz.
+/// The string decays by `gain_per_second` each second.
+/// A rising edge in the gate plucks the string with a noise burst.
+/// Allocates: pluck buffer.
+/// - Input 0: frequency in Hz
+/// - Input 1: high frequency damping in 0...1
+/// - Input 2: gate
+/// - Output 0: oscillator output
+///
+/// ### Example: Pluck With Vibrato
+/// ```
+/// use fundsp::hacker32::*;
+/// let node = lfo(|t| (xerp11(215.0, 225.0, sin_hz(5.0, t)), 0.5, 1.0)) >> pluck_var(100.0, 0.5);
+/// ```
+pub fn pluck_var(min_frequency: f32, gain_per_second: f32) -> An<PluckVar<f32>> {
+    An(PluckVar::new(min_frequency, gain_per_second))
+}
+
 /// Saw wavetable oscillator.
 /// Allocates: global saw wavetable.
 /// - Input 0: frequency in Hz
@@ -1597,6 +2485,24 @@ pub fn hammond() -> An<WaveSynth<'static, f32, U1>> {
     An(WaveSynth::new(DEFAULT_SR, &HAMMOND_TABLE))
 }
 
+/// Wavetable oscillator scanning through a stack of `tables` (at least one).
+/// Scan position 0 plays the first table and 1 the last.
+/// Neighboring tables are interpolated with `ScanInterpolation::Linear`
+/// or `ScanInterpolation::Cubic`; cubic scanning is smoother on sparse table sets.
+/// - Input 0: frequency in Hz
+/// - Input 1: scan position in 0...1
+/// - Output 0: audio
+///
+/// ### Example: Scanning From Saw To Organ
+/// ```
+/// use fundsp::hacker32::*;
+/// let tables = [SAW_TABLE.clone(), SOFT_SAW_TABLE.clone(), ORGAN_TABLE.clone()];
+/// (dc(110.0) | lfo(|t| 0.5 + 0.5 * sin_hz(0.25, t))) >> wave_scan(&tables, ScanInterpolation::Cubic);
+/// ```
+pub fn wave_scan(tables: &[Wavetable], interpolation: ScanInterpolation) -> An<WaveScan<f32>> {
+    An(WaveScan::new(DEFAULT_SR, tables, interpolation))
+}
+
 /// Fixed saw wavetable oscillator at `f` Hz.
 /// Allocates: global saw wavetable.
 /// - Output 0: saw wave
@@ -1604,6 +2510,24 @@ pub fn saw_hz(f: f32) -> An<Pipe<f32, Constant<U1, f32>, WaveSynth<'static, f32,
     super::prelude::saw_hz(f)
 }
 
+/// PolyBLEP saw oscillator with anti-aliasing `quality`.
+/// `Quality::Low` applies no anti-aliasing and is the cheapest.
+/// `Quality::Medium` corrects two samples around each discontinuity and
+/// `Quality::High` corrects four, trading a little CPU for less aliasing.
+/// The wavetable oscillator `saw` has the least aliasing of all.
+/// - Input 0: frequency in Hz
+/// - Output 0: saw wave
+pub fn saw_quality(quality: Quality) -> An<PolySaw<f32>> {
+    super::prelude::saw_quality(quality)
+}
+
+/// Fixed PolyBLEP saw oscillator at `f` Hz with anti-aliasing `quality`.
+/// See `saw_quality` for the quality levels.
+/// - Output 0: saw wave
+pub fn saw_hz_quality(f: f32, quality: Quality) -> An<Pipe<f32, Constant<U1, f32>, PolySaw<f32>>> {
+    super::prelude::saw_hz_quality(f, quality)
+}
+
 /// Fixed square wavetable oscillator at `f` Hz.
 /// Allocates: global square wavetable.
 /// - Output 0: square wave
@@ -1893,6 +2817,45 @@ pub fn highshelf_q(
     super::prelude::highshelf_q::<f32, f32>(q, gain)
 }
 
+/// Biquad low shelf at `f` Hz with `gain_db` dB of gain at DC and transition `slope` in 0...1.
+/// A slope of 1 is the steepest that keeps the response monotonic.
+/// Setting: (frequency, gain in dB, slope).
+/// - Input 0: audio
+/// - Output 0: filtered audio
+pub fn lowshelf_slope(f: f32, gain_db: f32, slope: f32) -> An<BiquadShelf<f32, f32>> {
+    super::prelude::lowshelf_slope(f, gain_db, slope)
+}
+
+/// Biquad high shelf at `f` Hz with `gain_db` dB of gain at Nyquist and transition `slope` in 0...1.
+/// A slope of 1 is the steepest that keeps the response monotonic.
+/// Setting: (frequency, gain in dB, slope).
+/// - Input 0: audio
+/// - Output 0: filtered audio
+///
+/// ### Example: Air Band Lift
+/// ```
+/// use fundsp::hacker32::*;
+/// highshelf_slope(8000.0, 2.0, 1.0);
+/// ```
+pub fn highshelf_slope(f: f32, gain_db: f32, slope: f32) -> An<BiquadShelf<f32, f32>> {
+    super::prelude::highshelf_slope(f, gain_db, slope)
+}
+
+/// Tilt equalizer that tilts the spectrum by `gain_db` dB around `pivot` Hz.
+/// Positive gain boosts highs and cuts lows by up to half the gain each.
+/// Setting: gain in dB.
+/// - Input 0: audio
+/// - Output 0: filtered audio
+///
+/// ### Example: Brighten A Saw Wave
+/// ```
+/// use fundsp::hacker32::*;
+/// saw_hz(110.0) >> tilt_eq(4.0, 800.0);
+/// ```
+pub fn tilt_eq(gain_db: f32, pivot: f32) -> An<TiltEq<f32, f32>> {
+    super::prelude::tilt_eq(gain_db, pivot)
+}
+
 /// Resonant two-pole lowpass filter.
 /// - Input 0: audio
 /// - Input 1: cutoff frequency
@@ -1953,6 +2916,38 @@ pub fn pulse() -> An<super::prelude::PulseWave<f32>> {
     super::prelude::pulse()
 }
 
+/// PolyBLEP pulse oscillator with pulse width modulation.
+/// Both edges are bandlimited, so width sweeps do not alias.
+/// Width 0.5 is a square wave; other widths add even harmonics.
+/// - Input 0: frequency in Hz
+/// - Input 1: pulse width in 0...1
+/// - Output 0: pulse wave
+///
+/// ### Example: Pulse Width Modulation
+/// ```
+/// use fundsp::hacker32::*;
+/// lfo(|t| (110.0, 0.5 + 0.4 * sin_hz(0.5, t))) >> pulse_pwm();
+/// ```
+pub fn pulse_pwm() -> An<PolyPulse<f32>> {
+    super::prelude::pulse_pwm()
+}
+
+/// Saw oscillator with a phase-locked sub-oscillator `octaves` (1 or 2) below.
+/// The sub-oscillator `wave` is `SubWave::Square` or `SubWave::Sine`.
+/// Setting: sub-oscillator waveform.
+/// - Input 0: frequency in Hz
+/// - Output 0: saw wave
+/// - Output 1: sub-oscillator wave
+///
+/// ### Example: Bass With Square Sub
+/// ```
+/// use fundsp::hacker32::*;
+/// dc(55.0) >> sub_osc(1, SubWave::Square) >> (pass() + mul(0.5));
+/// ```
+pub fn sub_osc(octaves: usize, wave: SubWave) -> An<SubOsc<f32>> {
+    super::prelude::sub_osc(octaves, wave)
+}
+
 /// Play back a channel of a Wave64.
 /// Optional loop point is the index to jump to at the end of the wave.
 /// - Output 0: wave
@@ -2041,6 +3036,34 @@ pub fn wave32_at(
     ))
 }
 
+/// Play back a channel of a Wave32 from sample `start`, inclusive, to sample `end`, exclusive.
+/// If `looping` is set, then playback jumps back to `start` at the end.
+/// The wave is resampled with linear interpolation if its sample rate differs.
+/// - Output 0: wave
+///
+/// ### Example: Looping Sample
+/// ```no_run
+/// use fundsp::hacker32::*;
+/// use std::sync::Arc;
+/// let wave = Arc::new(Wave32::load_wav("loop.wav").unwrap());
+/// let player = waveplayer(&wave, 0, 0, wave.length(), true);
+/// ```
+pub fn waveplayer(
+    wave: &Arc<Wave32>,
+    channel: usize,
+    start: usize,
+    end: usize,
+    looping: bool,
+) -> An<Wave32Player<f32>> {
+    An(Wave32Player::new(
+        wave,
+        channel,
+        start,
+        end,
+        if looping { Some(start) } else { None },
+    ))
+}
+
 /// Mono chorus, 5 voices. For stereo, stack two of these using different seed values.
 /// `seed`: LFO seed.
 /// `separation`: base voice separation in seconds (for example, 0.015).
@@ -2103,6 +3126,22 @@ pub fn phaser<X: Fn(f32) -> f32 + Clone + Send + Sync>(
     super::prelude::phaser::<f32, _>(feedback_amount, phase_f)
 }
 
+/// Phaser with `stages` 1st order allpass sections (for example, 8) swept by a built-in sine LFO.
+/// `feedback` amount in -0.99...0.99 is taken from the last section to the first.
+/// `rate` is the LFO rate in Hz and `depth` in 0...1 sets the sweep range,
+/// which spans up to 6 octaves upward from 200 Hz.
+/// - Input 0: audio
+/// - Output 0: phased audio
+///
+/// ### Example: Phased Saw Wave
+/// ```
+/// use fundsp::hacker32::*;
+/// saw_hz(110.0) >> phaser_lfo(8, 0.5, 0.2, 0.8);
+/// ```
+pub fn phaser_lfo(stages: usize, feedback: f32, rate: f32, depth: f32) -> An<Phaser<f32>> {
+    An(Phaser::new(stages, feedback, rate as f64, depth as f64))
+}
+
 /// Shared float variable. Can be read from and written to from multiple threads.
 ///
 /// ### Example: Add Chorus With Wetness Control
@@ -2129,6 +3168,53 @@ pub fn var(shared: &Shared<f32>) -> An<Var<f32>> {
     An(Var::new(shared))
 }
 
+/// Outputs the value of the shared variable smoothed with a one-pole lowpass
+/// with time constant `time` seconds. The shared variable is read atomically
+/// on every sample. Changes to it are approached exponentially, avoiding zipper noise.
+/// Setting: time constant.
+///
+/// - Output 0: smoothed value
+///
+/// ### Example: Smoothed Volume Control
+/// ```
+/// use fundsp::hacker32::*;
+/// let volume = shared(0.5);
+/// saw_hz(110.0) * var_smooth(&volume, 0.02);
+/// ```
+pub fn var_smooth(shared: &Shared<f32>, time: f32) -> An<VarSmooth<f32>> {
+    An(VarSmooth::new(shared, time as f64))
+}
+
+/// Outputs the value of the shared variable on `N` identical channels.
+/// Where `var` has a single output, `dc_var` serves as a control bus.
+/// - Output(s): value
+///
+/// ### Example: Two Oscillators Following One Pitch
+/// ```
+/// use fundsp::hacker32::*;
+/// let pitch = shared(220.0);
+/// dc_var::<U2>(&pitch) >> (sine() | saw());
+/// ```
+pub fn dc_var<N: Size<f32>>(shared: &Shared<f32>) -> An<DcVar<N, f32>> {
+    An(DcVar::new(shared))
+}
+
+/// Pass through input and write the latest input sample to the shared variable.
+/// Lighter than `snoop` for displaying control signals in another thread.
+/// - Input 0: signal
+/// - Output 0: signal passed through
+///
+/// ### Example: Display An LFO
+/// ```
+/// use fundsp::hacker32::*;
+/// let level = shared(0.0);
+/// let mut node = sine_hz(0.5) >> var_tap(&level);
+/// node.get_mono();
+/// ```
+pub fn var_tap(shared: &Shared<f32>) -> An<VarTap<f32>> {
+    An(VarTap::new(shared))
+}
+
 /// Shared variable mapped through a function.
 /// Outputs the value of the function, which may be scalar or tuple.
 ///
@@ -2172,3 +3258,29 @@ pub fn snoop(capacity: usize) -> (Snoop<f32>, An<SnoopBackend<f32>>) {
     let (snoop, backend) = Snoop::new(capacity);
     (snoop, An(backend))
 }
+
+/// Spectral processing with a windowed overlap-add framework.
+/// Input is split into Hann windowed frames of length `window` samples spaced `hop` samples apart.
+/// The spectrum of each frame is processed in place by `f`, which receives all `window` bins.
+/// The hop length must divide the window length. Latency is `window` samples.
+/// - Input 0: audio
+/// - Output 0: processed audio
+///
+/// ### Example: Spectral Gate
+/// ```
+/// use fundsp::hacker32::*;
+/// let mut gate = stft(1024, 256, |spectrum| {
+///     for x in spectrum.iter_mut() {
+///         if x.norm() < 0.1 {
+///             *x *= 0.0;
+///         }
+///     }
+/// });
+/// assert_eq!(gate.latency(), Some(1024.0));
+/// ```
+pub fn stft<S>(window: usize, hop: usize, f: S) -> An<Stft<f32, S>>
+where
+    S: FnMut(&mut [num_complex::Complex64]) + Clone + Send + Sync,
+{
+    An(Stft::new(window, hop, f))
+}
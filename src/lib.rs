@@ -228,10 +228,21 @@ pub trait Real: Num + Float {
     fn tan(self) -> Self;
     fn tanh(self) -> Self;
     fn atan(self) -> Self;
+
+    /// Feedforward part of a biquad filter:
+    /// `y[i] = b0 * x[i + 2] + b1 * x[i + 1] + b2 * x[i]` for `i` in `0..y.len()`,
+    /// where `x` contains two samples of history followed by the block.
+    /// The implementations for `f32` and `f64` use SIMD where available.
+    #[inline]
+    fn biquad_feedforward(b0: Self, b1: Self, b2: Self, x: &[Self], y: &mut [Self]) {
+        for (i, y) in y.iter_mut().enumerate() {
+            *y = b0 * x[i + 2] + b1 * x[i + 1] + b2 * x[i];
+        }
+    }
 }
 
 macro_rules! impl_real {
-    ( $($t:ty),* ) => {
+    ( $($t:ty => $feedforward:path),* ) => {
     $( impl Real for $t {
         #[inline] fn sqrt(self) -> Self { self.sqrt() }
         #[inline] fn exp(self) -> Self { self.exp() }
@@ -244,20 +255,26 @@ macro_rules! impl_real {
         #[inline] fn tan(self) -> Self { <$t>::tan(self) }
         #[inline] fn tanh(self) -> Self { <$t>::tanh(self) }
         #[inline] fn atan(self) -> Self { <$t>::atan(self) }
+        #[inline] fn biquad_feedforward(b0: Self, b1: Self, b2: Self, x: &[Self], y: &mut [Self]) {
+            $feedforward(b0, b1, b2, x, y)
+        }
     }) *
     }
 }
-impl_real! { f32, f64 }
+impl_real! { f32 => simd::biquad_feedforward_f32, f64 => simd::biquad_feedforward_f64 }
 
 pub mod adsr;
+pub mod arp;
 pub mod audionode;
 pub mod audiounit;
+pub mod binaural;
 pub mod buffer;
 pub mod combinator;
 pub mod delay;
 pub mod dynamics;
 pub mod envelope;
 pub mod feedback;
+pub mod fft;
 pub mod filter;
 pub mod fir;
 pub mod follow;
@@ -266,6 +283,7 @@ pub mod granular;
 pub mod hacker;
 pub mod hacker32;
 pub mod math;
+pub mod mixer;
 pub mod moog;
 pub mod net;
 pub mod noise;
@@ -276,12 +294,14 @@ pub mod prelude;
 pub mod realnet;
 pub mod realseq;
 pub mod resample;
+pub mod reverb;
 pub mod rez;
 pub mod sequencer;
 pub mod setting;
 pub mod shape;
 pub mod shared;
 pub mod signal;
+pub mod simd;
 pub mod slot;
 pub mod snoop;
 pub mod sound;
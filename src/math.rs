@@ -562,6 +562,13 @@ pub fn midi_hz<T: Real>(x: T) -> T {
     T::new(440) * exp2((x - T::new(69)) / T::new(12))
 }
 
+/// Convert frequency in Hz to MIDI note number. Inverse of `midi_hz`.
+/// Returns 69.0 for 440 Hz. The result is fractional for frequencies between notes.
+#[inline]
+pub fn hz_midi<T: Real>(x: T) -> T {
+    T::new(69) + T::new(12) * log2(x / T::new(440))
+}
+
 /// Convert BPM (beats per minute) to Hz.
 #[inline]
 pub fn bpm_hz<T: Real>(bpm: T) -> T {
@@ -612,6 +619,14 @@ impl AttoHash {
     }
 }
 
+/// Deterministic per-voice detune derived from a node hash (as set via `ping`).
+/// Returns the detune of voice number `voice` in -`spread`...`spread`.
+/// The same hash, voice and spread always yield the same result.
+#[inline]
+pub fn voice_spread<T: Float>(hash: u64, voice: u64, spread: T) -> T {
+    spread * AttoHash::new(hash).hash(voice).hash11::<T>()
+}
+
 /// Trait for symmetric/asymmetric interpolation in `ease_noise`.
 pub trait SegmentInterpolator<T: Float>: Clone {
     /// Interpolate between `y1` and `y2` at relative position `t` in 0...1.
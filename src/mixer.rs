@@ -0,0 +1,178 @@
+//! Mixer with per-input gain, mute and solo controls.
+
+use super::audionode::*;
+use super::math::*;
+use super::shared::*;
+use super::signal::*;
+use super::*;
+use numeric_array::typenum::*;
+
+/// Mixes `N` inputs into one output. Each input has a gain, a mute switch
+/// and a solo switch. If any input is soloed, then inputs that are not soloed are muted.
+/// Controls are shared atomic variables, so they can be changed live from another thread.
+/// Clones of the mixer share its controls. Gain changes are smoothed to avoid clicks.
+/// - Input(s): `N` signals
+/// - Output 0: mix
+#[derive(Clone)]
+pub struct Mixer<N: Size<T>, T: Atomic> {
+    gain: Vec<Shared<T>>,
+    mute: Vec<Shared<T>>,
+    solo: Vec<Shared<T>>,
+    /// Current smoothed gains.
+    current: Frame<T, N>,
+    /// Smoothing time constant in seconds.
+    smoothing: f64,
+    /// Per-sample smoothing coefficient.
+    coeff: T,
+}
+
+impl<N: Size<T>, T: Atomic> Mixer<N, T> {
+    /// Create new mixer. All inputs start at unity gain.
+    /// Gain changes are smoothed with time constant `smoothing` seconds.
+    pub fn new(sample_rate: f64, smoothing: f64) -> Self {
+        let mut node = Self {
+            gain: (0..N::USIZE).map(|_| Shared::new(T::one())).collect(),
+            mute: (0..N::USIZE).map(|_| Shared::new(T::zero())).collect(),
+            solo: (0..N::USIZE).map(|_| Shared::new(T::zero())).collect(),
+            current: Frame::splat(T::one()),
+            smoothing,
+            coeff: T::zero(),
+        };
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Set gain (in amplitude) of input `channel`.
+    pub fn set_gain(&self, channel: usize, gain: T) {
+        self.gain[channel].set_value(gain);
+    }
+
+    /// Gain (in amplitude) of input `channel`.
+    pub fn gain(&self, channel: usize) -> T {
+        self.gain[channel].value()
+    }
+
+    /// Shared gain variable of input `channel`.
+    pub fn gain_shared(&self, channel: usize) -> &Shared<T> {
+        &self.gain[channel]
+    }
+
+    /// Mute or unmute input `channel`.
+    pub fn set_mute(&self, channel: usize, mute: bool) {
+        self.mute[channel].set_value(if mute { T::one() } else { T::zero() });
+    }
+
+    /// Whether input `channel` is muted.
+    pub fn is_muted(&self, channel: usize) -> bool {
+        self.mute[channel].value() > T::zero()
+    }
+
+    /// Solo or unsolo input `channel`.
+    pub fn set_solo(&self, channel: usize, solo: bool) {
+        self.solo[channel].set_value(if solo { T::one() } else { T::zero() });
+    }
+
+    /// Whether input `channel` is soloed.
+    pub fn is_soloed(&self, channel: usize) -> bool {
+        self.solo[channel].value() > T::zero()
+    }
+
+    /// Smoothing time constant in seconds.
+    pub fn smoothing(&self) -> f64 {
+        self.smoothing
+    }
+
+    /// Effective target gains after mute and solo have been applied.
+    fn targets(&self) -> Frame<T, N> {
+        let any_solo = (0..N::USIZE).any(|i| self.is_soloed(i));
+        Frame::generate(|i| {
+            if self.is_muted(i) || (any_solo && !self.is_soloed(i)) {
+                T::zero()
+            } else {
+                self.gain(i)
+            }
+        })
+    }
+}
+
+impl<N: Size<T>, T: Atomic> AudioNode for Mixer<N, T> {
+    const ID: u64 = 81;
+    type Sample = T;
+    type Inputs = N;
+    type Outputs = U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.current = self.targets();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.coeff = if self.smoothing > 0.0 {
+            T::from_f64(1.0 - exp(-1.0 / (self.smoothing * sample_rate)))
+        } else {
+            T::one()
+        };
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let targets = self.targets();
+        let mut output = T::zero();
+        for i in 0..N::USIZE {
+            let current = self.current[i];
+            let current = current + (targets[i] - current) * self.coeff;
+            self.current[i] = current;
+            output += input[i] * current;
+        }
+        [output].into()
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        // Controls are read once per block.
+        let targets = self.targets();
+        output[0][..size].fill(T::zero());
+        for channel in 0..N::USIZE {
+            let target = targets[channel];
+            let mut current = self.current[channel];
+            for (o, i) in output[0][..size]
+                .iter_mut()
+                .zip(input[channel][..size].iter())
+            {
+                current += (target - current) * self.coeff;
+                *o += *i * current;
+            }
+            self.current[channel] = current;
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        let targets = self.targets();
+        output[0] = input[0].scale(targets[0].to_f64());
+        for i in 1..N::USIZE {
+            output[0] = output[0].combine_linear(
+                input[i].scale(targets[i].to_f64()),
+                0.0,
+                |x, y| x + y,
+                |x, y| x + y,
+            );
+        }
+        output
+    }
+
+    fn heap_footprint(&self) -> usize {
+        let controls = self.gain.capacity() + self.mute.capacity() + self.solo.capacity();
+        // The controls are shared, but the mixer allocates them.
+        controls * std::mem::size_of::<Shared<T>>()
+            + (self.gain.len() + self.mute.len() + self.solo.len())
+                * std::mem::size_of::<T::Storage>()
+    }
+}
@@ -6,11 +6,15 @@ use super::buffer::*;
 use super::combinator::*;
 use super::math::*;
 use super::realnet::*;
+use super::sequencer::*;
 use super::signal::*;
+use super::wave::*;
 use super::*;
 use duplicate::duplicate_item;
+use num_complex::Complex64;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use thingbuf::mpsc::blocking::{channel, Receiver, Sender};
 
 pub type NodeIndex = usize;
@@ -33,6 +37,9 @@ impl NodeId {
 
 const ID: u64 = 63;
 
+/// Time constant in seconds for smoothing output gain changes.
+const OUTPUT_GAIN_SMOOTHING: f64 = 0.005;
+
 /// Input or output port.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum Port {
@@ -43,6 +50,8 @@ pub enum Port {
     /// Unconnected input. Unconnected output ports are not marked anywhere.
     #[default]
     Zero,
+    /// Node output delayed by one sample. This is the source of a feedback connection.
+    Feedback(NodeIndex, PortIndex),
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -56,10 +65,74 @@ pub fn edge(source: Port, target: Port) -> Edge {
     Edge { source, target }
 }
 
+/// Errors from editing a network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetError {
+    /// The connection would create a cycle without delay, which cannot be computed.
+    Cycle,
+}
+
+impl std::fmt::Display for NetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            NetError::Cycle => write!(f, "Connection would create a zero-delay cycle"),
+        }
+    }
+}
+
+impl std::error::Error for NetError {}
+
+/// Problems found by validating a network with `validate`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetValidationError {
+    /// A cycle without delay, which cannot be computed, passes through the listed nodes
+    /// in signal flow order.
+    Cycle(Vec<NodeId>),
+    /// Node input (node, port) is not connected.
+    Unconnected(NodeId, PortIndex),
+}
+
+impl std::fmt::Display for NetValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            NetValidationError::Cycle(nodes) => {
+                write!(f, "Zero-delay cycle through nodes {:?}", nodes)
+            }
+            NetValidationError::Unconnected(node, port) => {
+                write!(f, "Input {} of node {:?} is not connected", port, node)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NetValidationError {}
+
 #[duplicate_item(
-    f48       Vertex48       AudioUnit48;
-    [ f64 ]   [ Vertex64 ]   [ AudioUnit64 ];
-    [ f32 ]   [ Vertex32 ]   [ AudioUnit32 ];
+    f48       VertexFade48       AudioUnit48;
+    [ f64 ]   [ VertexFade64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ VertexFade32 ]   [ AudioUnit32 ];
+)]
+#[derive(Clone)]
+/// Unit that is being crossfaded out of a vertex.
+struct VertexFade48 {
+    /// The unit being faded out.
+    pub unit: Box<dyn AudioUnit48>,
+    /// Fade curve.
+    pub fade: Fade,
+    /// Fade length in samples.
+    pub length: usize,
+    /// Fade position in samples. The fade is complete when it reaches the length.
+    pub position: usize,
+    /// Output buffers of the unit being faded out.
+    pub output: Buffer<f48>,
+    /// Output of the unit being faded out for tick iteration.
+    pub tick_output: Vec<f48>,
+}
+
+#[duplicate_item(
+    f48       Vertex48       VertexFade48       AudioUnit48;
+    [ f64 ]   [ Vertex64 ]   [ VertexFade64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ Vertex32 ]   [ VertexFade32 ]   [ AudioUnit32 ];
 )]
 #[derive(Clone)]
 /// Individual AudioUnits are vertices in the graph.
@@ -76,6 +149,13 @@ struct Vertex48 {
     pub tick_input: Vec<f48>,
     /// Output for tick iteration. The length is equal to the number of outputs.
     pub tick_output: Vec<f48>,
+    /// Previous sample of inputs sourced from feedback connections.
+    /// The length is equal to the number of inputs.
+    pub feedback: Vec<f48>,
+    /// Latency compensation delay lines, one per input. Inputs with empty lines are not delayed.
+    pub compensation: Vec<Vec<f48>>,
+    /// Sample position in the latency compensation delay lines.
+    pub compensation_position: usize,
     /// Stable, globally unique ID for this vertex.
     pub id: NodeId,
     /// This is set if all vertex inputs are sourced from matching outputs of the indicated node.
@@ -83,12 +163,14 @@ struct Vertex48 {
     pub source_vertex: Option<NodeIndex>,
     /// Network revision in which this vertex was changed last.
     pub changed: u64,
+    /// Previous unit that is being crossfaded out, if any.
+    pub fade: Option<VertexFade48>,
 }
 
 #[duplicate_item(
-    f48       Vertex48       AudioUnit48;
-    [ f64 ]   [ Vertex64 ]   [ AudioUnit64 ];
-    [ f32 ]   [ Vertex32 ]   [ AudioUnit32 ];
+    f48       Vertex48       VertexFade48       AudioUnit48;
+    [ f64 ]   [ Vertex64 ]   [ VertexFade64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ Vertex32 ]   [ VertexFade32 ]   [ AudioUnit32 ];
 )]
 impl Vertex48 {
     pub fn new(id: NodeId, index: NodeIndex, unit: Box<dyn AudioUnit48>) -> Self {
@@ -101,9 +183,13 @@ impl Vertex48 {
             output: Buffer::with_channels(outputs),
             tick_input: vec![0.0; inputs],
             tick_output: vec![0.0; outputs],
+            feedback: vec![0.0; inputs],
+            compensation: vec![Vec::new(); inputs],
+            compensation_position: 0,
             id,
             source_vertex: None,
             changed: 0,
+            fade: None,
         };
         for i in 0..vertex.inputs() {
             vertex.source.push(edge(Port::Zero, Port::Local(index, i)));
@@ -123,12 +209,42 @@ impl Vertex48 {
         self.tick_output.len()
     }
 
+    /// Memory footprint of the vertex in bytes, including the contained unit and all buffers.
+    pub fn total_footprint(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.unit.total_footprint()
+            + self.source.capacity() * std::mem::size_of::<Edge>()
+            + self.input.footprint()
+            + self.output.footprint()
+            + (self.tick_input.capacity() + self.tick_output.capacity() + self.feedback.capacity())
+                * std::mem::size_of::<f48>()
+            + self
+                .compensation
+                .iter()
+                .map(|x| x.capacity())
+                .sum::<usize>()
+                * std::mem::size_of::<f48>()
+            + self
+                .fade
+                .as_ref()
+                .map(|fade| {
+                    fade.unit.total_footprint()
+                        + fade.output.footprint()
+                        + fade.tick_output.capacity() * std::mem::size_of::<f48>()
+                })
+                .unwrap_or(0)
+    }
+
     /// Update source vertex shortcut.
     pub fn update_source_vertex(&mut self) {
         self.source_vertex = None;
         if self.inputs() == 0 {
             return;
         }
+        if self.is_compensated() {
+            // Delayed inputs must be copied.
+            return;
+        }
         let mut source_node = 0;
         for i in 0..self.inputs() {
             match self.source[i].source {
@@ -150,9 +266,113 @@ impl Vertex48 {
         self.source_vertex = Some(source_node);
     }
 
+    /// Whether any input is delayed for latency compensation.
+    pub fn is_compensated(&self) -> bool {
+        self.compensation.iter().any(|x| !x.is_empty())
+    }
+
+    /// Set latency compensation `delay` in samples for each input.
+    /// Delay lines that change length are cleared.
+    pub fn set_compensation(&mut self, delay: &[usize]) {
+        for (line, &delay) in self.compensation.iter_mut().zip(delay.iter()) {
+            if line.len() != delay {
+                *line = vec![0.0; delay];
+            }
+        }
+    }
+
+    /// Delay tick inputs for latency compensation.
+    #[inline]
+    pub fn compensate_tick(&mut self) {
+        let position = self.compensation_position;
+        for (x, line) in self.tick_input.iter_mut().zip(self.compensation.iter_mut()) {
+            if !line.is_empty() {
+                let i = position % line.len();
+                *x = std::mem::replace(&mut line[i], *x);
+            }
+        }
+        self.compensation_position = position.wrapping_add(1);
+    }
+
+    /// Delay a block of `size` samples in the input buffers for latency compensation.
+    pub fn compensate_block(&mut self, size: usize) {
+        let position = self.compensation_position;
+        for (channel, line) in self.compensation.iter_mut().enumerate() {
+            if !line.is_empty() {
+                let len = line.len();
+                for (i, x) in self.input.mut_at(channel)[..size].iter_mut().enumerate() {
+                    std::mem::swap(x, &mut line[position.wrapping_add(i) % len]);
+                }
+            }
+        }
+        self.compensation_position = position.wrapping_add(size);
+    }
+
     /// Preallocate everything.
     pub fn allocate(&mut self) {
         self.unit.allocate();
+        if let Some(fade) = self.fade.as_mut() {
+            fade.unit.allocate();
+        }
+    }
+
+    /// Start crossfading from `unit` to the current unit over `length` samples.
+    pub fn start_fade(&mut self, unit: Box<dyn AudioUnit48>, fade: Fade, length: usize) {
+        let outputs = self.outputs();
+        self.fade = Some(VertexFade48 {
+            unit,
+            fade,
+            length,
+            position: 0,
+            output: Buffer::with_channels(outputs),
+            tick_output: vec![0.0; outputs],
+        });
+    }
+
+    /// Mix one sample of the unit being faded out, if any, into the tick output.
+    /// The unit is dropped once the fade is complete.
+    #[inline]
+    pub fn tick_fade(&mut self) {
+        if let Some(fade) = self.fade.as_mut() {
+            if fade.position < fade.length {
+                fade.unit.tick(&self.tick_input, &mut fade.tick_output);
+                let x = fade.position as f48 / fade.length as f48;
+                let (a, b) = (fade.fade.at(x), fade.fade.at(1.0 - x));
+                for (y, z) in self.tick_output.iter_mut().zip(fade.tick_output.iter()) {
+                    *y = *y * a + *z * b;
+                }
+                fade.position += 1;
+            }
+            if fade.position >= fade.length {
+                self.fade = None;
+            }
+        }
+    }
+
+    /// Mix a block of the unit being faded out, if any, into the output buffers.
+    /// The unit is processed from `input`, which is the same input given to the current unit.
+    /// The unit is dropped once the fade is complete.
+    pub fn process_fade(&mut self, size: usize, input: &[&[f48]]) {
+        if let Some(fade) = self.fade.as_mut() {
+            if fade.position < fade.length {
+                fade.unit.process(size, input, fade.output.self_mut());
+                let n = min(size, fade.length - fade.position);
+                for channel in 0..self.output.channels() {
+                    for (i, (y, z)) in self.output.mut_at(channel)[..n]
+                        .iter_mut()
+                        .zip(fade.output.at(channel)[..n].iter())
+                        .enumerate()
+                    {
+                        let x = (fade.position + i) as f48 / fade.length as f48;
+                        *y = *y * fade.fade.at(x) + *z * fade.fade.at(1.0 - x);
+                    }
+                }
+                fade.position += n;
+            }
+            if fade.position >= fade.length {
+                self.fade = None;
+            }
+        }
     }
 }
 
@@ -188,6 +408,20 @@ pub struct Net48 {
     /// Revision number. This is used by frontends and backends only.
     /// The revision is incremented after each commit.
     revision: u64,
+    /// Crossfade length in samples requested for this version when it reaches the backend.
+    /// Zero means the backend switches over immediately.
+    fade: usize,
+    /// Target gains of global outputs.
+    output_gain: Vec<f48>,
+    /// Smoothed gains of global outputs.
+    output_gain_current: Vec<f48>,
+    /// Per-sample output gain smoothing coefficient.
+    gain_coeff: f48,
+    /// Whether any node input is sourced from a feedback connection.
+    /// This is computed along with the order.
+    has_feedback: bool,
+    /// Whether node inputs are delayed to align latencies.
+    latency_compensation: bool,
 }
 
 #[duplicate_item(
@@ -210,14 +444,33 @@ impl Clone for Net48 {
             backend_inputs: self.backend_inputs,
             backend_outputs: self.backend_outputs,
             revision: self.revision,
+            // Crossfade requests apply to a single commit.
+            fade: 0,
+            output_gain: self.output_gain.clone(),
+            output_gain_current: self.output_gain_current.clone(),
+            gain_coeff: self.gain_coeff,
+            has_feedback: self.has_feedback,
+            latency_compensation: self.latency_compensation,
         }
     }
 }
 
 #[duplicate_item(
-    f48       Net48       NetBackend48       Vertex48       AudioUnit48;
-    [ f64 ]   [ Net64 ]   [ NetBackend64 ]   [ Vertex64 ]   [ AudioUnit64 ];
-    [ f32 ]   [ Net32 ]   [ NetBackend32 ]   [ Vertex32 ]   [ AudioUnit32 ];
+    Net48       NetSnapshot48;
+    [ Net64 ]   [ NetSnapshot64 ];
+    [ Net32 ]   [ NetSnapshot32 ];
+)]
+/// Snapshot of network structure and unit settings, taken with `snapshot`.
+/// It can be restored any number of times, which makes it suitable for undo and redo.
+#[derive(Clone)]
+pub struct NetSnapshot48 {
+    net: Net48,
+}
+
+#[duplicate_item(
+    f48       Net48       NetBackend48       NetSnapshot48       Vertex48       AudioUnit48;
+    [ f64 ]   [ Net64 ]   [ NetBackend64 ]   [ NetSnapshot64 ]   [ Vertex64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ Net32 ]   [ NetBackend32 ]   [ NetSnapshot32 ]   [ Vertex32 ]   [ AudioUnit32 ];
 )]
 impl Net48 {
     /// Create a new network with the given number of inputs and outputs.
@@ -244,6 +497,12 @@ impl Net48 {
             backend_inputs: inputs,
             backend_outputs: outputs,
             revision: 0,
+            fade: 0,
+            output_gain: vec![1.0; outputs],
+            output_gain_current: vec![1.0; outputs],
+            gain_coeff: Self::gain_coeff(DEFAULT_SR),
+            has_feedback: false,
+            latency_compensation: false,
         };
         for channel in 0..outputs {
             net.output_edge
@@ -339,7 +598,11 @@ impl Net48 {
             if let Port::Local(index, port) = self.output_edge[channel].source {
                 if index == node_index {
                     self.output_edge[channel].source = if link {
-                        self.vertex[node_index].source[port].source
+                        // Global outputs cannot be delayed, so they take a feedback source undelayed.
+                        match self.vertex[node_index].source[port].source {
+                            Port::Feedback(source, source_port) => Port::Local(source, source_port),
+                            source => source,
+                        }
                     } else {
                         Port::Zero
                     };
@@ -349,14 +612,28 @@ impl Net48 {
         // Replace all local ports that use an output of the node.
         for vertex in 0..self.size() {
             for channel in 0..self.vertex[vertex].inputs() {
-                if let Port::Local(index, port) = self.vertex[vertex].source[channel].source {
-                    if index == node_index {
+                match self.vertex[vertex].source[channel].source {
+                    Port::Local(index, port) if index == node_index => {
                         self.vertex[vertex].source[channel].source = if link {
                             self.vertex[node_index].source[port].source
                         } else {
                             Port::Zero
                         };
                     }
+                    Port::Feedback(index, port) if index == node_index => {
+                        // Feedback connections stay delayed.
+                        self.vertex[vertex].source[channel].source = if link {
+                            match self.vertex[node_index].source[port].source {
+                                Port::Local(source, source_port) => {
+                                    Port::Feedback(source, source_port)
+                                }
+                                source => source,
+                            }
+                        } else {
+                            Port::Zero
+                        };
+                    }
+                    _ => (),
                 }
             }
         }
@@ -376,11 +653,16 @@ impl Net48 {
             }
             for vertex in 0..self.size() - 1 {
                 for channel in 0..self.vertex[vertex].inputs() {
-                    if let Port::Local(index, port) = self.vertex[vertex].source[channel].source {
-                        if index == last_index {
+                    match self.vertex[vertex].source[channel].source {
+                        Port::Local(index, port) if index == last_index => {
                             self.vertex[vertex].source[channel].source =
                                 Port::Local(node_index, port);
                         }
+                        Port::Feedback(index, port) if index == last_index => {
+                            self.vertex[vertex].source[channel].source =
+                                Port::Feedback(node_index, port);
+                        }
+                        _ => (),
                     }
                 }
             }
@@ -421,6 +703,53 @@ impl Net48 {
         unit
     }
 
+    /// Replaces the given node in the network, crossfading from the current unit
+    /// to the new `unit` over `fade_time` seconds using the `fade` curve.
+    /// All connections are retained and other nodes are unaffected.
+    /// The replacement must have the same number of inputs and outputs
+    /// as the node it is replacing. If the network has a backend,
+    /// the crossfade starts from the running unit when the change is committed.
+    ///
+    /// ### Example (Crossfade From Saw Wave To Square Wave)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(0, 1);
+    /// let id = net.push(Box::new(saw_hz(220.0)));
+    /// net.pipe_output(id);
+    /// net.crossfade(id, Fade::Smooth, 0.1, Box::new(square_hz(220.0)));
+    /// net.check();
+    /// ```
+    #[allow(clippy::unnecessary_cast)]
+    pub fn crossfade(
+        &mut self,
+        node: NodeId,
+        fade: Fade,
+        fade_time: f48,
+        mut unit: Box<dyn AudioUnit48>,
+    ) {
+        assert!(fade_time >= 0.0);
+        unit.set_sample_rate(self.sample_rate);
+        let previous = self.replace(node, unit);
+        let length = (fade_time as f64 * self.sample_rate).round() as usize;
+        if length > 0 {
+            let node_index = self.node_index[&node];
+            self.vertex[node_index].start_fade(previous, fade, length);
+        }
+    }
+
+    /// Iterate over the IDs of all nodes in the network.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(0, 1);
+    /// let id = net.chain(Box::new(dc(1.0)));
+    /// assert!(net.node_ids().eq([id]));
+    /// ```
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.vertex.iter().map(|vertex| vertex.id)
+    }
+
     /// Connect the given unit output (`source`, `source_port`)
     /// to the given unit input (`target`, `target_port`).
     /// There is one connection for each unit input.
@@ -443,10 +772,78 @@ impl Net48 {
         target: NodeId,
         target_port: PortIndex,
     ) {
-        assert!(source != target);
+        if let Err(error) = self.try_connect(source, source_port, target, target_port) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Connect the given unit output (`source`, `source_port`)
+    /// to the given unit input (`target`, `target_port`).
+    /// Returns an error, leaving the network unchanged, if the connection
+    /// would create a cycle. Cycles are computable only if they pass through a delay,
+    /// so feedback loops must be contained in a feedback unit such as `Feedback64`.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(1, 1);
+    /// let id1 = net.push(Box::new(pass()));
+    /// let id2 = net.push(Box::new(pass()));
+    /// assert_eq!(net.try_connect(id1, 0, id2, 0), Ok(()));
+    /// assert_eq!(net.try_connect(id2, 0, id1, 0), Err(NetError::Cycle));
+    /// ```
+    pub fn try_connect(
+        &mut self,
+        source: NodeId,
+        source_port: PortIndex,
+        target: NodeId,
+        target_port: PortIndex,
+    ) -> Result<(), NetError> {
         let source_index = self.node_index[&source];
         let target_index = self.node_index[&target];
+        if self.reaches(target_index, source_index) {
+            return Err(NetError::Cycle);
+        }
         self.connect_index(source_index, source_port, target_index, target_port);
+        Ok(())
+    }
+
+    /// Connect the given unit output (`source`, `source_port`)
+    /// to the given unit input (`target`, `target_port`) through a one sample delay.
+    /// The delay makes the connection computable even if it closes a loop,
+    /// as in comb filters and feedback delay networks. Each feedback connection
+    /// adds one sample of latency to the loop. A network with feedback connections
+    /// processes blocks one sample at a time.
+    ///
+    /// ### Example (Feedback Comb Filter)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(1, 1);
+    /// let id1 = net.push(Box::new(pass() + pass()));
+    /// let id2 = net.push(Box::new(delay(0.01) * 0.5));
+    /// net.connect_input(0, id1, 0);
+    /// net.connect(id1, 0, id2, 0);
+    /// net.connect_feedback(id2, 0, id1, 1);
+    /// net.pipe_output(id1);
+    /// net.check();
+    /// ```
+    pub fn connect_feedback(
+        &mut self,
+        source: NodeId,
+        source_port: PortIndex,
+        target: NodeId,
+        target_port: PortIndex,
+    ) {
+        let source_index = self.node_index[&source];
+        let target_index = self.node_index[&target];
+        assert!(source_port < self.vertex[source_index].outputs());
+        assert!(target_port < self.vertex[target_index].inputs());
+        self.vertex[target_index].source[target_port] = edge(
+            Port::Feedback(source_index, source_port),
+            Port::Local(target_index, target_port),
+        );
+        self.vertex[target_index].feedback[target_port] = 0.0;
+        self.invalidate_order();
     }
 
     /// Disconnect `node` input `port`, replacing it with zero input.
@@ -476,6 +873,8 @@ impl Net48 {
         target: NodeIndex,
         target_port: PortIndex,
     ) {
+        assert!(source_port < self.vertex[source].outputs());
+        assert!(target_port < self.vertex[target].inputs());
         self.vertex[target].source[target_port] = edge(
             Port::Local(source, source_port),
             Port::Local(target, target_port),
@@ -609,6 +1008,133 @@ impl Net48 {
         self.invalidate_order();
     }
 
+    /// Set gain (in amplitude) of global `output`. The default gain is 1.
+    /// Gain changes are smoothed over a few milliseconds to avoid clicks.
+    /// When networks are combined with `^` or `|`, each side keeps the gains of its outputs,
+    /// and `!` keeps the gains of existing outputs. `>>` keeps the gains of the second network.
+    /// Other operators sum outputs and reset gains to 1.
+    ///
+    /// ### Example (Trim Master Level)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(0, 2);
+    /// net.chain(Box::new(noise() | noise()));
+    /// net.set_output_gain(0, db_amp(-6.0));
+    /// net.set_output_gain(1, db_amp(-6.0));
+    /// ```
+    pub fn set_output_gain(&mut self, output: PortIndex, gain: f48) {
+        self.output_gain[output] = gain;
+    }
+
+    /// Gain (in amplitude) of global `output`.
+    pub fn output_gain(&self, output: PortIndex) -> f48 {
+        self.output_gain[output]
+    }
+
+    /// Enable or disable latency compensation. The default is disabled.
+    /// With compensation, node inputs that arrive earlier than other inputs of the same node
+    /// are delayed to match the latest one, so parallel paths that are summed or otherwise
+    /// combined stay aligned, for example, when only one of them is oversampled.
+    /// Latencies are obtained from `latency` of each node and propagated through the network.
+    /// Fractional latency differences are rounded to the nearest sample without interpolation.
+    /// Global outputs are not aligned with each other, and inputs with unknown latencies,
+    /// such as feedback connections, are not delayed.
+    /// The compensation delays are included in the latency reported by the network.
+    ///
+    /// ### Example (Aligned Oversampled Path)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::wrap(Box::new(pass())) + Net64::wrap(Box::new(oversample_unit(2, Box::new(pass()))));
+    /// net.set_latency_compensation(true);
+    /// assert_eq!(net.latency(), Some(32.0));
+    /// ```
+    pub fn set_latency_compensation(&mut self, enabled: bool) {
+        self.latency_compensation = enabled;
+        self.invalidate_order();
+    }
+
+    /// Whether latency compensation is enabled.
+    pub fn latency_compensation(&self) -> bool {
+        self.latency_compensation
+    }
+
+    /// Output gain smoothing coefficient at `sample_rate` Hz.
+    fn gain_coeff(sample_rate: f64) -> f48 {
+        f48::from_f64(1.0 - exp(-1.0 / (OUTPUT_GAIN_SMOOTHING * sample_rate)))
+    }
+
+    /// Advance gain smoothing of global `output` by one sample. Return the new gain.
+    #[inline]
+    fn next_output_gain(&mut self, output: PortIndex) -> f48 {
+        let target = self.output_gain[output];
+        let current = &mut self.output_gain_current[output];
+        *current += (target - *current) * self.gain_coeff;
+        // Snap to the target when close enough so the fast path resumes.
+        if abs(target - *current) < 1.0e-6 {
+            *current = target;
+        }
+        *current
+    }
+
+    /// Whether global `output` has unity gain that is not changing.
+    #[inline]
+    fn is_unity_gain(&self, output: PortIndex) -> bool {
+        self.output_gain[output] == 1.0 && self.output_gain_current[output] == 1.0
+    }
+
+    /// Tick all units in network order. Global inputs are read from `input`.
+    /// The network must be ordered.
+    fn tick_vertices(&mut self, input: impl Fn(PortIndex) -> f48) {
+        for &node_index in self.order.get_or_insert(Vec::new()).iter() {
+            for channel in 0..self.vertex[node_index].inputs() {
+                self.vertex[node_index].tick_input[channel] =
+                    match self.vertex[node_index].source[channel].source {
+                        Port::Zero => 0.0,
+                        Port::Global(port) => input(port),
+                        Port::Local(source, port) => self.vertex[source].tick_output[port],
+                        Port::Feedback(_, _) => self.vertex[node_index].feedback[channel],
+                    };
+            }
+            let vertex = &mut self.vertex[node_index];
+            if self.latency_compensation {
+                vertex.compensate_tick();
+            }
+            vertex
+                .unit
+                .tick(&vertex.tick_input, &mut vertex.tick_output);
+            vertex.tick_fade();
+        }
+        if self.has_feedback {
+            // Latch feedback values for the next sample.
+            for node_index in 0..self.size() {
+                for channel in 0..self.vertex[node_index].inputs() {
+                    if let Port::Feedback(source, port) =
+                        self.vertex[node_index].source[channel].source
+                    {
+                        self.vertex[node_index].feedback[channel] =
+                            self.vertex[source].tick_output[port];
+                    }
+                }
+            }
+        }
+    }
+
+    /// Value of global output `channel` after a tick, with output gain applied.
+    /// Global inputs are read from `input`.
+    #[inline]
+    fn tick_output_at(&mut self, channel: PortIndex, input: impl Fn(PortIndex) -> f48) -> f48 {
+        let value = match self.output_edge[channel].source {
+            Port::Global(port) => input(port),
+            Port::Local(node, port) => self.vertex[node].tick_output[port],
+            _ => 0.0,
+        };
+        if self.is_unity_gain(channel) {
+            value
+        } else {
+            value * self.next_output_gain(channel)
+        }
+    }
+
     /// Connect `source` node outputs to `target` node inputs.
     /// The number of outputs in `source` and number of inputs in `target` must match.
     ///
@@ -687,16 +1213,159 @@ impl Net48 {
         &mut *self.vertex[self.node_index[&node]].unit
     }
 
+    /// Whether the network contains a cycle, which makes it uncomputable.
+    /// Connections made with `connect` never form cycles.
+    pub fn has_cycle(&self) -> bool {
+        let mut order = Vec::new();
+        !self.determine_order_in(&mut order)
+    }
+
+    /// Whether node `to` can be reached from node `from` by following connections.
+    /// A node always reaches itself.
+    fn reaches(&self, from: NodeIndex, to: NodeIndex) -> bool {
+        let mut visited = vec![false; self.vertex.len()];
+        let mut stack = vec![from];
+        visited[from] = true;
+        while let Some(node) = stack.pop() {
+            if node == to {
+                return true;
+            }
+            for (index, vertex) in self.vertex.iter().enumerate() {
+                let fed = vertex.source.iter().any(|edge| match edge.source {
+                    Port::Local(source, _) => source == node,
+                    _ => false,
+                });
+                if fed && !visited[index] {
+                    visited[index] = true;
+                    stack.push(index);
+                }
+            }
+        }
+        false
+    }
+
+    /// Find a cycle of connections, if any. Returns the nodes of the cycle in signal flow order.
+    fn find_cycle(&self) -> Option<Vec<NodeIndex>> {
+        // Nodes fed by each node.
+        let mut targets = vec![Vec::new(); self.vertex.len()];
+        for (index, vertex) in self.vertex.iter().enumerate() {
+            for edge in vertex.source.iter() {
+                if let Port::Local(source, _) = edge.source {
+                    targets[source].push(index);
+                }
+            }
+        }
+        // Depth-first search state: 0 = unvisited, 1 = on the stack, 2 = finished.
+        let mut state = vec![0u8; self.vertex.len()];
+        for root in 0..self.vertex.len() {
+            if state[root] != 0 {
+                continue;
+            }
+            state[root] = 1;
+            // Stack of (node, index of next target to visit).
+            let mut stack = vec![(root, 0)];
+            while let Some((node, next)) = stack.last_mut() {
+                let node = *node;
+                if *next < targets[node].len() {
+                    let target = targets[node][*next];
+                    *next += 1;
+                    match state[target] {
+                        0 => {
+                            state[target] = 1;
+                            stack.push((target, 0));
+                        }
+                        1 => {
+                            let start = stack.iter().position(|(x, _)| *x == target).unwrap();
+                            return Some(stack[start..].iter().map(|(x, _)| *x).collect());
+                        }
+                        _ => (),
+                    }
+                } else {
+                    state[node] = 2;
+                    stack.pop();
+                }
+            }
+        }
+        None
+    }
+
+    /// Translate vertex indices to node IDs.
+    fn node_ids_of(&self, nodes: &[NodeIndex]) -> Vec<NodeId> {
+        nodes.iter().map(|&index| self.vertex[index].id).collect()
+    }
+
     /// Compute and store node order for this network.
     fn determine_order(&mut self) {
-        for vertex in self.vertex.iter_mut() {
-            vertex.update_source_vertex();
-        }
         let mut order = Vec::new();
         if !self.determine_order_in(&mut order) {
             panic!("Cycle detected");
         }
+        self.determine_compensation(&order);
+        for vertex in self.vertex.iter_mut() {
+            vertex.update_source_vertex();
+        }
         self.order = Some(order);
+        self.has_feedback = self.vertex.iter().any(|vertex| {
+            vertex
+                .source
+                .iter()
+                .any(|edge| matches!(edge.source, Port::Feedback(..)))
+        });
+    }
+
+    /// Determine latency compensation delays of node inputs, visiting nodes in `order`.
+    /// Without latency compensation, all delays are removed.
+    fn determine_compensation(&mut self, order: &[NodeIndex]) {
+        if !self.latency_compensation {
+            for vertex in self.vertex.iter_mut() {
+                if vertex.is_compensated() {
+                    let inputs = vertex.inputs();
+                    vertex.set_compensation(&vec![0; inputs]);
+                }
+            }
+            return;
+        }
+        // Latency of each node output in samples, if known.
+        let mut latency: Vec<Vec<Option<f64>>> = self
+            .vertex
+            .iter()
+            .map(|vertex| vec![None; vertex.outputs()])
+            .collect();
+        for &index in order.iter() {
+            let input_latency: Vec<Option<f64>> = self.vertex[index]
+                .source
+                .iter()
+                .map(|edge| match edge.source {
+                    Port::Global(_) => Some(0.0),
+                    Port::Local(node, port) => latency[node][port],
+                    _ => None,
+                })
+                .collect();
+            // Inputs are aligned with the latest input.
+            let target = input_latency
+                .iter()
+                .flatten()
+                .fold(0.0, |x: f64, y| x.max(*y));
+            let delay: Vec<usize> = input_latency
+                .iter()
+                .map(|x| x.map(|x| round(target - x) as usize).unwrap_or(0))
+                .collect();
+            self.vertex[index].set_compensation(&delay);
+            let mut input = new_signal_frame(delay.len());
+            for channel in 0..delay.len() {
+                if let Some(x) = input_latency[channel] {
+                    input[channel] = Signal::Latency(x + delay[channel] as f64);
+                }
+            }
+            let output = self.vertex[index].unit.route(&input, 1.0);
+            // Some units return extra channels from routing; only the outputs are used.
+            for (port, signal) in output.iter().take(latency[index].len()).enumerate() {
+                latency[index][port] = match signal {
+                    Signal::Latency(x) => Some(*x),
+                    _ => None,
+                };
+            }
+        }
     }
 
     /// Determine node order in the supplied vector. Returns true if successful, false
@@ -726,7 +1395,8 @@ impl Net48 {
 
         // Start from network inputs.
         for (_, edge) in all_edges.iter().enumerate() {
-            if let (Port::Global(_) | Port::Zero, Port::Local(vertex, _)) =
+            // Feedback connections read the previous sample, so they do not constrain the order.
+            if let (Port::Global(_) | Port::Zero | Port::Feedback(..), Port::Local(vertex, _)) =
                 (edge.source, edge.target)
             {
                 if vertex_left[vertex] {
@@ -802,11 +1472,43 @@ impl Net48 {
         net
     }
 
+    /// Overall output latency of the network in samples, for reporting
+    /// plugin delay compensation to a host. Latencies are traced from network inputs
+    /// and generators through the graph using signal routing (see `route`).
+    /// Where paths merge, the shortest latency is retained, as it is the one
+    /// that is heard first; the result is the maximum over network outputs.
+    /// Feedback units contribute the latency of their direct path.
+    /// Outputs that carry no latency information count as zero.
+    ///
+    /// ### Example (Delay Line)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(1, 1);
+    /// net.chain(Box::new(delay(0.01)));
+    /// assert_eq!(net.report_latency(), 441.0);
+    /// ```
+    pub fn report_latency(&mut self) -> f64 {
+        let mut input = new_signal_frame(self.inputs());
+        for i in 0..self.inputs() {
+            input[i] = Signal::Latency(0.0);
+        }
+        let output = self.route(&input, 1.0);
+        let mut latency = 0.0;
+        for channel in 0..self.outputs() {
+            if let Signal::Latency(x) = output[channel] {
+                latency = max(latency, x);
+            }
+        }
+        latency
+    }
+
     /// Check internal consistency of the network. Panic if something is wrong.
     pub fn check(&self) {
         assert_eq!(self.input.channels(), self.inputs());
         assert_eq!(self.output.channels(), self.outputs());
         assert_eq!(self.output_edge.len(), self.outputs());
+        assert_eq!(self.output_gain.len(), self.outputs());
+        assert_eq!(self.output_gain_current.len(), self.outputs());
         assert_eq!(self.node_index.len(), self.size());
         for channel in 0..self.outputs() {
             assert_eq!(self.output_edge[channel].target, Port::Global(channel));
@@ -851,13 +1553,142 @@ impl Net48 {
                         assert!(node != index);
                         assert!(port < self.vertex[node].outputs());
                     }
+                    Port::Feedback(node, port) => {
+                        assert!(node < self.size());
+                        assert!(port < self.vertex[node].outputs());
+                    }
                     Port::Global(port) => {
                         assert!(port < self.inputs());
                     }
                     _ => (),
                 }
             }
+            assert_eq!(
+                self.vertex[index].feedback.len(),
+                self.vertex[index].inputs()
+            );
+        }
+    }
+
+    /// Validate the network before processing. Returns an error identifying the nodes
+    /// of a cycle without delay, which cannot be computed. If `unconnected` is true,
+    /// node inputs that are not connected are reported as well.
+    /// Unconnected inputs are valid in general: they receive zero,
+    /// and operations such as `disconnect` and `share_tail` may leave them.
+    /// Cycles are reported before unconnected inputs.
+    ///
+    /// ### Example
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(0, 1);
+    /// let id = net.push(Box::new(lowpass_hz(1000.0, 1.0)));
+    /// net.pipe_output(id);
+    /// assert_eq!(net.validate(false), Ok(()));
+    /// assert_eq!(net.validate(true), Err(NetValidationError::Unconnected(id, 0)));
+    /// ```
+    pub fn validate(&self, unconnected: bool) -> Result<(), NetValidationError> {
+        if let Some(cycle) = self.find_cycle() {
+            return Err(NetValidationError::Cycle(self.node_ids_of(&cycle)));
+        }
+        if unconnected {
+            for vertex in self.vertex.iter() {
+                for channel in 0..vertex.inputs() {
+                    if vertex.source[channel].source == Port::Zero {
+                        return Err(NetValidationError::Unconnected(vertex.id, channel));
+                    }
+                }
+            }
         }
+        Ok(())
+    }
+
+    /// Process a block of `size` samples through the vertex at `node_index`.
+    /// Its sources must have been processed already.
+    #[inline]
+    fn process_vertex(&mut self, node_index: NodeIndex, size: usize, input: &[&[f48]]) {
+        if let Some(source_node) = self.vertex[node_index].source_vertex {
+            // We can source inputs directly from a source vertex.
+            let ptr = &mut self.vertex[source_node].output as *mut Buffer<f48>;
+            let vertex = &mut self.vertex[node_index];
+            // Safety: we know there is no aliasing, as self connections are prohibited.
+            unsafe {
+                vertex
+                    .unit
+                    .process(size, (*ptr).self_ref(), vertex.output.self_mut());
+                vertex.process_fade(size, (*ptr).self_ref());
+            }
+        } else {
+            let ptr = &mut self.vertex[node_index].input as *mut Buffer<f48>;
+            // Gather inputs for this vertex.
+            for channel in 0..self.vertex[node_index].inputs() {
+                // Safety: we know there is no aliasing, as self connections are prohibited.
+                unsafe {
+                    match self.vertex[node_index].source[channel].source {
+                        Port::Zero => (*ptr).mut_at(channel)[..size].fill(0.0),
+                        Port::Global(port) => {
+                            (*ptr).mut_at(channel)[..size].copy_from_slice(&input[port][..size])
+                        }
+                        Port::Local(source, port) => {
+                            (*ptr).mut_at(channel)[..size]
+                                .copy_from_slice(&self.vertex[source].output.at(port)[..size]);
+                        }
+                        Port::Feedback(_, _) => (*ptr).mut_at(channel)[..size]
+                            .fill(self.vertex[node_index].feedback[channel]),
+                    }
+                }
+            }
+            let vertex = &mut self.vertex[node_index];
+            if self.latency_compensation {
+                vertex.compensate_block(size);
+            }
+            // Safety: we know there is no aliasing, as self connections are prohibited.
+            unsafe {
+                vertex
+                    .unit
+                    .process(size, (*ptr).self_ref(), vertex.output.self_mut());
+                vertex.process_fade(size, (*ptr).self_ref());
+            }
+        }
+    }
+
+    /// Measure the cost of each unit by processing `duration` seconds of silent input
+    /// in blocks of `MAX_BUFFER_SIZE` samples, timing every `process` call.
+    /// Returns the average time in microseconds each node spends per block.
+    /// Units advance their state as they would during normal processing.
+    ///
+    /// ### Example (Finding The Expensive Node)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(0, 2);
+    /// let source = net.chain(Box::new(noise() >> split::<U2>()));
+    /// let reverb = net.chain(Box::new(reverb_stereo(10.0, 2.0)));
+    /// let cost = net.profile(0.1);
+    /// println!("noise: {} us, reverb: {} us", cost[&source], cost[&reverb]);
+    /// ```
+    #[cfg(feature = "profiling")]
+    pub fn profile(&mut self, duration: f64) -> HashMap<NodeId, f64> {
+        if !self.is_ordered() {
+            self.determine_order();
+        }
+        let blocks =
+            ((duration * self.sample_rate / MAX_BUFFER_SIZE as f64).ceil() as usize).max(1);
+        let zeros = [0.0; MAX_BUFFER_SIZE];
+        let input: Vec<&[f48]> = vec![&zeros[..]; self.inputs()];
+        let mut time = vec![0.0; self.size()];
+        let order = self.order.take().unwrap_or_default();
+        for _ in 0..blocks {
+            for &node_index in order.iter() {
+                let start = std::time::Instant::now();
+                self.process_vertex(node_index, MAX_BUFFER_SIZE, &input);
+                time[node_index] += start.elapsed().as_secs_f64();
+            }
+        }
+        self.order = Some(order);
+        self.vertex
+            .iter()
+            .zip(time)
+            .map(|(vertex, time)| (vertex.id, time * 1.0e6 / blocks as f64))
+            .collect()
     }
 
     /// Disambiguate IDs in this network so they don't conflict with those in `other` network.
@@ -884,9 +1715,42 @@ impl Net48 {
                         &mut self.vertex[index].unit,
                         &mut new.vertex[new_index].unit,
                     );
+                    // Continue any crossfade in progress.
+                    std::mem::swap(
+                        &mut self.vertex[index].fade,
+                        &mut new.vertex[new_index].fade,
+                    );
+                    if self.vertex[index].feedback.len() == new.vertex[new_index].feedback.len() {
+                        std::mem::swap(
+                            &mut self.vertex[index].feedback,
+                            &mut new.vertex[new_index].feedback,
+                        );
+                    }
+                    // Keep delayed samples if latency compensation is unchanged.
+                    let same_delays = self.vertex[index]
+                        .compensation
+                        .iter()
+                        .map(|x| x.len())
+                        .eq(new.vertex[new_index].compensation.iter().map(|x| x.len()));
+                    if same_delays {
+                        std::mem::swap(
+                            &mut self.vertex[index].compensation,
+                            &mut new.vertex[new_index].compensation,
+                        );
+                        new.vertex[new_index].compensation_position =
+                            self.vertex[index].compensation_position;
+                    }
+                } else if let Some(fade) = new.vertex[new_index].fade.as_mut() {
+                    // Crossfade from the running unit instead of the copy made by the frontend.
+                    if fade.position == 0 {
+                        std::mem::swap(&mut self.vertex[index].unit, &mut fade.unit);
+                    }
                 }
             }
         }
+        // Continue output gain smoothing from where we are.
+        new.output_gain_current
+            .copy_from_slice(&self.output_gain_current);
     }
 
     /// Create a real-time friendly backend for this network.
@@ -920,6 +1784,7 @@ impl Net48 {
         // Send over the original nodes to the backend.
         // This is necessary if the nodes contain any backends, which cannot be cloned effectively.
         std::mem::swap(&mut net.vertex, &mut self.vertex);
+        self.clear_fades();
         net.allocate();
         self.revision += 1;
         NetBackend48::new(sender_b, receiver_a, net)
@@ -933,6 +1798,12 @@ impl Net48 {
     /// Commit changes made to this frontend to the backend.
     /// This may be called only if the network has a backend.
     pub fn commit(&mut self) {
+        self.commit_fade(0);
+    }
+
+    /// Commit changes to the backend, crossfading over `fade` samples
+    /// from the running version if `fade` is nonzero.
+    fn commit_fade(&mut self, fade: usize) {
         assert!(self.has_backend());
         if self.inputs() != self.backend_inputs {
             panic!("The number of inputs has changed since last commit. The number of inputs must stay the same.");
@@ -944,9 +1815,12 @@ impl Net48 {
             self.determine_order();
         }
         let mut net = self.clone();
+        net.fade = fade;
         // Send over the original nodes to the backend.
         // This is necessary if the nodes contain any backends, which cannot be cloned effectively.
         std::mem::swap(&mut net.vertex, &mut self.vertex);
+        // Crossfades are carried out by the backend.
+        self.clear_fades();
         // Preallocate all necessary memory.
         net.allocate();
         if let Some((sender, receiver)) = &mut self.front {
@@ -958,6 +1832,76 @@ impl Net48 {
         self.revision += 1;
     }
 
+    /// Release units that are being crossfaded out.
+    fn clear_fades(&mut self) {
+        for vertex in self.vertex.iter_mut() {
+            vertex.fade = None;
+        }
+    }
+
+    /// Crossfade length in samples requested for this version. This is an internal function.
+    pub(crate) fn fade(&self) -> usize {
+        self.fade
+    }
+
+    /// Capture the structure of this network and the settings of its units
+    /// for undo and redo. Per-sample state is not captured: units in the snapshot are reset.
+    ///
+    /// ### Example (Undo An Edit)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(0, 1);
+    /// net.chain(Box::new(saw_hz(110.0)));
+    /// let undo = net.snapshot();
+    /// net.chain(Box::new(lowpass_hz(500.0, 1.0)));
+    /// net.restore(&undo, 0.0);
+    /// assert_eq!(net.size(), 1);
+    /// ```
+    pub fn snapshot(&self) -> NetSnapshot48 {
+        let mut net = self.clone();
+        net.reset();
+        NetSnapshot48 { net }
+    }
+
+    /// Return the network to the structure and settings captured in `snapshot`.
+    /// Node IDs are restored as well. If the network has a backend, the restored version
+    /// is committed. Then, if `fade_time` is positive, the backend crossfades
+    /// from the running version over `fade_time` seconds to avoid clicks;
+    /// otherwise, units that have not been changed since the snapshot keep running
+    /// in the backend without interruption.
+    /// The number of inputs and outputs must match.
+    pub fn restore(&mut self, snapshot: &NetSnapshot48, fade_time: f64) {
+        assert_eq!(self.inputs(), snapshot.net.inputs());
+        assert_eq!(self.outputs(), snapshot.net.outputs());
+        let mut net = snapshot.net.clone();
+        for vertex in net.vertex.iter_mut() {
+            // A unit is unchanged if the current version of the node has the same revision.
+            let unchanged = self
+                .node_index
+                .get(&vertex.id)
+                .map(|&index| self.vertex[index].changed == vertex.changed)
+                .unwrap_or(false);
+            if !unchanged {
+                vertex.changed = self.revision;
+            }
+        }
+        if net.sample_rate != self.sample_rate {
+            net.set_sample_rate(self.sample_rate);
+        }
+        self.input = net.input;
+        self.output = net.output;
+        self.output_edge = net.output_edge;
+        self.output_gain = net.output_gain;
+        self.vertex = net.vertex;
+        self.order = net.order;
+        self.has_feedback = net.has_feedback;
+        self.latency_compensation = net.latency_compensation;
+        self.node_index = net.node_index;
+        if self.has_backend() {
+            self.commit_fade((fade_time * self.sample_rate).round() as usize);
+        }
+    }
+
     /// Resolve new frontend for a binary combination.
     fn resolve_frontend(&mut self, other: &mut Net48) {
         if self.has_backend() && other.has_backend() {
@@ -988,8 +1932,12 @@ impl AudioUnit48 for Net48 {
 
     fn set_sample_rate(&mut self, sample_rate: f64) {
         self.sample_rate = sample_rate;
+        self.gain_coeff = Self::gain_coeff(sample_rate);
         for vertex in &mut self.vertex {
             vertex.unit.set_sample_rate(sample_rate);
+            if let Some(fade) = vertex.fade.as_mut() {
+                fade.unit.set_sample_rate(sample_rate);
+            }
         }
         // Take the opportunity to unload some calculations.
         if !self.is_ordered() {
@@ -1000,7 +1948,17 @@ impl AudioUnit48 for Net48 {
     fn reset(&mut self) {
         for vertex in &mut self.vertex {
             vertex.unit.reset();
+            vertex.feedback.fill(0.0);
+            for line in vertex.compensation.iter_mut() {
+                line.fill(0.0);
+            }
+            vertex.compensation_position = 0;
+            // Crossfades in progress are completed.
+            if let Some(fade) = vertex.fade.as_mut() {
+                fade.position = fade.length;
+            }
         }
+        self.output_gain_current.copy_from_slice(&self.output_gain);
         // Take the opportunity to unload some calculations.
         if !self.is_ordered() {
             self.determine_order();
@@ -1011,31 +1969,11 @@ impl AudioUnit48 for Net48 {
         if !self.is_ordered() {
             self.determine_order();
         }
-        // Iterate units in network order.
-        for &node_index in self.order.get_or_insert(Vec::new()).iter() {
-            for channel in 0..self.vertex[node_index].inputs() {
-                match self.vertex[node_index].source[channel].source {
-                    Port::Zero => self.vertex[node_index].tick_input[channel] = 0.0,
-                    Port::Global(port) => self.vertex[node_index].tick_input[channel] = input[port],
-                    Port::Local(source, port) => {
-                        self.vertex[node_index].tick_input[channel] =
-                            self.vertex[source].tick_output[port]
-                    }
-                }
-            }
-            let vertex = &mut self.vertex[node_index];
-            vertex
-                .unit
-                .tick(&vertex.tick_input, &mut vertex.tick_output);
-        }
+        self.tick_vertices(|port| input[port]);
 
         // Then we set the global outputs.
         for channel in 0..output.len() {
-            match self.output_edge[channel].source {
-                Port::Global(port) => output[channel] = input[port],
-                Port::Local(node, port) => output[channel] = self.vertex[node].tick_output[port],
-                Port::Zero => output[channel] = 0.0,
-            }
+            output[channel] = self.tick_output_at(channel, |port| input[port]);
         }
     }
 
@@ -1043,45 +1981,22 @@ impl AudioUnit48 for Net48 {
         if !self.is_ordered() {
             self.determine_order();
         }
-        // Iterate units in network order.
-        for &node_index in self.order.get_or_insert(Vec::new()).iter() {
-            if let Some(source_node) = self.vertex[node_index].source_vertex {
-                // We can source inputs directly from a source vertex.
-                let ptr = &mut self.vertex[source_node].output as *mut Buffer<f48>;
-                let vertex = &mut self.vertex[node_index];
-                // Safety: we know there is no aliasing, as self connections are prohibited.
-                unsafe {
-                    vertex
-                        .unit
-                        .process(size, (*ptr).self_ref(), vertex.output.self_mut());
-                }
-            } else {
-                let ptr = &mut self.vertex[node_index].input as *mut Buffer<f48>;
-                // Gather inputs for this vertex.
-                for channel in 0..self.vertex[node_index].inputs() {
-                    // Safety: we know there is no aliasing, as self connections are prohibited.
-                    unsafe {
-                        match self.vertex[node_index].source[channel].source {
-                            Port::Zero => (*ptr).mut_at(channel)[..size].fill(0.0),
-                            Port::Global(port) => {
-                                (*ptr).mut_at(channel)[..size].copy_from_slice(&input[port][..size])
-                            }
-                            Port::Local(source, port) => {
-                                (*ptr).mut_at(channel)[..size]
-                                    .copy_from_slice(&self.vertex[source].output.at(port)[..size]);
-                            }
-                        }
-                    }
-                }
-                let vertex = &mut self.vertex[node_index];
-                // Safety: we know there is no aliasing, as self connections are prohibited.
-                unsafe {
-                    vertex
-                        .unit
-                        .process(size, (*ptr).self_ref(), vertex.output.self_mut());
+        if self.has_feedback {
+            // Feedback connections are resolved one sample at a time.
+            for i in 0..size {
+                self.tick_vertices(|port| input[port][i]);
+                for channel in 0..output.len() {
+                    output[channel][i] = self.tick_output_at(channel, |port| input[port][i]);
                 }
             }
+            return;
         }
+        // Iterate units in network order.
+        let order = self.order.take().unwrap_or_default();
+        for &node_index in order.iter() {
+            self.process_vertex(node_index, size, input);
+        }
+        self.order = Some(order);
 
         // Then we set the global outputs.
         for channel in 0..output.len() {
@@ -1089,7 +2004,12 @@ impl AudioUnit48 for Net48 {
                 Port::Global(port) => output[channel][..size].copy_from_slice(&input[port][..size]),
                 Port::Local(node, port) => output[channel][..size]
                     .copy_from_slice(&self.vertex[node].output.at(port)[..size]),
-                Port::Zero => output[channel][..size].fill(0.0),
+                _ => output[channel][..size].fill(0.0),
+            }
+            if !self.is_unity_gain(channel) {
+                for x in output[channel][..size].iter_mut() {
+                    *x *= self.next_output_gain(channel);
+                }
             }
         }
     }
@@ -1121,6 +2041,15 @@ impl AudioUnit48 for Net48 {
                     Port::Local(j, port) => input_signal[channel] = inner_signal[j][port],
                     Port::Global(j) => input_signal[channel] = input[j],
                     Port::Zero => input_signal[channel] = Signal::Value(0.0),
+                    Port::Feedback(_, _) => input_signal[channel] = Signal::Unknown,
+                }
+                let delay = self.vertex[unit_index].compensation[channel].len() as f64;
+                if delay > 0.0 {
+                    let z = Complex64::from_polar(1.0, -TAU * frequency * delay / self.sample_rate);
+                    input_signal[channel] = match input_signal[channel] {
+                        Signal::Response(..) => input_signal[channel].filter(delay, |r| r * z),
+                        signal => signal.delay(delay),
+                    };
                 }
             }
             inner_signal[unit_index] = self.vertex[unit_index].unit.route(&input_signal, frequency);
@@ -1134,7 +2063,11 @@ impl AudioUnit48 for Net48 {
                 Port::Local(node, port) => {
                     output_signal[channel] = inner_signal[node][port];
                 }
-                Port::Zero => output_signal[channel] = Signal::Value(0.0),
+                _ => output_signal[channel] = Signal::Value(0.0),
+            }
+            if self.output_gain[channel] != 1.0 {
+                output_signal[channel] =
+                    output_signal[channel].scale(self.output_gain[channel].to_f64());
             }
         }
         output_signal
@@ -1144,6 +2077,18 @@ impl AudioUnit48 for Net48 {
         std::mem::size_of::<Self>()
     }
 
+    fn total_footprint(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.input.footprint()
+            + self.output.footprint()
+            + self.output_edge.capacity() * std::mem::size_of::<Edge>()
+            + self
+                .vertex
+                .iter()
+                .map(|vertex| vertex.total_footprint())
+                .sum::<usize>()
+    }
+
     fn allocate(&mut self) {
         if !self.is_ordered() {
             self.determine_order();
@@ -1152,6 +2097,140 @@ impl AudioUnit48 for Net48 {
             vertex.allocate();
         }
     }
+
+    fn is_allocated(&self) -> bool {
+        self.is_ordered() && self.vertex.iter().all(|vertex| vertex.unit.is_allocated())
+    }
+}
+
+#[duplicate_item(
+    f48       Net48       AudioUnit48       Wave48       Wave48Player;
+    [ f64 ]   [ Net64 ]   [ AudioUnit64 ]   [ Wave64 ]   [ Wave64Player ];
+    [ f32 ]   [ Net32 ]   [ AudioUnit32 ]   [ Wave32 ]   [ Wave32Player ];
+)]
+impl Net48 {
+    /// Freeze generator `node` into a looping buffer of `length` samples.
+    /// A copy of the node is reset and rendered at the current sample rate,
+    /// then the node is replaced with a player that loops the rendering.
+    /// Returns the unit that was replaced.
+    ///
+    /// This trades memory for CPU and is only valid for static layers:
+    /// the node must have no inputs, its output must be deterministic
+    /// and it should repeat seamlessly every `length` samples,
+    /// for example an oscillator with a period that divides `length`.
+    /// Settings and sample rate changes made afterwards do not affect the baked loop.
+    /// Playback starts from the beginning of the loop, so bake before starting the stream
+    /// or reset the network afterwards.
+    ///
+    /// ### Example (Baked Chord)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(0, 1);
+    /// let id = net.chain(Box::new(sine_hz(220.5) + sine_hz(441.0) + sine_hz(661.5)));
+    /// net.bake(id, 200);
+    /// net.check();
+    /// ```
+    pub fn bake(&mut self, node: NodeId, length: usize) -> Box<dyn AudioUnit48> {
+        assert!(length > 0);
+        let mut unit = self.vertex[self.node_index[&node]].unit.clone();
+        assert_eq!(unit.inputs(), 0);
+        unit.reset();
+        let wave = Wave48::render(
+            self.sample_rate,
+            length as f64 / self.sample_rate,
+            &mut *unit,
+        );
+        assert_eq!(wave.length(), length);
+        let wave = Arc::new(wave);
+        let outputs = unit.outputs();
+        let mut player = Net48::new(0, outputs);
+        for channel in 0..outputs {
+            let id = player.push(Box::new(An(Wave48Player::<f48>::new(
+                &wave,
+                channel,
+                0,
+                length,
+                Some(0),
+            ))));
+            player.connect_output(id, 0, channel);
+        }
+        self.replace(node, Box::new(player))
+    }
+
+    /// Factor a shared effect out of duplicated voices.
+    /// Outputs of `nodes` are summed channel-wise into a single instance of `tail`,
+    /// so the effect processes the mix once instead of once per voice.
+    /// Connections from the first node are rerouted to the tail outputs and
+    /// connections from the other nodes are replaced with zeros.
+    /// Returns the ID of the tail.
+    ///
+    /// The result equals the original network with `tail` applied to the sum
+    /// wherever the nodes were mixed together, which holds for linear,
+    /// time-invariant effects such as reverbs and filters.
+    /// All nodes must have as many outputs as `tail` has inputs and outputs,
+    /// and none of them may feed another.
+    ///
+    /// ### Example (Shared Filter)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut net = Net64::new(0, 1);
+    /// let id1 = net.push(Box::new(saw_hz(110.0)));
+    /// let id2 = net.push(Box::new(saw_hz(165.0)));
+    /// let mix = net.push(Box::new(pass() + pass()));
+    /// net.connect(id1, 0, mix, 0);
+    /// net.connect(id2, 0, mix, 1);
+    /// net.pipe_output(mix);
+    /// net.share_tail(&[id1, id2], Box::new(lowpass_hz(1000.0, 1.0)));
+    /// net.check();
+    /// ```
+    pub fn share_tail(&mut self, nodes: &[NodeId], tail: Box<dyn AudioUnit48>) -> NodeId {
+        assert!(!nodes.is_empty());
+        let channels = tail.inputs();
+        assert_eq!(tail.outputs(), channels);
+        let index: Vec<NodeIndex> = nodes.iter().map(|node| self.node_index[node]).collect();
+        for &i in index.iter() {
+            assert_eq!(self.vertex[i].outputs(), channels);
+            for &j in index.iter() {
+                assert!(i == j || !self.reaches(i, j));
+            }
+        }
+        let tail_id = self.push(tail);
+        let tail_index = self.node_index[&tail_id];
+        let reroute = |port: Port| match port {
+            Port::Local(node, channel) if node == index[0] => Port::Local(tail_index, channel),
+            Port::Local(node, _) if index.contains(&node) => Port::Zero,
+            Port::Feedback(node, channel) if node == index[0] => {
+                Port::Feedback(tail_index, channel)
+            }
+            Port::Feedback(node, _) if index.contains(&node) => Port::Zero,
+            _ => port,
+        };
+        for channel in 0..self.outputs() {
+            self.output_edge[channel].source = reroute(self.output_edge[channel].source);
+        }
+        for vertex in self.vertex.iter_mut() {
+            for edge in vertex.source.iter_mut() {
+                edge.source = reroute(edge.source);
+            }
+        }
+        for channel in 0..channels {
+            let mut sum = (index[0], channel);
+            for &node in index[1..].iter() {
+                let adder = self.push(Box::new(An(Binop::<f48, _, _, _>::new(
+                    Pass::<f48>::new(),
+                    Pass::<f48>::new(),
+                    FrameAdd::new(),
+                ))));
+                let adder_index = self.node_index[&adder];
+                self.connect_index(sum.0, sum.1, adder_index, 0);
+                self.connect_index(node, channel, adder_index, 1);
+                sum = (adder_index, 0);
+            }
+            self.connect_index(sum.0, sum.1, tail_index, channel);
+        }
+        self.invalidate_order();
+        tail_id
+    }
 }
 
 #[duplicate_item(
@@ -1166,6 +2245,8 @@ impl Net48 {
         net.output.resize(net.inputs());
         net.output_edge
             .resize(net.inputs(), edge(Port::Zero, Port::Zero));
+        net.output_gain.resize(net.inputs(), 1.0);
+        net.output_gain_current.resize(net.inputs(), 1.0);
         for i in outputs..net.inputs() {
             net.output_edge[i] = edge(Port::Global(i), Port::Global(i));
         }
@@ -1187,6 +2268,9 @@ impl Net48 {
         let outputs = net1.outputs() + net2.outputs();
         net1.vertex.append(&mut net2.vertex);
         net1.output_edge.append(&mut net2.output_edge);
+        net1.output_gain.append(&mut net2.output_gain);
+        net1.output_gain_current
+            .append(&mut net2.output_gain_current);
         net1.output.resize(outputs);
         for i in output_offset..net1.output_edge.len() {
             match net1.output_edge[i].source {
@@ -1199,7 +2283,7 @@ impl Net48 {
                 Port::Global(source_port) => {
                     net1.output_edge[i] = edge(Port::Global(source_port), Port::Global(i));
                 }
-                Port::Zero => {
+                _ => {
                     net1.output_edge[i] = edge(Port::Zero, Port::Global(i));
                 }
             }
@@ -1218,6 +2302,12 @@ impl Net48 {
                         net1.vertex[node].source[port] =
                             edge(Port::Global(source_port), Port::Local(node, port));
                     }
+                    Port::Feedback(source_node, source_port) => {
+                        net1.vertex[node].source[port] = edge(
+                            Port::Feedback(source_node + offset, source_port),
+                            Port::Local(node, port),
+                        );
+                    }
                     Port::Zero => {
                         net1.vertex[node].source[port] = edge(Port::Zero, Port::Local(node, port));
                     }
@@ -1239,6 +2329,9 @@ impl Net48 {
         let outputs = net1.outputs() + net2.outputs();
         net1.vertex.append(&mut net2.vertex);
         net1.output_edge.append(&mut net2.output_edge);
+        net1.output_gain.append(&mut net2.output_gain);
+        net1.output_gain_current
+            .append(&mut net2.output_gain_current);
         net1.output.resize(outputs);
         net1.input.resize(inputs);
         for i in output_offset..net1.output_edge.len() {
@@ -1253,7 +2346,7 @@ impl Net48 {
                     net1.output_edge[i] =
                         edge(Port::Global(source_port + input_offset), Port::Global(i));
                 }
-                Port::Zero => {
+                _ => {
                     net1.output_edge[i] = edge(Port::Zero, Port::Global(i));
                 }
             }
@@ -1274,6 +2367,12 @@ impl Net48 {
                             Port::Local(node, port),
                         );
                     }
+                    Port::Feedback(source_node, source_port) => {
+                        net1.vertex[node].source[port] = edge(
+                            Port::Feedback(source_node + offset, source_port),
+                            Port::Local(node, port),
+                        );
+                    }
                     Port::Zero => {
                         net1.vertex[node].source[port] = edge(Port::Zero, Port::Local(node, port));
                     }
@@ -1322,12 +2421,21 @@ impl Net48 {
                             Port::Local(node, port),
                         );
                     }
+                    Port::Feedback(source_node, source_port) => {
+                        net1.vertex[node].source[port] = edge(
+                            Port::Feedback(source_node + offset, source_port),
+                            Port::Local(node, port),
+                        );
+                    }
                     Port::Zero => {
                         net1.vertex[node].source[port] = edge(Port::Zero, Port::Local(node, port));
                     }
                 }
             }
         }
+        // The outputs are new sums, so gains start from unity.
+        net1.output_gain.fill(1.0);
+        net1.output_gain_current.fill(1.0);
         let add_offset = net1.vertex.len();
         for i in 0..net1.outputs() {
             net1.push(Box::new(An(Binop::<f48, _, _, _>::new(
@@ -1399,12 +2507,21 @@ impl Net48 {
                         net1.vertex[node].source[port] =
                             edge(Port::Global(source_port), Port::Local(node, port));
                     }
+                    Port::Feedback(source_node, source_port) => {
+                        net1.vertex[node].source[port] = edge(
+                            Port::Feedback(source_node + offset, source_port),
+                            Port::Local(node, port),
+                        );
+                    }
                     Port::Zero => {
                         net1.vertex[node].source[port] = edge(Port::Zero, Port::Local(node, port));
                     }
                 }
             }
         }
+        // The outputs are new sums, so gains start from unity.
+        net1.output_gain.fill(1.0);
+        net1.output_gain_current.fill(1.0);
         let add_offset = net1.vertex.len();
         for i in 0..net1.outputs() {
             net1.push(Box::new(An(Binop::<f48, _, _, _>::new(
@@ -1470,6 +2587,12 @@ impl Net48 {
                             Port::Local(node, port),
                         );
                     }
+                    Port::Feedback(source_node, source_port) => {
+                        net1.vertex[node].source[port] = edge(
+                            Port::Feedback(source_node + offset, source_port),
+                            Port::Local(node, port),
+                        );
+                    }
                     Port::Zero => {
                         net1.vertex[node].source[port] = edge(Port::Zero, Port::Local(node, port));
                     }
@@ -1480,6 +2603,8 @@ impl Net48 {
         let output_edge1 = net1.output_edge;
         net1.output_edge = net2.output_edge.clone();
         net1.output = net2.output.clone();
+        net1.output_gain = net2.output_gain.clone();
+        net1.output_gain_current = net2.output_gain_current.clone();
         for output_port in 0..net1.outputs() {
             match net1.output_edge[output_port].source {
                 Port::Local(source_node, source_port) => {
@@ -316,6 +316,178 @@ impl<T: Float> AudioNode for Pluck<T> {
             self.initialize_line();
         }
     }
+
+    fn is_allocated(&self) -> bool {
+        self.initialized
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.line.capacity() * std::mem::size_of::<T>()
+    }
+}
+
+/// Karplus-Strong oscillator with continuously variable pitch and damping.
+/// A rising edge in the gate input replaces the contents of the loop with a noise burst.
+/// The loop delay, in samples, is the sample rate divided by the frequency.
+/// One sample of it is from the damping filter, and the rest is split into
+/// an integer delay line length, which is rounded down so that at least 0.2 samples remain,
+/// and a fraction of 0.2...1.2 samples tuned by a 1st order allpass filter.
+/// Allocates: pluck buffer.
+/// - Input 0: frequency in Hz. It is clamped between the minimum frequency and one quarter of the sample rate.
+/// - Input 1: high frequency damping in 0...1.
+/// - Input 2: gate. A rising edge above zero plucks the string.
+/// - Output 0: plucked string.
+#[derive(Clone)]
+pub struct PluckVar<T: Float> {
+    tuning: Allpole<T, T, typenum::U1>,
+    line: Vec<T>,
+    pos: usize,
+    /// Length of the integer part of the loop delay in samples.
+    delay: usize,
+    /// Previous inputs to the damping filter.
+    x1: T,
+    x2: T,
+    gain_per_second: T,
+    /// Feedback gain per loop.
+    gain: T,
+    min_frequency: T,
+    frequency: T,
+    gate: T,
+    rnd: Rnd,
+    hash: u64,
+    sample_rate: f64,
+}
+
+impl<T: Float> PluckVar<T> {
+    /// Create new Karplus-Strong oscillator. The pluck buffer is sized for
+    /// frequencies down to `min_frequency` Hz. The string decays by `gain_per_second`
+    /// each second.
+    pub fn new(min_frequency: T, gain_per_second: T) -> Self {
+        assert!(min_frequency > T::zero());
+        let mut node = Self {
+            tuning: Allpole::new(T::one()),
+            line: Vec::new(),
+            pos: 0,
+            delay: 1,
+            x1: T::zero(),
+            x2: T::zero(),
+            gain_per_second,
+            gain: T::zero(),
+            min_frequency,
+            frequency: T::zero(),
+            gate: T::zero(),
+            rnd: Rnd::from_u64(0),
+            hash: 0,
+            sample_rate: 0.0,
+        };
+        node.set_sample_rate(DEFAULT_SR);
+        node
+    }
+
+    /// Update loop delay and feedback gain for a new frequency.
+    fn set_frequency(&mut self, frequency: T) {
+        self.frequency = frequency;
+        self.gain = T::from_f64(pow(self.gain_per_second.to_f64(), 1.0 / frequency.to_f64()));
+        // Allpass filter delay is in epsilon ... epsilon + 1.
+        let epsilon = 0.2;
+        // Damping filter delay is 1 sample.
+        let total_delay = self.sample_rate / frequency.to_f64() - 1.0;
+        let loop_delay = floor(total_delay - epsilon);
+        self.delay = loop_delay as usize;
+        self.tuning.set_delay(T::from_f64(total_delay - loop_delay));
+    }
+
+    /// Fill the loop with a zero mean noise burst.
+    fn excite(&mut self) {
+        let mask = self.line.len() - 1;
+        let start = self.pos + self.line.len() - self.delay;
+        let mut mean = 0.0;
+        for i in 0..self.delay {
+            let x = self.rnd.f32_in(-1.0, 1.0);
+            self.line[(start + i) & mask] = T::from_f32(x);
+            mean += x as f64;
+        }
+        mean /= self.delay as f64;
+        for i in 0..self.delay {
+            self.line[(start + i) & mask] -= T::from_f64(mean);
+        }
+    }
+}
+
+impl<T: Float> AudioNode for PluckVar<T> {
+    const ID: u64 = 127;
+    type Sample = T;
+    type Inputs = typenum::U3;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.line.fill(T::zero());
+        self.pos = 0;
+        self.x1 = T::zero();
+        self.x2 = T::zero();
+        self.tuning.reset();
+        self.gate = T::zero();
+        self.rnd = Rnd::from_u64(self.hash);
+        // Recalculate loop parameters on the next sample.
+        self.frequency = T::zero();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            let length = ceil(sample_rate / self.min_frequency.to_f64()) as usize + 1;
+            self.line.resize(length.next_power_of_two(), T::zero());
+            self.reset();
+        }
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let frequency = clamp(
+            self.min_frequency,
+            T::from_f64(self.sample_rate * 0.25),
+            input[0],
+        );
+        if frequency != self.frequency {
+            self.set_frequency(frequency);
+        }
+        if input[2] > T::zero() && self.gate <= T::zero() {
+            self.excite();
+        }
+        self.gate = input[2];
+        let mask = self.line.len() - 1;
+        let x0 = self.line[(self.pos + self.line.len() - self.delay) & mask] * self.gain;
+        // Symmetric 3-tap damping filter with a gain of 1 - damping at Nyquist.
+        let damping = clamp01(input[1]);
+        let alpha = T::one() - damping / T::new(2);
+        let beta = damping / T::new(4);
+        let output = beta * (x0 + self.x2) + alpha * self.x1;
+        self.x2 = self.x1;
+        self.x1 = x0;
+        let output = self.tuning.filter_mono(output);
+        self.line[self.pos] = output;
+        self.pos = (self.pos + 1) & mask;
+        [output].into()
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.rnd = Rnd::from_u64(hash);
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = Signal::Latency(0.0);
+        output
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.line.capacity() * std::mem::size_of::<T>()
+    }
 }
 
 /// Rossler dynamical system oscillator.
@@ -449,3 +621,953 @@ impl<T: Float> AudioNode for Lorenz<T> {
         output
     }
 }
+
+/// Anti-aliasing quality of the PolyBLEP oscillators.
+/// Higher quality corrects more samples around each discontinuity,
+/// which reduces aliasing at a small additional cost per sample.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quality {
+    /// No anti-aliasing. Cheapest, with audible aliasing at high pitches.
+    Low,
+    /// 2-point polynomial correction. Removes most audible aliasing at moderate pitches.
+    Medium,
+    /// 4-point polynomial correction derived from a cubic B-spline.
+    /// Reduces aliasing further while slightly softening the highest partials.
+    High,
+}
+
+/// Residual of the 2-point PolyBLEP at distance `t` (in 0...1) samples from the discontinuity.
+#[inline]
+fn blep2<T: Real>(t: T) -> T {
+    squared(T::one() - t) * T::from_f64(0.5)
+}
+
+/// Residual of the 4-point B-spline PolyBLEP at distance `t` (in 0...2) samples from the discontinuity.
+#[inline]
+fn blep4<T: Real>(t: T) -> T {
+    if t < T::one() {
+        let t2 = t * t;
+        T::from_f64(0.5) - t * T::from_f64(2.0 / 3.0) + t2 * t * T::from_f64(1.0 / 3.0)
+            - t2 * t2 * T::from_f64(0.125)
+    } else {
+        let u = T::new(2) - t;
+        squared(u * u) * T::from_f64(1.0 / 24.0)
+    }
+}
+
+/// PolyBLEP saw oscillator with selectable anti-aliasing quality.
+/// - Input 0: frequency in Hz.
+/// - Output 0: saw wave.
+#[derive(Clone)]
+pub struct PolySaw<T: Real> {
+    phase: T,
+    sample_duration: T,
+    hash: u64,
+    quality: Quality,
+}
+
+impl<T: Real> PolySaw<T> {
+    /// Create saw oscillator with the given anti-aliasing `quality`.
+    pub fn new(sample_rate: f64, quality: Quality) -> Self {
+        let mut node = Self {
+            phase: T::zero(),
+            sample_duration: T::zero(),
+            hash: 0,
+            quality,
+        };
+        node.reset();
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Anti-aliasing quality.
+    pub fn quality(&self) -> Quality {
+        self.quality
+    }
+
+    /// Saw value at the current phase, `dt` being the phase increment per sample.
+    #[inline]
+    fn value(&self, dt: T) -> T {
+        let phase = self.phase;
+        let naive = phase + phase - T::one();
+        // The wave drops by 2 at each phase wrap. Smooth the samples around it.
+        match self.quality {
+            Quality::Low => naive,
+            Quality::Medium => {
+                let mut y = naive;
+                if phase < dt {
+                    y += blep2(phase / dt) * T::new(2);
+                }
+                if phase > T::one() - dt {
+                    y -= blep2((T::one() - phase) / dt) * T::new(2);
+                }
+                y
+            }
+            Quality::High => {
+                let mut y = naive;
+                let window = dt + dt;
+                if phase < window {
+                    y += blep4(phase / dt) * T::new(2);
+                }
+                if phase > T::one() - window {
+                    y -= blep4((T::one() - phase) / dt) * T::new(2);
+                }
+                y
+            }
+        }
+    }
+}
+
+impl<T: Real> AudioNode for PolySaw<T> {
+    const ID: u64 = 87;
+    type Sample = T;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.phase = T::from_f64(rnd(self.hash as i64));
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_duration = convert(1.0 / sample_rate);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let dt = input[0] * self.sample_duration;
+        self.phase += dt;
+        self.phase -= self.phase.floor();
+        [self.value(dt)].into()
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.reset();
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = Signal::Latency(0.0);
+        output
+    }
+}
+
+/// PolyBLEP pulse oscillator with a pulse width input.
+/// Both edges of the pulse are corrected, so width can be modulated without aliasing.
+/// The output is free of DC at any width.
+/// - Input 0: frequency in Hz.
+/// - Input 1: pulse width in 0...1.
+/// - Output 0: pulse wave.
+#[derive(Clone)]
+pub struct PolyPulse<T: Real> {
+    phase: T,
+    sample_duration: T,
+    hash: u64,
+    quality: Quality,
+}
+
+impl<T: Real> PolyPulse<T> {
+    /// Create pulse oscillator with the given anti-aliasing `quality`.
+    pub fn new(sample_rate: f64, quality: Quality) -> Self {
+        let mut node = Self {
+            phase: T::zero(),
+            sample_duration: T::zero(),
+            hash: 0,
+            quality,
+        };
+        node.reset();
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Anti-aliasing quality.
+    pub fn quality(&self) -> Quality {
+        self.quality
+    }
+
+    /// PolyBLEP residual at distance `t` cycles from a discontinuity,
+    /// `dt` being the phase increment per sample.
+    #[inline]
+    fn residual(&self, t: T, dt: T) -> T {
+        match self.quality {
+            Quality::Low => T::zero(),
+            Quality::Medium if t < dt => blep2(t / dt),
+            Quality::High if t < dt + dt => blep4(t / dt),
+            _ => T::zero(),
+        }
+    }
+
+    /// Pulse value at the current phase with pulse `width`.
+    #[inline]
+    fn value(&self, dt: T, width: T) -> T {
+        let phase = self.phase;
+        let mut y = if phase < width { T::one() } else { -T::one() };
+        // The wave rises by 2 at phase 0.
+        y += T::new(2) * (self.residual(T::one() - phase, dt) - self.residual(phase, dt));
+        // The wave falls by 2 at phase `width`.
+        let mut after = phase - width;
+        if after < T::zero() {
+            after += T::one();
+        }
+        let mut before = width - phase;
+        if before <= T::zero() {
+            before += T::one();
+        }
+        y -= T::new(2) * (self.residual(before, dt) - self.residual(after, dt));
+        // Remove DC.
+        y - (width + width - T::one())
+    }
+}
+
+impl<T: Real> AudioNode for PolyPulse<T> {
+    const ID: u64 = 103;
+    type Sample = T;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.phase = T::from_f64(rnd(self.hash as i64));
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_duration = convert(1.0 / sample_rate);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let dt = input[0] * self.sample_duration;
+        let width = clamp01(input[1]);
+        self.phase += dt;
+        self.phase -= self.phase.floor();
+        [self.value(dt, width)].into()
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.reset();
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = Signal::Latency(0.0);
+        output
+    }
+}
+
+/// Waveform of a sub-oscillator.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SubWave {
+    /// Band-limited square wave.
+    #[default]
+    Square,
+    /// Sine wave.
+    Sine,
+}
+
+/// Saw oscillator with a phase-locked sub-oscillator one or two octaves below,
+/// as found in analog bass synthesizers. The sub-oscillator is derived by
+/// dividing the saw cycles, so its edges always coincide with saw resets.
+/// Setting: sub-oscillator waveform.
+/// - Input 0: frequency in Hz.
+/// - Output 0: saw wave.
+/// - Output 1: sub-oscillator wave.
+#[derive(Clone)]
+pub struct SubOsc<T: Real> {
+    saw: PolySaw<T>,
+    /// Number of saw cycles per sub-oscillator cycle.
+    divider: usize,
+    /// Saw cycles completed in the current sub-oscillator cycle.
+    count: usize,
+    wave: SubWave,
+}
+
+impl<T: Real> SubOsc<T> {
+    /// Create new oscillator with the sub-oscillator `octaves` (1 or 2) below the saw.
+    pub fn new(sample_rate: f64, octaves: usize, wave: SubWave) -> Self {
+        assert!(octaves == 1 || octaves == 2);
+        Self {
+            saw: PolySaw::new(sample_rate, Quality::High),
+            divider: 1 << octaves,
+            count: 0,
+            wave,
+        }
+    }
+
+    /// Number of octaves between the saw and the sub-oscillator.
+    pub fn octaves(&self) -> usize {
+        self.divider.trailing_zeros() as usize
+    }
+
+    /// Sub-oscillator waveform.
+    pub fn wave(&self) -> SubWave {
+        self.wave
+    }
+
+    /// Set sub-oscillator waveform.
+    pub fn set_wave(&mut self, wave: SubWave) {
+        self.wave = wave;
+    }
+
+    /// Sub-oscillator value at sub-oscillator `phase`, `dt` being the phase increment per sample.
+    #[inline]
+    fn sub(&self, phase: T, dt: T) -> T {
+        match self.wave {
+            SubWave::Sine => sin(phase * T::from_f64(TAU)),
+            SubWave::Square => {
+                let residual = |t: T| if t < dt { blep2(t / dt) } else { T::zero() };
+                let half = T::from_f64(0.5);
+                let mut y = if phase < half { T::one() } else { -T::one() };
+                // The wave rises by 2 at phase 0 and falls by 2 at phase 0.5.
+                y += T::new(2) * (residual(T::one() - phase) - residual(phase));
+                let (before, after) = if phase < half {
+                    (half - phase, phase + half)
+                } else {
+                    (T::new(3) * half - phase, phase - half)
+                };
+                y - T::new(2) * (residual(before) - residual(after))
+            }
+        }
+    }
+}
+
+impl<T: Real> AudioNode for SubOsc<T> {
+    const ID: u64 = 113;
+    type Sample = T;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U2;
+    type Setting = SubWave;
+
+    fn set(&mut self, wave: Self::Setting) {
+        self.set_wave(wave);
+    }
+
+    fn reset(&mut self) {
+        self.saw.reset();
+        self.count = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.saw.set_sample_rate(sample_rate);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let previous = self.saw.phase;
+        let saw = self.saw.tick(input)[0];
+        if self.saw.phase < previous && input[0] > T::zero() {
+            self.count = (self.count + 1) % self.divider;
+        }
+        let divider = T::from_f64(self.divider as f64);
+        let phase = (T::from_f64(self.count as f64) + self.saw.phase) / divider;
+        let dt = input[0] * self.saw.sample_duration / divider;
+        [saw, self.sub(phase, dt)].into()
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.saw.set_hash(hash);
+        self.count = 0;
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = Signal::Latency(0.0);
+        output[1] = Signal::Latency(0.0);
+        output
+    }
+}
+
+/// Phase modulated sine operator, the building block of FM synthesis.
+#[derive(Default, Clone)]
+struct Operator<T: Real> {
+    phase: T,
+    /// Previous output, for self-feedback.
+    previous: T,
+}
+
+impl<T: Real> Operator<T> {
+    /// Advance phase by `delta` cycles and return the output
+    /// with phase offset `modulation` and self-feedback amount `feedback` (both in radians).
+    #[inline]
+    fn tick(&mut self, delta: T, modulation: T, feedback: T) -> T {
+        let output = sin(self.phase * T::from_f64(TAU) + modulation + feedback * self.previous);
+        self.phase += delta;
+        self.phase -= self.phase.floor();
+        self.previous = output;
+        output
+    }
+}
+
+/// FM operator: a sine oscillator whose phase is modulated by an input signal.
+/// The operator runs at `ratio` times the pitch input.
+/// The modulation input is scaled by the modulation `index` (in radians).
+/// - Input 0: pitch in Hz
+/// - Input 1: modulation signal
+/// - Output 0: operator output
+#[derive(Clone)]
+pub struct FmOp<T: Real> {
+    ratio: T,
+    index: T,
+    operator: Operator<T>,
+    sample_duration: T,
+}
+
+impl<T: Real> FmOp<T> {
+    /// Create new FM operator with frequency `ratio` and modulation `index`.
+    pub fn new(sample_rate: f64, ratio: T, index: T) -> Self {
+        let mut node = Self {
+            ratio,
+            index,
+            operator: Operator::default(),
+            sample_duration: T::zero(),
+        };
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Frequency ratio relative to the pitch input.
+    pub fn ratio(&self) -> T {
+        self.ratio
+    }
+
+    /// Modulation index in radians.
+    pub fn index(&self) -> T {
+        self.index
+    }
+
+    /// Set modulation index in radians.
+    pub fn set_index(&mut self, index: T) {
+        self.index = index;
+    }
+}
+
+impl<T: Real> AudioNode for FmOp<T> {
+    const ID: u64 = 89;
+    type Sample = T;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.operator = Operator::default();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_duration = convert(1.0 / sample_rate);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let delta = input[0] * self.ratio * self.sample_duration;
+        [self.operator.tick(delta, input[1] * self.index, T::zero())].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = input[0].combine_nonlinear(input[1], 0.0);
+        output
+    }
+}
+
+/// Classic two operator FM voice. A modulator with optional self-feedback
+/// modulates the phase of a sine carrier.
+/// - Input 0: pitch in Hz
+/// - Output 0: carrier output
+#[derive(Clone)]
+pub struct Fm2<T: Real> {
+    carrier_ratio: T,
+    modulator_ratio: T,
+    index: T,
+    feedback: T,
+    carrier: Operator<T>,
+    modulator: Operator<T>,
+    sample_duration: T,
+}
+
+impl<T: Real> Fm2<T> {
+    /// Create new two operator FM voice. The carrier and modulator run at
+    /// `carrier_ratio` and `modulator_ratio` times the pitch input, respectively.
+    /// Modulation `index` and modulator self-`feedback` are in radians.
+    pub fn new(
+        sample_rate: f64,
+        carrier_ratio: T,
+        modulator_ratio: T,
+        index: T,
+        feedback: T,
+    ) -> Self {
+        let mut node = Self {
+            carrier_ratio,
+            modulator_ratio,
+            index,
+            feedback,
+            carrier: Operator::default(),
+            modulator: Operator::default(),
+            sample_duration: T::zero(),
+        };
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Modulation index in radians.
+    pub fn index(&self) -> T {
+        self.index
+    }
+
+    /// Set modulation index in radians.
+    pub fn set_index(&mut self, index: T) {
+        self.index = index;
+    }
+
+    /// Modulator self-feedback amount in radians.
+    pub fn feedback(&self) -> T {
+        self.feedback
+    }
+
+    /// Set modulator self-feedback amount in radians.
+    pub fn set_feedback(&mut self, feedback: T) {
+        self.feedback = feedback;
+    }
+}
+
+impl<T: Real> AudioNode for Fm2<T> {
+    const ID: u64 = 90;
+    type Sample = T;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.carrier = Operator::default();
+        self.modulator = Operator::default();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_duration = convert(1.0 / sample_rate);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let delta = input[0] * self.sample_duration;
+        let modulation =
+            self.modulator
+                .tick(delta * self.modulator_ratio, T::zero(), self.feedback);
+        [self.carrier.tick(
+            delta * self.carrier_ratio,
+            modulation * self.index,
+            T::zero(),
+        )]
+        .into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = input[0].distort(0.0);
+        output
+    }
+}
+
+/// Phase distortion oscillator in the style of Casio CZ synthesizers.
+/// The phase ramp is bent at a breakpoint before the sine lookup:
+/// the first half cycle is traversed faster as distortion increases,
+/// producing bright, resonant-like spectra from a single sine.
+/// - Input 0: frequency in Hz
+/// - Input 1: distortion amount in 0...1 (0 = pure sine)
+/// - Output 0: phase distorted wave
+#[derive(Default, Clone)]
+pub struct PhaseDistort<T: Real> {
+    phase: T,
+    sample_duration: T,
+    hash: u64,
+}
+
+impl<T: Real> PhaseDistort<T> {
+    /// Create phase distortion oscillator.
+    pub fn new(sample_rate: f64) -> Self {
+        let mut node = Self::default();
+        node.reset();
+        node.set_sample_rate(sample_rate);
+        node
+    }
+}
+
+/// Bend `phase` (in 0...1) so that half a cycle is reached at `breakpoint` (in 0...1).
+#[inline]
+fn bend_phase<T: Real>(phase: T, breakpoint: T) -> T {
+    let half = T::from_f64(0.5);
+    if phase < breakpoint {
+        phase * half / breakpoint
+    } else {
+        half + (phase - breakpoint) * half / (T::one() - breakpoint)
+    }
+}
+
+impl<T: Real> AudioNode for PhaseDistort<T> {
+    const ID: u64 = 91;
+    type Sample = T;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.phase = T::from_f64(rnd(self.hash as i64));
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_duration = convert(1.0 / sample_rate);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        self.phase += input[0] * self.sample_duration;
+        self.phase -= self.phase.floor();
+        // Keep the breakpoint away from zero to avoid a division by zero.
+        let amount = clamp(T::zero(), T::from_f64(0.99), input[1]);
+        let breakpoint = T::from_f64(0.5) * (T::one() - amount);
+        [sin(bend_phase(self.phase, breakpoint) * T::from_f64(TAU))].into()
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.reset();
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = Signal::Latency(0.0);
+        output
+    }
+}
+
+/// Formant frequency (Hz), bandwidth (Hz) and gain (dB) of vowels a, e, i, o and u
+/// for a tenor voice.
+const VOWEL_FORMANTS: [[(f64, f64, f64); 5]; 5] = [
+    [
+        (650.0, 80.0, 0.0),
+        (1080.0, 90.0, -6.0),
+        (2650.0, 120.0, -7.0),
+        (2900.0, 130.0, -8.0),
+        (3250.0, 140.0, -22.0),
+    ],
+    [
+        (400.0, 70.0, 0.0),
+        (1700.0, 80.0, -14.0),
+        (2600.0, 100.0, -12.0),
+        (3200.0, 120.0, -14.0),
+        (3580.0, 120.0, -20.0),
+    ],
+    [
+        (290.0, 40.0, 0.0),
+        (1870.0, 90.0, -15.0),
+        (2800.0, 100.0, -18.0),
+        (3250.0, 120.0, -20.0),
+        (3540.0, 120.0, -30.0),
+    ],
+    [
+        (400.0, 70.0, 0.0),
+        (800.0, 80.0, -10.0),
+        (2600.0, 100.0, -12.0),
+        (2800.0, 130.0, -12.0),
+        (3000.0, 135.0, -26.0),
+    ],
+    [
+        (350.0, 40.0, 0.0),
+        (600.0, 60.0, -20.0),
+        (2700.0, 100.0, -17.0),
+        (2900.0, 120.0, -14.0),
+        (3300.0, 120.0, -26.0),
+    ],
+];
+
+/// Opening phase of the Rosenberg glottal pulse as a fraction of the period.
+const GLOTTAL_OPEN: f64 = 0.4;
+
+/// Closing phase of the Rosenberg glottal pulse as a fraction of the period.
+const GLOTTAL_CLOSE: f64 = 0.16;
+
+/// Output gain of the voice, leaving peaks near unity.
+const VOICE_GAIN: f64 = 0.05;
+
+/// Vocal synthesizer. A Rosenberg glottal pulse excites a parallel bank
+/// of five formant resonators. The source is the derivative of the glottal flow,
+/// which includes lip radiation, and its closure is band-limited with PolyBLEP.
+/// Vowel is a continuous value in 0...4: 0 is "a", 1 is "e", 2 is "i", 3 is "o" and 4 is "u".
+/// Intermediate values interpolate formants between neighboring vowels.
+/// - Input 0: pitch in Hz
+/// - Input 1: vowel in 0...4
+/// - Output 0: voice
+#[derive(Clone)]
+pub struct Voice<T: Real> {
+    phase: T,
+    sample_duration: T,
+    sample_rate: f64,
+    /// Vowel the formants are currently tuned to.
+    vowel: T,
+    formants: [Biquad<T, T>; 5],
+    gains: [T; 5],
+    hash: u64,
+}
+
+impl<T: Real> Voice<T> {
+    /// Create new voice tuned to `vowel` in 0...4.
+    pub fn new(sample_rate: f64, vowel: T) -> Self {
+        let mut node = Self {
+            phase: T::zero(),
+            sample_duration: T::zero(),
+            sample_rate,
+            vowel,
+            formants: Default::default(),
+            gains: [T::zero(); 5],
+            hash: 0,
+        };
+        node.reset();
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Vowel the formants are tuned to, in 0...4.
+    pub fn vowel(&self) -> T {
+        self.vowel
+    }
+
+    /// Tune formants to `vowel` in 0...4.
+    pub fn set_vowel(&mut self, vowel: T) {
+        let vowel = clamp(T::zero(), T::new(4), vowel);
+        self.vowel = vowel;
+        let v = vowel.to_f64();
+        let i = min(v.floor() as usize, 3);
+        let t = v - i as f64;
+        for k in 0..5 {
+            let (f0, b0, g0) = VOWEL_FORMANTS[i][k];
+            let (f1, b1, g1) = VOWEL_FORMANTS[i + 1][k];
+            let center = min(lerp(f0, f1, t), 0.45 * self.sample_rate);
+            let bandwidth = lerp(b0, b1, t);
+            self.formants[k].set_coefs(BiquadCoefs::resonator(
+                T::from_f64(self.sample_rate),
+                T::from_f64(center),
+                T::from_f64(bandwidth),
+            ));
+            self.gains[k] = T::from_f64(db_amp(lerp(g0, g1, t)) * VOICE_GAIN);
+        }
+    }
+
+    /// Glottal flow derivative at the current phase, `dt` being the phase increment per sample.
+    #[inline]
+    fn source(&self, dt: T) -> T {
+        let open = T::from_f64(GLOTTAL_OPEN);
+        let close = T::from_f64(GLOTTAL_CLOSE);
+        let phase = self.phase;
+        let mut y = if phase < open {
+            T::from_f64(PI * 0.5 / GLOTTAL_OPEN) * sin(T::from_f64(PI) * phase / open)
+        } else if phase < open + close {
+            T::from_f64(-PI * 0.5 / GLOTTAL_CLOSE)
+                * sin(T::from_f64(PI * 0.5) * (phase - open) / close)
+        } else {
+            T::zero()
+        };
+        // The derivative jumps back to zero at glottal closure.
+        let jump = T::from_f64(PI * 0.5 / GLOTTAL_CLOSE);
+        let closure = open + close;
+        if phase >= closure && phase - closure < dt {
+            y -= jump * blep2((phase - closure) / dt);
+        } else if phase < closure && closure - phase < dt {
+            y += jump * blep2((closure - phase) / dt);
+        }
+        y
+    }
+}
+
+impl<T: Real> AudioNode for Voice<T> {
+    const ID: u64 = 112;
+    type Sample = T;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.phase = T::from_f64(rnd(self.hash as i64));
+        for formant in self.formants.iter_mut() {
+            formant.reset();
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        self.sample_duration = convert(1.0 / sample_rate);
+        for formant in self.formants.iter_mut() {
+            formant.set_sample_rate(sample_rate);
+        }
+        self.set_vowel(self.vowel);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        if input[1] != self.vowel {
+            self.set_vowel(input[1]);
+        }
+        let dt = input[0] * self.sample_duration;
+        self.phase += dt;
+        self.phase -= self.phase.floor();
+        let x = self.source(dt);
+        let mut output = T::zero();
+        for (formant, gain) in self.formants.iter_mut().zip(self.gains.iter()) {
+            output += formant.tick(&[x].into())[0] * *gain;
+        }
+        [output].into()
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.hash = hash;
+        self.reset();
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = Signal::Latency(0.0);
+        output
+    }
+}
+
+/// Unison of `N` detuned copies of a voice.
+/// Per-voice detune and phase are derived deterministically from the node hash,
+/// so identically seeded graphs always produce the same voice arrangement.
+/// The voice must be an oscillator with a frequency input.
+/// Output is normalized by the square root of the number of voices.
+/// - Input 0: frequency in Hz.
+/// - Output 0: sum of voices.
+#[derive(Clone)]
+pub struct Unison<N, T, X>
+where
+    N: Size<T>,
+    T: Real,
+    X: AudioNode<Sample = T, Inputs = typenum::U1, Outputs = typenum::U1>,
+{
+    voices: Vec<X>,
+    /// Maximum detune in semitones.
+    spread: T,
+    /// Per-voice detune in semitones.
+    detune: Frame<T, N>,
+    /// Per-voice frequency ratio.
+    ratio: Frame<T, N>,
+}
+
+impl<N, T, X> Unison<N, T, X>
+where
+    N: Size<T>,
+    T: Real,
+    X: AudioNode<Sample = T, Inputs = typenum::U1, Outputs = typenum::U1>,
+{
+    /// Create new unison from `voice` with maximum detune `spread` in semitones.
+    pub fn new(spread: T, voice: X) -> Self {
+        let mut node = Self {
+            voices: vec![voice; N::USIZE],
+            spread,
+            detune: Frame::default(),
+            ratio: Frame::splat(T::one()),
+        };
+        node.set_hash(0);
+        node
+    }
+
+    /// Maximum detune in semitones.
+    pub fn spread(&self) -> T {
+        self.spread
+    }
+
+    /// Detune of voice `i` in semitones.
+    pub fn detune(&self, i: usize) -> T {
+        self.detune[i]
+    }
+}
+
+impl<N, T, X> AudioNode for Unison<N, T, X>
+where
+    N: Size<T>,
+    T: Real,
+    X: AudioNode<Sample = T, Inputs = typenum::U1, Outputs = typenum::U1>,
+{
+    const ID: u64 = 85;
+    type Sample = T;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        for voice in self.voices.iter_mut() {
+            voice.reset();
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        for voice in self.voices.iter_mut() {
+            voice.set_sample_rate(sample_rate);
+        }
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let mut output = T::zero();
+        for (voice, ratio) in self.voices.iter_mut().zip(self.ratio.iter()) {
+            output += voice.tick(&[input[0] * *ratio].into())[0];
+        }
+        [output / sqrt(T::new(N::I64))].into()
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        for (i, voice) in self.voices.iter_mut().enumerate() {
+            let detune = voice_spread(hash, i as u64, self.spread);
+            self.detune[i] = detune;
+            self.ratio[i] = semitone_ratio(detune);
+            // Voices pick their initial phases from hashes derived from ours.
+            voice.ping(false, AttoHash::new(hash).hash(i as u64));
+        }
+    }
+
+    fn allocate(&mut self) {
+        for voice in self.voices.iter_mut() {
+            voice.allocate();
+        }
+    }
+
+    fn is_allocated(&self) -> bool {
+        self.voices.iter().all(|voice| voice.is_allocated())
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.voices.capacity() * std::mem::size_of::<X>()
+            + self
+                .voices
+                .iter()
+                .map(|x| x.heap_footprint())
+                .sum::<usize>()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        Routing::Arbitrary.propagate(input, self.outputs())
+    }
+}
@@ -1,9 +1,13 @@
 //! Oversampling.
 
 use super::audionode::*;
+use super::audiounit::*;
+use super::buffer::*;
 use super::math::*;
 use super::signal::*;
 use super::*;
+use duplicate::duplicate_item;
+use num_complex::Complex64;
 use numeric_array::typenum::*;
 
 // Coefficients from https://fiiir.com/, a linear phase Kaiser windowed filter with
@@ -298,4 +302,345 @@ where
     fn allocate(&mut self) {
         self.x.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.x.heap_footprint()
+    }
+}
+
+/// Length of each polyphase branch of the `Oversample64` filters in outer samples.
+/// The filters have `OVERSAMPLE_TAPS * factor + 1` coefficients,
+/// so interpolation and decimation together delay the signal by `OVERSAMPLE_TAPS` samples.
+const OVERSAMPLE_TAPS: usize = 32;
+
+/// Cutoff of the `Oversample64` filters relative to the outer Nyquist frequency.
+const OVERSAMPLE_CUTOFF: f64 = 0.9;
+
+/// Blackman windowed sinc lowpass kernel for oversampling by `factor`,
+/// normalized to unity gain at DC.
+fn oversample_kernel(factor: usize) -> Vec<f64> {
+    let length = OVERSAMPLE_TAPS * factor + 1;
+    let center = (length / 2) as f64;
+    let rho = OVERSAMPLE_CUTOFF / factor as f64;
+    let mut kernel: Vec<f64> = (0..length)
+        .map(|i| {
+            let x = PI * rho * (i as f64 - center);
+            let sinc = if x == 0.0 { 1.0 } else { sin(x) / x };
+            let u = TAU * i as f64 / (length - 1) as f64;
+            sinc * (0.42 - 0.5 * cos(u) + 0.08 * cos(2.0 * u))
+        })
+        .collect();
+    let sum = kernel.iter().sum::<f64>();
+    for x in kernel.iter_mut() {
+        *x /= sum;
+    }
+    kernel
+}
+
+/// Runs the contained unit at an integer multiple of the sample rate.
+/// Inputs are upsampled with polyphase FIR interpolation and outputs are decimated
+/// with the same linear phase lowpass filter, which suppresses aliasing
+/// from nonlinear processing such as waveshaping.
+/// Filtering adds a latency of 32 samples, which is reported by `latency`.
+#[duplicate_item(
+    f48       Oversample48       AudioUnit48;
+    [ f64 ]   [ Oversample64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ Oversample32 ]   [ AudioUnit32 ];
+)]
+#[derive(Clone)]
+pub struct Oversample48 {
+    unit: Box<dyn AudioUnit48>,
+    factor: usize,
+    sample_rate: f64,
+    /// Lowpass filter kernel at the oversampled rate.
+    kernel: Vec<f48>,
+    /// Input history at the outer rate, one ring buffer per input.
+    input: Vec<Vec<f48>>,
+    /// Output history at the oversampled rate, one ring buffer per output.
+    output: Vec<Vec<f48>>,
+    input_mask: usize,
+    output_mask: usize,
+    /// Position of the latest input sample.
+    input_index: usize,
+    /// Position of the latest oversampled output sample.
+    output_index: usize,
+    /// Oversampled blocks for the contained unit.
+    inner_input: Buffer<f48>,
+    inner_output: Buffer<f48>,
+    tick_input: Vec<f48>,
+    tick_output: Vec<f48>,
+}
+
+#[allow(clippy::unnecessary_cast)]
+#[duplicate_item(
+    f48       Oversample48       AudioUnit48;
+    [ f64 ]   [ Oversample64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ Oversample32 ]   [ AudioUnit32 ];
+)]
+impl Oversample48 {
+    /// Create new wrapper that oversamples `unit` by `factor`, which is typically 2 or 4.
+    pub fn new(factor: usize, mut unit: Box<dyn AudioUnit48>) -> Self {
+        assert!((2..=16).contains(&factor));
+        unit.set_sample_rate(DEFAULT_SR * factor as f64);
+        let inputs = unit.inputs();
+        let outputs = unit.outputs();
+        let kernel: Vec<f48> = oversample_kernel(factor)
+            .iter()
+            .map(|x| *x as f48)
+            .collect();
+        let input_length = (kernel.len() / factor + 1).next_power_of_two();
+        let output_length = (kernel.len() + factor).next_power_of_two();
+        Self {
+            unit,
+            factor,
+            sample_rate: DEFAULT_SR,
+            kernel,
+            input: vec![Vec::new(); inputs],
+            output: vec![Vec::new(); outputs],
+            input_mask: input_length - 1,
+            output_mask: output_length - 1,
+            input_index: 0,
+            output_index: 0,
+            inner_input: Buffer::new(),
+            inner_output: Buffer::new(),
+            tick_input: vec![0.0; inputs],
+            tick_output: vec![0.0; outputs],
+        }
+    }
+
+    /// Oversampling factor.
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+
+    /// Access the contained unit.
+    pub fn unit(&self) -> &dyn AudioUnit48 {
+        &*self.unit
+    }
+
+    /// Access the contained unit mutably.
+    pub fn unit_mut(&mut self) -> &mut dyn AudioUnit48 {
+        &mut *self.unit
+    }
+
+    /// Whether the filter buffers have been allocated.
+    fn has_buffers(&self) -> bool {
+        self.input.iter().all(|v| v.len() == self.input_mask + 1)
+            && self.output.iter().all(|v| v.len() == self.output_mask + 1)
+            && self.inner_input.channels() == self.input.len()
+            && self.inner_output.channels() == self.output.len()
+    }
+
+    /// Allocate filter buffers if needed.
+    fn allocate_buffers(&mut self) {
+        if !self.has_buffers() {
+            for buffer in self.input.iter_mut() {
+                buffer.resize(self.input_mask + 1, 0.0);
+            }
+            for buffer in self.output.iter_mut() {
+                buffer.resize(self.output_mask + 1, 0.0);
+            }
+            self.inner_input.resize(self.input.len());
+            self.inner_output.resize(self.output.len());
+        }
+    }
+
+    /// Store the next input frame, obtained from `input`.
+    #[inline]
+    fn push_input(&mut self, input: impl Fn(usize) -> f48) {
+        self.input_index = (self.input_index + 1) & self.input_mask;
+        for (channel, buffer) in self.input.iter_mut().enumerate() {
+            buffer[self.input_index] = input(channel);
+        }
+    }
+
+    /// Interpolated input of `channel` at oversampled `phase` of the latest input sample.
+    #[inline]
+    fn interpolate(&self, channel: usize, phase: usize) -> f48 {
+        let buffer = &self.input[channel];
+        let mut x = 0.0;
+        for (j, k) in (phase..self.kernel.len()).step_by(self.factor).enumerate() {
+            x += self.kernel[k] * buffer[self.input_index.wrapping_sub(j) & self.input_mask];
+        }
+        // Compensate for the zeros inserted between input samples.
+        x * self.factor as f48
+    }
+
+    /// Store the next oversampled output frame, obtained from `output`.
+    #[inline]
+    fn push_output(&mut self, output: impl Fn(usize) -> f48) {
+        self.output_index = (self.output_index + 1) & self.output_mask;
+        for (channel, buffer) in self.output.iter_mut().enumerate() {
+            buffer[self.output_index] = output(channel);
+        }
+    }
+
+    /// Decimated output of `channel`, aligned with the first phase of the latest outer sample.
+    #[inline]
+    fn decimate(&self, channel: usize) -> f48 {
+        let buffer = &self.output[channel];
+        let start = self.output_index.wrapping_sub(self.factor - 1);
+        let mut y = 0.0;
+        for (k, w) in self.kernel.iter().enumerate() {
+            y += *w * buffer[start.wrapping_sub(k) & self.output_mask];
+        }
+        y
+    }
+}
+
+#[allow(clippy::unnecessary_cast)]
+#[duplicate_item(
+    f48       Oversample48       AudioUnit48;
+    [ f64 ]   [ Oversample64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ Oversample32 ]   [ AudioUnit32 ];
+)]
+impl AudioUnit48 for Oversample48 {
+    fn reset(&mut self) {
+        self.unit.reset();
+        for buffer in self.input.iter_mut().chain(self.output.iter_mut()) {
+            buffer.fill(0.0);
+        }
+        self.input_index = 0;
+        self.output_index = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        self.unit.set_sample_rate(sample_rate * self.factor as f64);
+    }
+
+    fn tick(&mut self, input: &[f48], output: &mut [f48]) {
+        self.allocate_buffers();
+        self.push_input(|channel| input[channel]);
+        let mut tick_input = std::mem::take(&mut self.tick_input);
+        let mut tick_output = std::mem::take(&mut self.tick_output);
+        for phase in 0..self.factor {
+            for (channel, x) in tick_input.iter_mut().enumerate() {
+                *x = self.interpolate(channel, phase);
+            }
+            self.unit.tick(&tick_input, &mut tick_output);
+            self.push_output(|channel| tick_output[channel]);
+        }
+        for (channel, y) in output.iter_mut().enumerate() {
+            *y = self.decimate(channel);
+        }
+        self.tick_input = tick_input;
+        self.tick_output = tick_output;
+    }
+
+    fn process(&mut self, size: usize, input: &[&[f48]], output: &mut [&mut [f48]]) {
+        self.allocate_buffers();
+        let inputs = self.input.len();
+        let outputs = self.output.len();
+        let block = MAX_BUFFER_SIZE / self.factor;
+        let mut i = 0;
+        while i < size {
+            let n = min(size - i, block);
+            for j in 0..n {
+                self.push_input(|channel| input[channel][i + j]);
+                for phase in 0..self.factor {
+                    for channel in 0..inputs {
+                        let x = self.interpolate(channel, phase);
+                        self.inner_input.mut_at(channel)[j * self.factor + phase] = x;
+                    }
+                }
+            }
+            self.unit.process(
+                n * self.factor,
+                self.inner_input.get_ref(inputs),
+                self.inner_output.get_mut(outputs),
+            );
+            for j in 0..n {
+                for phase in 0..self.factor {
+                    let index = j * self.factor + phase;
+                    self.output_index = (self.output_index + 1) & self.output_mask;
+                    for channel in 0..outputs {
+                        self.output[channel][self.output_index] =
+                            self.inner_output.at(channel)[index];
+                    }
+                }
+                for channel in 0..outputs {
+                    output[channel][i + j] = self.decimate(channel);
+                }
+            }
+            i += n;
+        }
+    }
+
+    fn inputs(&self) -> usize {
+        self.input.len()
+    }
+
+    fn outputs(&self) -> usize {
+        self.output.len()
+    }
+
+    fn get_id(&self) -> u64 {
+        const ID: u64 = 125;
+        ID
+    }
+
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.unit.ping(probe, hash.hash(self.get_id()))
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        // Latencies are converted between the rates.
+        let factor = self.factor as f64;
+        let mut inner_input = new_signal_frame(self.inputs());
+        for i in 0..self.inputs() {
+            inner_input[i] = match input[i] {
+                Signal::Latency(l) => Signal::Latency(l * factor),
+                Signal::Response(r, l) => Signal::Response(r, l * factor),
+                x => x,
+            };
+        }
+        let inner_output = self.unit.route(&inner_input, frequency);
+        // The response of interpolation and decimation filters, including their delay.
+        let z1 = Complex64::from_polar(1.0, -TAU * frequency / (self.sample_rate * factor));
+        let mut z = Complex64::new(1.0, 0.0);
+        let mut filter = Complex64::new(0.0, 0.0);
+        for w in self.kernel.iter() {
+            filter += *w as f64 * z;
+            z *= z1;
+        }
+        let filter = filter * filter;
+        let delay = OVERSAMPLE_TAPS as f64;
+        let mut output = new_signal_frame(self.outputs());
+        for i in 0..self.outputs() {
+            output[i] = match inner_output[i] {
+                Signal::Latency(l) => Signal::Latency(l / factor + delay),
+                Signal::Response(r, l) => Signal::Response(r * filter, l / factor + delay),
+                x => x,
+            };
+        }
+        output
+    }
+
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn total_footprint(&self) -> usize {
+        let buffers = self.input.iter().chain(self.output.iter());
+        std::mem::size_of::<Self>()
+            + self.unit.total_footprint()
+            + buffers.map(|v| v.capacity()).sum::<usize>() * std::mem::size_of::<f48>()
+            + self.kernel.capacity() * std::mem::size_of::<f48>()
+            + self.inner_input.footprint()
+            + self.inner_output.footprint()
+    }
+
+    fn allocate(&mut self) {
+        self.allocate_buffers();
+        self.unit.allocate();
+    }
+
+    fn is_allocated(&self) -> bool {
+        self.has_buffers() && self.unit.is_allocated()
+    }
 }
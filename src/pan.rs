@@ -4,6 +4,7 @@ use super::audionode::*;
 use super::math::*;
 use super::signal::*;
 use super::*;
+use num_complex::Complex64;
 use numeric_array::*;
 use std::marker::PhantomData;
 
@@ -14,7 +15,40 @@ fn pan_weights<T: Real>(value: T) -> (T, T) {
     (cos(angle), sin(angle))
 }
 
-/// Mono-to-stereo equal power panner. Number of inputs is `N`, either 1 or 2.
+/// Panning law: how the level of a source is split between channels.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PanLaw {
+    /// Weights sum to one. The center is attenuated by -6 dB.
+    Linear,
+    /// Squared weights sum to one. The center is attenuated by -3 dB.
+    #[default]
+    ConstantPower,
+    /// Geometric mean of the linear and constant power laws.
+    /// The center is attenuated by -4.5 dB.
+    Compromise,
+}
+
+impl PanLaw {
+    /// Left and right weights for pan value in -1...1.
+    #[inline]
+    pub fn weights<T: Real>(&self, value: T) -> (T, T) {
+        match self {
+            PanLaw::Linear => {
+                let right = (clamp11(value) + T::one()) * T::from_f64(0.5);
+                (T::one() - right, right)
+            }
+            PanLaw::ConstantPower => pan_weights(value),
+            PanLaw::Compromise => {
+                let (left, right) = PanLaw::Linear.weights(value);
+                let (left_power, right_power) = pan_weights(value);
+                (sqrt(left * left_power), sqrt(right * right_power))
+            }
+        }
+    }
+}
+
+/// Mono-to-stereo panner. Number of inputs is `N`, either 1 or 2.
+/// The panning law is equal power by default.
 /// Setting: pan value.
 /// Input 0: mono audio
 /// Input 1 (optional): pan value in -1...1
@@ -23,22 +57,44 @@ fn pan_weights<T: Real>(value: T) -> (T, T) {
 #[derive(Clone)]
 pub struct Panner<T: Real, N: Size<T>> {
     _marker: PhantomData<(T, N)>,
+    law: PanLaw,
+    value: T,
     left_weight: T,
     right_weight: T,
 }
 
 impl<T: Real, N: Size<T>> Panner<T, N> {
     pub fn new(value: T) -> Self {
-        let (left_weight, right_weight) = pan_weights(value);
+        Self::with_law(PanLaw::ConstantPower, value)
+    }
+
+    /// Create new panner with panning `law`.
+    pub fn with_law(law: PanLaw, value: T) -> Self {
+        let (left_weight, right_weight) = law.weights(value);
         Self {
             _marker: PhantomData::default(),
+            law,
+            value,
             left_weight,
             right_weight,
         }
     }
+
+    /// Panning law.
+    pub fn law(&self) -> PanLaw {
+        self.law
+    }
+
+    /// Set panning law.
+    pub fn set_law(&mut self, law: PanLaw) {
+        self.law = law;
+        self.set_pan(self.value);
+    }
+
     #[inline]
     pub fn set_pan(&mut self, value: T) {
-        let (left_weight, right_weight) = pan_weights(value);
+        self.value = value;
+        let (left_weight, right_weight) = self.law.weights(value);
         self.left_weight = left_weight;
         self.right_weight = right_weight;
     }
@@ -77,10 +133,7 @@ impl<T: Real, N: Size<T>> AudioNode for Panner<T, N> {
         output[1][..size].clone_from_slice(&input[0][..size]);
         for i in 0..size {
             if N::USIZE > 1 {
-                let value = input[1][i];
-                let (left_weight, right_weight) = pan_weights(value);
-                self.left_weight = left_weight;
-                self.right_weight = right_weight;
+                self.set_pan(input[1][i]);
             }
             output[0][i] *= self.left_weight;
             output[1][i] *= self.right_weight;
@@ -95,3 +148,738 @@ impl<T: Real, N: Size<T>> AudioNode for Panner<T, N> {
         output
     }
 }
+
+/// Head radius in meters used for interaural time differences.
+const HEAD_RADIUS: f64 = 0.0875;
+
+/// Speed of sound in meters per second.
+const SPEED_OF_SOUND: f64 = 343.0;
+
+/// Distance in meters at which the spatial panner has unity gain.
+const REFERENCE_DISTANCE: f64 = 1.0;
+
+/// Lightweight mono-to-stereo spatializer. This is an approximation, not an HRTF.
+/// Azimuth is in radians: 0 is straight ahead, positive angles are to the right
+/// and `PI / 2` is hard right. Sources behind the listener mirror those in front.
+/// Azimuth sets equal power level differences and, optionally,
+/// interaural time differences from a spherical head model.
+/// Distance (in meters) attenuates the signal by the inverse distance law
+/// beyond a reference distance of 1 meter, and far sources are gently lowpassed.
+/// Number of inputs is `N`, either 1 or 3.
+/// Setting: (azimuth, distance).
+/// Input 0: mono audio
+/// Input 1 (optional): azimuth in radians
+/// Input 2 (optional): distance in meters
+/// Output 0: left output
+/// Output 1: right output
+#[derive(Clone)]
+pub struct SpatialPanner<T: Real, N: Size<T>> {
+    _marker: PhantomData<N>,
+    azimuth: T,
+    distance: T,
+    left_weight: T,
+    right_weight: T,
+    /// Interaural time difference in samples. Positive when the source is on the right.
+    itd: T,
+    /// Whether interaural time differences are applied.
+    itd_enabled: bool,
+    /// Air absorption lowpass coefficient.
+    coeff: T,
+    /// Air absorption lowpass state.
+    value: T,
+    /// Ring buffer holding the lowpassed signal for the far ear.
+    buffer: Vec<T>,
+    index: usize,
+    sample_rate: f64,
+}
+
+impl<T: Real, N: Size<T>> SpatialPanner<T, N> {
+    /// Create new spatial panner at `azimuth` radians and `distance` meters.
+    /// Interaural time differences are enabled.
+    pub fn new(sample_rate: f64, azimuth: T, distance: T) -> Self {
+        let mut node = Self {
+            _marker: PhantomData::default(),
+            azimuth,
+            distance,
+            left_weight: T::zero(),
+            right_weight: T::zero(),
+            itd: T::zero(),
+            itd_enabled: true,
+            coeff: T::zero(),
+            value: T::zero(),
+            buffer: Vec::new(),
+            index: 0,
+            sample_rate,
+        };
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Azimuth in radians.
+    pub fn azimuth(&self) -> T {
+        self.azimuth
+    }
+
+    /// Distance in meters.
+    pub fn distance(&self) -> T {
+        self.distance
+    }
+
+    /// Enable or disable interaural time differences.
+    pub fn set_itd(&mut self, enabled: bool) {
+        self.itd_enabled = enabled;
+    }
+
+    /// Set source position: `azimuth` in radians and `distance` in meters.
+    pub fn set_position(&mut self, azimuth: T, distance: T) {
+        self.azimuth = azimuth;
+        self.distance = distance;
+        let lateral = clamp11(sin(azimuth.to_f64()));
+        let distance = max(distance.to_f64(), REFERENCE_DISTANCE);
+        let gain = REFERENCE_DISTANCE / distance;
+        let (left_weight, right_weight) = pan_weights(lateral);
+        self.left_weight = T::from_f64(left_weight * gain);
+        self.right_weight = T::from_f64(right_weight * gain);
+        // Woodworth's formula for a spherical head.
+        let angle = lateral.asin();
+        self.itd =
+            T::from_f64(HEAD_RADIUS / SPEED_OF_SOUND * (angle + sin(angle)) * self.sample_rate);
+        // Air absorption: the cutoff falls from 20 kHz at the reference distance,
+        // leveling off at 2 kHz for distant sources.
+        let cutoff = max(20_000.0 * REFERENCE_DISTANCE / distance, 2_000.0);
+        self.coeff = T::from_f64(exp(
+            -TAU * min(cutoff, 0.45 * self.sample_rate) / self.sample_rate
+        ));
+    }
+
+    /// Interaural time difference in samples.
+    /// Positive when the right ear leads.
+    pub fn itd(&self) -> T {
+        self.itd
+    }
+
+    #[inline]
+    fn delayed(&self, samples: T) -> T {
+        let length = self.buffer.len();
+        let delay = samples.to_f64();
+        let whole = delay as usize;
+        let fraction = T::from_f64(delay - whole as f64);
+        let i0 = (self.index + length - whole) % length;
+        let i1 = (i0 + length - 1) % length;
+        self.buffer[i0] + (self.buffer[i1] - self.buffer[i0]) * fraction
+    }
+}
+
+impl<T: Real, N: Size<T>> AudioNode for SpatialPanner<T, N> {
+    const ID: u64 = 95;
+    type Sample = T;
+    type Inputs = N;
+    type Outputs = typenum::U2;
+    type Setting = (T, T);
+
+    fn set(&mut self, (azimuth, distance): Self::Setting) {
+        self.set_position(azimuth, distance);
+    }
+
+    fn reset(&mut self) {
+        self.value = T::zero();
+        self.buffer.fill(T::zero());
+        self.index = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate;
+        let max_itd = HEAD_RADIUS / SPEED_OF_SOUND * (PI * 0.5 + 1.0) * sample_rate;
+        self.buffer = vec![T::zero(); max_itd.ceil() as usize + 2];
+        self.index = 0;
+        self.set_position(self.azimuth, self.distance);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        if N::USIZE > 1 && (input[1] != self.azimuth || input[2] != self.distance) {
+            self.set_position(input[1], input[2]);
+        }
+        self.value = (T::one() - self.coeff) * input[0] + self.coeff * self.value;
+        self.index = (self.index + 1) % self.buffer.len();
+        self.buffer[self.index] = self.value;
+        let (left, right) = if !self.itd_enabled || self.itd == T::zero() {
+            (self.value, self.value)
+        } else if self.itd > T::zero() {
+            (self.delayed(self.itd), self.value)
+        } else {
+            (self.value, self.delayed(-self.itd))
+        };
+        [self.left_weight * left, self.right_weight * right].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        // Pretend the position is constant.
+        let c = self.coeff.to_f64();
+        let z1 = Complex64::from_polar(1.0, -TAU * frequency / self.sample_rate);
+        let lowpass = (1.0 - c) / (1.0 - c * z1);
+        let itd = if self.itd_enabled {
+            self.itd.to_f64()
+        } else {
+            0.0
+        };
+        let (left_delay, right_delay) = (max(itd, 0.0), max(-itd, 0.0));
+        let weights = [
+            (self.left_weight.to_f64(), left_delay),
+            (self.right_weight.to_f64(), right_delay),
+        ];
+        for (channel, (weight, delay)) in weights.into_iter().enumerate() {
+            // The delay is linearly interpolated between whole samples.
+            let whole = floor(delay);
+            let fraction = delay - whole;
+            let interpolated =
+                Complex64::from_polar(1.0, -TAU * frequency * whole / self.sample_rate)
+                    * (1.0 - fraction + fraction * z1);
+            output[channel] = input[0].filter(delay, |r| r * lowpass * weight * interpolated);
+        }
+        output
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.buffer.capacity() * std::mem::size_of::<T>()
+    }
+}
+
+/// Smoothing time constant in seconds for master gain changes.
+const MASTER_SMOOTHING: f64 = 0.005;
+
+/// Stereo master section with output gain, balance and width.
+/// Gain changes are smoothed to avoid clicks.
+/// Balance in -1...1 attenuates the opposite channel: -1 is fully left and 1 is fully right.
+/// Width scales the side signal: 0 is mono, 1 is neutral and values above 1 widen the image.
+/// Setting: (gain, balance, width).
+/// Input 0: left input
+/// Input 1: right input
+/// Output 0: left output
+/// Output 1: right output
+#[derive(Clone)]
+pub struct Master<T: Real> {
+    gain: T,
+    balance: T,
+    width: T,
+    /// Current smoothed gain.
+    current: T,
+    /// Per-sample smoothing coefficient.
+    coeff: T,
+}
+
+impl<T: Real> Master<T> {
+    /// Create new master section with `gain` (in amplitude), `balance` and `width`.
+    pub fn new(sample_rate: f64, gain: T, balance: T, width: T) -> Self {
+        assert!(width >= T::zero());
+        let mut node = Self {
+            gain,
+            balance: clamp11(balance),
+            width,
+            current: gain,
+            coeff: T::zero(),
+        };
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Output gain in amplitude.
+    pub fn gain(&self) -> T {
+        self.gain
+    }
+
+    /// Set output gain in amplitude. The change is smoothed.
+    pub fn set_gain(&mut self, gain: T) {
+        self.gain = gain;
+    }
+
+    /// Balance in -1...1.
+    pub fn balance(&self) -> T {
+        self.balance
+    }
+
+    /// Set balance in -1...1.
+    pub fn set_balance(&mut self, balance: T) {
+        self.balance = clamp11(balance);
+    }
+
+    /// Stereo width. 1 is neutral.
+    pub fn width(&self) -> T {
+        self.width
+    }
+
+    /// Set stereo width (`width` >= 0). 0 is mono and 1 is neutral.
+    pub fn set_width(&mut self, width: T) {
+        assert!(width >= T::zero());
+        self.width = width;
+    }
+
+    /// Mixing weights (left from left, left from right, right from left, right from right)
+    /// without gain.
+    #[inline]
+    fn weights(&self) -> (T, T, T, T) {
+        let half = T::from_f64(0.5);
+        let direct = half * (T::one() + self.width);
+        let cross = half * (T::one() - self.width);
+        let left = min(T::one(), T::one() - self.balance);
+        let right = min(T::one(), T::one() + self.balance);
+        (left * direct, left * cross, right * cross, right * direct)
+    }
+}
+
+impl<T: Real> AudioNode for Master<T> {
+    const ID: u64 = 108;
+    type Sample = T;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U2;
+    type Setting = (T, T, T);
+
+    fn set(&mut self, (gain, balance, width): Self::Setting) {
+        self.set_gain(gain);
+        self.set_balance(balance);
+        self.set_width(width);
+    }
+
+    fn reset(&mut self) {
+        self.current = self.gain;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.coeff = T::from_f64(1.0 - exp(-1.0 / (MASTER_SMOOTHING * sample_rate)));
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let (ll, lr, rl, rr) = self.weights();
+        self.current += (self.gain - self.current) * self.coeff;
+        let left = (ll * input[0] + lr * input[1]) * self.current;
+        let right = (rl * input[0] + rr * input[1]) * self.current;
+        [left, right].into()
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        let (ll, lr, rl, rr) = self.weights();
+        let mut current = self.current;
+        for i in 0..size {
+            current += (self.gain - current) * self.coeff;
+            let (x, y) = (input[0][i], input[1][i]);
+            output[0][i] = (ll * x + lr * y) * current;
+            output[1][i] = (rl * x + rr * y) * current;
+        }
+        self.current = current;
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        let (ll, lr, rl, rr) = self.weights();
+        let gain = self.gain.to_f64();
+        let mix = |a: T, b: T| {
+            input[0].scale(a.to_f64() * gain).combine_linear(
+                input[1].scale(b.to_f64() * gain),
+                0.0,
+                |x, y| x + y,
+                |x, y| x + y,
+            )
+        };
+        output[0] = mix(ll, lr);
+        output[1] = mix(rl, rr);
+        output
+    }
+}
+
+/// Mid-side encoder. Converts left and right channels into
+/// mid `M = (L + R) / 2` and side `S = (L - R) / 2` channels.
+/// Input 0: left input
+/// Input 1: right input
+/// Output 0: mid output
+/// Output 1: side output
+#[derive(Clone)]
+pub struct MidSide<T: Real> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: Real> MidSide<T> {
+    /// Create new mid-side encoder.
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Real> Default for MidSide<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Real> AudioNode for MidSide<T> {
+    const ID: u64 = 121;
+    type Sample = T;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U2;
+    type Setting = ();
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let half = T::from_f64(0.5);
+        [(input[0] + input[1]) * half, (input[0] - input[1]) * half].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] =
+            input[0].combine_linear(input[1], 0.0, |x, y| (x + y) * 0.5, |x, y| (x + y) * 0.5);
+        output[1] =
+            input[0].combine_linear(input[1], 0.0, |x, y| (x - y) * 0.5, |x, y| (x - y) * 0.5);
+        output
+    }
+}
+
+/// Mid-side decoder. Converts mid and side channels back into
+/// left `L = M + S` and right `R = M - S` channels. This is the inverse of `MidSide`.
+/// Input 0: mid input
+/// Input 1: side input
+/// Output 0: left output
+/// Output 1: right output
+#[derive(Clone)]
+pub struct SideMid<T: Real> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: Real> SideMid<T> {
+    /// Create new mid-side decoder.
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Real> Default for SideMid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Real> AudioNode for SideMid<T> {
+    const ID: u64 = 122;
+    type Sample = T;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U2;
+    type Setting = ();
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        [input[0] + input[1], input[0] - input[1]].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = input[0].combine_linear(input[1], 0.0, |x, y| x + y, |x, y| x + y);
+        output[1] = input[0].combine_linear(input[1], 0.0, |x, y| x - y, |x, y| x - y);
+        output
+    }
+}
+
+/// Stereo width control. Scales the side channel by `amount` while passing the mid channel.
+/// Width 0 is mono, 1 is an exact identity and values above 1 widen the image.
+/// Setting: width.
+/// Input 0: left input
+/// Input 1: right input
+/// Output 0: left output
+/// Output 1: right output
+#[derive(Clone)]
+pub struct Width<T: Real> {
+    amount: T,
+}
+
+impl<T: Real> Width<T> {
+    /// Create new width control.
+    pub fn new(amount: T) -> Self {
+        Self { amount }
+    }
+
+    /// Stereo width.
+    pub fn amount(&self) -> T {
+        self.amount
+    }
+
+    /// Set stereo width.
+    pub fn set_amount(&mut self, amount: T) {
+        self.amount = amount;
+    }
+}
+
+impl<T: Real> AudioNode for Width<T> {
+    const ID: u64 = 123;
+    type Sample = T;
+    type Inputs = typenum::U2;
+    type Outputs = typenum::U2;
+    type Setting = T;
+
+    fn set(&mut self, setting: Self::Setting) {
+        self.set_amount(setting);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        // Adding the change in side to the inputs keeps width 1 exact.
+        let side = (input[0] - input[1]) * T::from_f64(0.5) * (self.amount - T::one());
+        [input[0] + side, input[1] - side].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        let direct = 0.5 * (1.0 + self.amount.to_f64());
+        let cross = 0.5 * (1.0 - self.amount.to_f64());
+        output[0] = input[0].combine_linear(
+            input[1],
+            0.0,
+            |x, y| x * direct + y * cross,
+            |x, y| x * direct + y * cross,
+        );
+        output[1] = input[0].combine_linear(
+            input[1],
+            0.0,
+            |x, y| x * cross + y * direct,
+            |x, y| x * cross + y * direct,
+        );
+        output
+    }
+}
+
+/// Unit vector pointing at `azimuth` and `elevation` radians in B-format axes:
+/// X points ahead, Y to the left and Z up.
+#[inline]
+fn ambi_direction(azimuth: f64, elevation: f64) -> (f64, f64, f64) {
+    (
+        cos(azimuth) * cos(elevation),
+        -sin(azimuth) * cos(elevation),
+        sin(elevation),
+    )
+}
+
+/// First order ambisonic encoder. Places a mono source on the unit sphere,
+/// producing traditional B-format channels W, X, Y and Z (W is scaled by 1/sqrt(2)).
+/// Azimuth is in radians: 0 is straight ahead and `PI / 2` is hard right,
+/// as in the other panners. Elevation is 0 level and `PI / 2` straight up.
+/// B-format Y points to the left, so sources on the right have negative Y.
+/// Setting: (azimuth, elevation).
+/// Input 0: mono audio
+/// Output 0: W (omnidirectional)
+/// Output 1: X (front-back)
+/// Output 2: Y (left-right)
+/// Output 3: Z (up-down)
+#[derive(Clone)]
+pub struct AmbiEncoder<T: Real> {
+    azimuth: T,
+    elevation: T,
+    weights: Frame<T, typenum::U4>,
+}
+
+impl<T: Real> AmbiEncoder<T> {
+    /// Create new encoder with source at `azimuth` and `elevation` radians.
+    pub fn new(azimuth: T, elevation: T) -> Self {
+        let mut node = Self {
+            azimuth,
+            elevation,
+            weights: Frame::default(),
+        };
+        node.set_position(azimuth, elevation);
+        node
+    }
+
+    /// Azimuth in radians.
+    pub fn azimuth(&self) -> T {
+        self.azimuth
+    }
+
+    /// Elevation in radians.
+    pub fn elevation(&self) -> T {
+        self.elevation
+    }
+
+    /// Set source position in radians.
+    pub fn set_position(&mut self, azimuth: T, elevation: T) {
+        self.azimuth = azimuth;
+        self.elevation = elevation;
+        let (x, y, z) = ambi_direction(azimuth.to_f64(), elevation.to_f64());
+        self.weights = [
+            T::from_f64(sqrt(0.5)),
+            T::from_f64(x),
+            T::from_f64(y),
+            T::from_f64(z),
+        ]
+        .into();
+    }
+
+    /// Encoding gains for the W, X, Y and Z channels.
+    pub fn weights(&self) -> &Frame<T, typenum::U4> {
+        &self.weights
+    }
+}
+
+impl<T: Real> AudioNode for AmbiEncoder<T> {
+    const ID: u64 = 110;
+    type Sample = T;
+    type Inputs = typenum::U1;
+    type Outputs = typenum::U4;
+    type Setting = (T, T);
+
+    fn set(&mut self, (azimuth, elevation): Self::Setting) {
+        self.set_position(azimuth, elevation);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        Frame::generate(|i| self.weights[i] * input[0])
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        for (channel, weight) in output.iter_mut().zip(self.weights.iter()) {
+            for (o, i) in channel[..size].iter_mut().zip(input[0][..size].iter()) {
+                *o = *i * *weight;
+            }
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        for i in 0..4 {
+            output[i] = input[0].scale(self.weights[i].to_f64());
+        }
+        output
+    }
+}
+
+/// First order ambisonic decoder for a layout of `N` speakers.
+/// Decodes traditional B-format (W, X, Y, Z) by projecting the sound field
+/// onto each speaker direction. Layouts where every speaker has zero elevation
+/// are decoded horizontally, ignoring Z. For regular layouts such as a square
+/// or a cube, the speaker gains of an encoded source sum to one.
+/// Input 0: W (omnidirectional)
+/// Input 1: X (front-back)
+/// Input 2: Y (left-right)
+/// Input 3: Z (up-down)
+/// Output(s): `N` speaker signals
+#[derive(Clone)]
+pub struct AmbiDecoder<T: Real, N: Size<T>> {
+    _marker: PhantomData<N>,
+    /// Decoding gains for the W, X, Y and Z channels, one entry per speaker.
+    weights: Vec<[T; 4]>,
+}
+
+impl<T: Real, N: Size<T>> AmbiDecoder<T, N> {
+    /// Create new decoder for speakers at (azimuth, elevation) positions in radians.
+    /// Azimuth and elevation follow the conventions of `AmbiEncoder`.
+    /// The number of positions must equal `N`.
+    pub fn new(layout: &[(T, T)]) -> Self {
+        assert!(layout.len() == N::USIZE);
+        let horizontal = layout.iter().all(|(_, elevation)| *elevation == T::zero());
+        // Velocity gain that restores unit pressure and direction for regular layouts.
+        let order = if horizontal { 2.0 } else { 3.0 };
+        let scale = 1.0 / N::USIZE as f64;
+        let weights = layout
+            .iter()
+            .map(|(azimuth, elevation)| {
+                let (x, y, z) = ambi_direction(azimuth.to_f64(), elevation.to_f64());
+                let z = if horizontal { 0.0 } else { z };
+                [
+                    T::from_f64(scale * sqrt(2.0)),
+                    T::from_f64(scale * order * x),
+                    T::from_f64(scale * order * y),
+                    T::from_f64(scale * order * z),
+                ]
+            })
+            .collect();
+        Self {
+            _marker: PhantomData::default(),
+            weights,
+        }
+    }
+}
+
+impl<T: Real, N: Size<T>> AudioNode for AmbiDecoder<T, N> {
+    const ID: u64 = 111;
+    type Sample = T;
+    type Inputs = typenum::U4;
+    type Outputs = N;
+    type Setting = ();
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        Frame::generate(|i| {
+            let w = &self.weights[i];
+            w[0] * input[0] + w[1] * input[1] + w[2] * input[2] + w[3] * input[3]
+        })
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        for (channel, w) in output.iter_mut().zip(self.weights.iter()) {
+            for i in 0..size {
+                channel[i] = w[0] * input[0][i]
+                    + w[1] * input[1][i]
+                    + w[2] * input[2][i]
+                    + w[3] * input[3][i];
+            }
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        for (i, w) in self.weights.iter().enumerate() {
+            output[i] = input[0].scale(w[0].to_f64());
+            for j in 1..4 {
+                output[i] = output[i].combine_linear(
+                    input[j].scale(w[j].to_f64()),
+                    0.0,
+                    |x, y| x + y,
+                    |x, y| x + y,
+                );
+            }
+        }
+        output
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.weights.capacity() * std::mem::size_of::<[T; 4]>()
+    }
+}
@@ -1,19 +1,23 @@
 //! Generic prelude.
 
+pub use super::arp::*;
 pub use super::audionode::*;
 pub use super::audiounit::*;
+pub use super::binaural::*;
 pub use super::buffer::*;
 pub use super::combinator::*;
 pub use super::delay::*;
 pub use super::dynamics::*;
 pub use super::envelope::*;
 pub use super::feedback::*;
+pub use super::fft::*;
 pub use super::filter::*;
 pub use super::fir::*;
 pub use super::follow::*;
 pub use super::gen::*;
 pub use super::granular::*;
 pub use super::math::*;
+pub use super::mixer::*;
 pub use super::moog::*;
 pub use super::net::*;
 pub use super::noise::*;
@@ -23,6 +27,7 @@ pub use super::pan::*;
 pub use super::realnet::*;
 pub use super::realseq::*;
 pub use super::resample::*;
+pub use super::reverb::*;
 pub use super::rez::*;
 pub use super::sequencer::*;
 pub use super::setting::*;
@@ -299,6 +304,27 @@ pub fn meter<T: Real>(meter: Meter) -> An<MeterNode<T>> {
     An(MeterNode::new(meter))
 }
 
+/// Over counter. Passes through input and counts samples whose absolute value
+/// exceeds amplitude `threshold`. Returns (frontend, backend).
+/// The frontend reports the over count and the time since the last over.
+/// - Input 0: signal
+/// - Output 0: signal passed through
+///
+/// ### Example: Count Overs At 0 dBFS
+/// ```
+/// use fundsp::prelude::*;
+/// let (overs, backend) = overs::<f64>(1.0);
+/// let mut node = sine_hz::<f64>(110.0) * 1.5 >> backend;
+/// for _ in 0..1000 {
+///     node.get_mono();
+/// }
+/// assert!(overs.count() > 0);
+/// ```
+pub fn overs<T: Float>(threshold: T) -> (OversMeter, An<Overs<T>>) {
+    let (meter, backend) = Overs::new(DEFAULT_SR, threshold);
+    (meter, An(backend))
+}
+
 /// Mono sink. Input is discarded.
 /// -Input 0: signal
 pub fn sink<T: Float>() -> An<Sink<U1, T>> {
@@ -349,6 +375,101 @@ pub fn sine_hz<T: Real>(f: T) -> An<Pipe<T, Constant<U1, T>, Sine<T>>> {
     constant(f) >> sine()
 }
 
+/// Unison of `N` copies of oscillator `voice` detuned by up to `spread` semitones.
+/// Detunes and initial phases are derived from the node hash,
+/// so identically seeded graphs sound the same.
+/// Output is normalized by the square root of `N`.
+/// - Input 0: frequency (Hz)
+/// - Output 0: unison
+///
+/// ### Example: Detuned Sine Pad
+/// ```
+/// use fundsp::prelude::*;
+/// dc(220.0) >> unison::<U7, f64, _>(0.15, sine());
+/// ```
+pub fn unison<N, T, X>(spread: T, voice: An<X>) -> An<Unison<N, T, X>>
+where
+    N: Size<T>,
+    T: Real,
+    X: AudioNode<Sample = T, Inputs = U1, Outputs = U1>,
+{
+    An(Unison::new(spread, voice.0))
+}
+
+/// FM operator: sine oscillator at `ratio` times the pitch input
+/// with its phase modulated by the modulation input scaled by `index` (in radians).
+/// - Input 0: pitch in Hz
+/// - Input 1: modulation signal
+/// - Output 0: operator output
+///
+/// ### Example: Two Operator Stack
+/// ```
+/// use fundsp::prelude::*;
+/// let pitch = 220.0;
+/// (dc(pitch) | (dc(pitch) | zero()) >> fm_op::<f64>(2.0, 0.0)) >> fm_op(1.0, 2.0);
+/// ```
+pub fn fm_op<T: Real>(ratio: T, index: T) -> An<FmOp<T>> {
+    An(FmOp::new(DEFAULT_SR, ratio, index))
+}
+
+/// Two operator FM voice. The modulator runs at `modulator_ratio` and
+/// the carrier at `carrier_ratio` times the pitch input.
+/// The modulator drives the carrier phase with modulation `index`
+/// and itself with `feedback`, both in radians.
+/// - Input 0: pitch in Hz
+/// - Output 0: FM voice
+///
+/// ### Example: Electric Piano
+/// ```
+/// use fundsp::prelude::*;
+/// dc(440.0) >> fm2::<f64>(1.0, 14.0, 0.8, 0.0);
+/// ```
+pub fn fm2<T: Real>(carrier_ratio: T, modulator_ratio: T, index: T, feedback: T) -> An<Fm2<T>> {
+    An(Fm2::new(
+        DEFAULT_SR,
+        carrier_ratio,
+        modulator_ratio,
+        index,
+        feedback,
+    ))
+}
+
+/// Phase distortion oscillator. The phase ramp of a sine is bent at a breakpoint
+/// that moves towards the start of the cycle as distortion increases.
+/// - Input 0: frequency in Hz
+/// - Input 1: distortion amount in 0...1 (0 = pure sine)
+/// - Output 0: phase distorted wave
+///
+/// ### Example: Enveloped Phase Distortion
+/// ```
+/// use fundsp::prelude::*;
+/// (dc(110.0) | lfo(|t: f64| exp(-t))) >> phase_distort::<f64>();
+/// ```
+pub fn phase_distort<T: Real>() -> An<PhaseDistort<T>> {
+    An(PhaseDistort::new(DEFAULT_SR))
+}
+
+/// Fixed phase distortion oscillator at `f` Hz with distortion `amount` in 0...1.
+/// - Output 0: phase distorted wave
+pub fn phase_distort_hz<T: Real>(f: T, amount: T) -> An<Pipe<T, Constant<U2, T>, PhaseDistort<T>>> {
+    constant((f, amount)) >> phase_distort()
+}
+
+/// Vocal synthesizer: a glottal pulse source filtered by a bank of formant resonators.
+/// Vowel is continuous in 0...4, morphing through "a", "e", "i", "o" and "u".
+/// - Input 0: pitch in Hz
+/// - Input 1: vowel in 0...4
+/// - Output 0: voice
+///
+/// ### Example: Vowel Glide
+/// ```
+/// use fundsp::prelude::*;
+/// (dc(110.0) | lfo(|t: f64| min(t, 4.0))) >> voice::<f64>();
+/// ```
+pub fn voice<T: Real>() -> An<Voice<T>> {
+    An(Voice::new(DEFAULT_SR, T::zero()))
+}
+
 /// Rossler dynamical system oscillator.
 /// - Input 0: frequency. The Rossler oscillator exhibits peaks at multiples of this frequency.
 /// - Output 0: system output
@@ -456,6 +577,43 @@ pub fn butterpass_hz<T: Float, F: Real>(f: T) -> An<ButterLowpass<T, F, U1>> {
     An(ButterLowpass::new(convert(f)))
 }
 
+/// Butterworth highpass filter (2nd order).
+/// - Input 0: audio
+/// - Input 1: cutoff frequency (Hz)
+/// - Output 0: filtered audio
+///
+/// ### Example: Filtered Noise
+/// ```
+/// use fundsp::prelude::*;
+/// (noise() | dc(1000.0)) >> butterhighpass::<f32, f32>();
+/// ```
+pub fn butterhighpass<T: Float, F: Real>() -> An<ButterHighpass<T, F, U2>> {
+    An(ButterHighpass::new(F::new(440)))
+}
+
+/// Butterworth highpass filter (2nd order) with fixed cutoff frequency `f` Hz.
+/// - Input 0: audio
+/// - Output 0: filtered audio
+pub fn butterhighpass_hz<T: Float, F: Real>(f: T) -> An<ButterHighpass<T, F, U1>> {
+    An(ButterHighpass::new(convert(f)))
+}
+
+/// 4th order Linkwitz-Riley crossover at `frequency` Hz.
+/// The sum of the two bands has a flat magnitude response.
+/// Setting: crossover frequency.
+/// - Input 0: audio
+/// - Output 0: low band
+/// - Output 1: high band
+///
+/// ### Example: Two-Band Distortion
+/// ```
+/// use fundsp::prelude::*;
+/// crossover2::<f64, f64>(200.0) >> (pass() + shape(Shape::Tanh(2.0)));
+/// ```
+pub fn crossover2<T: Float, F: Real>(frequency: T) -> An<Crossover2<T, F>> {
+    An(Crossover2::new(convert(frequency)))
+}
+
 /// One-pole lowpass filter (1st order).
 /// - Input 0: audio
 /// - Input 1: cutoff frequency (Hz)
@@ -483,6 +641,52 @@ pub fn lowpole_hz<T: Float, F: Real>(f: T) -> An<Lowpole<T, F, U1>> {
     An(Lowpole::new(convert(f)))
 }
 
+/// Leaky integrator with time constant `time` seconds.
+/// The DC gain equals the time constant.
+/// - Input 0: audio
+/// - Output 0: integrated audio
+///
+/// ### Example: Brownian Motion
+/// ```
+/// use fundsp::prelude::*;
+/// noise() >> leaky_integrator::<f64, f64>(1.0);
+/// ```
+pub fn leaky_integrator<T: Float, F: Real>(time: T) -> An<LeakyIntegrator<T, F, U1>> {
+    An(LeakyIntegrator::new(convert(time), false))
+}
+
+/// Saturating leaky integrator with time constant `time` seconds.
+/// The integrator state passes through `tanh`, so it stays in -1...1.
+/// - Input 0: audio
+/// - Output 0: integrated audio
+pub fn leaky_integrator_tanh<T: Float, F: Real>(time: T) -> An<LeakyIntegrator<T, F, U1>> {
+    An(LeakyIntegrator::new(convert(time), true))
+}
+
+/// Leaky integrator with a time constant input.
+/// The DC gain equals the time constant.
+/// - Input 0: audio
+/// - Input 1: time constant (seconds)
+/// - Output 0: integrated audio
+///
+/// ### Example: Modulated Integrator
+/// ```
+/// use fundsp::prelude::*;
+/// (noise() | lfo(|t| xerp11(0.1, 1.0, sin_hz(0.1, t)))) >> leaky_integrator_var::<f64, f64>();
+/// ```
+pub fn leaky_integrator_var<T: Float, F: Real>() -> An<LeakyIntegrator<T, F, U2>> {
+    An(LeakyIntegrator::new(F::one(), false))
+}
+
+/// Saturating leaky integrator with a time constant input.
+/// The integrator state passes through `tanh`, so it stays in -1...1.
+/// - Input 0: audio
+/// - Input 1: time constant (seconds)
+/// - Output 0: integrated audio
+pub fn leaky_integrator_tanh_var<T: Float, F: Real>() -> An<LeakyIntegrator<T, F, U2>> {
+    An(LeakyIntegrator::new(F::one(), true))
+}
+
 /// Allpass filter (1st order) with a configurable delay (delay > 0) in samples at DC.
 /// - Input 0: audio
 /// - Input 1: delay in samples
@@ -498,6 +702,22 @@ pub fn allpole_delay<T: Float, F: Float>(delay: F) -> An<Allpole<T, F, U1>> {
     An(Allpole::new(delay))
 }
 
+/// Dispersion filter with `stiffness` in 0...1: a cascade of allpass filters
+/// that delays low frequencies more than high frequencies.
+/// Each of the 8 stages delays DC by (1 + `stiffness`) / (1 - `stiffness`) samples.
+/// Setting: stiffness.
+/// - Input 0: audio
+/// - Output 0: filtered audio
+///
+/// ### Example: Stiff Plucked String
+/// ```
+/// use fundsp::prelude::*;
+/// pluck::<f64>(220.0, 0.8, 0.5) >> dispersion::<f64>(0.3);
+/// ```
+pub fn dispersion<T: Real>(stiffness: T) -> An<Dispersion<T>> {
+    An(Dispersion::new(stiffness))
+}
+
 /// One-pole, one-zero highpass filter (1st order).
 /// - Input 0: audio
 /// - Input 1: cutoff frequency (Hz)
@@ -541,6 +761,77 @@ pub fn resonator_hz<T: Float, F: Real>(center: T, bandwidth: T) -> An<Resonator<
     An(Resonator::new(convert(center), convert(bandwidth)))
 }
 
+/// Multimode state variable filter with simultaneous lowpass, highpass, bandpass and notch outputs.
+/// The bandpass output has a peak gain of Q.
+/// - Input 0: audio
+/// - Input 1: cutoff frequency (Hz)
+/// - Input 2: Q
+/// - Output 0: lowpass
+/// - Output 1: highpass
+/// - Output 2: bandpass
+/// - Output 3: notch
+///
+/// ### Example: Swept Multimode Filter
+/// ```
+/// use fundsp::prelude::*;
+/// (noise() | lfo(|t: f64| (xerp11(200.0, 2000.0, sin_hz(0.5, t)), 2.0))) >> svf_multi::<f64, f64>();
+/// ```
+pub fn svf_multi<T: Float, F: Real>() -> An<MultiSvf<T, F, U3>> {
+    An(MultiSvf::new(F::new(440), F::one()))
+}
+
+/// Multimode state variable filter with fixed `cutoff` frequency (Hz) and `q`.
+/// The bandpass output has a peak gain of `q`.
+/// Setting: (cutoff, Q).
+/// - Input 0: audio
+/// - Output 0: lowpass
+/// - Output 1: highpass
+/// - Output 2: bandpass
+/// - Output 3: notch
+///
+/// ### Example: Lowpass And Highpass Bands
+/// ```
+/// use fundsp::prelude::*;
+/// noise() >> svf_multi_hz::<f64, f64>(1000.0, 0.7) >> (pass() | pass() | sink() | sink());
+/// ```
+pub fn svf_multi_hz<T: Float, F: Real>(cutoff: T, q: T) -> An<MultiSvf<T, F, U1>> {
+    An(MultiSvf::new(convert(cutoff), convert(q)))
+}
+
+/// Morphing state variable filter with modulated cutoff, Q and morph.
+/// Morph blends lowpass (-1), bandpass (0) and highpass (1) responses.
+/// The bandpass response has a peak gain of Q.
+/// - Input 0: audio
+/// - Input 1: cutoff frequency (Hz)
+/// - Input 2: Q
+/// - Input 3: morph in -1...1
+/// - Output 0: filtered audio
+///
+/// ### Example: Morph Sweep
+/// ```
+/// use fundsp::prelude::*;
+/// (noise() | lfo(|t: f64| (1000.0, 2.0, sin_hz(0.25, t)))) >> svf_morph::<f64, f64>();
+/// ```
+pub fn svf_morph<T: Float, F: Real>() -> An<SvfMorph<T, F, U4>> {
+    An(SvfMorph::new(F::new(440), F::one(), F::zero()))
+}
+
+/// Morphing state variable filter with fixed `cutoff` frequency (Hz), `q` and `morph`.
+/// Morph blends lowpass (-1), bandpass (0) and highpass (1) responses.
+/// Morph changes made via settings are smoothed.
+/// Setting: (cutoff, Q, morph).
+/// - Input 0: audio
+/// - Output 0: filtered audio
+///
+/// ### Example: Lowpass Leaning Filter
+/// ```
+/// use fundsp::prelude::*;
+/// noise() >> svf_morph_hz::<f64, f64>(800.0, 1.0, -0.5);
+/// ```
+pub fn svf_morph_hz<T: Float, F: Real>(cutoff: T, q: T, morph: T) -> An<SvfMorph<T, F, U1>> {
+    An(SvfMorph::new(convert(cutoff), convert(q), convert(morph)))
+}
+
 /// An arbitrary biquad filter with coefficients in normalized form.
 /// - Input 0: signal
 /// - Output 0: filtered signal
@@ -550,6 +841,24 @@ pub fn biquad<T: Float, F: Real>(a1: F, a2: F, b0: F, b1: F, b2: F) -> An<Biquad
     )))
 }
 
+/// Graphic equalizer with `bands` fixed ISO bands: 10 for octave bands
+/// or 31 for third-octave bands. Each band is a peaking biquad.
+/// Band gains start at 0 dB and can be adjusted with `set_gain_db`.
+/// Setting: (band, gain in dB).
+/// - Input 0: signal
+/// - Output 0: equalized signal
+///
+/// ### Example: Boost 1 kHz Octave Band
+/// ```
+/// use fundsp::prelude::*;
+/// let mut eq = graphic_eq::<f64, f64>(10);
+/// eq.set_gain_db(5, 6.0);
+/// assert_eq!(eq.center(5), 1000.0);
+/// ```
+pub fn graphic_eq<T: Float, F: Real>(bands: usize) -> An<GraphicEq<T, F>> {
+    An(GraphicEq::new(bands))
+}
+
 /// Moog resonant lowpass filter.
 /// - Input 0: input signal
 /// - Input 1: cutoff frequency (Hz)
@@ -783,6 +1092,45 @@ where
     An(EnvelopeIn::new(F::from_f64(0.002), DEFAULT_SR, f))
 }
 
+/// Linear ramp from `from` to `to` in `duration` seconds, after which the output holds at `to`.
+/// The ramp starts over when reset.
+/// - Output 0: ramp value
+///
+/// ### Example: Filter Cutoff Sweep
+/// ```
+/// use fundsp::prelude::*;
+/// (noise() | ramp::<f64>(100.0, 10_000.0, 5.0) | constant(1.0)) >> lowpass::<f64, f64>();
+/// ```
+pub fn ramp<T: Float>(from: T, to: T, duration: f64) -> An<Ramp<T>> {
+    An(Ramp::new(DEFAULT_SR, from, to, duration))
+}
+
+/// Unit impulse test signal: 1.0 on the first sample after reset, then zeros.
+/// - Output 0: impulse
+///
+/// ### Example: Impulse Response
+/// ```
+/// use fundsp::prelude::*;
+/// let mut node = impulse::<f64>() >> lowpole_hz::<f64, f64>(1000.0);
+/// let response: Vec<f64> = (0..100).map(|_| node.get_mono()).collect();
+/// ```
+pub fn impulse<T: Float>() -> An<Impulse<T>> {
+    An(Impulse::new())
+}
+
+/// Unit step test signal: 0.0 on the first sample after reset, then 1.0.
+/// - Output 0: step
+///
+/// ### Example: Step Response
+/// ```
+/// use fundsp::prelude::*;
+/// let mut node = step::<f64>() >> lowpole_hz::<f64, f64>(1000.0);
+/// let response: Vec<f64> = (0..100).map(|_| node.get_mono()).collect();
+/// ```
+pub fn step<T: Float>() -> An<Step<T>> {
+    An(Step::new())
+}
+
 /// ADSR envelope.
 ///
 /// When a positive value is given by the input, its output increases from 0.0 to 1.0 in the time
@@ -808,6 +1156,39 @@ where
     super::adsr::adsr_live(attack, decay, sustain, release)
 }
 
+/// Arpeggiator cycling through held notes in `pattern` at `rate` steps per second.
+/// Notes are held and released via the returned frontend as MIDI note numbers.
+/// Returns (frontend, backend).
+/// - Output 0: frequency in Hz, or zero if no notes are held.
+///
+/// ### Example: Arpeggiated C Major Chord
+/// ```
+/// use fundsp::prelude::*;
+/// let (notes, arp) = arpeggiator::<f64>(ArpPattern::UpDown, 8.0);
+/// notes.set_notes(&[60.0, 64.0, 67.0]);
+/// let synth = arp >> saw();
+/// ```
+pub fn arpeggiator<T: Real>(pattern: ArpPattern, rate: f64) -> (ArpNotes, An<Arpeggiator<T>>) {
+    let (notes, backend) = Arpeggiator::new(DEFAULT_SR, pattern, rate);
+    (notes, An(backend))
+}
+
+/// Pitch to frequency converter with A4 (MIDI note 69) at `reference` Hz.
+/// Quantization to semitones and octave stretch can be configured on the node.
+/// - Input 0: pitch in semitones
+/// - Output 0: frequency in Hz
+///
+/// ### Example: Baroque Tuning With Quantized Pitch Bend
+/// ```
+/// use fundsp::prelude::*;
+/// let mut converter = tune::<f64>(415.0);
+/// converter.set_quantize(true);
+/// let synth = (dc(60.0) + lfo(|t: f64| sin_hz(0.5, t))) >> converter >> saw();
+/// ```
+pub fn tune<T: Real>(reference: T) -> An<Tune<T>> {
+    An(Tune::new(reference))
+}
+
 /// Maximum Length Sequence noise generator from an `n`-bit sequence (1 <= `n` <= 31).
 /// - Output 0: repeating white noise sequence of only -1 and 1 values.
 ///
@@ -946,6 +1327,35 @@ pub fn delay<T: Float>(t: f64) -> An<Delay<T>> {
     An(Delay::new(t))
 }
 
+/// Stereo feedback delay with delay times `left_time` and `right_time` in seconds.
+/// Delayed signal feeds back into the same channel with gain `feedback`
+/// and into the opposite channel with gain `cross_feedback`.
+/// Outputs the delayed signal only. Delay times are rounded to the nearest sample.
+/// Allocates: the delay lines.
+/// - Input 0: left signal.
+/// - Input 1: right signal.
+/// - Output 0: left delayed signal.
+/// - Output 1: right delayed signal.
+///
+/// ### Example: Dotted Eighth And Quarter Note Echoes At 120 BPM
+/// ```
+/// use fundsp::prelude::*;
+/// multipass() & 0.3 * stereo_delay::<f64>(0.375, 0.5, 0.4, 0.2);
+/// ```
+pub fn stereo_delay<T: Float>(
+    left_time: f64,
+    right_time: f64,
+    feedback: T,
+    cross_feedback: T,
+) -> An<StereoDelay<T>> {
+    An(StereoDelay::new(
+        left_time,
+        right_time,
+        feedback,
+        cross_feedback,
+    ))
+}
+
 /// Tapped delay line with cubic interpolation.
 /// Minimum and maximum delay times are in seconds.
 /// Allocates: the delay line.
@@ -962,6 +1372,22 @@ pub fn tap<T: Float>(min_delay: T, max_delay: T) -> An<Tap<U1, T>> {
     An(Tap::new(min_delay, max_delay))
 }
 
+/// Variable delay line with cubic Hermite interpolation.
+/// Delay time is clamped between one sample and `max_delay` seconds.
+/// Allocates: the delay line.
+/// - Input 0: signal.
+/// - Input 1: delay time in seconds.
+/// - Output 0: delayed signal.
+///
+/// ### Example: Chorus Voice
+/// ```
+/// use fundsp::prelude::*;
+/// pass::<f64>() & (pass() | lfo(|t| lerp11(0.01, 0.02, sin_hz(0.5, t)))) >> delay_var::<f64>(0.02);
+/// ```
+pub fn delay_var<T: Float>(max_delay: f64) -> An<DelayVar<T>> {
+    An(DelayVar::new(max_delay))
+}
+
 /// Tapped delay line with cubic interpolation.
 /// The number of taps is `N`.
 /// Minimum and maximum delay times are in seconds.
@@ -1075,6 +1501,46 @@ where
     An(Feedback2::new(node.0, loopback.0, FrameId::new()))
 }
 
+/// Flush outputs of `node`, such as a reverb or a feedback delay, to zero below -200 dB,
+/// so a decaying tail ends in silence. Inputs are passed to `node` untouched.
+/// To keep feedback paths out of the denormal range, see `loop_stabilizer`.
+/// - Input(s): input of `node`.
+/// - Output(s): output of `node`.
+///
+/// ### Example: Stabilized Reverb
+/// ```
+/// use fundsp::prelude::*;
+/// stabilize(reverb_stereo::<f64>(10.0, 5.0));
+/// ```
+pub fn stabilize<T, X>(node: An<X>) -> An<Stabilizer<T, X>>
+where
+    T: Float,
+    X: AudioNode<Sample = T>,
+    X::Inputs: Size<T>,
+    X::Outputs: Size<T>,
+{
+    An(Stabilizer::new(node.0))
+}
+
+/// Feedback loop processing that keeps a recursive structure out of the denormal range.
+/// Each channel is DC blocked and receives inaudible decorrelated dither.
+/// Use it as the loopback of `feedback2` or `fdn2` to touch only the fed-back signal.
+/// - Input(s): fed-back signal.
+/// - Output(s): stabilized signal.
+///
+/// ### Example: Stabilized Feedback Delay
+/// ```
+/// use fundsp::prelude::*;
+/// stabilize(pass::<f64>() & feedback2(delay(0.1) * db_amp(-3.0), loop_stabilizer()));
+/// ```
+pub fn loop_stabilizer<N, T>() -> An<LoopStabilizer<N, T>>
+where
+    N: Size<T>,
+    T: Float,
+{
+    An(LoopStabilizer::new())
+}
+
 /// Transform channels freely. Accounted as non-linear processing for signal flow.
 ///
 /// ### Example: Max Operator
@@ -1155,6 +1621,21 @@ pub fn shape<T: Real>(mode: Shape<T>) -> An<Shaper<T>> {
     An(Shaper::new(mode))
 }
 
+/// Shape signal with a per-sample `drive` input using the curve `mode`.
+/// The signal is multiplied by the drive before shaping.
+/// - Input 0: input signal
+/// - Input 1: drive amount (1 = unity)
+/// - Output 0: shaped signal
+///
+/// ### Example: Wavefolder
+/// ```
+/// use fundsp::prelude::*;
+/// (sine_hz::<f64>(110.0) | dc(4.0)) >> shape_drive(DriveShape::Wavefold);
+/// ```
+pub fn shape_drive<T: Real>(mode: DriveShape) -> An<DriveShaper<T>> {
+    An(DriveShaper::new(mode))
+}
+
 /// Clip signal to -1...1.
 /// - Input 0: input signal
 /// - Output 0: clipped signal
@@ -1169,6 +1650,30 @@ pub fn clip_to<T: Real>(minimum: T, maximum: T) -> An<Shaper<T>> {
     An(Shaper::<T>::new(Shape::ClipTo(minimum, maximum)))
 }
 
+/// Hard clip signal to -`threshold`...`threshold` with no lookahead.
+/// Setting: threshold.
+/// - Input 0: input signal
+/// - Output 0: clipped signal
+///
+/// ### Example: Safety Clipper
+/// ```
+/// use fundsp::prelude::*;
+/// let safe = (sine_hz::<f64>(110.0) * 2.0) >> clipper(0.9);
+/// ```
+pub fn clipper<T: Real>(threshold: T) -> An<Clipper<T>> {
+    An(Clipper::new(threshold, T::zero()))
+}
+
+/// Soft clip signal to -`threshold`...`threshold` with no lookahead.
+/// Signals within `threshold` - `knee` pass unchanged;
+/// the knee bends the curve into the threshold over a width of 2 * `knee`
+/// (0 <= `knee` <= `threshold`). Setting: threshold.
+/// - Input 0: input signal
+/// - Output 0: clipped signal
+pub fn clipper_soft<T: Real>(threshold: T, knee: T) -> An<Clipper<T>> {
+    An(Clipper::new(threshold, knee))
+}
+
 /// Equal power mono-to-stereo panner.
 /// - Input 0: input signal
 /// - Input 1: pan in -1...1 (left to right).
@@ -1198,6 +1703,194 @@ pub fn pan<T: Real>(pan: T) -> An<Panner<T, U1>> {
     An(Panner::new(pan))
 }
 
+/// Fixed mono-to-stereo panner with panning `law` and `pan` value in -1...1 (left to right).
+/// `PanLaw::ConstantPower` is the law used by `pan`.
+/// - Input 0: input signal
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example (Linear Panning)
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz::<f64>(440.0) >> pan_law(PanLaw::Linear, 0.5);
+/// ```
+pub fn pan_law<T: Real>(law: PanLaw, pan: T) -> An<Panner<T, U1>> {
+    An(Panner::with_law(law, pan))
+}
+
+/// Mono-to-stereo panner with panning `law`. The pan value is an input.
+/// - Input 0: input signal
+/// - Input 1: pan in -1...1 (left to right).
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example: Panning Noise Linearly
+/// ```
+/// use fundsp::prelude::*;
+/// (noise() | sine_hz(0.5)) >> panner_law::<f64>(PanLaw::Linear);
+/// ```
+pub fn panner_law<T: Real>(law: PanLaw) -> An<Panner<T, U2>> {
+    An(Panner::with_law(law, T::zero()))
+}
+
+/// Mono-to-stereo spatializer with azimuth and distance inputs.
+/// Applies level and time differences for azimuth,
+/// inverse distance attenuation and a gentle lowpass for far sources.
+/// Azimuth is 0 straight ahead and `PI / 2` hard right.
+/// - Input 0: input signal
+/// - Input 1: azimuth in radians
+/// - Input 2: distance in meters
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example: Source Circling The Listener
+/// ```
+/// use fundsp::prelude::*;
+/// (noise() | lfo(|t: f64| t) | dc(2.0)) >> spatial_panner::<f64>();
+/// ```
+pub fn spatial_panner<T: Real>() -> An<SpatialPanner<T, U3>> {
+    An(SpatialPanner::new(DEFAULT_SR, T::zero(), T::one()))
+}
+
+/// Stereo master section with smoothed output `gain` (in amplitude), `balance` in -1...1
+/// and stereo `width` (`width` >= 0). Width 0 is mono, 1 is neutral and above 1 widens.
+/// Balance -1 mutes the right channel and 1 mutes the left channel.
+/// Setting: (gain, balance, width).
+/// - Input 0: left input
+/// - Input 1: right input
+/// - Output 0: left output
+/// - Output 1: right output
+///
+/// ### Example: Narrowed Final Mix
+/// ```
+/// use fundsp::prelude::*;
+/// (saw_hz::<f64>(110.0) | square_hz(110.5)) >> master(db_amp(-3.0), 0.0, 0.5);
+/// ```
+pub fn master<T: Real>(gain: T, balance: T, width: T) -> An<Master<T>> {
+    An(Master::new(DEFAULT_SR, gain, balance, width))
+}
+
+/// Mid-side encoder. Converts left and right channels into
+/// mid `(L + R) / 2` and side `(L - R) / 2` channels.
+/// - Input 0: left input
+/// - Input 1: right input
+/// - Output 0: mid output
+/// - Output 1: side output
+///
+/// ### Example: Processing The Side Channel
+/// ```
+/// use fundsp::prelude::*;
+/// midside::<f64>() >> (pass() | highpass_hz::<f64, f64>(200.0, 0.7)) >> sidemid();
+/// ```
+pub fn midside<T: Real>() -> An<MidSide<T>> {
+    An(MidSide::new())
+}
+
+/// Mid-side decoder. Converts mid and side channels
+/// into left `M + S` and right `M - S` channels. This is the inverse of `midside`.
+/// - Input 0: mid input
+/// - Input 1: side input
+/// - Output 0: left output
+/// - Output 1: right output
+pub fn sidemid<T: Real>() -> An<SideMid<T>> {
+    An(SideMid::new())
+}
+
+/// Stereo width control. Scales the side channel by `amount` while passing the mid channel.
+/// Width 0 is mono, 1 is an exact identity and values above 1 widen the image.
+/// Setting: width.
+/// - Input 0: left input
+/// - Input 1: right input
+/// - Output 0: left output
+/// - Output 1: right output
+///
+/// ### Example: Wider Stereo Image
+/// ```
+/// use fundsp::prelude::*;
+/// (saw_hz::<f64>(110.0) | saw_hz(110.5)) >> width(1.5);
+/// ```
+pub fn width<T: Real>(amount: T) -> An<Width<T>> {
+    An(Width::new(amount))
+}
+
+/// First order ambisonic encoder. Places the input at `azimuth` and `elevation` radians
+/// in a traditional B-format sound field. Azimuth is 0 straight ahead
+/// and `PI / 2` hard right. Elevation is 0 level and `PI / 2` straight up.
+/// Setting: (azimuth, elevation).
+/// - Input 0: input signal
+/// - Output 0: W (omnidirectional, scaled by 1/sqrt(2))
+/// - Output 1: X (front)
+/// - Output 2: Y (left)
+/// - Output 3: Z (up)
+///
+/// ### Example: Noise Front Right
+/// ```
+/// use fundsp::prelude::*;
+/// noise::<f64>() >> ambi_encode(0.25 * PI, 0.0);
+/// ```
+pub fn ambi_encode<T: Real>(azimuth: T, elevation: T) -> An<AmbiEncoder<T>> {
+    An(AmbiEncoder::new(azimuth, elevation))
+}
+
+/// First order ambisonic decoder for `N` speakers at (azimuth, elevation) positions
+/// in `layout`, using the same conventions as `ambi_encode`.
+/// Layouts with all speakers at zero elevation are decoded horizontally.
+/// - Input 0: W
+/// - Input 1: X
+/// - Input 2: Y
+/// - Input 3: Z
+/// - Output(s): `N` speaker signals
+///
+/// ### Example: Square Speaker Layout
+/// ```
+/// use fundsp::prelude::*;
+/// let layout = [(-0.25 * PI, 0.0), (0.25 * PI, 0.0), (0.75 * PI, 0.0), (-0.75 * PI, 0.0)];
+/// noise::<f64>() >> ambi_encode(0.25 * PI, 0.0) >> ambi_decode::<f64, U4>(&layout);
+/// ```
+pub fn ambi_decode<T: Real, N: Size<T>>(layout: &[(T, T)]) -> An<AmbiDecoder<T, N>> {
+    An(AmbiDecoder::new(layout))
+}
+
+/// Binaural spatializer using the built-in spherical head HRIR set.
+/// Source is at `azimuth` and `elevation` radians. Azimuth is 0 straight ahead
+/// and `PI / 2` hard right. Elevation is 0 level and `PI / 2` straight up.
+/// Setting: (azimuth, elevation).
+/// - Input 0: input signal
+/// - Output 0: left channel
+/// - Output 1: right channel
+///
+/// ### Example: Noise To The Left
+/// ```
+/// use fundsp::prelude::*;
+/// noise() >> binaural::<f64>(-0.5 * PI, 0.0);
+/// ```
+pub fn binaural<T: Float>(azimuth: T, elevation: T) -> An<Binaural<T>> {
+    An(Binaural::spherical_head(
+        DEFAULT_SR,
+        azimuth.to_f64(),
+        elevation.to_f64(),
+    ))
+}
+
+/// Binaural spatializer convolving with HRIR pairs from `set`.
+/// Source is at `azimuth` and `elevation` radians.
+/// Setting: (azimuth, elevation).
+/// - Input 0: input signal
+/// - Output 0: left channel
+/// - Output 1: right channel
+pub fn binaural_hrir<T: Float>(set: &HrirSet, azimuth: T, elevation: T) -> An<Binaural<T>> {
+    An(Binaural::new(set, azimuth.to_f64(), elevation.to_f64()))
+}
+
+/// Fixed mono-to-stereo spatializer with source at `azimuth` radians and `distance` meters.
+/// Azimuth is 0 straight ahead and `PI / 2` hard right.
+/// - Input 0: input signal
+/// - Output 0: left channel
+/// - Output 1: right channel
+pub fn spatial_pan<T: Real>(azimuth: T, distance: T) -> An<SpatialPanner<T, U1>> {
+    An(SpatialPanner::new(DEFAULT_SR, azimuth, distance))
+}
+
 /// Parameter follower filter with halfway response time `t` seconds.
 /// - Input 0: input signal
 /// - Output 0: smoothed signal
@@ -1232,6 +1925,77 @@ pub fn limiter_stereo<T: Real, S: ScalarOrPair<Sample = T>>(time: S) -> An<Limit
     An(Limiter::new(DEFAULT_SR, time))
 }
 
+/// RMS compressor. Levels above `threshold_db` are compressed by `ratio`
+/// with `attack` and `release` times in seconds.
+/// The RMS window is 10 ms by default; it can be changed with `Compressor::set_window`.
+/// Setting: (threshold in dB, ratio).
+/// - Input 0: signal
+/// - Output 0: compressed signal
+///
+/// ### Example: Vocal Compression
+/// ```
+/// use fundsp::prelude::*;
+/// noise::<f64>() >> compressor(-18.0, 3.0, 0.005, 0.1);
+/// ```
+pub fn compressor<T: Real>(
+    threshold_db: T,
+    ratio: T,
+    attack: T,
+    release: T,
+) -> An<Compressor<T, U1, U1>> {
+    An(Compressor::new(
+        DEFAULT_SR,
+        threshold_db,
+        ratio,
+        attack.to_f64(),
+        release.to_f64(),
+    ))
+}
+
+/// Stereo RMS compressor with linked detection. Levels above `threshold_db`
+/// are compressed by `ratio` with `attack` and `release` times in seconds.
+/// Setting: (threshold in dB, ratio).
+/// - Input 0: left signal
+/// - Input 1: right signal
+/// - Output 0: compressed left signal
+/// - Output 1: compressed right signal
+pub fn compressor_stereo<T: Real>(
+    threshold_db: T,
+    ratio: T,
+    attack: T,
+    release: T,
+) -> An<Compressor<T, U2, U2>> {
+    An(Compressor::new(
+        DEFAULT_SR,
+        threshold_db,
+        ratio,
+        attack.to_f64(),
+        release.to_f64(),
+    ))
+}
+
+/// RMS compressor with a gain reduction output for metering.
+/// Levels above `threshold_db` are compressed by `ratio`
+/// with `attack` and `release` times in seconds.
+/// Setting: (threshold in dB, ratio).
+/// - Input 0: signal
+/// - Output 0: compressed signal
+/// - Output 1: gain reduction in dB (zero or negative)
+pub fn compressor_meter<T: Real>(
+    threshold_db: T,
+    ratio: T,
+    attack: T,
+    release: T,
+) -> An<Compressor<T, U1, U2>> {
+    An(Compressor::new(
+        DEFAULT_SR,
+        threshold_db,
+        ratio,
+        attack.to_f64(),
+        release.to_f64(),
+    ))
+}
+
 /// Pinking filter.
 /// - Input 0: input signal
 /// - Output 0: filtered signal
@@ -1239,12 +2003,22 @@ pub fn pinkpass<T: Float, F: Float>() -> An<Pinkpass<T, F>> {
     An(Pinkpass::new())
 }
 
-/// Pink noise.
+/// Pink noise: white noise filtered with `pinkpass`.
+/// Energy is roughly equal in each octave. The RMS level is approximately 0.2.
 /// - Output 0: pink noise
 pub fn pink<T: Float, F: Float>() -> An<Pipe<T, Noise<T>, Pinkpass<T, F>>> {
     white() >> pinkpass::<T, F>()
 }
 
+/// Pink noise normalized to an RMS level of approximately 1.
+/// Energy is roughly equal in each octave.
+/// - Output 0: pink noise
+pub fn pink_normalized<T: Float, F: Float>(
+) -> An<Pipe<T, Noise<T>, Binop<T, FrameMul<U1, T>, Pinkpass<T, F>, Constant<U1, T>>>> {
+    // Empirical normalization factor.
+    white() >> pinkpass::<T, F>() * dc(T::from_f64(4.9))
+}
+
 /// Brown noise.
 /// - Output 0: brown noise
 pub fn brown<T: Float, F: Real>(
@@ -1253,6 +2027,21 @@ pub fn brown<T: Float, F: Real>(
     white() >> lowpole_hz::<T, F>(T::from_f64(10.0)) * dc(T::from_f64(13.7))
 }
 
+/// Blue noise (+3 dB/octave): differentiated pink noise.
+/// - Output 0: blue noise
+pub fn blue_noise<T: Float, F: Float>() -> An<Pipe<T, Pipe<T, Noise<T>, Pinkpass<T, F>>, Fir<T, U2>>>
+{
+    // Empirical normalization factor, matching the level of pink noise.
+    pink::<T, F>() >> fir((T::from_f64(1.65), T::from_f64(-1.65)))
+}
+
+/// Violet noise (+6 dB/octave): differentiated white noise.
+/// - Output 0: violet noise
+pub fn violet_noise<T: Float>() -> An<Pipe<T, Noise<T>, Fir<T, U2>>> {
+    // Normalization factor matching the level of pink noise.
+    white() >> fir((T::from_f64(0.245), T::from_f64(-0.245)))
+}
+
 /// Feedback delay network.
 /// Mix output of enclosed circuit `x` back to its input.
 /// The output is diffused with a Hadamard matrix for feedback.
@@ -1567,6 +2356,46 @@ where
     An(Join::new())
 }
 
+/// Sum `N` channels into one with power-preserving gain `1 / sqrt(N)`.
+/// Uncorrelated inputs of equal level keep their level in the sum.
+/// - Input(s): `N`.
+/// - Output 0: scaled sum.
+///
+/// ### Example: Mix Four Noise Sources
+/// ```
+/// use fundsp::prelude::*;
+/// let mix = (noise::<f64>() | noise() | noise() | noise()) >> join_power::<U4, f64>();
+/// ```
+pub fn join_power<N, T>() -> An<JoinPower<N, T>>
+where
+    T: Float,
+    N: Size<T>,
+{
+    An(JoinPower::new())
+}
+
+/// Mixer with per-input gain, mute and solo controls. Mixes `N` inputs into one.
+/// Controls can be set live via the node or any of its clones.
+/// Gain transitions are smoothed with a 10 ms time constant.
+/// - Input(s): `N` signals.
+/// - Output 0: mix.
+///
+/// ### Example: Solo The Second Input
+/// ```
+/// use fundsp::prelude::*;
+/// let mix = mixer::<U3, f64>();
+/// let control = mix.clone();
+/// let graph = (sine_hz(110.0) | sine_hz(220.0) | sine_hz(440.0)) >> mix;
+/// control.set_solo(1, true);
+/// ```
+pub fn mixer<N, T>() -> An<Mixer<N, T>>
+where
+    N: Size<T>,
+    T: Atomic,
+{
+    An(Mixer::new(DEFAULT_SR, 0.01))
+}
+
 /// Average `N` branches of `M` channels into one branch with `M` channels.
 /// The input has `N` * `M` channels. Inverse of `multisplit::<M, N>`.
 /// - Input(s): `N` * `M`.
@@ -1633,6 +2462,26 @@ where
     //        * dc((T::from_f64(1.0 / 16.0), T::from_f64(1.0 / 16.0)))
 }
 
+/// Dattorro plate reverb.
+/// `decay` is reverberation time to -60 dB in seconds.
+/// `damping` in 0...1 is the amount of high frequency absorption.
+/// `predelay` is in seconds. Outputs the reverberated signal only.
+/// Modulation depth can be adjusted with `set_modulation`.
+/// Allocates: the delay lines.
+/// - Input 0: left signal
+/// - Input 1: right signal
+/// - Output 0: reverberated left signal
+/// - Output 1: reverberated right signal
+///
+/// ### Example: Add 20% Plate Reverb
+/// ```
+/// use fundsp::prelude::*;
+/// multipass() & 0.2 * plate_reverb::<f64>(2.0, 0.3, 0.02);
+/// ```
+pub fn plate_reverb<T: Float>(decay: f64, damping: f64, predelay: f64) -> An<PlateReverb<T>> {
+    An(PlateReverb::new(DEFAULT_SR, decay, damping, predelay))
+}
+
 /// Saw-like discrete summation formula oscillator.
 /// - Input 0: frequency in Hz
 /// - Input 1: roughness in 0...1 is the attenuation of successive partials.
@@ -1688,6 +2537,25 @@ pub fn pluck<T: Float>(
     ))
 }
 
+/// Karplus-Strong plucked string oscillator with variable pitch and damping.
+/// The pluck buffer is sized for frequencies down to `min_frequency` Hz.
+/// The string decays by `gain_per_second` each second.
+/// A rising edge in the gate plucks the string with a noise burst.
+/// Allocates: pluck buffer.
+/// - Input 0: frequency in Hz
+/// - Input 1: high frequency damping in 0...1
+/// - Input 2: gate
+/// - Output 0: oscillator output
+///
+/// ### Example: Pluck With Vibrato
+/// ```
+/// use fundsp::prelude::*;
+/// let node = lfo(|t| (xerp11(215.0, 225.0, sin_hz(5.0, t)), 0.5, 1.0)) >> pluck_var::<f64>(100.0, 0.5);
+/// ```
+pub fn pluck_var<T: Float>(min_frequency: T, gain_per_second: T) -> An<PluckVar<T>> {
+    An(PluckVar::new(min_frequency, gain_per_second))
+}
+
 /// Saw wavetable oscillator.
 /// Allocates: global saw wavetable.
 /// - Input 0: frequency in Hz
@@ -1737,6 +2605,27 @@ pub fn hammond<T: Float>() -> An<WaveSynth<'static, T, U1>> {
     An(WaveSynth::new(DEFAULT_SR, &HAMMOND_TABLE))
 }
 
+/// Wavetable oscillator scanning through a stack of `tables` (at least one).
+/// Scan position 0 plays the first table and 1 the last.
+/// Neighboring tables are interpolated with `ScanInterpolation::Linear`
+/// or `ScanInterpolation::Cubic`; cubic scanning is smoother on sparse table sets.
+/// - Input 0: frequency in Hz
+/// - Input 1: scan position in 0...1
+/// - Output 0: audio
+///
+/// ### Example: Scanning From Saw To Organ
+/// ```
+/// use fundsp::prelude::*;
+/// let tables = [SAW_TABLE.clone(), SOFT_SAW_TABLE.clone(), ORGAN_TABLE.clone()];
+/// (dc(110.0) | lfo(|t: f64| 0.5 + 0.5 * sin_hz(0.25, t))) >> wave_scan::<f64>(&tables, ScanInterpolation::Cubic);
+/// ```
+pub fn wave_scan<T: Float>(
+    tables: &[Wavetable],
+    interpolation: ScanInterpolation,
+) -> An<WaveScan<T>> {
+    An(WaveScan::new(DEFAULT_SR, tables, interpolation))
+}
+
 /// Fixed saw wavetable oscillator at `f` Hz.
 /// Allocates: global saw wavetable.
 /// - Output 0: saw wave
@@ -1744,6 +2633,30 @@ pub fn saw_hz<T: Float>(f: T) -> An<Pipe<T, Constant<U1, T>, WaveSynth<'static,
     constant(f) >> saw()
 }
 
+/// PolyBLEP saw oscillator with anti-aliasing `quality`.
+/// `Quality::Low` applies no anti-aliasing and is the cheapest.
+/// `Quality::Medium` corrects two samples around each discontinuity and
+/// `Quality::High` corrects four, trading a little CPU for less aliasing.
+/// The wavetable oscillator `saw` has the least aliasing of all.
+/// - Input 0: frequency in Hz
+/// - Output 0: saw wave
+///
+/// ### Example: Cheap Bass Saw
+/// ```
+/// use fundsp::prelude::*;
+/// dc(55.0) >> saw_quality::<f32>(Quality::Medium);
+/// ```
+pub fn saw_quality<T: Real>(quality: Quality) -> An<PolySaw<T>> {
+    An(PolySaw::new(DEFAULT_SR, quality))
+}
+
+/// Fixed PolyBLEP saw oscillator at `f` Hz with anti-aliasing `quality`.
+/// See `saw_quality` for the quality levels.
+/// - Output 0: saw wave
+pub fn saw_hz_quality<T: Real>(f: T, quality: Quality) -> An<Pipe<T, Constant<U1, T>, PolySaw<T>>> {
+    constant(f) >> saw_quality(quality)
+}
+
 /// Fixed square wavetable oscillator at `f` Hz.
 /// Allocates: global square wavetable.
 /// - Output 0: square wave
@@ -2249,6 +3162,55 @@ pub fn highshelf_q<T: Float, F: Real>(
         ))
 }
 
+/// Biquad low shelf at `f` Hz with `gain_db` dB of gain at DC and transition `slope` in 0...1.
+/// A slope of 1 is the steepest that keeps the response monotonic.
+/// Setting: (frequency, gain in dB, slope).
+/// - Input 0: audio
+/// - Output 0: filtered audio
+///
+/// ### Example: Gentle Bass Boost
+/// ```
+/// use fundsp::prelude::*;
+/// lowshelf_slope::<f64, f64>(200.0, 3.0, 0.5);
+/// ```
+pub fn lowshelf_slope<T: Float, F: Real>(f: T, gain_db: T, slope: T) -> An<BiquadShelf<T, F>> {
+    An(BiquadShelf::new(
+        ShelfType::Low,
+        convert(f),
+        convert(gain_db),
+        convert(slope),
+    ))
+}
+
+/// Biquad high shelf at `f` Hz with `gain_db` dB of gain at Nyquist and transition `slope` in 0...1.
+/// A slope of 1 is the steepest that keeps the response monotonic.
+/// Setting: (frequency, gain in dB, slope).
+/// - Input 0: audio
+/// - Output 0: filtered audio
+pub fn highshelf_slope<T: Float, F: Real>(f: T, gain_db: T, slope: T) -> An<BiquadShelf<T, F>> {
+    An(BiquadShelf::new(
+        ShelfType::High,
+        convert(f),
+        convert(gain_db),
+        convert(slope),
+    ))
+}
+
+/// Tilt equalizer that tilts the spectrum by `gain_db` dB around `pivot` Hz.
+/// Positive gain boosts highs and cuts lows by up to half the gain each.
+/// Setting: gain in dB.
+/// - Input 0: audio
+/// - Output 0: filtered audio
+///
+/// ### Example: Darken Pink Noise
+/// ```
+/// use fundsp::prelude::*;
+/// pink::<f64, f64>() >> tilt_eq::<f64, f64>(-6.0, 1000.0);
+/// ```
+pub fn tilt_eq<T: Float, F: Real>(gain_db: T, pivot: T) -> An<TiltEq<T, F>> {
+    An(TiltEq::new(convert(gain_db), convert(pivot)))
+}
+
 /// Resonant two-pole lowpass filter.
 /// - Input 0: audio
 /// - Input 1: cutoff frequency
@@ -2373,6 +3335,14 @@ impl<T: Float> AudioNode for PulseWave<T> {
     fn allocate(&mut self) {
         self.pulse.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.pulse.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.pulse.heap_footprint()
+    }
 }
 
 /// Pulse wave oscillator.
@@ -2383,6 +3353,38 @@ pub fn pulse<T: Float>() -> An<PulseWave<T>> {
     An(PulseWave::new())
 }
 
+/// PolyBLEP pulse oscillator with pulse width modulation.
+/// Both edges are bandlimited, so width sweeps do not alias.
+/// Width 0.5 is a square wave; other widths add even harmonics.
+/// - Input 0: frequency in Hz
+/// - Input 1: pulse width in 0...1
+/// - Output 0: pulse wave
+///
+/// ### Example: Pulse Width Modulation
+/// ```
+/// use fundsp::prelude::*;
+/// lfo(|t: f64| (110.0, 0.5 + 0.4 * sin_hz(0.5, t))) >> pulse_pwm::<f64>();
+/// ```
+pub fn pulse_pwm<T: Real>() -> An<PolyPulse<T>> {
+    An(PolyPulse::new(DEFAULT_SR, Quality::High))
+}
+
+/// Saw oscillator with a phase-locked sub-oscillator `octaves` (1 or 2) below.
+/// The sub-oscillator `wave` is `SubWave::Square` or `SubWave::Sine`.
+/// Setting: sub-oscillator waveform.
+/// - Input 0: frequency in Hz
+/// - Output 0: saw wave
+/// - Output 1: sub-oscillator wave
+///
+/// ### Example: Bass With Square Sub
+/// ```
+/// use fundsp::prelude::*;
+/// dc(55.0) >> sub_osc::<f64>(1, SubWave::Square) >> (pass() + mul(0.5));
+/// ```
+pub fn sub_osc<T: Real>(octaves: usize, wave: SubWave) -> An<SubOsc<T>> {
+    An(SubOsc::new(DEFAULT_SR, octaves, wave))
+}
+
 /// Morphing filter that morphs between lowpass, peak and highpass modes.
 /// - Input 0: input signal
 /// - Input 1: center frequency (Hz)
@@ -2605,6 +3607,22 @@ pub fn phaser<T: Real, X: Fn(T) -> T + Clone + Send + Sync>(
         )
 }
 
+/// Phaser with `stages` 1st order allpass sections (for example, 8) swept by a built-in sine LFO.
+/// `feedback` amount in -0.99...0.99 is taken from the last section to the first.
+/// `rate` is the LFO rate in Hz and `depth` in 0...1 sets the sweep range,
+/// which spans up to 6 octaves upward from 200 Hz.
+/// - Input 0: audio
+/// - Output 0: phased audio
+///
+/// ### Example: Phased Saw Wave
+/// ```
+/// use fundsp::prelude::*;
+/// saw_hz(110.0) >> phaser_lfo::<f64>(8, 0.5, 0.2, 0.8);
+/// ```
+pub fn phaser_lfo<T: Real>(stages: usize, feedback: T, rate: f64, depth: f64) -> An<Phaser<T>> {
+    An(Phaser::new(stages, feedback, rate, depth))
+}
+
 /// Shared float variable. Can be read from and written to from multiple threads.
 ///
 /// ### Example: Add Chorus With Wetness Control
@@ -2631,6 +3649,54 @@ pub fn var<T: Atomic>(shared: &Shared<T>) -> An<Var<T>> {
     An(Var::new(shared))
 }
 
+/// Outputs the value of the shared variable smoothed with a one-pole lowpass
+/// with time constant `time` seconds. The shared variable is read atomically
+/// on every sample. Changes to it are approached exponentially, avoiding zipper noise.
+/// Setting: time constant.
+///
+/// - Output 0: smoothed value
+///
+/// ### Example: Smoothed Volume Control
+/// ```
+/// use fundsp::prelude::*;
+/// let volume = shared::<f32>(0.5);
+/// saw_hz::<f32>(110.0) * var_smooth(&volume, 0.02);
+/// ```
+pub fn var_smooth<T: Atomic>(shared: &Shared<T>, time: f64) -> An<VarSmooth<T>> {
+    An(VarSmooth::new(shared, time))
+}
+
+/// Outputs the value of the shared variable on `N` identical channels.
+/// Where `var` has a single output, `dc_var` serves as a control bus.
+///
+/// - Output(s): value
+///
+/// ### Example: Two Oscillators Following One Pitch
+/// ```
+/// use fundsp::prelude::*;
+/// let pitch = shared::<f64>(220.0);
+/// dc_var::<U2, f64>(&pitch) >> (sine() | saw());
+/// ```
+pub fn dc_var<N: Size<T>, T: Atomic>(shared: &Shared<T>) -> An<DcVar<N, T>> {
+    An(DcVar::new(shared))
+}
+
+/// Pass through input and write the latest input sample to the shared variable.
+/// Lighter than `snoop` for displaying control signals in another thread.
+/// - Input 0: signal
+/// - Output 0: signal passed through
+///
+/// ### Example: Display An LFO
+/// ```
+/// use fundsp::prelude::*;
+/// let level = shared::<f64>(0.0);
+/// let mut node = sine_hz(0.5) >> var_tap(&level);
+/// node.get_mono();
+/// ```
+pub fn var_tap<T: Atomic>(shared: &Shared<T>) -> An<VarTap<T>> {
+    An(VarTap::new(shared))
+}
+
 /// Shared variable mapped through a function.
 /// Outputs the value of the function, which may be scalar or tuple.
 ///
@@ -2675,3 +3741,30 @@ pub fn snoop<T: Float>(capacity: usize) -> (Snoop<T>, An<SnoopBackend<T>>) {
     let (snoop, backend) = Snoop::new(capacity);
     (snoop, An(backend))
 }
+
+/// Spectral processing with a windowed overlap-add framework.
+/// Input is split into Hann windowed frames of length `window` samples spaced `hop` samples apart.
+/// The spectrum of each frame is processed in place by `f`, which receives all `window` bins.
+/// The hop length must divide the window length. Latency is `window` samples.
+/// - Input 0: audio
+/// - Output 0: processed audio
+///
+/// ### Example: Spectral Gate
+/// ```
+/// use fundsp::prelude::*;
+/// let mut gate = stft::<f32, _>(1024, 256, |spectrum| {
+///     for x in spectrum.iter_mut() {
+///         if x.norm() < 0.1 {
+///             *x *= 0.0;
+///         }
+///     }
+/// });
+/// assert_eq!(gate.latency(), Some(1024.0));
+/// ```
+pub fn stft<T, S>(window: usize, hop: usize, f: S) -> An<Stft<T, S>>
+where
+    T: Float,
+    S: FnMut(&mut [num_complex::Complex64]) + Clone + Send + Sync,
+{
+    An(Stft::new(window, hop, f))
+}
@@ -1,6 +1,7 @@
 //! Real-time friendly backend for Net64 and Net32.
 
 use super::audiounit::*;
+use super::buffer::*;
 use super::math::*;
 use super::net::*;
 use super::signal::*;
@@ -18,6 +19,16 @@ pub struct NetBackend48 {
     /// For receiving new versions from the frontend.
     receiver: Receiver<Net48>,
     net: Net48,
+    /// Previous version that is being crossfaded out.
+    previous: Option<Net48>,
+    /// Crossfade length in samples.
+    fade_length: usize,
+    /// Crossfade position in samples.
+    fade_position: usize,
+    /// Output buffer for the previous version.
+    buffer: Buffer<f48>,
+    /// Tick output for the previous version.
+    tick_output: Vec<f48>,
 }
 
 #[duplicate_item(
@@ -29,11 +40,7 @@ impl Clone for NetBackend48 {
     fn clone(&self) -> Self {
         // Allocate a dummy channel.
         let (sender, receiver) = channel(1);
-        NetBackend48 {
-            sender,
-            receiver,
-            net: self.net.clone(),
-        }
+        NetBackend48::new(sender, receiver, self.net.clone())
     }
 }
 
@@ -45,13 +52,35 @@ impl Clone for NetBackend48 {
 impl NetBackend48 {
     /// Create new backend.
     pub fn new(sender: Sender<Net48>, receiver: Receiver<Net48>, net: Net48) -> Self {
+        let outputs = net.outputs();
         Self {
             sender,
             receiver,
             net,
+            previous: None,
+            fade_length: 0,
+            fade_position: 0,
+            buffer: Buffer::with_channels(outputs),
+            tick_output: vec![0.0; outputs],
+        }
+    }
+
+    /// Stop any crossfade in progress and send the previous version back for deallocation.
+    fn finish_fade(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            if self.sender.try_send(previous).is_ok() {}
         }
     }
 
+    /// Current crossfade weight of the new version.
+    #[inline]
+    fn fade_weight(&self, offset: usize) -> f48 {
+        smooth5(min(
+            1.0,
+            (self.fade_position + offset) as f48 / self.fade_length as f48,
+        ))
+    }
+
     /// Handle changes made to the backend.
     fn handle_messages(&mut self) {
         let mut latest_net: Option<Net48> = None;
@@ -69,11 +98,20 @@ impl NetBackend48 {
             }
         }
         if let Some(mut net) = latest_net {
-            // Migrate existing nodes to the new network.
-            self.net.migrate(&mut net);
-            std::mem::swap(&mut net, &mut self.net);
-            // Send the previous network back for deallocation.
-            if self.sender.try_send(net).is_ok() {}
+            if net.fade() > 0 {
+                // Crossfade from the running version, which keeps playing until the fade is done.
+                self.finish_fade();
+                std::mem::swap(&mut net, &mut self.net);
+                self.fade_length = self.net.fade();
+                self.fade_position = 0;
+                self.previous = Some(net);
+            } else {
+                // Migrate existing nodes to the new network.
+                self.net.migrate(&mut net);
+                std::mem::swap(&mut net, &mut self.net);
+                // Send the previous network back for deallocation.
+                if self.sender.try_send(net).is_ok() {}
+            }
         }
     }
 }
@@ -93,23 +131,54 @@ impl AudioUnit48 for NetBackend48 {
     }
 
     fn reset(&mut self) {
+        self.finish_fade();
         self.net.reset();
         self.handle_messages();
     }
 
     fn set_sample_rate(&mut self, sample_rate: f64) {
         self.net.set_sample_rate(sample_rate);
+        if let Some(previous) = &mut self.previous {
+            previous.set_sample_rate(sample_rate);
+        }
         self.handle_messages();
     }
 
     fn tick(&mut self, input: &[f48], output: &mut [f48]) {
         self.handle_messages();
         self.net.tick(input, output);
+        if let Some(previous) = &mut self.previous {
+            previous.tick(input, &mut self.tick_output);
+            let weight = self.fade_weight(0);
+            for (y, x) in output.iter_mut().zip(self.tick_output.iter()) {
+                *y = *x + (*y - *x) * weight;
+            }
+            self.fade_position += 1;
+            if self.fade_position >= self.fade_length {
+                self.finish_fade();
+            }
+        }
     }
 
     fn process(&mut self, size: usize, input: &[&[f48]], output: &mut [&mut [f48]]) {
         self.handle_messages();
         self.net.process(size, input, output);
+        if let Some(previous) = &mut self.previous {
+            previous.process(size, input, self.buffer.self_mut());
+            for (channel, y) in output.iter_mut().enumerate() {
+                for (i, (y, x)) in y[..size]
+                    .iter_mut()
+                    .zip(self.buffer.at(channel)[..size].iter())
+                    .enumerate()
+                {
+                    *y = *x + (*y - *x) * self.fade_weight(i);
+                }
+            }
+            self.fade_position += size;
+            if self.fade_position >= self.fade_length {
+                self.finish_fade();
+            }
+        }
     }
 
     fn get_id(&self) -> u64 {
@@ -130,7 +199,15 @@ impl AudioUnit48 for NetBackend48 {
         self.net.footprint()
     }
 
+    fn total_footprint(&self) -> usize {
+        self.net.total_footprint()
+    }
+
     fn allocate(&mut self) {
         self.net.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.net.is_allocated()
+    }
 }
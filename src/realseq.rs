@@ -25,6 +25,10 @@ pub enum Message48 {
     Edit(EventId, Edit48),
     /// Edit event in relative time.
     EditRelative(EventId, Edit48),
+    /// Set or clear loop region.
+    Loop(Option<(f48, f48)>),
+    /// Set or clear voice limit.
+    Voices(Option<(usize, StealPolicy)>),
 }
 
 #[duplicate_item(
@@ -105,6 +109,12 @@ impl SequencerBackend48 {
                     self.sequencer
                         .edit_relative(id, edit.end_time, edit.fade_out);
                 }
+                Message48::Loop(looping) => {
+                    self.sequencer.set_loop_region(looping);
+                }
+                Message48::Voices(voices) => {
+                    self.sequencer.set_voice_limit(voices);
+                }
                 Message48::Null => {}
             }
         }
@@ -144,6 +154,7 @@ impl AudioUnit48 for SequencerBackend48 {
             if self.sender.try_send(Some(event)).is_ok() {}
         }
         self.sequencer.reset();
+        self.sequencer.publish_time();
     }
 
     fn set_sample_rate(&mut self, sample_rate: f64) {
@@ -155,6 +166,7 @@ impl AudioUnit48 for SequencerBackend48 {
     fn tick(&mut self, input: &[f48], output: &mut [f48]) {
         self.handle_messages();
         self.sequencer.tick(input, output);
+        self.sequencer.publish_time();
         // Tick and process are the only places where events may be pushed to the past vector.
         if !self.sequencer.replay_events() {
             self.send_back_past();
@@ -164,6 +176,7 @@ impl AudioUnit48 for SequencerBackend48 {
     fn process(&mut self, size: usize, input: &[&[f48]], output: &mut [&mut [f48]]) {
         self.handle_messages();
         self.sequencer.process(size, input, output);
+        self.sequencer.publish_time();
         // Tick and process are the only places where events may be pushed to the past vector.
         if !self.sequencer.replay_events() {
             self.send_back_past();
@@ -188,7 +201,15 @@ impl AudioUnit48 for SequencerBackend48 {
         self.sequencer.footprint()
     }
 
+    fn total_footprint(&self) -> usize {
+        self.sequencer.total_footprint()
+    }
+
     fn allocate(&mut self) {
         self.sequencer.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.sequencer.is_allocated()
+    }
 }
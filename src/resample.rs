@@ -1,9 +1,12 @@
-//! Cubic variable speed resampler.
+//! Cubic variable speed resampler and fixed rate resampling wrapper.
 
 use super::audionode::*;
+use super::audiounit::*;
 use super::math::*;
 use super::signal::*;
 use super::*;
+use duplicate::duplicate_item;
+use num_complex::Complex64;
 use numeric_array::typenum::*;
 
 #[derive(Clone)]
@@ -119,4 +122,293 @@ where
     fn allocate(&mut self) {
         self.x.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.is_allocated()
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.x.heap_footprint()
+    }
+}
+
+/// Number of sinc zero crossings on each side of the `Resampled64` interpolation kernels.
+const RESAMPLED_ZEROS: f64 = 16.0;
+
+/// Blackman windowed sinc kernel with cutoff `rho` relative to Nyquist (0 < `rho` <= 1),
+/// evaluated `t` source samples from its center.
+#[inline]
+fn resampled_kernel(rho: f64, t: f64) -> f64 {
+    let width = RESAMPLED_ZEROS / rho;
+    if abs(t) >= width {
+        return 0.0;
+    }
+    let x = PI * rho * t;
+    let sinc = if x == 0.0 { 1.0 } else { sin(x) / x };
+    let u = PI * t / width;
+    rho * sinc * (0.42 + 0.5 * cos(u) + 0.08 * cos(2.0 * u))
+}
+
+/// Runs the contained unit at a fixed internal sample rate regardless of the outer sample rate.
+/// Inputs and outputs are converted between the rates with windowed sinc interpolation,
+/// lowpass filtered at the lower of the two Nyquist frequencies.
+/// Unlike oversampling, the internal rate need not be a multiple of the outer rate.
+/// The conversion adds latency, which is reported by `latency`.
+#[duplicate_item(
+    f48       Resampled48       AudioUnit48;
+    [ f64 ]   [ Resampled64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ Resampled32 ]   [ AudioUnit32 ];
+)]
+#[derive(Clone)]
+pub struct Resampled48 {
+    unit: Box<dyn AudioUnit48>,
+    inner_rate: f64,
+    /// Outer samples per inner sample.
+    step: f64,
+    /// Input kernel cutoff relative to outer Nyquist.
+    rho_in: f64,
+    /// Output kernel cutoff relative to inner Nyquist.
+    rho_out: f64,
+    /// Half width of the input kernel in outer samples.
+    width_in: f64,
+    /// Half width of the output kernel in inner samples.
+    width_out: f64,
+    /// Conversion latency in outer samples.
+    delay: f64,
+    /// Input history at the outer rate, one ring buffer per input.
+    input: Vec<Vec<f48>>,
+    /// Output history at the inner rate, one ring buffer per output.
+    output: Vec<Vec<f48>>,
+    input_mask: usize,
+    output_mask: usize,
+    /// Number of outer samples processed since reset.
+    outer: usize,
+    /// Number of inner samples processed since reset.
+    inner: usize,
+    inner_input: Vec<f48>,
+    inner_output: Vec<f48>,
+    tick_input: Vec<f48>,
+    tick_output: Vec<f48>,
+}
+
+#[allow(clippy::unnecessary_cast)]
+#[duplicate_item(
+    f48       Resampled48       AudioUnit48;
+    [ f64 ]   [ Resampled64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ Resampled32 ]   [ AudioUnit32 ];
+)]
+impl Resampled48 {
+    /// Create new wrapper that runs `unit` at `inner_rate` Hz.
+    pub fn new(inner_rate: f64, mut unit: Box<dyn AudioUnit48>) -> Self {
+        assert!(inner_rate > 0.0);
+        unit.set_sample_rate(inner_rate);
+        let inputs = unit.inputs();
+        let outputs = unit.outputs();
+        let mut wrapper = Self {
+            unit,
+            inner_rate,
+            step: 1.0,
+            rho_in: 1.0,
+            rho_out: 1.0,
+            width_in: 0.0,
+            width_out: 0.0,
+            delay: 0.0,
+            input: vec![Vec::new(); inputs],
+            output: vec![Vec::new(); outputs],
+            input_mask: 0,
+            output_mask: 0,
+            outer: 0,
+            inner: 0,
+            inner_input: vec![0.0; inputs],
+            inner_output: vec![0.0; outputs],
+            tick_input: vec![0.0; inputs],
+            tick_output: vec![0.0; outputs],
+        };
+        wrapper.set_sample_rate(DEFAULT_SR);
+        wrapper
+    }
+
+    /// Internal sample rate in Hz.
+    pub fn inner_rate(&self) -> f64 {
+        self.inner_rate
+    }
+
+    /// Access the contained unit.
+    pub fn unit(&self) -> &dyn AudioUnit48 {
+        &*self.unit
+    }
+
+    /// Access the contained unit mutably.
+    pub fn unit_mut(&mut self) -> &mut dyn AudioUnit48 {
+        &mut *self.unit
+    }
+}
+
+#[allow(clippy::unnecessary_cast)]
+#[duplicate_item(
+    f48       Resampled48       AudioUnit48;
+    [ f64 ]   [ Resampled64 ]   [ AudioUnit64 ];
+    [ f32 ]   [ Resampled32 ]   [ AudioUnit32 ];
+)]
+impl AudioUnit48 for Resampled48 {
+    fn reset(&mut self) {
+        self.unit.reset();
+        for buffer in self.input.iter_mut().chain(self.output.iter_mut()) {
+            buffer.fill(0.0);
+        }
+        self.outer = 0;
+        self.inner = 0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.step = sample_rate / self.inner_rate;
+        self.rho_in = min(1.0, self.inner_rate / sample_rate);
+        self.rho_out = min(1.0, sample_rate / self.inner_rate);
+        self.width_in = RESAMPLED_ZEROS / self.rho_in;
+        self.width_out = RESAMPLED_ZEROS / self.rho_out;
+        // Inner samples are produced once their input kernel is complete,
+        // and outputs are delayed until their output kernel is complete.
+        self.delay = self.width_in + (self.width_out + 1.0) * self.step;
+        let input_length = (2.0 * self.width_in + self.step + 4.0).ceil() as usize;
+        let output_length =
+            (self.width_in / self.step + 2.0 * self.width_out + 4.0).ceil() as usize;
+        let input_length = input_length.next_power_of_two();
+        let output_length = output_length.next_power_of_two();
+        for buffer in self.input.iter_mut() {
+            buffer.resize(input_length, 0.0);
+        }
+        for buffer in self.output.iter_mut() {
+            buffer.resize(output_length, 0.0);
+        }
+        self.input_mask = input_length - 1;
+        self.output_mask = output_length - 1;
+        self.reset();
+    }
+
+    fn tick(&mut self, input: &[f48], output: &mut [f48]) {
+        let n = self.outer;
+        for (buffer, x) in self.input.iter_mut().zip(input.iter()) {
+            buffer[n & self.input_mask] = *x;
+        }
+        // Run the inner unit for every inner sample whose input kernel is complete.
+        loop {
+            let t = self.inner as f64 * self.step;
+            if t + self.width_in > n as f64 {
+                break;
+            }
+            self.inner_input.fill(0.0);
+            if !self.input.is_empty() {
+                let k0 = max(0.0, (t - self.width_in).ceil()) as usize;
+                let k1 = (t + self.width_in).floor() as usize;
+                for k in k0..=k1 {
+                    let w = resampled_kernel(self.rho_in, t - k as f64) as f48;
+                    for (x, buffer) in self.inner_input.iter_mut().zip(self.input.iter()) {
+                        *x += buffer[k & self.input_mask] * w;
+                    }
+                }
+            }
+            self.unit.tick(&self.inner_input, &mut self.inner_output);
+            for (buffer, y) in self.output.iter_mut().zip(self.inner_output.iter()) {
+                buffer[self.inner & self.output_mask] = *y;
+            }
+            self.inner += 1;
+        }
+        // Interpolate the delayed output from inner samples.
+        output.fill(0.0);
+        let tau = (n as f64 - self.delay) / self.step;
+        let m1 = (tau + self.width_out).floor();
+        if m1 >= 0.0 {
+            let m0 = max(0.0, (tau - self.width_out).ceil()) as usize;
+            for m in m0..=m1 as usize {
+                let w = resampled_kernel(self.rho_out, tau - m as f64) as f48;
+                for (y, buffer) in output.iter_mut().zip(self.output.iter()) {
+                    *y += buffer[m & self.output_mask] * w;
+                }
+            }
+        }
+        self.outer += 1;
+    }
+
+    fn process(&mut self, size: usize, input: &[&[f48]], output: &mut [&mut [f48]]) {
+        let mut tick_input = std::mem::take(&mut self.tick_input);
+        let mut tick_output = std::mem::take(&mut self.tick_output);
+        for i in 0..size {
+            for (x, channel) in tick_input.iter_mut().zip(input.iter()) {
+                *x = channel[i];
+            }
+            self.tick(&tick_input, &mut tick_output);
+            for (y, channel) in tick_output.iter().zip(output.iter_mut()) {
+                channel[i] = *y;
+            }
+        }
+        self.tick_input = tick_input;
+        self.tick_output = tick_output;
+    }
+
+    fn inputs(&self) -> usize {
+        self.input.len()
+    }
+
+    fn outputs(&self) -> usize {
+        self.output.len()
+    }
+
+    fn get_id(&self) -> u64 {
+        const ID: u64 = 109;
+        ID
+    }
+
+    fn ping(&mut self, probe: bool, hash: AttoHash) -> AttoHash {
+        self.unit.ping(probe, hash.hash(self.get_id()))
+    }
+
+    fn route(&mut self, input: &SignalFrame, frequency: f64) -> SignalFrame {
+        // Latencies are converted between the rates.
+        // Responses are valid only below both Nyquist frequencies.
+        let mut inner_input = new_signal_frame(self.inputs());
+        for i in 0..self.inputs() {
+            inner_input[i] = match input[i] {
+                Signal::Latency(l) => Signal::Latency(l / self.step),
+                Signal::Response(r, l) => Signal::Response(r, l / self.step),
+                x => x,
+            };
+        }
+        let nyquist = 0.5 * min(self.inner_rate, self.inner_rate * self.step);
+        let inner_output = self.unit.route(&inner_input, frequency);
+        let phase = Complex64::from_polar(
+            1.0,
+            -TAU * frequency * self.delay / (self.inner_rate * self.step),
+        );
+        let mut output = new_signal_frame(self.outputs());
+        for i in 0..self.outputs() {
+            output[i] = match inner_output[i] {
+                Signal::Latency(l) => Signal::Latency(l * self.step + self.delay),
+                Signal::Response(r, l) if frequency < nyquist => {
+                    Signal::Response(r * phase, l * self.step + self.delay)
+                }
+                Signal::Response(_, l) => Signal::Latency(l * self.step + self.delay),
+                x => x,
+            };
+        }
+        output
+    }
+
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn total_footprint(&self) -> usize {
+        let buffers = self.input.iter().chain(self.output.iter());
+        std::mem::size_of::<Self>()
+            + self.unit.total_footprint()
+            + buffers.map(|v| v.capacity()).sum::<usize>() * std::mem::size_of::<f48>()
+    }
+
+    fn allocate(&mut self) {
+        self.unit.allocate();
+    }
+
+    fn is_allocated(&self) -> bool {
+        self.unit.is_allocated()
+    }
 }
@@ -0,0 +1,379 @@
+//! Dattorro plate reverb.
+
+use super::audionode::*;
+use super::math::*;
+use super::signal::*;
+use super::*;
+use numeric_array::typenum::*;
+
+/// Sample rate of the reference design. Delay lengths are scaled from this rate.
+const REFERENCE_SR: f64 = 29761.0;
+
+/// Peak modulation excursion of the tank allpasses at the reference sample rate.
+const EXCURSION: f64 = 16.0;
+
+/// Output tap positions at the reference sample rate, left channel first.
+const TAPS: [f64; 14] = [
+    266.0, 2974.0, 1913.0, 1996.0, 1990.0, 187.0, 1066.0, 353.0, 3627.0, 1228.0, 2673.0, 2111.0,
+    335.0, 121.0,
+];
+
+/// Plain delay line with integer and interpolated taps.
+#[derive(Clone, Default)]
+struct DelayLine {
+    buffer: Vec<f64>,
+    index: usize,
+}
+
+impl DelayLine {
+    fn resize(&mut self, length: usize) {
+        self.buffer = vec![0.0; max(1, length)];
+        self.index = 0;
+    }
+
+    fn clear(&mut self) {
+        self.buffer.fill(0.0);
+        self.index = 0;
+    }
+
+    /// Size of the buffer in bytes.
+    fn footprint(&self) -> usize {
+        self.buffer.capacity() * std::mem::size_of::<f64>()
+    }
+
+    /// Sample written `length` samples ago.
+    #[inline]
+    fn output(&self) -> f64 {
+        self.buffer[self.index]
+    }
+
+    /// Sample written `delay` samples ago, where 1 <= `delay` <= length.
+    #[inline]
+    fn tap(&self, delay: usize) -> f64 {
+        let length = self.buffer.len();
+        self.buffer[(self.index + length - delay) % length]
+    }
+
+    /// Linearly interpolated tap, where 1 <= `delay` < length.
+    #[inline]
+    fn tap_linear(&self, delay: f64) -> f64 {
+        let i = delay as usize;
+        let t = delay - i as f64;
+        lerp(self.tap(i), self.tap(i + 1), t)
+    }
+
+    #[inline]
+    fn push(&mut self, x: f64) {
+        self.buffer[self.index] = x;
+        self.index += 1;
+        if self.index == self.buffer.len() {
+            self.index = 0;
+        }
+    }
+}
+
+/// Schroeder allpass with an optionally modulated delay.
+#[derive(Clone, Default)]
+struct Allpass {
+    line: DelayLine,
+    /// Nominal delay in samples.
+    delay: usize,
+    coefficient: f64,
+}
+
+impl Allpass {
+    /// Allocate for nominal `delay` samples plus `excursion` samples of modulation.
+    fn resize(&mut self, delay: usize, excursion: usize) {
+        self.delay = max(1, delay);
+        self.line.resize(self.delay + excursion);
+    }
+
+    #[inline]
+    fn feed(&mut self, x: f64, delayed: f64) -> f64 {
+        let w = x - self.coefficient * delayed;
+        self.line.push(w);
+        delayed + self.coefficient * w
+    }
+
+    #[inline]
+    fn tick(&mut self, x: f64) -> f64 {
+        let delayed = self.line.tap(self.delay);
+        self.feed(x, delayed)
+    }
+
+    /// Process with the delay offset by `offset` samples.
+    #[inline]
+    fn tick_modulated(&mut self, x: f64, offset: f64) -> f64 {
+        let delayed = self.line.tap_linear(self.delay as f64 + offset);
+        self.feed(x, delayed)
+    }
+}
+
+/// Plate reverb after Jon Dattorro, "Effect Design, Part 1: Reverberator
+/// and Other Filters" (1997). The input is predelayed, bandlimited and diffused
+/// before entering a figure-eight tank of modulated allpasses and delays.
+/// Stereo output is tapped from several points in the tank.
+/// Only the reverberated signal is output.
+/// Allocates: the delay lines.
+/// - Input 0: left signal
+/// - Input 1: right signal
+/// - Output 0: reverberated left signal
+/// - Output 1: reverberated right signal
+#[derive(Clone)]
+pub struct PlateReverb<T: Float> {
+    _marker: std::marker::PhantomData<T>,
+    predelay: DelayLine,
+    diffuser: [Allpass; 4],
+    /// Modulated tank allpasses.
+    tank_allpass1: [Allpass; 2],
+    tank_delay1: [DelayLine; 2],
+    tank_allpass2: [Allpass; 2],
+    tank_delay2: [DelayLine; 2],
+    /// State of the input bandwidth filter.
+    bandwidth: f64,
+    /// State of the tank damping filters.
+    damping_state: [f64; 2],
+    /// Output tap positions in samples.
+    taps: [usize; 14],
+    /// Modulation LFO phase in 0...1.
+    phase: f64,
+    /// Scaling from reference delay lengths to the current sample rate.
+    scale: f64,
+    /// Tank feedback gain derived from the decay time.
+    gain: f64,
+    decay: f64,
+    damping: f64,
+    modulation: f64,
+    predelay_time: f64,
+    sample_rate: f64,
+}
+
+impl<T: Float> PlateReverb<T> {
+    /// Create new plate reverb. `decay` is the -60 dB decay time in seconds,
+    /// `damping` in 0...1 is the amount of high frequency absorption in the tank
+    /// and `predelay` is in seconds. Modulation depth is set to 1.
+    pub fn new(sample_rate: f64, decay: f64, damping: f64, predelay: f64) -> Self {
+        assert!(decay > 0.0 && predelay >= 0.0);
+        let mut node = Self {
+            _marker: std::marker::PhantomData,
+            predelay: DelayLine::default(),
+            diffuser: Default::default(),
+            tank_allpass1: Default::default(),
+            tank_delay1: Default::default(),
+            tank_allpass2: Default::default(),
+            tank_delay2: Default::default(),
+            bandwidth: 0.0,
+            damping_state: [0.0; 2],
+            taps: [1; 14],
+            phase: 0.0,
+            scale: 1.0,
+            gain: 0.0,
+            decay,
+            damping: clamp01(damping),
+            modulation: 1.0,
+            predelay_time: predelay,
+            sample_rate: 0.0,
+        };
+        for (allpass, coefficient) in node.diffuser.iter_mut().zip([0.75, 0.75, 0.625, 0.625]) {
+            allpass.coefficient = coefficient;
+        }
+        for allpass in node.tank_allpass1.iter_mut() {
+            allpass.coefficient = -0.7;
+        }
+        for allpass in node.tank_allpass2.iter_mut() {
+            allpass.coefficient = 0.5;
+        }
+        node.set_sample_rate(sample_rate);
+        node
+    }
+
+    /// Decay time (to -60 dB) in seconds.
+    pub fn decay(&self) -> f64 {
+        self.decay
+    }
+
+    /// Set decay time (to -60 dB) in seconds.
+    pub fn set_decay(&mut self, decay: f64) {
+        assert!(decay > 0.0);
+        self.decay = decay;
+        self.update_gain();
+    }
+
+    /// High frequency damping in 0...1.
+    pub fn damping(&self) -> f64 {
+        self.damping
+    }
+
+    /// Set high frequency damping in 0...1.
+    pub fn set_damping(&mut self, damping: f64) {
+        self.damping = clamp01(damping);
+    }
+
+    /// Tank modulation depth in 0...1.
+    pub fn modulation(&self) -> f64 {
+        self.modulation
+    }
+
+    /// Set tank modulation depth in 0...1. Modulation smears resonances in the tail.
+    /// At depth 1, delays vary by about half a millisecond.
+    pub fn set_modulation(&mut self, modulation: f64) {
+        self.modulation = clamp01(modulation);
+    }
+
+    /// Scale a reference delay length to the current sample rate.
+    fn length(&self, samples: f64) -> usize {
+        max(1.0, round(samples * self.scale)) as usize
+    }
+
+    /// Process half `i` of the tank with modulation `offset` in samples.
+    #[inline]
+    fn tank_half(&mut self, i: usize, x: f64, offset: f64) {
+        let a = self.tank_allpass1[i].tick_modulated(x, offset);
+        let d = self.tank_delay1[i].output();
+        self.tank_delay1[i].push(a);
+        self.damping_state[i] = lerp(d, self.damping_state[i], self.damping);
+        let a = self.tank_allpass2[i].tick(self.damping_state[i] * self.gain);
+        self.tank_delay2[i].push(a);
+    }
+
+    /// Derive tank gain from the decay time. The gain is applied
+    /// four times in one round trip of the tank.
+    fn update_gain(&mut self) {
+        let round_trip = (0..2).fold(0.0, |acc, i| {
+            acc + (self.tank_allpass1[i].delay
+                + self.tank_delay1[i].buffer.len()
+                + self.tank_allpass2[i].delay
+                + self.tank_delay2[i].buffer.len()) as f64
+        }) / self.sample_rate;
+        self.gain = db_amp(-60.0 * round_trip / (4.0 * self.decay));
+    }
+}
+
+impl<T: Float> AudioNode for PlateReverb<T> {
+    const ID: u64 = 83;
+    type Sample = T;
+    type Inputs = U2;
+    type Outputs = U2;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.predelay.clear();
+        for allpass in self
+            .diffuser
+            .iter_mut()
+            .chain(self.tank_allpass1.iter_mut())
+            .chain(self.tank_allpass2.iter_mut())
+        {
+            allpass.line.clear();
+        }
+        for line in self
+            .tank_delay1
+            .iter_mut()
+            .chain(self.tank_delay2.iter_mut())
+        {
+            line.clear();
+        }
+        self.bandwidth = 0.0;
+        self.damping_state = [0.0; 2];
+        self.phase = 0.0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate == sample_rate {
+            return;
+        }
+        self.sample_rate = sample_rate;
+        self.scale = sample_rate / REFERENCE_SR;
+        let excursion = ceil(EXCURSION * self.scale) as usize + 1;
+        self.predelay
+            .resize(max(1.0, round(self.predelay_time * sample_rate)) as usize);
+        for (i, length) in [142.0, 107.0, 379.0, 277.0].into_iter().enumerate() {
+            let length = self.length(length);
+            self.diffuser[i].resize(length, 0);
+        }
+        for (i, (a1, d1, a2, d2)) in [
+            (672.0, 4453.0, 1800.0, 3720.0),
+            (908.0, 4217.0, 2656.0, 3163.0),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let (a1, d1, a2, d2) = (
+                self.length(a1),
+                self.length(d1),
+                self.length(a2),
+                self.length(d2),
+            );
+            self.tank_allpass1[i].resize(a1, excursion);
+            self.tank_delay1[i].resize(d1);
+            self.tank_allpass2[i].resize(a2, 0);
+            self.tank_delay2[i].resize(d2);
+        }
+        self.taps = TAPS.map(|samples| self.length(samples));
+        self.update_gain();
+        self.reset();
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let x = (input[0].to_f64() + input[1].to_f64()) * 0.5;
+        let delayed = self.predelay.output();
+        self.predelay.push(x);
+
+        // Input bandwidth filter and diffusers.
+        self.bandwidth += (delayed - self.bandwidth) * 0.9995;
+        let mut diffused = self.bandwidth;
+        for allpass in self.diffuser.iter_mut() {
+            diffused = allpass.tick(diffused);
+        }
+
+        // The tank. Each half feeds the other. Modulation runs at 1 Hz,
+        // in opposite phase in the two halves.
+        let offset = self.modulation * EXCURSION * self.scale * sin(TAU * self.phase);
+        self.phase += 1.0 / self.sample_rate;
+        if self.phase >= 1.0 {
+            self.phase -= 1.0;
+        }
+        let feedback = [
+            self.tank_delay2[1].output() * self.gain,
+            self.tank_delay2[0].output() * self.gain,
+        ];
+        self.tank_half(0, diffused + feedback[0], offset);
+        self.tank_half(1, diffused + feedback[1], -offset);
+
+        // Output taps.
+        let t = &self.taps;
+        let left = self.tank_delay1[1].tap(t[0]) + self.tank_delay1[1].tap(t[1])
+            - self.tank_allpass2[1].line.tap(t[2])
+            + self.tank_delay2[1].tap(t[3])
+            - self.tank_delay1[0].tap(t[4])
+            - self.tank_allpass2[0].line.tap(t[5])
+            - self.tank_delay2[0].tap(t[6]);
+        let right = self.tank_delay1[0].tap(t[7]) + self.tank_delay1[0].tap(t[8])
+            - self.tank_allpass2[0].line.tap(t[9])
+            + self.tank_delay2[0].tap(t[10])
+            - self.tank_delay1[1].tap(t[11])
+            - self.tank_allpass2[1].line.tap(t[12])
+            - self.tank_delay2[1].tap(t[13]);
+        [convert(left * 0.6), convert(right * 0.6)].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        Routing::Arbitrary.propagate(input, self.outputs())
+    }
+
+    fn heap_footprint(&self) -> usize {
+        let allpasses = self
+            .diffuser
+            .iter()
+            .chain(self.tank_allpass1.iter())
+            .chain(self.tank_allpass2.iter());
+        let delays = self.tank_delay1.iter().chain(self.tank_delay2.iter());
+        self.predelay.footprint()
+            + allpasses.map(|a| a.line.footprint()).sum::<usize>()
+            + delays.map(|d| d.footprint()).sum::<usize>()
+    }
+}
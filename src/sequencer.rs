@@ -5,16 +5,18 @@ use super::buffer::*;
 use super::math::*;
 use super::realseq::*;
 use super::signal::*;
+use super::wave::*;
 use super::*;
 use duplicate::duplicate_item;
 use std::cmp::{Eq, Ord, Ordering};
 use std::collections::BinaryHeap;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
 use thingbuf::mpsc::blocking::{channel, Receiver, Sender};
 
 /// Fade curves.
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum Fade {
     /// Equal power fade. Results in equal power mixing
     /// when fade out of one event coincides with the fade in of another.
@@ -35,6 +37,85 @@ impl Fade {
     }
 }
 
+/// Fade-in applied to output after a reset or sample rate change
+/// so that a restarted stream does not begin with a click.
+#[derive(Clone)]
+pub(crate) struct FadeIn<T: Float> {
+    /// Fade-in time in seconds. Zero disables the fade.
+    time: T,
+    /// Fade-in phase in 0...1. The fade is complete at 1.
+    phase: T,
+}
+
+impl<T: Float> Default for FadeIn<T> {
+    fn default() -> Self {
+        Self {
+            time: T::zero(),
+            phase: T::one(),
+        }
+    }
+}
+
+impl<T: Float> FadeIn<T> {
+    /// Set fade-in time in seconds. Zero disables the fade.
+    pub fn set_time(&mut self, time: T) {
+        assert!(time >= T::zero());
+        self.time = time;
+    }
+
+    /// Fade-in time in seconds.
+    pub fn time(&self) -> T {
+        self.time
+    }
+
+    /// Whether the fade-in is in progress.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.phase < T::one()
+    }
+
+    /// Restart the fade-in, if enabled.
+    pub fn restart(&mut self) {
+        if self.time > T::zero() {
+            self.phase = T::zero();
+        }
+    }
+
+    /// Apply the fade-in to `size` samples of `output`, if it is in progress.
+    pub fn apply(&mut self, sample_rate: T, size: usize, output: &mut [&mut [T]]) {
+        if !self.is_active() {
+            return;
+        }
+        let step = T::one() / (self.time * sample_rate);
+        let n = min(size, ceil((T::one() - self.phase) / step).to_f64() as usize);
+        for channel in output.iter_mut() {
+            let mut phase = self.phase;
+            for x in channel[..n].iter_mut() {
+                *x *= smooth5(phase);
+                phase += step;
+            }
+        }
+        self.phase = min(T::one(), self.phase + T::from_f64(n as f64) * step);
+    }
+}
+
+/// Which event to steal when the voice limit of a sequencer is exceeded.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StealPolicy {
+    /// Steal the event that started first.
+    #[default]
+    Oldest,
+    /// Steal the event with the lowest recent peak output level.
+    /// Ties go to the oldest event.
+    Quietest,
+}
+
+/// Fade out time in seconds for events stolen by the voice limit.
+const STEAL_FADE: f64 = 0.005;
+
+/// Release time constant in seconds of the event level tracker used for voice stealing.
+const STEAL_RELEASE: f64 = 0.01;
+
 /// Globally unique ID for a sequencer event.
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub struct EventId(u64);
@@ -63,13 +144,15 @@ pub struct Event48 {
     pub fade_ease: Fade,
     pub fade_in: f48,
     pub fade_out: f48,
+    /// Amplitude of the event.
+    pub gain: f48,
     pub id: EventId,
 }
 
 #[duplicate_item(
-    f48       Event48       AudioUnit48;
-    [ f64 ]   [ Event64 ]   [ AudioUnit64 ];
-    [ f32 ]   [ Event32 ]   [ AudioUnit32 ];
+    f48       Event48       AudioUnit48       EventInfo48;
+    [ f64 ]   [ Event64 ]   [ AudioUnit64 ]   [ EventInfo64 ];
+    [ f32 ]   [ Event32 ]   [ AudioUnit32 ]   [ EventInfo32 ];
 )]
 impl Event48 {
     pub fn new(
@@ -87,9 +170,39 @@ impl Event48 {
             fade_ease,
             fade_in,
             fade_out,
+            gain: 1.0,
             id: EventId::new(),
         }
     }
+
+    /// Timing and fade parameters of this event.
+    pub fn info(&self) -> EventInfo48 {
+        EventInfo48 {
+            id: self.id,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            fade_ease: self.fade_ease.clone(),
+            fade_in: self.fade_in,
+            fade_out: self.fade_out,
+        }
+    }
+}
+
+/// Timing and fade parameters of a scheduled sequencer event.
+/// Times are absolute, in seconds.
+#[duplicate_item(
+    f48       EventInfo48;
+    [ f64 ]   [ EventInfo64 ];
+    [ f32 ]   [ EventInfo32 ];
+)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventInfo48 {
+    pub id: EventId,
+    pub start_time: f48,
+    pub end_time: f48,
+    pub fade_ease: Fade,
+    pub fade_in: f48,
+    pub fade_out: f48,
 }
 
 #[duplicate_item(
@@ -253,13 +366,16 @@ fn fade_out48(
 /// Sequencer unit.
 /// The sequencer mixes together outputs of audio units with sample accurate timing.
 #[duplicate_item(
-    f48       Event48       AudioUnit48       Sequencer48       Message48        Edit48;
-    [ f64 ]   [ Event64 ]   [ AudioUnit64 ]   [ Sequencer64 ]   [ Message64 ]    [ Edit64 ];
-    [ f32 ]   [ Event32 ]   [ AudioUnit32 ]   [ Sequencer32 ]   [ Message32 ]    [ Edit32 ];
+    f48       Event48       AudioUnit48       Sequencer48       Message48        Edit48       EventInfo48;
+    [ f64 ]   [ Event64 ]   [ AudioUnit64 ]   [ Sequencer64 ]   [ Message64 ]    [ Edit64 ]   [ EventInfo64 ];
+    [ f32 ]   [ Event32 ]   [ AudioUnit32 ]   [ Sequencer32 ]   [ Message32 ]    [ Edit32 ]   [ EventInfo32 ];
 )]
 pub struct Sequencer48 {
     /// Current events, unsorted.
     active: Vec<Event48>,
+    /// Recent peak output levels of current events, indexed like `active`.
+    /// Tracked when the quietest voice is stolen.
+    levels: Vec<f48>,
     /// IDs of current events.
     active_map: HashMap<EventId, usize>,
     /// Events that start before the active threshold are active.
@@ -279,6 +395,26 @@ pub struct Sequencer48 {
     /// Optional frontend.
     front: Option<(Sender<Message48>, Receiver<Option<Event48>>)>,
     replay_events: bool,
+    /// Optional loop region (start, end) in seconds.
+    looping: Option<(f48, f48)>,
+    /// Output buffer for ticking through loop boundaries.
+    loop_buffer: Vec<f48>,
+    /// Swing subdivision length in seconds and swing amount.
+    swing: (f48, f48),
+    /// Humanization timing jitter in seconds and velocity jitter.
+    humanize: (f48, f48),
+    /// Seed for humanization.
+    seed: u64,
+    /// Number of humanized events so far.
+    humanized: u64,
+    /// Fade-in applied after a reset or sample rate change.
+    declick: FadeIn<f48>,
+    /// Optional maximum number of simultaneous events and how to choose events to steal.
+    voices: Option<(usize, StealPolicy)>,
+    /// Events scheduled through a frontend, kept for queries.
+    schedule: Vec<EventInfo48>,
+    /// Time of the backend in seconds as `f64` bits, shared between frontend and backend.
+    clock: Option<Arc<AtomicU64>>,
 }
 
 #[duplicate_item(
@@ -293,6 +429,7 @@ impl Clone for Sequencer48 {
         }
         Self {
             active: self.active.clone(),
+            levels: self.levels.clone(),
             active_map: self.active_map.clone(),
             active_threshold: self.active_threshold,
             ready: self.ready.clone(),
@@ -306,15 +443,25 @@ impl Clone for Sequencer48 {
             tick_buffer: self.tick_buffer.clone(),
             front: None,
             replay_events: self.replay_events,
+            looping: self.looping,
+            loop_buffer: self.loop_buffer.clone(),
+            swing: self.swing,
+            humanize: self.humanize,
+            seed: self.seed,
+            humanized: self.humanized,
+            declick: self.declick.clone(),
+            voices: self.voices,
+            schedule: Vec::new(),
+            clock: None,
         }
     }
 }
 
 #[allow(clippy::unnecessary_cast)]
 #[duplicate_item(
-    f48       Event48       AudioUnit48       Sequencer48       SequencerBackend48       Message48       Edit48;
-    [ f64 ]   [ Event64 ]   [ AudioUnit64 ]   [ Sequencer64 ]   [ SequencerBackend64 ]   [ Message64 ]   [ Edit64 ];
-    [ f32 ]   [ Event32 ]   [ AudioUnit32 ]   [ Sequencer32 ]   [ SequencerBackend32 ]   [ Message32 ]   [ Edit32 ];
+    f48       Event48       AudioUnit48       Sequencer48       SequencerBackend48       Message48       Edit48       Wave48       EventInfo48;
+    [ f64 ]   [ Event64 ]   [ AudioUnit64 ]   [ Sequencer64 ]   [ SequencerBackend64 ]   [ Message64 ]   [ Edit64 ]   [ Wave64 ]   [ EventInfo64 ];
+    [ f32 ]   [ Event32 ]   [ AudioUnit32 ]   [ Sequencer32 ]   [ SequencerBackend32 ]   [ Message32 ]   [ Edit32 ]   [ Wave32 ]   [ EventInfo32 ];
 )]
 impl Sequencer48 {
     /// Create a new sequencer. The sequencer has zero inputs.
@@ -325,6 +472,7 @@ impl Sequencer48 {
     pub fn new(replay_events: bool, outputs: usize) -> Self {
         Self {
             active: Vec::with_capacity(16384),
+            levels: Vec::with_capacity(16384),
             active_map: HashMap::with_capacity(16384),
             active_threshold: -f48::INFINITY,
             ready: BinaryHeap::with_capacity(16384),
@@ -338,6 +486,16 @@ impl Sequencer48 {
             tick_buffer: vec![0.0; outputs],
             front: None,
             replay_events,
+            looping: None,
+            loop_buffer: vec![0.0; outputs],
+            swing: (0.0, 0.0),
+            humanize: (0.0, 0.0),
+            seed: 0,
+            humanized: 0,
+            declick: FadeIn::default(),
+            voices: None,
+            schedule: Vec::new(),
+            clock: None,
         }
     }
 
@@ -366,29 +524,240 @@ impl Sequencer48 {
         // Make sure the sample rate of the unit matches ours.
         unit.set_sample_rate(self.sample_rate as f64);
         unit.allocate();
-        let event = Event48::new(
+        let (jitter, gain) = self.humanize_event();
+        // Jitter may not move the event before time zero or the current time.
+        let jitter = max(jitter, min(0.0, max(0.0, self.current_time()) - start_time));
+        let offset = self.swing_offset(start_time) + jitter;
+        let mut event = Event48::new(
             unit,
-            start_time,
-            end_time,
+            start_time + offset,
+            end_time + offset,
             fade_ease,
             fade_in_time,
             fade_out_time,
         );
+        event.gain = gain;
         let id = event.id;
         self.push_event(event);
         id
     }
 
+    /// Set swing. Events added afterwards with absolute start times
+    /// that fall on off-beat subdivisions (odd multiples of `subdivision` seconds)
+    /// are delayed by `amount` times the subdivision (0 <= `amount` < 1).
+    /// For example, for 8th note swing at 120 BPM, the subdivision is 0.25 seconds.
+    /// Swing can be disabled by setting the amount to zero.
+    /// Events already scheduled and events added in relative time are not affected.
+    ///
+    /// ### Example (8th Note Swing At 120 BPM)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut sequencer = Sequencer64::new(false, 1);
+    /// sequencer.set_swing(0.25, 1.0 / 3.0);
+    /// for i in 0..8 {
+    ///     let t = i as f64 * 0.25;
+    ///     sequencer.push(t, t + 0.1, Fade::Smooth, 0.01, 0.01, Box::new(sine_hz(440.0)));
+    /// }
+    /// ```
+    pub fn set_swing(&mut self, subdivision: f48, amount: f48) {
+        assert!(subdivision > 0.0 && (0.0..1.0).contains(&amount));
+        self.swing = (subdivision, amount);
+    }
+
+    /// Humanize events added afterwards. Start times are offset randomly
+    /// by up to `timing_jitter` seconds in either direction and amplitudes are scaled
+    /// randomly by up to `velocity_jitter` in either direction (0 <= `velocity_jitter` <= 1).
+    /// Jitter does not move an event before time zero or the current time.
+    /// The jitter is deterministic: it depends only on the seed (see `set_seed`)
+    /// and the number of events added since the seed was set.
+    /// Humanization can be disabled by setting both jitters to zero.
+    ///
+    /// ### Example (Loose Hi-Hats)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut sequencer = Sequencer64::new(false, 1);
+    /// sequencer.humanize(0.005, 0.2);
+    /// for i in 0..16 {
+    ///     let t = 0.1 + i as f64 * 0.125;
+    ///     let hat = white() >> highpass_hz(8000.0, 1.0);
+    ///     sequencer.push(t, t + 0.05, Fade::Smooth, 0.0, 0.04, Box::new(hat));
+    /// }
+    /// ```
+    pub fn humanize(&mut self, timing_jitter: f48, velocity_jitter: f48) {
+        assert!(timing_jitter >= 0.0 && (0.0..=1.0).contains(&velocity_jitter));
+        self.humanize = (timing_jitter, velocity_jitter);
+    }
+
+    /// Set the humanization seed. This restarts the sequence of pseudorandom jitters.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.humanized = 0;
+    }
+
+    /// Fade in output over `time` seconds after a reset or sample rate change
+    /// to avoid a click when the stream restarts. Zero (the default) disables the fade.
+    /// Set this before creating a backend.
+    pub fn set_declick(&mut self, time: f48) {
+        self.declick.set_time(time);
+    }
+
+    /// Fade-in time in seconds after a reset or sample rate change.
+    pub fn declick(&self) -> f48 {
+        self.declick.time()
+    }
+
+    /// Limit the number of simultaneous events to `voices` (`voices` > 0).
+    /// When an event starts and the limit is exceeded, the event chosen
+    /// by `policy` is faded out quickly to make room.
+    /// Events that are already fading out do not count toward the limit.
+    ///
+    /// ### Example (Eight Voice Polyphony)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut sequencer = Sequencer64::new(false, 1);
+    /// sequencer.set_max_voices(8, StealPolicy::Oldest);
+    /// ```
+    pub fn set_max_voices(&mut self, voices: usize, policy: StealPolicy) {
+        assert!(voices > 0);
+        self.set_voice_limit(Some((voices, policy)));
+    }
+
+    /// Remove the limit on the number of simultaneous events.
+    pub fn clear_max_voices(&mut self) {
+        self.set_voice_limit(None);
+    }
+
+    /// Maximum number of simultaneous events and steal policy, if limited.
+    pub fn max_voices(&self) -> Option<(usize, StealPolicy)> {
+        self.voices
+    }
+
+    /// Set voice limit. This is an internal method.
+    pub(crate) fn set_voice_limit(&mut self, voices: Option<(usize, StealPolicy)>) {
+        if let Some((sender, receiver)) = &mut self.front {
+            // Deallocate all past events.
+            while receiver.try_recv().is_ok() {}
+            if sender.try_send(Message48::Voices(voices)).is_ok() {}
+        }
+        self.voices = voices;
+    }
+
+    /// Whether active event `i` has begun to fade out or end, as of the current time.
+    fn is_releasing(&self, i: usize) -> bool {
+        let event = &self.active[i];
+        event.end_time - event.fade_out <= max(self.time, event.start_time)
+    }
+
+    /// Level of active event `i` for voice stealing.
+    /// Events that have not been heard yet are treated as loud.
+    fn steal_level(&self, i: usize) -> f48 {
+        if self.active[i].start_time < self.time {
+            self.levels[i]
+        } else {
+            f48::INFINITY
+        }
+    }
+
+    /// Whether event output levels are tracked for voice stealing.
+    fn tracks_level(&self) -> bool {
+        matches!(self.voices, Some((_, StealPolicy::Quietest)))
+    }
+
+    /// Add event to the active set, stealing another event if the voice limit is exceeded.
+    fn push_active(&mut self, event: Event48) {
+        self.active_map.insert(event.id, self.active.len());
+        self.active.push(event);
+        self.levels.push(0.0);
+        let (voices, policy) = match self.voices {
+            Some(limit) => limit,
+            None => return,
+        };
+        let new = self.active.len() - 1;
+        let mut count = 1;
+        let mut victim: Option<usize> = None;
+        for i in 0..new {
+            if self.is_releasing(i) {
+                continue;
+            }
+            count += 1;
+            victim = match victim {
+                None => Some(i),
+                Some(j) => {
+                    let (a, b) = (&self.active[i], &self.active[j]);
+                    let better = match policy {
+                        StealPolicy::Oldest => a.start_time < b.start_time,
+                        StealPolicy::Quietest => {
+                            let (x, y) = (self.steal_level(i), self.steal_level(j));
+                            x < y || (x == y && a.start_time < b.start_time)
+                        }
+                    };
+                    if better {
+                        Some(i)
+                    } else {
+                        Some(j)
+                    }
+                }
+            };
+        }
+        if count > voices {
+            if let Some(i) = victim {
+                // Fade out the stolen event, or cut it if it has not started yet.
+                let now = max(self.time, self.active[i].start_time);
+                let fade = if self.active[i].start_time > self.time {
+                    0.0
+                } else {
+                    STEAL_FADE as f48
+                };
+                let end_time = min(self.active[i].end_time, now + fade);
+                self.active[i].end_time = end_time;
+                self.active[i].fade_out = end_time - now;
+            }
+        }
+    }
+
+    /// Draw humanization timing offset and gain for the next event.
+    fn humanize_event(&mut self) -> (f48, f48) {
+        let (timing_jitter, velocity_jitter) = self.humanize;
+        if timing_jitter == 0.0 && velocity_jitter == 0.0 {
+            return (0.0, 1.0);
+        }
+        let base = hash(self.seed.wrapping_add(self.humanized << 1) as i64);
+        self.humanized += 1;
+        let timing = (rnd(base) * 2.0 - 1.0) as f48 * timing_jitter;
+        let gain = 1.0 + (rnd(base ^ 1) * 2.0 - 1.0) as f48 * velocity_jitter;
+        (timing, gain)
+    }
+
+    /// Delay applied by swing to an event starting at `start_time`.
+    fn swing_offset(&self, start_time: f48) -> f48 {
+        let (subdivision, amount) = self.swing;
+        if amount == 0.0 {
+            return 0.0;
+        }
+        let position = start_time / subdivision;
+        let index = round(position);
+        // Only events that start on the subdivision grid are swung.
+        if abs(position - index) * subdivision < 0.5 * self.sample_duration
+            && (index as i64) % 2 != 0
+        {
+            amount * subdivision
+        } else {
+            0.0
+        }
+    }
+
     /// Add event. This is an internal method.
     pub(crate) fn push_event(&mut self, event: Event48) {
+        if self.front.is_some() {
+            self.schedule_event(event.info());
+        }
         if let Some((sender, receiver)) = &mut self.front {
             // Deallocate all past events.
             while receiver.try_recv().is_ok() {}
             // Send the new event over.
             if sender.try_send(Message48::Push(event)).is_ok() {}
         } else if event.start_time < self.active_threshold {
-            self.active_map.insert(event.id, self.active.len());
-            self.active.push(event);
+            self.push_active(event);
         } else {
             self.ready.push(event);
         }
@@ -414,14 +783,18 @@ impl Sequencer48 {
         // Make sure the sample rate of the unit matches ours.
         unit.set_sample_rate(self.sample_rate as f64);
         unit.allocate();
-        let event = Event48::new(
+        let (jitter, gain) = self.humanize_event();
+        // Jitter may not move the event into the past.
+        let jitter = max(jitter, min(0.0, -start_time));
+        let mut event = Event48::new(
             unit,
-            start_time,
-            end_time,
+            max(0.0, start_time + jitter),
+            end_time + jitter,
             fade_ease,
             fade_in_time,
             fade_out_time,
         );
+        event.gain = gain;
         let id = event.id;
         self.push_relative_event(event);
         id
@@ -429,6 +802,15 @@ impl Sequencer48 {
 
     /// Add relative event. This is an internal method.
     pub(crate) fn push_relative_event(&mut self, mut event: Event48) {
+        if self.front.is_some() {
+            // The backend offsets the event by its time when it receives it,
+            // so the scheduled times are estimates.
+            let now = self.current_time();
+            let mut info = event.info();
+            info.start_time += now;
+            info.end_time += now;
+            self.schedule_event(info);
+        }
         if let Some((sender, receiver)) = &mut self.front {
             // Deallocate all past events.
             while receiver.try_recv().is_ok() {}
@@ -438,8 +820,7 @@ impl Sequencer48 {
             event.start_time += self.time;
             event.end_time += self.time;
             if event.start_time < self.active_threshold {
-                self.active_map.insert(event.id, self.active.len());
-                self.active.push(event);
+                self.push_active(event);
             } else {
                 self.ready.push(event);
             }
@@ -474,6 +855,9 @@ impl Sequencer48 {
     /// how long they need to play. The original end time can be set to infinity,
     /// for example.
     pub fn edit(&mut self, id: EventId, end_time: f48, fade_out_time: f48) {
+        if self.front.is_some() {
+            self.schedule_edit(id, end_time, fade_out_time);
+        }
         if let Some((sender, receiver)) = &mut self.front {
             // Deallocate all past events.
             while receiver.try_recv().is_ok() {}
@@ -515,6 +899,10 @@ impl Sequencer48 {
     /// how long they need to play. The original end time can be set to infinity,
     /// for example.
     pub fn edit_relative(&mut self, id: EventId, end_time: f48, fade_out_time: f48) {
+        if self.front.is_some() {
+            let now = self.current_time();
+            self.schedule_edit(id, now + end_time, fade_out_time);
+        }
         if let Some((sender, receiver)) = &mut self.front {
             // Deallocate all past events.
             while receiver.try_recv().is_ok() {}
@@ -548,6 +936,71 @@ impl Sequencer48 {
         }
     }
 
+    /// Loop the region from `start` to `end` (in seconds).
+    /// When playback reaches `end`, it jumps back to `start` and all events
+    /// are retriggered from the beginning, including their fades.
+    /// Events that span the loop end are cut off at the jump,
+    /// so give them a fade out that ends at the loop end to avoid clicks.
+    /// Events that span the loop start play from the beginning of their unit when the loop restarts.
+    /// The sequencer must retain past events (`replay_events` must be true).
+    ///
+    /// ### Example (Looping Drone)
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut sequencer = Sequencer64::new(true, 1);
+    /// sequencer.set_loop(0.0, 2.0);
+    /// sequencer.push(0.0, 2.0, Fade::Smooth, 0.1, 0.1, Box::new(sine_hz(110.0)));
+    /// ```
+    pub fn set_loop(&mut self, start: f48, end: f48) {
+        assert!(self.replay_events && start >= 0.0 && start < end);
+        if let Some((sender, receiver)) = &mut self.front {
+            // Deallocate all past events.
+            while receiver.try_recv().is_ok() {}
+            if sender.try_send(Message48::Loop(Some((start, end)))).is_ok() {}
+        }
+        self.looping = Some((start, end));
+    }
+
+    /// Stop looping. Playback continues from the current time.
+    pub fn clear_loop(&mut self) {
+        if let Some((sender, receiver)) = &mut self.front {
+            // Deallocate all past events.
+            while receiver.try_recv().is_ok() {}
+            if sender.try_send(Message48::Loop(None)).is_ok() {}
+        }
+        self.looping = None;
+    }
+
+    /// Current loop region (start, end) in seconds, if any.
+    pub fn loop_region(&self) -> Option<(f48, f48)> {
+        self.looping
+    }
+
+    /// Set loop region. This is an internal method.
+    pub(crate) fn set_loop_region(&mut self, looping: Option<(f48, f48)>) {
+        self.looping = looping;
+    }
+
+    /// Jump back to `time`, rearming all events.
+    fn rewind(&mut self, time: f48) {
+        while let Some(ready) = self.ready.pop() {
+            self.active.push(ready);
+        }
+        while let Some(past) = self.past.pop() {
+            self.active.push(past);
+        }
+        for i in 0..self.active.len() {
+            self.active[i].unit.reset();
+        }
+        while let Some(active) = self.active.pop() {
+            self.ready.push(active);
+        }
+        self.active_map.clear();
+        self.levels.clear();
+        self.time = time;
+        self.active_threshold = -f48::INFINITY;
+    }
+
     /// Move units that start before the end time to the active set.
     fn ready_to_active(&mut self, next_end_time: f48) {
         self.active_threshold = next_end_time - self.sample_duration * 0.5;
@@ -556,7 +1009,6 @@ impl Sequencer48 {
             // which always falls on a sample.
             if ready.start_time < self.active_threshold {
                 if let Some(mut ready) = self.ready.pop() {
-                    self.active_map.insert(ready.id, self.active.len());
                     // Check for edits to the event.
                     if self.edit_map.contains_key(&ready.id) {
                         let edit = &self.edit_map[&ready.id];
@@ -564,7 +1016,7 @@ impl Sequencer48 {
                         ready.end_time = edit.end_time;
                         self.edit_map.remove(&ready.id);
                     }
-                    self.active.push(ready);
+                    self.push_active(ready);
                 }
             } else {
                 break;
@@ -585,6 +1037,10 @@ impl Sequencer48 {
         let (sender_b, receiver_b) = channel(16384);
         let mut sequencer = self.clone();
         sequencer.allocate();
+        let clock = Arc::new(AtomicU64::new((self.time as f64).to_bits()));
+        sequencer.clock = Some(Arc::clone(&clock));
+        self.schedule = self.events().filter(|x| x.end_time > self.time).collect();
+        self.clock = Some(clock);
         self.front = Some((sender_a, receiver_b));
         SequencerBackend48::new(sender_b, receiver_a, sequencer)
     }
@@ -599,6 +1055,102 @@ impl Sequencer48 {
         self.replay_events
     }
 
+    /// Current time in seconds. For frontends, this is the time of the backend
+    /// as of its latest processed block.
+    fn current_time(&self) -> f48 {
+        match &self.clock {
+            Some(clock) if self.front.is_some() => {
+                f64::from_bits(clock.load(std::sync::atomic::Ordering::Relaxed)) as f48
+            }
+            _ => self.time,
+        }
+    }
+
+    /// Publish current time to the frontend. This is an internal method.
+    pub(crate) fn publish_time(&self) {
+        if let Some(clock) = &self.clock {
+            clock.store(
+                (self.time as f64).to_bits(),
+                std::sync::atomic::Ordering::Relaxed,
+            );
+        }
+    }
+
+    /// Information on active and ready events, with pending edits applied.
+    fn events(&self) -> impl Iterator<Item = EventInfo48> + '_ {
+        self.active.iter().chain(self.ready.iter()).map(|event| {
+            let mut info = event.info();
+            if let Some(edit) = self.edit_map.get(&event.id) {
+                info.end_time = edit.end_time;
+                info.fade_out = edit.fade_out;
+            }
+            info
+        })
+    }
+
+    /// Record an event pushed through the frontend, forgetting events that have ended.
+    fn schedule_event(&mut self, info: EventInfo48) {
+        let now = self.current_time();
+        self.schedule.retain(|x| x.end_time > now);
+        self.schedule.push(info);
+    }
+
+    /// Record an edit made through the frontend.
+    fn schedule_edit(&mut self, id: EventId, end_time: f48, fade_out_time: f48) {
+        if let Some(info) = self.schedule.iter_mut().find(|x| x.id == id) {
+            info.end_time = end_time;
+            info.fade_out = fade_out_time;
+        }
+    }
+
+    /// Iterate over events that have not ended yet, in no particular order.
+    /// This includes events that are playing and events scheduled to start later.
+    /// Frontends report events as they were pushed and edited, without disturbing
+    /// the backend. Relative times are estimated from the latest time of the backend,
+    /// and changes made by the backend, such as voice stealing, are not reflected.
+    ///
+    /// ### Example: Find Playing Events
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut sequencer = Sequencer64::new(false, 1);
+    /// sequencer.push(0.0, 1.0, Fade::Smooth, 0.1, 0.1, Box::new(sine_hz(220.0)));
+    /// let now = sequencer.time();
+    /// let playing = sequencer.active_events().filter(|x| x.start_time <= now).count();
+    /// assert_eq!(playing, 1);
+    /// ```
+    pub fn active_events(&self) -> impl Iterator<Item = EventInfo48> + '_ {
+        let now = self.current_time();
+        let front = self.has_backend();
+        self.schedule
+            .iter()
+            .cloned()
+            .chain(self.events().filter(move |_| !front))
+            .filter(move |x| x.end_time > now)
+    }
+
+    /// Look up an event that has not ended yet. See `active_events`.
+    pub fn event(&self, id: EventId) -> Option<EventInfo48> {
+        self.active_events().find(|x| x.id == id)
+    }
+
+    /// Render `duration` seconds of the arrangement offline at `sample_rate` Hz,
+    /// starting from the current time. Events that have not ended by then,
+    /// including events with an infinite end time, are cut off at the end.
+    /// Sets the sample rate of the sequencer. Frontends cannot be rendered.
+    ///
+    /// ### Example: Bounce A Note
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut sequencer = Sequencer64::new(false, 1);
+    /// sequencer.push(0.5, 1.5, Fade::Smooth, 0.01, 0.1, Box::new(sine_hz(440.0)));
+    /// let wave = sequencer.render(2.0, 44100.0);
+    /// assert_eq!(wave.length(), 88200);
+    /// ```
+    pub fn render(&mut self, duration: f64, sample_rate: f64) -> Wave48 {
+        assert!(!self.has_backend());
+        Wave48::render(sample_rate, duration, self)
+    }
+
     /// Get past events. This is an internal method.
     pub(crate) fn get_past_event(&mut self) -> Option<Event48> {
         self.past.pop()
@@ -612,6 +1164,7 @@ impl Sequencer48 {
     /// Get active events. This is an internal method.
     pub(crate) fn get_active_event(&mut self) -> Option<Event48> {
         if let Some(event) = self.active.pop() {
+            self.levels.pop();
             self.active_map.remove(&event.id);
             return Some(event);
         }
@@ -641,15 +1194,18 @@ impl AudioUnit48 for Sequencer48 {
                 self.ready.push(active);
             }
             self.active_map.clear();
+            self.levels.clear();
         } else {
             while let Some(_ready) = self.ready.pop() {}
             while let Some(_past) = self.past.pop() {}
             while let Some(_active) = self.active.pop() {}
             self.edit_map.clear();
             self.active_map.clear();
+            self.levels.clear();
         }
         self.time = 0.0;
         self.active_threshold = -f48::INFINITY;
+        self.declick.restart();
     }
 
     fn set_sample_rate(&mut self, sample_rate: f64) {
@@ -672,7 +1228,9 @@ impl AudioUnit48 for Sequencer48 {
                 self.ready.push(active);
             }
             self.active_map.clear();
+            self.levels.clear();
             self.active_threshold = -f48::INFINITY;
+            self.declick.restart();
         }
     }
 
@@ -681,11 +1239,18 @@ impl AudioUnit48 for Sequencer48 {
         if !self.replay_events {
             while let Some(_past) = self.past.pop() {}
         }
+        if let Some((start, end)) = self.looping {
+            if self.time >= end - 0.5 * self.sample_duration {
+                self.rewind(start);
+            }
+        }
         for channel in 0..self.outputs {
             output[channel] = 0.0;
         }
         let end_time = self.time + self.sample_duration;
         self.ready_to_active(end_time);
+        let track_level = self.tracks_level();
+        let level_decay = exp(-self.sample_duration / STEAL_RELEASE as f48);
         let mut i = 0;
         while i < self.active.len() {
             if self.active[i].end_time <= self.time + 0.5 * self.sample_duration {
@@ -695,6 +1260,7 @@ impl AudioUnit48 for Sequencer48 {
                         .insert(self.active[self.active.len() - 1].id, i);
                 }
                 self.past.push(self.active.swap_remove(i));
+                self.levels.swap_remove(i);
             } else {
                 self.active[i].unit.tick(input, &mut self.tick_buffer);
                 if self.active[i].fade_in > 0.0 {
@@ -740,15 +1306,40 @@ impl AudioUnit48 for Sequencer48 {
                     }
                 }
                 for channel in 0..self.outputs {
-                    output[channel] += self.tick_buffer[channel];
+                    output[channel] += self.tick_buffer[channel] * self.active[i].gain;
+                }
+                if track_level {
+                    let peak = self
+                        .tick_buffer
+                        .iter()
+                        .fold(0.0, |peak, x| max(peak, abs(*x)));
+                    let gain = self.active[i].gain;
+                    self.levels[i] = max(peak * abs(gain), self.levels[i] * level_decay);
                 }
                 i += 1;
             }
         }
+        if self.declick.is_active() {
+            self.declick.apply(self.sample_rate, 1, &mut [output]);
+        }
         self.time = end_time;
     }
 
     fn process(&mut self, size: usize, input: &[&[f48]], output: &mut [&mut [f48]]) {
+        if let Some((_start, end)) = self.looping {
+            if self.time + self.sample_duration * (size as f48 - 0.5) >= end {
+                // The block touches the loop end, so render it sample by sample.
+                let mut buffer = std::mem::take(&mut self.loop_buffer);
+                for i in 0..size {
+                    self.tick(&[], &mut buffer);
+                    for channel in 0..self.outputs {
+                        output[channel][i] = buffer[channel];
+                    }
+                }
+                self.loop_buffer = buffer;
+                return;
+            }
+        }
         if !self.replay_events {
             while let Some(_past) = self.past.pop() {}
         }
@@ -759,6 +1350,8 @@ impl AudioUnit48 for Sequencer48 {
         }
         let end_time = self.time + self.sample_duration * size as f48;
         self.ready_to_active(end_time);
+        let track_level = self.tracks_level();
+        let level_decay = exp(-self.sample_duration * size as f48 / STEAL_RELEASE as f48);
         let buffer_output = self.buffer.get_mut(self.outputs);
         let mut i = 0;
         while i < self.active.len() {
@@ -769,6 +1362,7 @@ impl AudioUnit48 for Sequencer48 {
                         .insert(self.active[self.active.len() - 1].id, i);
                 }
                 self.past.push(self.active.swap_remove(i));
+                self.levels.swap_remove(i);
             } else {
                 let start_index = if self.active[i].start_time <= self.time {
                     0
@@ -806,15 +1400,26 @@ impl AudioUnit48 for Sequencer48 {
                         self.active[i].end_time,
                         buffer_output,
                     );
+                    let gain = self.active[i].gain;
                     for channel in 0..self.outputs {
                         for j in start_index..end_index {
-                            output[channel][j] += buffer_output[channel][j - start_index];
+                            output[channel][j] += buffer_output[channel][j - start_index] * gain;
                         }
                     }
+                    if track_level {
+                        let length = end_index - start_index;
+                        let peak = buffer_output.iter().fold(0.0, |peak, channel| {
+                            channel[..length]
+                                .iter()
+                                .fold(peak, |peak, x| max(peak, abs(*x)))
+                        });
+                        self.levels[i] = max(peak * abs(gain), self.levels[i] * level_decay);
+                    }
                 }
                 i += 1;
             }
         }
+        self.declick.apply(self.sample_rate, size, output);
         self.time = end_time;
     }
 
@@ -842,4 +1447,19 @@ impl AudioUnit48 for Sequencer48 {
     fn footprint(&self) -> usize {
         std::mem::size_of::<Self>()
     }
+
+    fn total_footprint(&self) -> usize {
+        let events = self
+            .active
+            .iter()
+            .chain(self.ready.iter())
+            .chain(self.past.iter());
+        let samples = self.tick_buffer.capacity() + self.loop_buffer.capacity();
+        std::mem::size_of::<Self>()
+            + events
+                .map(|event| event.unit.total_footprint())
+                .sum::<usize>()
+            + self.buffer.footprint()
+            + samples * std::mem::size_of::<f48>()
+    }
 }
@@ -89,8 +89,19 @@ pub enum Shape<T: Real> {
     /// Smoothing timescale is specified in seconds.
     /// It is the time it takes for level estimation to move halfway to a new level.
     /// The argument to `tanh` is divided by the RMS level of the signal and multiplied by hardness.
+    /// In other words, hardness is the target level that the RMS level of the signal
+    /// is normalized to before distortion.
     /// Minimum estimated signal level for adaptive distortion is approximately -60 dB.
     AdaptiveTanh(T, T),
+    /// Adaptive normalizing `tanh` distortion with detection window and target level as parameters.
+    /// The detection window is specified in milliseconds. It is the time constant
+    /// of RMS level estimation: after a change in level, the estimate (and output gain)
+    /// covers 63% of the distance to the new level in one window.
+    /// The signal is normalized so that its RMS level maps to the target level
+    /// before `tanh` distortion is applied. Low target levels leave the signal nearly
+    /// undistorted at a constant RMS level, while high target levels saturate it.
+    /// This is equivalent to `AdaptiveTanh` with a timescale of `ln(2)` windows.
+    AdaptiveTanhWindow(T, T),
 }
 
 /// Waveshaper with various shaping modes.
@@ -126,8 +137,14 @@ impl<T: Real> AudioNode for Shaper<T> {
     }
 
     fn set_sample_rate(&mut self, sample_rate: f64) {
-        if let Shape::AdaptiveTanh(timescale, _) = self.shape {
-            self.smoothing = T::from_f64(pow(0.5, 1.0 / (timescale.to_f64() * sample_rate)));
+        match self.shape {
+            Shape::AdaptiveTanh(timescale, _) => {
+                self.smoothing = T::from_f64(pow(0.5, 1.0 / (timescale.to_f64() * sample_rate)));
+            }
+            Shape::AdaptiveTanhWindow(window, _) => {
+                self.smoothing = T::from_f64(exp(-1000.0 / (window.to_f64() * sample_rate)));
+            }
+            _ => (),
         }
     }
 
@@ -149,7 +166,7 @@ impl<T: Real> AudioNode for Shaper<T> {
                 let y = floor(x);
                 [(y + smooth9(smooth9(x - y))) / levels].into()
             }
-            Shape::AdaptiveTanh(_timescale, hardness) => {
+            Shape::AdaptiveTanh(_, hardness) | Shape::AdaptiveTanhWindow(_, hardness) => {
                 self.state = self.smoothing * self.state
                     + (T::one() - self.smoothing) * (T::from_f32(1.0e-6) + squared(input));
                 [tanh(input * hardness / sqrt(self.state))].into()
@@ -203,7 +220,7 @@ impl<T: Real> AudioNode for Shaper<T> {
                     *x = (b + smooth9(smooth9(a - b))) / levels;
                 }
             }
-            Shape::AdaptiveTanh(_timescale, hardness) => {
+            Shape::AdaptiveTanh(_, hardness) | Shape::AdaptiveTanhWindow(_, hardness) => {
                 for (x, y) in output[0..size].iter_mut().zip(input[0..size].iter()) {
                     self.state = self.smoothing * self.state
                         + (T::one() - self.smoothing) * (T::from_f32(1.0e-6) + squared(*y));
@@ -219,3 +236,166 @@ impl<T: Real> AudioNode for Shaper<T> {
         output
     }
 }
+
+/// Waveshaping curves for the driven waveshaper.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DriveShape {
+    /// Apply `tanh` distortion to the driven signal.
+    Tanh,
+    /// Clip the driven signal to -1...1.
+    Clip,
+    /// Fold the driven signal back from -1 and 1, repeatedly if necessary.
+    Wavefold,
+}
+
+/// Triangle wavefolder: identity in -1...1, reflected back from the boundaries beyond.
+#[inline]
+fn wavefold<T: Real>(x: T) -> T {
+    let phase = (x + T::one()) * T::from_f64(0.25);
+    T::one() - T::new(4) * abs(phase - floor(phase) - T::from_f64(0.5))
+}
+
+/// Waveshaper with a per-sample drive input.
+/// The input signal is multiplied by the drive before shaping,
+/// so an envelope or LFO can modulate the intensity of distortion.
+/// - Input 0: input signal
+/// - Input 1: drive amount (1 = unity)
+/// - Output 0: shaped signal
+#[derive(Clone)]
+pub struct DriveShaper<T: Real> {
+    shape: DriveShape,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Real> DriveShaper<T> {
+    /// Create new drive shaper with `shape`.
+    pub fn new(shape: DriveShape) -> Self {
+        Self {
+            shape,
+            _marker: PhantomData::default(),
+        }
+    }
+
+    /// Waveshaping curve.
+    pub fn shape(&self) -> DriveShape {
+        self.shape
+    }
+}
+
+impl<T: Real> AudioNode for DriveShaper<T> {
+    const ID: u64 = 88;
+    type Sample = T;
+    type Inputs = U2;
+    type Outputs = U1;
+    type Setting = ();
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let x = input[0] * input[1];
+        let y = match self.shape {
+            DriveShape::Tanh => tanh(x),
+            DriveShape::Clip => clamp11(x),
+            DriveShape::Wavefold => wavefold(x),
+        };
+        [y].into()
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = input[0].combine_nonlinear(input[1], 0.0);
+        output
+    }
+}
+
+/// Instantaneous clipper with adjustable threshold and knee. Signals within
+/// `threshold` - `knee` pass unchanged. With a nonzero knee, the transfer curve
+/// bends quadratically into the threshold over a region of width 2 * `knee`.
+/// There is no lookahead or envelope, unlike in the limiter.
+/// - Input 0: input signal
+/// - Output 0: clipped signal
+#[derive(Clone)]
+pub struct Clipper<T: Real> {
+    threshold: T,
+    knee: T,
+}
+
+impl<T: Real> Clipper<T> {
+    /// Create new clipper. Setting `knee` to zero results in hard clipping.
+    pub fn new(threshold: T, knee: T) -> Self {
+        assert!(threshold > T::zero() && knee >= T::zero() && knee <= threshold);
+        Self { threshold, knee }
+    }
+
+    /// Clipping threshold in amplitude.
+    pub fn threshold(&self) -> T {
+        self.threshold
+    }
+
+    /// Set clipping threshold in amplitude. The knee is narrowed if necessary.
+    pub fn set_threshold(&mut self, threshold: T) {
+        assert!(threshold > T::zero());
+        self.threshold = threshold;
+        self.knee = min(self.knee, threshold);
+    }
+
+    /// Half width of the soft knee in amplitude.
+    pub fn knee(&self) -> T {
+        self.knee
+    }
+
+    #[inline]
+    fn clip(&self, x: T) -> T {
+        let y = abs(x);
+        let start = self.threshold - self.knee;
+        let y = if y <= start {
+            y
+        } else if y >= self.threshold + self.knee {
+            self.threshold
+        } else {
+            let d = y - start;
+            y - d * d / (T::new(4) * self.knee)
+        };
+        signum(x) * y
+    }
+}
+
+impl<T: Real> AudioNode for Clipper<T> {
+    const ID: u64 = 107;
+    type Sample = T;
+    type Inputs = U1;
+    type Outputs = U1;
+    type Setting = T;
+
+    fn set(&mut self, setting: Self::Setting) {
+        self.set_threshold(setting);
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        [self.clip(input[0])].into()
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        for (o, i) in output[0][..size].iter_mut().zip(input[0][..size].iter()) {
+            *o = self.clip(*i);
+        }
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = input[0].distort(0.0);
+        output
+    }
+}
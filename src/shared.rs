@@ -2,8 +2,10 @@
 
 use super::audionode::*;
 use super::combinator::*;
+use super::signal::*;
 use super::*;
 use numeric_array::typenum::*;
+use std::marker::PhantomData;
 use std::sync::atomic::AtomicU32;
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
@@ -161,6 +163,260 @@ impl<T: Atomic> AudioNode for Var<T> {
     }
 }
 
+/// Outputs the value of a shared variable on `N` identical channels.
+/// Unlike `Var`, which has a single output, this is intended as a control bus
+/// feeding several inputs at once.
+pub struct DcVar<N: Size<T>, T: Atomic> {
+    value: Arc<T::Storage>,
+    _marker: PhantomData<N>,
+}
+
+impl<N: Size<T>, T: Atomic> Clone for DcVar<N, T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<N: Size<T>, T: Atomic> DcVar<N, T> {
+    pub fn new(shared: &Shared<T>) -> Self {
+        Self {
+            value: Arc::clone(shared.get_shared()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the value of this variable.
+    pub fn set_value(&self, t: T) {
+        T::store(&self.value, t)
+    }
+
+    /// Get the value of this variable.
+    pub fn value(&self) -> T {
+        T::get_stored(&self.value)
+    }
+}
+
+impl<N: Size<T>, T: Atomic> AudioNode for DcVar<N, T> {
+    const ID: u64 = 93;
+
+    type Sample = T;
+    type Inputs = U0;
+    type Outputs = N;
+    type Setting = ();
+
+    #[inline]
+    fn tick(
+        &mut self,
+        _: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        Frame::splat(self.value())
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        _input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        let sample = self.value();
+        for channel in output.iter_mut() {
+            channel[..size].fill(sample);
+        }
+    }
+}
+
+/// Passes its input through and writes the latest input sample to a shared variable.
+/// A lighter alternative to `Snoop` for reading slowly varying control signals,
+/// such as envelopes and LFOs, from another thread.
+pub struct VarTap<T: Atomic> {
+    value: Arc<T::Storage>,
+}
+
+impl<T: Atomic> Clone for VarTap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+        }
+    }
+}
+
+impl<T: Atomic> VarTap<T> {
+    /// Create a new tap that writes its input to the `shared` variable.
+    pub fn new(shared: &Shared<T>) -> Self {
+        Self {
+            value: Arc::clone(shared.get_shared()),
+        }
+    }
+
+    /// Get the latest value written.
+    pub fn value(&self) -> T {
+        T::get_stored(&self.value)
+    }
+}
+
+impl<T: Atomic> AudioNode for VarTap<T> {
+    const ID: u64 = 101;
+
+    type Sample = T;
+    type Inputs = U1;
+    type Outputs = U1;
+    type Setting = ();
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        T::store(&self.value, input[0]);
+        *input
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        if size > 0 {
+            // Only the last sample of the block is visible outside.
+            T::store(&self.value, input[0][size - 1]);
+        }
+        output[0][..size].clone_from_slice(&input[0][..size]);
+    }
+
+    fn route(&mut self, input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        input.clone()
+    }
+}
+
+/// Outputs the value of a shared variable smoothed with a one-pole lowpass.
+/// The shared variable is read atomically on every sample, also in block processing.
+/// Setting: time constant in seconds.
+/// - Output 0: smoothed value
+pub struct VarSmooth<T: Atomic> {
+    value: Arc<T::Storage>,
+    /// Smoother state.
+    state: T,
+    /// Per sample smoothing coefficient.
+    coeff: T,
+    /// Time constant in seconds.
+    time: f64,
+    sample_rate: f64,
+    /// Whether the state is set. The first sample after a reset jumps to the value.
+    active: bool,
+}
+
+impl<T: Atomic> Clone for VarSmooth<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+            state: self.state,
+            coeff: self.coeff,
+            time: self.time,
+            sample_rate: self.sample_rate,
+            active: self.active,
+        }
+    }
+}
+
+impl<T: Atomic> VarSmooth<T> {
+    /// Create new smoothed variable with time constant `time` in seconds.
+    pub fn new(shared: &Shared<T>, time: f64) -> Self {
+        assert!(time >= 0.0);
+        let mut node = Self {
+            value: Arc::clone(shared.get_shared()),
+            state: T::zero(),
+            coeff: T::one(),
+            time,
+            sample_rate: DEFAULT_SR,
+            active: false,
+        };
+        node.set_time(time);
+        node
+    }
+
+    /// Get the value of the shared variable. This is the value being approached.
+    pub fn value(&self) -> T {
+        T::get_stored(&self.value)
+    }
+
+    /// Time constant in seconds.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Set time constant in seconds. The output covers 1 - 1/e (about 63%)
+    /// of a step in this time. Zero disables smoothing.
+    pub fn set_time(&mut self, time: f64) {
+        assert!(time >= 0.0);
+        self.time = time;
+        let samples = time * self.sample_rate;
+        self.coeff = if samples > 0.0 {
+            T::from_f64(1.0 - (-1.0 / samples).exp())
+        } else {
+            T::one()
+        };
+    }
+
+    #[inline]
+    fn advance(&mut self) -> T {
+        let value = self.value();
+        if self.active {
+            self.state += (value - self.state) * self.coeff;
+        } else {
+            self.state = value;
+            self.active = true;
+        }
+        self.state
+    }
+}
+
+impl<T: Atomic> AudioNode for VarSmooth<T> {
+    const ID: u64 = 130;
+
+    type Sample = T;
+    type Inputs = U0;
+    type Outputs = U1;
+    type Setting = T;
+
+    fn set(&mut self, setting: Self::Setting) {
+        self.set_time(setting.to_f64());
+    }
+
+    fn reset(&mut self) {
+        self.active = false;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            self.set_time(self.time);
+        }
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        _: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        [self.advance()].into()
+    }
+
+    fn process(
+        &mut self,
+        size: usize,
+        _input: &[&[Self::Sample]],
+        output: &mut [&mut [Self::Sample]],
+    ) {
+        for x in output[0][..size].iter_mut() {
+            *x = self.advance();
+        }
+    }
+}
+
 /// Outputs the value of a shared variable mapped through a function.
 #[derive(Default)]
 pub struct VarFn<T, F, R>
@@ -0,0 +1,72 @@
+//! SIMD kernels for block processing.
+//! On x86_64 the kernels use SSE2, which is always available there.
+//! Other architectures use the equivalent scalar loops.
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Feedforward part of a biquad filter:
+/// `y[i] = b0 * x[i + 2] + b1 * x[i + 1] + b2 * x[i]` for `i` in `0..y.len()`.
+/// `x` contains two samples of history followed by the block.
+/// Operations are done in the same order as the scalar formula, so results are identical.
+#[inline]
+pub fn biquad_feedforward_f64(b0: f64, b1: f64, b2: f64, x: &[f64], y: &mut [f64]) {
+    let size = y.len();
+    assert!(x.len() >= size + 2);
+    #[allow(unused_mut)]
+    let mut i = 0;
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: SSE2 is part of the x86_64 baseline, and all loads and stores are within bounds.
+    unsafe {
+        let c0 = _mm_set1_pd(b0);
+        let c1 = _mm_set1_pd(b1);
+        let c2 = _mm_set1_pd(b2);
+        while i + 2 <= size {
+            let x0 = _mm_loadu_pd(x.as_ptr().add(i));
+            let x1 = _mm_loadu_pd(x.as_ptr().add(i + 1));
+            let x2 = _mm_loadu_pd(x.as_ptr().add(i + 2));
+            let v = _mm_add_pd(
+                _mm_add_pd(_mm_mul_pd(c0, x2), _mm_mul_pd(c1, x1)),
+                _mm_mul_pd(c2, x0),
+            );
+            _mm_storeu_pd(y.as_mut_ptr().add(i), v);
+            i += 2;
+        }
+    }
+    for (j, y) in y.iter_mut().enumerate().skip(i) {
+        *y = b0 * x[j + 2] + b1 * x[j + 1] + b2 * x[j];
+    }
+}
+
+/// Feedforward part of a biquad filter:
+/// `y[i] = b0 * x[i + 2] + b1 * x[i + 1] + b2 * x[i]` for `i` in `0..y.len()`.
+/// `x` contains two samples of history followed by the block.
+/// Operations are done in the same order as the scalar formula, so results are identical.
+#[inline]
+pub fn biquad_feedforward_f32(b0: f32, b1: f32, b2: f32, x: &[f32], y: &mut [f32]) {
+    let size = y.len();
+    assert!(x.len() >= size + 2);
+    #[allow(unused_mut)]
+    let mut i = 0;
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: SSE2 is part of the x86_64 baseline, and all loads and stores are within bounds.
+    unsafe {
+        let c0 = _mm_set1_ps(b0);
+        let c1 = _mm_set1_ps(b1);
+        let c2 = _mm_set1_ps(b2);
+        while i + 4 <= size {
+            let x0 = _mm_loadu_ps(x.as_ptr().add(i));
+            let x1 = _mm_loadu_ps(x.as_ptr().add(i + 1));
+            let x2 = _mm_loadu_ps(x.as_ptr().add(i + 2));
+            let v = _mm_add_ps(
+                _mm_add_ps(_mm_mul_ps(c0, x2), _mm_mul_ps(c1, x1)),
+                _mm_mul_ps(c2, x0),
+            );
+            _mm_storeu_ps(y.as_mut_ptr().add(i), v);
+            i += 4;
+        }
+    }
+    for (j, y) in y.iter_mut().enumerate().skip(i) {
+        *y = b0 * x[j + 2] + b1 * x[j + 1] + b2 * x[j];
+    }
+}
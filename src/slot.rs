@@ -74,6 +74,7 @@ impl Slot48 {
             sender: sender_a,
             buffer: Buffer::with_channels(outputs),
             tick: vec![0.0; outputs],
+            declick: FadeIn::default(),
         };
         (slot, backend)
     }
@@ -121,6 +122,8 @@ pub struct SlotBackend48 {
     sender: Sender<SlotMessage48>,
     buffer: Buffer<f48>,
     tick: Vec<f48>,
+    /// Fade-in applied after a reset or sample rate change.
+    declick: FadeIn<f48>,
 }
 
 #[duplicate_item(
@@ -148,6 +151,7 @@ impl Clone for SlotBackend48 {
             sender,
             buffer: Buffer::with_channels(self.outputs),
             tick: self.tick.clone(),
+            declick: self.declick.clone(),
         }
     }
 }
@@ -158,6 +162,18 @@ impl Clone for SlotBackend48 {
     [ f32 ]   [ Slot32 ]   [ SlotMessage32 ]   [ SlotBackend32 ]   [ AudioUnit32 ];
 )]
 impl SlotBackend48 {
+    /// Fade in output over `time` seconds after a reset or sample rate change
+    /// so that a restarted stream does not begin with a click.
+    /// Zero (the default) disables the fade.
+    pub fn set_declick(&mut self, time: f48) {
+        self.declick.set_time(time);
+    }
+
+    /// Fade-in time in seconds after a reset or sample rate change.
+    pub fn declick(&self) -> f48 {
+        self.declick.time()
+    }
+
     /// Handle updates.
     fn handle_messages(&mut self) {
         while let Ok(message) = self.receiver.try_recv() {
@@ -209,10 +225,14 @@ impl AudioUnit48 for SlotBackend48 {
             if self.sender.try_send(SlotMessage48::Return(next)).is_ok() {}
         }
         self.current.reset();
+        self.declick.restart();
     }
 
     #[allow(clippy::unnecessary_cast)]
     fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate != sample_rate as f48 {
+            self.declick.restart();
+        }
         self.sample_rate = sample_rate as f48;
         self.current.set_sample_rate(sample_rate);
         if let Some(next) = self.next.as_deref_mut() {
@@ -241,6 +261,9 @@ impl AudioUnit48 for SlotBackend48 {
                 self.next_phase();
             }
         }
+        if self.declick.is_active() {
+            self.declick.apply(self.sample_rate, 1, &mut [output]);
+        }
     }
 
     fn process(&mut self, size: usize, input: &[&[f48]], output: &mut [&mut [f48]]) {
@@ -302,6 +325,7 @@ impl AudioUnit48 for SlotBackend48 {
                 self.next_phase();
             }
         }
+        self.declick.apply(self.sample_rate, size, output);
     }
 
     fn inputs(&self) -> usize {
@@ -338,6 +362,16 @@ impl AudioUnit48 for SlotBackend48 {
         std::mem::size_of::<SlotBackend48>()
     }
 
+    fn total_footprint(&self) -> usize {
+        std::mem::size_of::<SlotBackend48>()
+            + self.current.total_footprint()
+            + self.next.as_ref().map_or(0, |next| next.total_footprint())
+            + self
+                .latest
+                .as_ref()
+                .map_or(0, |latest| latest.total_footprint())
+    }
+
     fn allocate(&mut self) {
         self.current.allocate();
         if let Some(next) = self.next.as_deref_mut() {
@@ -347,4 +381,13 @@ impl AudioUnit48 for SlotBackend48 {
             latest.allocate();
         }
     }
+
+    fn is_allocated(&self) -> bool {
+        self.current.is_allocated()
+            && self.next.as_ref().map_or(true, |next| next.is_allocated())
+            && self
+                .latest
+                .as_ref()
+                .map_or(true, |latest| latest.is_allocated())
+    }
 }
@@ -1,6 +1,7 @@
 //! The snoop node shares audio data with a frontend thread.
 
 use super::audionode::*;
+use super::math::*;
 use super::signal::*;
 use super::*;
 use numeric_array::*;
@@ -46,6 +47,10 @@ pub struct Snoop<T: Float> {
     index: usize,
     total: u64,
     latest: Vec<T>,
+    /// Peak level held by `peak_hold`.
+    held: T,
+    /// Value of `total` at the previous call to `peak_hold`.
+    held_total: u64,
 }
 
 impl<T: Float> Snoop<T> {
@@ -58,6 +63,8 @@ impl<T: Float> Snoop<T> {
             index: 0,
             total: 0,
             latest: vec![T::zero(); capacity],
+            held: T::zero(),
+            held_total: 0,
         };
         let snoop_backend = SnoopBackend {
             index: 0,
@@ -82,6 +89,49 @@ impl<T: Float> Snoop<T> {
         self.total
     }
 
+    /// Number of buffered samples available for analysis.
+    fn available(&self) -> usize {
+        min(self.total, self.latest.len() as u64) as usize
+    }
+
+    /// RMS level of the latest `window` samples.
+    /// The window is measured in samples and is limited to the buffered samples.
+    /// Returns zero if no samples have been received.
+    pub fn rms(&self, window: usize) -> T {
+        let window = min(window, self.available());
+        if window == 0 {
+            return T::zero();
+        }
+        let mut sum = T::zero();
+        for i in 0..window {
+            sum += squared(self.at(i));
+        }
+        T::from_f64(sqrt(sum.to_f64() / window as f64))
+    }
+
+    /// Peak absolute level of the buffered samples.
+    pub fn peak(&self) -> T {
+        (0..self.available()).fold(T::zero(), |peak, i| max(peak, abs(self.at(i))))
+    }
+
+    /// Peak absolute level held across calls. The held peak is updated
+    /// with samples received since the previous call and decays by a factor of `decay`
+    /// (0 <= `decay` <= 1) per sample, for example, 0.9999 for a slow falloff.
+    /// Call this after `update`.
+    pub fn peak_hold(&mut self, decay: T) -> T {
+        let received = self.total - self.held_total;
+        let new = min(received, self.latest.len() as u64) as usize;
+        // Samples that were overwritten before we saw them only decay the peak.
+        if received > new as u64 {
+            self.held *= pow(decay, T::from_f64((received - new as u64) as f64));
+        }
+        for i in (0..new).rev() {
+            self.held = max(self.held * decay, abs(self.at(i)));
+        }
+        self.held_total = self.total;
+        self.held
+    }
+
     /// Get the next buffer of data, if available.
     /// Either this method or `update` should be polled repeatedly.
     pub fn get(&mut self) -> Option<SnoopBuffer<T>> {
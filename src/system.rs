@@ -108,4 +108,8 @@ impl<T: Float, X: AudioNode, F: FnMut(T, T, &mut X) + Clone + Sync + Send> Audio
     fn allocate(&mut self) {
         self.x.allocate();
     }
+
+    fn is_allocated(&self) -> bool {
+        self.x.is_allocated()
+    }
 }
@@ -62,6 +62,41 @@ fn write_wav_header<W: Write>(
     std::io::Result::Ok(())
 }
 
+/// Read a little endian 16-bit value from WAV data at byte offset `i`.
+#[inline]
+fn read16(bytes: &[u8], i: usize) -> u16 {
+    u16::from_le_bytes([bytes[i], bytes[i + 1]])
+}
+
+/// Read a little endian 32-bit value from WAV data at byte offset `i`.
+#[inline]
+fn read32(bytes: &[u8], i: usize) -> u32 {
+    u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]])
+}
+
+/// Error for malformed or unsupported WAV data.
+fn wav_error(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+}
+
+/// Decoder of a single sample in a WAV file, normalized to -1...1 for integer formats.
+type WavDecoder = fn(&[u8]) -> f64;
+
+/// Find the sample decoder for WAV `format` tag and `bits` per sample.
+fn wav_decoder(format: u16, bits: usize) -> std::io::Result<WavDecoder> {
+    // Audio data format 1 = WAVE_FORMAT_PCM, 3 = WAVE_FORMAT_IEEE_FLOAT.
+    let decoder: WavDecoder = match (format, bits) {
+        (1, 8) => |b| (b[0] as f64 - 128.0) / 128.0,
+        (1, 16) => |b| i16::from_le_bytes([b[0], b[1]]) as f64 / 32768.0,
+        (1, 24) => |b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f64 / 8388608.0,
+        (1, 32) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64 / 2147483648.0,
+        (3, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+        (3, 64) => |b| f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]),
+        _ => return Err(wav_error("unsupported WAV sample format")),
+    };
+    Ok(decoder)
+}
+
 /// Multichannel wave.
 #[duplicate_item(
     f48       Wave48       AudioUnit48;
@@ -515,6 +550,49 @@ impl Wave48 {
         }
     }
 
+    /// Render independent generators `units` on multiple threads and sum them
+    /// into a wave with length `duration` seconds. Sets the sample rate of each unit.
+    /// Does not discard pre-delay. The units must have no inputs and
+    /// an equal number of outputs.
+    ///
+    /// Each unit is rendered with `render` on its own task and the results are
+    /// summed in order, so the result equals a serial render sample-for-sample.
+    /// Units are moved across threads, which `Send + Sync` units permit.
+    /// Units may share `Shared` variables only if nothing writes to them during rendering.
+    ///
+    /// ### Example: Render A Chord
+    /// ```
+    /// use fundsp::hacker::*;
+    /// let mut voices: Vec<Box<dyn AudioUnit64>> = vec![Box::new(sine_hz(220.0)), Box::new(sine_hz(330.0))];
+    /// let wave = Wave64::render_parallel(44100.0, 1.0, &mut voices);
+    /// assert!(wave.channels() == 1 && wave.duration() == 1.0);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn render_parallel(
+        sample_rate: f64,
+        duration: f64,
+        units: &mut [Box<dyn AudioUnit48>],
+    ) -> Self {
+        use rayon::prelude::*;
+        assert!(!units.is_empty());
+        let outputs = units[0].outputs();
+        assert!(units.iter().all(|unit| unit.outputs() == outputs));
+        let waves: Vec<Self> = units
+            .par_iter_mut()
+            .map(|unit| Self::render(sample_rate, duration, unit.as_mut()))
+            .collect();
+        let mut waves = waves.into_iter();
+        let mut wave = waves.next().unwrap();
+        for other in waves {
+            for (channel, other_channel) in wave.vec.iter_mut().zip(other.vec.iter()) {
+                for (x, y) in channel.iter_mut().zip(other_channel.iter()) {
+                    *x += *y;
+                }
+            }
+        }
+        wave
+    }
+
     /// Filter this wave with `node` and return the resulting wave.
     /// Sets the sample rate of `node`. Does not discard pre-delay.
     /// The `node` must have as many inputs as there are channels in this wave.
@@ -679,9 +757,85 @@ impl Wave48 {
         let mut file = File::create(path.as_ref())?;
         self.write_wav32(&mut file)
     }
+
+    /// Read a WAV file from a buffer. Supported formats are 8, 16, 24 and 32-bit PCM
+    /// and 32 and 64-bit float. Integer samples are normalized to the range -1...1.
+    /// Float samples are read as is.
+    pub fn read_wav<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(wav_error("not a WAV file"));
+        }
+        let mut format = None;
+        let mut data = None;
+        let mut i = 12;
+        while i + 8 <= bytes.len() {
+            let size = read32(&bytes, i + 4) as usize;
+            let body = i + 8;
+            // Tolerate a truncated last chunk.
+            let end = min(body + size, bytes.len());
+            match &bytes[i..i + 4] {
+                b"fmt " => {
+                    if end - body < 16 {
+                        return Err(wav_error("malformed WAV format chunk"));
+                    }
+                    let mut tag = read16(&bytes, body);
+                    // WAVE_FORMAT_EXTENSIBLE stores the format tag at the start of the subformat GUID.
+                    if tag == 0xfffe && end - body >= 26 {
+                        tag = read16(&bytes, body + 24);
+                    }
+                    let channels = read16(&bytes, body + 2) as usize;
+                    let sample_rate = read32(&bytes, body + 4) as f64;
+                    let bits = read16(&bytes, body + 14) as usize;
+                    format = Some((tag, channels, sample_rate, bits));
+                }
+                b"data" => data = Some(&bytes[body..end]),
+                _ => (),
+            }
+            // Chunks are padded to an even length.
+            i = body + size + (size & 1);
+        }
+        let (tag, channels, sample_rate, bits) =
+            format.ok_or_else(|| wav_error("missing WAV format chunk"))?;
+        let data = data.ok_or_else(|| wav_error("missing WAV data chunk"))?;
+        if channels == 0 || sample_rate <= 0.0 {
+            return Err(wav_error("malformed WAV format chunk"));
+        }
+        let decoder = wav_decoder(tag, bits)?;
+        let width = bits / 8;
+        let length = data.len() / (width * channels);
+        let mut wave = Self::with_capacity(channels, sample_rate, length);
+        for frame in data.chunks_exact(width * channels) {
+            for (channel, sample) in frame.chunks_exact(width).enumerate() {
+                wave.vec[channel].push(decoder(sample) as f48);
+            }
+        }
+        wave.len = length;
+        Ok(wave)
+    }
+
+    /// Load a WAV file. Supported formats are 8, 16, 24 and 32-bit PCM
+    /// and 32 and 64-bit float. Integer samples are normalized to the range -1...1.
+    /// Unlike `load`, this does not need the `files` feature.
+    ///
+    /// ### Example: Save And Load
+    /// ```no_run
+    /// use fundsp::hacker::*;
+    /// let wave = Wave64::render(44100.0, 1.0, &mut (sine_hz(440.0)));
+    /// wave.save_wav16("sine.wav").unwrap();
+    /// let loaded = Wave64::load_wav("sine.wav").unwrap();
+    /// assert_eq!(loaded.length(), wave.length());
+    /// ```
+    pub fn load_wav<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let mut file = File::open(path.as_ref())?;
+        Self::read_wav(&mut file)
+    }
 }
 
 /// Play back one channel of a wave.
+/// If the sample rate differs from the sample rate of the wave,
+/// then the wave is resampled with linear interpolation.
 /// - Output 0: wave
 #[duplicate_item(
     f48       Wave48       Wave48Player;
@@ -693,12 +847,17 @@ pub struct Wave48Player<T: Float> {
     wave: Arc<Wave48>,
     channel: usize,
     index: usize,
+    /// Fractional position between `index` and the next sample.
+    fraction: f64,
+    /// Wave samples per output sample.
+    step: f64,
     start_point: usize,
     end_point: usize,
     loop_point: Option<usize>,
     _marker: PhantomData<T>,
 }
 
+#[allow(clippy::unnecessary_cast)]
 #[duplicate_item(
     f48       Wave48       Wave48Player;
     [ f64 ]   [ Wave64 ]   [ Wave64Player ];
@@ -718,14 +877,33 @@ impl<T: Float> Wave48Player<T> {
             wave: wave.clone(),
             channel,
             index: start_point,
+            fraction: 0.0,
+            step: wave.sample_rate() / DEFAULT_SR,
             start_point,
             end_point,
             loop_point,
             _marker: PhantomData::default(),
         }
     }
+
+    /// Sample following `index` for interpolation.
+    #[inline]
+    fn next_sample(&self) -> f48 {
+        if self.index + 1 < self.end_point {
+            self.wave.at(self.channel, self.index + 1)
+        } else if let Some(point) = self.loop_point {
+            if point < self.end_point {
+                self.wave.at(self.channel, point)
+            } else {
+                0.0
+            }
+        } else {
+            0.0
+        }
+    }
 }
 
+#[allow(clippy::unnecessary_cast)]
 #[duplicate_item(
     f48       Wave48       Wave48Player;
     [ f64 ]   [ Wave64 ]   [ Wave64Player ];
@@ -740,6 +918,11 @@ impl<T: Float> AudioNode for Wave48Player<T> {
 
     fn reset(&mut self) {
         self.index = self.start_point;
+        self.fraction = 0.0;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.step = self.wave.sample_rate() / sample_rate;
     }
 
     #[inline]
@@ -748,11 +931,20 @@ impl<T: Float> AudioNode for Wave48Player<T> {
         _input: &Frame<Self::Sample, Self::Inputs>,
     ) -> Frame<Self::Sample, Self::Outputs> {
         if self.index < self.end_point {
-            let value = self.wave.at(self.channel, self.index);
-            self.index += 1;
-            if self.index == self.end_point {
+            let mut value = self.wave.at(self.channel, self.index);
+            if self.fraction > 0.0 {
+                value += (self.next_sample() - value) * self.fraction as f48;
+            }
+            self.fraction += self.step;
+            let whole = floor(self.fraction);
+            self.fraction -= whole;
+            self.index += whole as usize;
+            if self.index >= self.end_point {
                 if let Some(point) = self.loop_point {
-                    self.index = point;
+                    if point < self.end_point {
+                        self.index =
+                            point + (self.index - self.end_point) % (self.end_point - point);
+                    }
                 }
             }
             [convert(value)].into()
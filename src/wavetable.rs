@@ -8,6 +8,7 @@ use num_complex::Complex32;
 use rustfft::algorithm::Radix4;
 use rustfft::Fft;
 use rustfft::FftDirection;
+use std::sync::Arc;
 
 /// Interpolate between `a1` and `a2` taking previous (`a0`) and next (`a3`) points into account.
 /// Employs an optimal 4-point, 4th order interpolating polynomial for 4x oversampled signals.
@@ -71,10 +72,12 @@ where
     a.iter().map(|x| x.im * z).collect()
 }
 
+/// Bandlimited wavetable with a transposition table for each pitch range.
+/// The tables are immutable and reference counted, so clones share them.
 #[derive(Clone)]
 pub struct Wavetable {
     /// Frequency tables arranged in order of increasing frequency.
-    table: Vec<(f32, Vec<f32>)>,
+    table: Arc<Vec<(f32, Vec<f32>)>>,
 }
 
 impl Wavetable {
@@ -115,7 +118,14 @@ impl Wavetable {
         //    max_amplitude,
         //    total_size * 4
         //);
-        Wavetable { table }
+        Wavetable {
+            table: Arc::new(table),
+        }
+    }
+
+    /// Whether this wavetable shares its tables with `other`.
+    pub fn shares_tables(&self, other: &Wavetable) -> bool {
+        Arc::ptr_eq(&self.table, &other.table)
     }
 
     /// Read wave at the given phase (in 0...1).
@@ -201,6 +211,11 @@ where
             _marker: std::marker::PhantomData::default(),
         }
     }
+
+    /// Wavetable of the oscillator. Clones of the oscillator share the same table.
+    pub fn table(&self) -> &'a Wavetable {
+        self.table
+    }
 }
 
 impl<'a, T, N> AudioNode for WaveSynth<'a, T, N>
@@ -287,6 +302,11 @@ where
             _marker: std::marker::PhantomData::default(),
         }
     }
+
+    /// Wavetable of the oscillator. Clones of the oscillator share the same table.
+    pub fn table(&self) -> &'a Wavetable {
+        self.table
+    }
 }
 
 impl<'a, T> AudioNode for PhaseSynth<'a, T>
@@ -347,6 +367,143 @@ where
     }
 }
 
+/// Interpolation between neighboring wavetables when scanning a stack of wavetables.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ScanInterpolation {
+    /// Linear crossfade between the two nearest tables. Cheapest,
+    /// but the timbre changes direction abruptly at each table when scanning.
+    #[default]
+    Linear,
+    /// Catmull-Rom spline through the four nearest tables.
+    /// Timbre evolves smoothly through each table, which avoids grainy scanning
+    /// of sparse table sets at twice the cost.
+    Cubic,
+}
+
+/// Bandlimited wavetable synthesizer that scans a stack of wavetables.
+/// Scan position 0 plays the first table and 1 plays the last table;
+/// in between, neighboring tables are interpolated.
+/// Wavetables should have matching partial phases for smooth scanning.
+/// - Input 0: frequency in Hz.
+/// - Input 1: scan position in 0...1.
+/// - Output 0: audio.
+#[derive(Clone)]
+pub struct WaveScan<T: Float> {
+    tables: Vec<Wavetable>,
+    interpolation: ScanInterpolation,
+    /// Phase in 0...1.
+    phase: f32,
+    /// Initial phase in 0...1, seeded via pseudorandom phase system.
+    initial_phase: f32,
+    /// Previously used transposition table of each wavetable.
+    table_hint: Vec<usize>,
+    sample_rate: f32,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: Float> WaveScan<T> {
+    /// Create new scanning oscillator over `tables` (at least one) with `interpolation`.
+    /// The tables are shared with the originals.
+    pub fn new(sample_rate: f64, tables: &[Wavetable], interpolation: ScanInterpolation) -> Self {
+        assert!(!tables.is_empty());
+        Self {
+            tables: tables.to_vec(),
+            interpolation,
+            phase: 0.0,
+            initial_phase: 0.0,
+            table_hint: vec![0; tables.len()],
+            sample_rate: sample_rate as f32,
+            _marker: std::marker::PhantomData::default(),
+        }
+    }
+
+    /// Wavetables of the oscillator.
+    pub fn tables(&self) -> &[Wavetable] {
+        &self.tables
+    }
+
+    /// Interpolation between neighboring tables.
+    pub fn interpolation(&self) -> ScanInterpolation {
+        self.interpolation
+    }
+
+    /// Read table `i`, clamped to the stack, at the current phase.
+    #[inline]
+    fn read(&mut self, i: isize, frequency: f32) -> f32 {
+        let i = clamp(0, self.tables.len() as isize - 1, i) as usize;
+        let (output, hint) = self.tables[i].read(self.table_hint[i], frequency, self.phase);
+        self.table_hint[i] = hint;
+        output
+    }
+}
+
+impl<T: Float> AudioNode for WaveScan<T> {
+    const ID: u64 = 114;
+    type Sample = T;
+    type Inputs = numeric_array::typenum::U2;
+    type Outputs = numeric_array::typenum::U1;
+    type Setting = ();
+
+    fn reset(&mut self) {
+        self.phase = self.initial_phase;
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f64) {
+        self.sample_rate = sample_rate as f32;
+    }
+
+    fn set_hash(&mut self, hash: u64) {
+        self.initial_phase = super::hacker::rnd(hash as i64) as f32;
+        self.phase = self.initial_phase;
+    }
+
+    #[inline]
+    fn tick(
+        &mut self,
+        input: &Frame<Self::Sample, Self::Inputs>,
+    ) -> Frame<Self::Sample, Self::Outputs> {
+        let frequency = input[0].to_f32();
+        let delta = frequency / self.sample_rate;
+        self.phase += delta;
+        self.phase -= floor(self.phase);
+        let frequency = abs(frequency);
+        let last = self.tables.len() - 1;
+        if last == 0 {
+            return [convert(self.read(0, frequency))].into();
+        }
+        let position = clamp01(input[1].to_f32()) * last as f32;
+        let i = min(position as usize, last - 1);
+        let x = position - i as f32;
+        let i = i as isize;
+        let output = match self.interpolation {
+            ScanInterpolation::Linear => {
+                let y1 = self.read(i, frequency);
+                let y2 = self.read(i + 1, frequency);
+                y1 + (y2 - y1) * x
+            }
+            ScanInterpolation::Cubic => {
+                let y0 = self.read(i - 1, frequency);
+                let y1 = self.read(i, frequency);
+                let y2 = self.read(i + 1, frequency);
+                let y3 = self.read(i + 2, frequency);
+                spline(y0, y1, y2, y3, x)
+            }
+        };
+        [convert(output)].into()
+    }
+
+    fn route(&mut self, _input: &SignalFrame, _frequency: f64) -> SignalFrame {
+        let mut output = new_signal_frame(self.outputs());
+        output[0] = Signal::Latency(0.0);
+        output
+    }
+
+    fn heap_footprint(&self) -> usize {
+        self.tables.capacity() * std::mem::size_of::<Wavetable>()
+            + self.table_hint.capacity() * std::mem::size_of::<usize>()
+    }
+}
+
 lazy_static! {
     /// Saw wavetable.
     pub static ref SAW_TABLE: Wavetable = Wavetable::new(
@@ -231,6 +231,114 @@ fn test_basic() {
     sequencer.push(0.6, 0.7, Fade::Power, 0.01, 0.0, Box::new(noise() | mls()));
     check_wave(sequencer);
 
+    // Looping sequencer: an event at loop-relative time zero fires again on each loop.
+    let mut sequencer = Sequencer64::new(true, 2);
+    sequencer.set_loop(0.1, 0.3);
+    sequencer.push(0.1, 0.15, Fade::Smooth, 0.0, 0.0, Box::new(dc((1.0, 1.0))));
+    sequencer.push(
+        0.2,
+        0.35,
+        Fade::Smooth,
+        0.02,
+        0.02,
+        Box::new(noise() | sine_hz(440.0)),
+    );
+    check_wave(sequencer.clone());
+    let mut onsets = vec![];
+    let mut previous = 0.0;
+    for i in 0..44100 {
+        let (x, _) = sequencer.get_stereo();
+        if x == 1.0 && previous != 1.0 {
+            onsets.push(i);
+        }
+        previous = x;
+    }
+    assert!(onsets == vec![4410, 13230, 22050, 30870, 39690]);
+
+    // Swing: with 50% swing on 8th notes, off-beats are delayed by half an 8th note.
+    let mut sequencer = Sequencer64::new(false, 1);
+    sequencer.set_swing(0.1, 0.5);
+    for i in 0..4 {
+        let t = i as f64 * 0.1;
+        sequencer.push(t, t + 0.02, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+    }
+    let mut onsets = vec![];
+    let mut previous = 0.0;
+    for i in 0..22050 {
+        let x = sequencer.get_mono();
+        if x == 1.0 && previous != 1.0 {
+            onsets.push(i);
+        }
+        previous = x;
+    }
+    assert!(onsets == vec![0, 6615, 8820, 15435]);
+
+    // Humanization is deterministic for a fixed seed and stays within bounds.
+    let render_humanized = |seed: u64| {
+        let mut sequencer = Sequencer64::new(false, 1);
+        sequencer.set_seed(seed);
+        sequencer.humanize(0.01, 0.2);
+        for i in 0..8 {
+            let t = 0.1 + i as f64 * 0.1;
+            sequencer.push(t, t + 0.05, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+        }
+        let mut onsets = vec![];
+        let mut previous = 0.0;
+        for i in 0..44100 {
+            let x = sequencer.get_mono();
+            if x != 0.0 && previous == 0.0 {
+                onsets.push((i, x));
+            }
+            previous = x;
+        }
+        onsets
+    };
+    let onsets = render_humanized(1);
+    assert!(onsets == render_humanized(1));
+    assert!(onsets != render_humanized(2));
+    assert!(onsets.len() == 8);
+    for (k, &(i, x)) in onsets.iter().enumerate() {
+        let expected = 4410 * (k + 1);
+        assert!(i + 441 >= expected && i <= expected + 441);
+        assert!(x >= 0.8 && x <= 1.2);
+    }
+
+    // Jitter does not move events before time zero or the current time.
+    let mut sequencer = Sequencer64::new(false, 1);
+    sequencer.humanize(0.5, 0.0);
+    for _ in 0..16 {
+        let id = sequencer.push(0.0, 1.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+        assert!(sequencer.event(id).unwrap().start_time >= 0.0);
+    }
+    for _ in 0..44100 {
+        sequencer.get_mono();
+    }
+    let now = sequencer.time();
+    for _ in 0..16 {
+        let id = sequencer.push(now, now + 1.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+        assert!(sequencer.event(id).unwrap().start_time >= now);
+    }
+
+    // Unison spreads are determined by the graph hash.
+    let render_unison = |seed: u64| {
+        let mut graph = dc(220.0) >> unison::<U5, _>(0.2, sine());
+        graph.ping(false, AttoHash::new(seed));
+        (0..1000).map(|_| graph.get_mono()).collect::<Vec<f64>>()
+    };
+    assert!(render_unison(1) == render_unison(1));
+    assert!(render_unison(1) != render_unison(2));
+    let spread = |seed: u64| {
+        let mut voices = unison::<U5, _>(0.2, sine());
+        voices.ping(false, AttoHash::new(seed));
+        (0..5).map(|i| voices.detune(i)).collect::<Vec<f64>>()
+    };
+    assert!(spread(3) == spread(3));
+    assert!(spread(3) != spread(4));
+    assert!(spread(3).iter().all(|x| x.abs() <= 0.2));
+    for i in 0..5 {
+        assert!(spread(3)[i] == voice_spread(3, i as u64, 0.2));
+    }
+
     let mut net = Net64::new(0, 2);
     let id = net.push(Box::new(
         noise() >> moog_hz(1500.0, 0.8) | noise() >> moog_hz(500.0, 0.4),
@@ -255,6 +363,9 @@ fn test_basic() {
     check_wave(net);
 
     check_wave((noise() | envelope(|t| spline_noise(1, t * 10.0))) >> panner());
+    check_wave(
+        (noise() | envelope(|t| sin(t * 3.0) * 2.0) | envelope(|t| 1.0 + t)) >> spatial_panner(),
+    );
 
     // Wave filtering, tick vs. process rendering, node reseting.
     let input = Wave64::render(44100.0, 1.0, &mut (noise() | noise()));
@@ -270,6 +381,7 @@ fn test_basic() {
         &input,
         resonator_hz(440.0, 110.0) | resonator_hz(880.0, 110.0),
     );
+    check_wave_filter(&input, pinkpass() | (pinkpass() >> lowpole_hz(1000.0)));
 
     // Constants.
     let mut d = constant(1.0);
@@ -280,6 +392,15 @@ fn test_basic() {
     assert!(d.get_stereo() == (2.0, 3.0));
     assert!(d.get_mono() == 2.5);
 
+    // Frame conversions.
+    let frame: Frame<f64, U3> = (1.0, 2.0, 3.0).convert();
+    assert!(frame[0] == 1.0 && frame[1] == 2.0 && frame[2] == 3.0);
+    assert!(<(f64, f64, f64)>::from_frame(&frame) == (1.0, 2.0, 3.0));
+    assert!(try_frame::<f64, U3>(frame.as_slice()) == Some(frame));
+    assert!(try_frame::<f64, U2>(&[1.0, 2.0, 3.0]).is_none());
+    let array: [f64; 3] = frame.into_array().into();
+    assert!(array == [1.0, 2.0, 3.0]);
+
     // Random stuff.
     let c = constant((2.0, 3.0)) * dc((2.0, 3.0));
     let e = c >> (pass() | pass());
@@ -425,6 +546,30 @@ fn test_basic() {
         &mut (delay(5.0 / 44100.0))
     ));
 
+    // Test stereo delay: echoes arrive at the respective channel intervals.
+    let mut stereo = stereo_delay(100.0 / 44100.0, 150.0 / 44100.0, 0.5, 0.25);
+    let mut echoes = |x: (f64, f64), channel: usize| {
+        stereo.reset();
+        (0..400)
+            .filter_map(|i| {
+                let input = if i == 0 { x } else { (0.0, 0.0) };
+                let (l, r) = stereo.filter_stereo(input.0, input.1);
+                let y = if channel == 0 { l } else { r };
+                if y != 0.0 {
+                    Some((i, y))
+                } else {
+                    None
+                }
+            })
+            .take(2)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(echoes((1.0, 0.0), 0), vec![(100, 1.0), (200, 0.5)]);
+    assert_eq!(echoes((0.0, 1.0), 1), vec![(150, 1.0), (300, 0.5)]);
+    // Cross-feedback: the left echo reaches the right channel after a further right delay.
+    assert_eq!(echoes((1.0, 0.0), 1), vec![(250, 0.25), (350, 0.125)]);
+    assert_eq!(echoes((0.0, 1.0), 0), vec![(250, 0.25), (350, 0.125)]);
+
     // Test pseudorandom phase: generator outputs should diverge.
     assert!(outputs_diverge(
         &mut rnd,
@@ -545,3 +690,1782 @@ fn test_basic() {
     assert_eq!(inouts(!zero()), (0, 0)); //  A null unit. Stacking it with a graph modifies its sound subtly, as the hash is altered.
     assert_eq!(inouts(!-!!!--!!!-!!--!zero()), (0, 0)); // Hot-rodded null unit with a custom hash. Uses more electricity.
 }
+
+#[test]
+fn test_footprint() {
+    // The total footprint includes the delay line, which grows with delay length.
+    let short: Box<dyn AudioUnit64> = Box::new(delay(0.01));
+    let long: Box<dyn AudioUnit64> = Box::new(delay(1.0));
+    assert_eq!(short.footprint(), long.footprint());
+    assert!(short.total_footprint() > short.footprint());
+    let samples = round(DEFAULT_SR * 0.99) as usize;
+    assert!(
+        long.total_footprint() - short.total_footprint() >= samples * std::mem::size_of::<f64>()
+    );
+
+    // Heap allocations of children are counted recursively.
+    let chain: Box<dyn AudioUnit64> = Box::new(delay(1.0) >> lowpass_hz(1000.0, 1.0));
+    assert!(chain.total_footprint() >= long.total_footprint());
+    let mut net = Net64::wrap(Box::new(pass() & delay(1.0)));
+    assert!(net.total_footprint() > long.total_footprint());
+    net.allocate();
+    assert!(net.total_footprint() > net.footprint());
+
+    // Nodes that own heap memory report it.
+    let owners: [Box<dyn AudioUnit64>; 5] = [
+        Box::new(graphic_eq(10)),
+        Box::new(phaser_lfo(8, 0.5, 1.0, 0.5)),
+        Box::new(mixer::<U4>()),
+        Box::new(arpeggiator(ArpPattern::Up, 8.0).1),
+        Box::new(overs(1.0).1),
+    ];
+    for unit in owners {
+        assert!(unit.total_footprint() > unit.footprint());
+    }
+    let small: Box<dyn AudioUnit64> = Box::new(graphic_eq(10));
+    let large: Box<dyn AudioUnit64> = Box::new(graphic_eq(31));
+    assert!(large.total_footprint() > small.total_footprint());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_render_parallel() {
+    let voices = || -> Vec<Box<dyn AudioUnit64>> {
+        vec![
+            Box::new(saw_hz(110.0) >> lowpass_hz(2000.0, 1.0)),
+            Box::new(noise() >> resonator_hz(1000.0, 100.0)),
+            Box::new(square_hz(220.0) * 0.5),
+            Box::new(pink()),
+        ]
+    };
+    let mut serial = Wave64::silence(1, DEFAULT_SR, 1.0);
+    for mut voice in voices() {
+        let wave = Wave64::render(DEFAULT_SR, 1.0, voice.as_mut());
+        for i in 0..serial.len() {
+            serial.set(0, i, serial.at(0, i) + wave.at(0, i));
+        }
+    }
+    let parallel = Wave64::render_parallel(DEFAULT_SR, 1.0, &mut voices());
+    assert_eq!(parallel.len(), serial.len());
+    for i in 0..serial.len() {
+        assert_eq!(parallel.at(0, i), serial.at(0, i));
+    }
+}
+
+#[test]
+fn test_net_cycle() {
+    let mut net = Net64::new(1, 1);
+    let id1 = net.push(Box::new(pass()));
+    let id2 = net.push(Box::new(lowpass_hz(1000.0, 1.0)));
+
+    // A direct self-connection is a zero-delay cycle.
+    assert_eq!(net.try_connect(id1, 0, id1, 0), Err(NetError::Cycle));
+    assert_eq!(net.try_connect(id1, 0, id2, 0), Ok(()));
+    // So is a connection back from a downstream node.
+    assert_eq!(net.try_connect(id2, 0, id1, 0), Err(NetError::Cycle));
+    assert!(!net.has_cycle());
+    net.pipe_input(id1);
+    net.pipe_output(id2);
+    net.check();
+
+    // A loop that passes through the delay of a feedback unit is accepted.
+    let mut echo = Net64::wrap(Box::new(Feedback64::new(0.0, Box::new(net))));
+    assert!(!echo.has_cycle());
+    echo.check();
+    let mut plain = lowpass_hz(1000.0, 1.0);
+    // The first sample has not yet been fed back.
+    assert_eq!(echo.filter_mono(1.0), plain.filter_mono(1.0));
+    assert!(echo.filter_mono(0.0) != plain.filter_mono(0.0));
+}
+
+#[test]
+fn test_shared_tables() {
+    // Cloned oscillators share their wavetable.
+    let voice = saw();
+    let voices: Vec<_> = (0..16).map(|_| voice.clone()).collect();
+    for clone in voices.iter() {
+        assert!(clone.table().shares_tables(voice.table()));
+    }
+
+    // Clones copy the playback state of the original.
+    let mut voice = saw();
+    for _ in 0..1000 {
+        voice.filter_mono(110.0);
+    }
+    let mut clone = voice.clone();
+    for _ in 0..1000 {
+        assert_eq!(voice.filter_mono(110.0), clone.filter_mono(110.0));
+    }
+    // Resetting one of them leaves the other unaffected.
+    clone.reset();
+    let output: Vec<f64> = (0..100).map(|_| voice.filter_mono(110.0)).collect();
+    let reset: Vec<f64> = (0..100).map(|_| clone.filter_mono(110.0)).collect();
+    assert!(output != reset);
+
+    // So do clones of a custom wavetable.
+    let table = Wavetable::new(20.0, 20_000.0, 4.0, &|_| 0.0, &|_, i| 1.0 / (i * i) as f64);
+    let clone = table.clone();
+    assert!(clone.shares_tables(&table));
+    let other = Wavetable::new(20.0, 20_000.0, 4.0, &|_| 0.0, &|_, i| 1.0 / (i * i) as f64);
+    assert!(!other.shares_tables(&table));
+}
+
+#[test]
+fn test_dc_var() {
+    let value = shared(1.0);
+    let mut bus = dc_var::<U4>(&value);
+    assert_eq!(bus.outputs(), 4);
+    assert!(bus.tick(&Frame::default()).iter().all(|&x| x == 1.0));
+
+    value.set_value(-0.5);
+    assert!(bus.tick(&Frame::default()).iter().all(|&x| x == -0.5));
+
+    // Block processing and clones follow the same variable.
+    let control = bus.clone();
+    control.set_value(2.0);
+    let mut y = [[0.0; 64]; 4];
+    let [y0, y1, y2, y3] = &mut y;
+    bus.process(
+        64,
+        &[],
+        &mut [&mut y0[..], &mut y1[..], &mut y2[..], &mut y3[..]],
+    );
+    assert!(y.iter().flatten().all(|&x| x == 2.0));
+    assert_eq!(value.value(), 2.0);
+}
+
+#[test]
+fn test_var_tap() {
+    let level = shared(0.0);
+    let mut node = envelope(|t| t) >> var_tap(&level);
+    let mut last = 0.0;
+    for _ in 0..100 {
+        last = node.get_mono();
+    }
+    assert!(last > 0.0);
+    assert_eq!(level.value(), last);
+
+    // Block processing publishes the last sample of the block.
+    let mut tap = var_tap(&level);
+    let x: Vec<f64> = (0..64).map(|i| i as f64).collect();
+    let mut y = [0.0; 64];
+    tap.process(64, &[&x[..]], &mut [&mut y[..]]);
+    assert_eq!(&y[..], &x[..]);
+    assert_eq!(level.value(), 63.0);
+}
+
+#[test]
+fn test_var_smooth() {
+    let level = shared(0.0);
+    let mut node = var_smooth(&level, 0.01);
+    node.set_sample_rate(DEFAULT_SR);
+    for _ in 0..10 {
+        assert_eq!(node.get_mono(), 0.0);
+    }
+
+    // A step is approached exponentially with the time constant.
+    level.set_value(1.0);
+    let samples = (0.01 * DEFAULT_SR) as usize;
+    let mut last = 0.0;
+    for i in 1..=samples {
+        let x = node.get_mono();
+        assert!(x > last && x < 1.0);
+        let expected = 1.0 - exp(-(i as f64) / samples as f64);
+        assert!((x - expected).abs() < 1.0e-9);
+        last = x;
+    }
+    assert!((last - (1.0 - exp(-1.0))).abs() < 1.0e-9);
+
+    // Block processing matches ticking.
+    let mut tick = var_smooth(&level, 0.005);
+    tick.get_mono();
+    let mut block = tick.clone();
+    level.set_value(-1.0);
+    let mut y = [0.0; 64];
+    block.process(64, &[], &mut [&mut y[..]]);
+    for x in y {
+        assert_eq!(x, tick.get_mono());
+    }
+}
+
+#[test]
+fn test_pan_law() {
+    let weights = |law, position| {
+        let output = pan_law(law, position).tick(&[1.0].into());
+        (output[0], output[1])
+    };
+    for i in 0..=40 {
+        let position = i as f64 / 20.0 - 1.0;
+        let (left, right) = weights(PanLaw::ConstantPower, position);
+        assert!((left * left + right * right - 1.0).abs() < 1.0e-12);
+        let output = pan(position).tick(&[1.0].into());
+        assert_eq!((left, right), (output[0], output[1]));
+        let (left, right) = weights(PanLaw::Linear, position);
+        assert!((left + right - 1.0).abs() < 1.0e-12);
+    }
+    let center = |law| {
+        let (left, right) = weights(law, 0.0);
+        assert!((left - right).abs() < 1.0e-12);
+        amp_db(left)
+    };
+    assert!((center(PanLaw::Linear) + 6.02).abs() < 0.01);
+    assert!((center(PanLaw::ConstantPower) + 3.01).abs() < 0.01);
+    assert!((center(PanLaw::Compromise) + 4.52).abs() < 0.01);
+
+    // The pan input modulates at audio rate.
+    let mut node = panner_law(PanLaw::Linear);
+    assert_eq!(node.filter_stereo(1.0, -1.0), (1.0, 0.0));
+    assert_eq!(node.filter_stereo(1.0, 1.0), (0.0, 1.0));
+    assert_eq!(node.filter_stereo(1.0, 0.0), (0.5, 0.5));
+}
+
+#[test]
+fn test_spatial_pan() {
+    // Settled DC level of the left channel.
+    let level = |azimuth: f64, distance: f64| {
+        let mut node = spatial_pan(azimuth, distance);
+        let mut left = 0.0;
+        for _ in 0..1000 {
+            left = node.tick(&[1.0].into())[0];
+        }
+        left
+    };
+    // Level follows the inverse distance law beyond the reference distance.
+    let near = level(0.0, 2.0);
+    assert!((near - 0.5 / SQRT_2).abs() < 1.0e-6);
+    assert!((level(0.0, 4.0) / near - 0.5).abs() < 1.0e-6);
+    assert!((level(0.0, 16.0) / near - 0.125).abs() < 1.0e-6);
+    // Within the reference distance the gain does not grow further.
+    assert!((level(0.0, 0.5) - 1.0 / SQRT_2).abs() < 1.0e-6);
+
+    // A source on the right reaches the right ear first.
+    let mut node = spatial_pan(0.25 * PI, 1.0);
+    assert!(node.itd() > 16.0 && node.itd() < 17.0);
+    let mut first_left = None;
+    let mut first_right = None;
+    for i in 0..100 {
+        let output = node.tick(&[if i == 0 { 1.0 } else { 0.0 }].into());
+        let (left, right) = (output[0], output[1]);
+        if first_left.is_none() && left.abs() > 1.0e-3 {
+            first_left = Some(i);
+        }
+        if first_right.is_none() && right.abs() > 1.0e-3 {
+            first_right = Some(i);
+        }
+    }
+    assert_eq!(first_right, Some(0));
+    assert!(first_left.unwrap() >= 16);
+}
+
+#[test]
+fn test_binaural() {
+    // Index of the first sample reaching a tenth of the peak.
+    let onset = |response: &[f64]| {
+        let peak = response.iter().fold(0.0f64, |a, x| a.max(x.abs()));
+        response.iter().position(|x| x.abs() >= 0.1 * peak).unwrap()
+    };
+
+    // A hard left source reaches the left ear first.
+    let mut node = binaural(-0.5 * PI, 0.0);
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    for i in 0..200 {
+        let output = node.tick(&[if i == 0 { 1.0 } else { 0.0 }].into());
+        left.push(output[0]);
+        right.push(output[1]);
+    }
+    assert_eq!(onset(&left), 0);
+    assert!(onset(&right) > 20);
+    // The head shadows the far ear.
+    let energy = |x: &[f64]| x.iter().map(|x| x * x).sum::<f64>();
+    assert!(energy(&right) < energy(&left));
+
+    // Measured directions are reproduced exactly.
+    let set = HrirSet::spherical_head(DEFAULT_SR);
+    let mut l = vec![0.0; set.length()];
+    let mut r = vec![0.0; set.length()];
+    let hrir = &set.hrirs()[5];
+    set.interpolate(hrir.azimuth, hrir.elevation, &mut l, &mut r);
+    assert_eq!(l, hrir.left);
+    assert_eq!(r, hrir.right);
+
+    // Clones share the set, and direction changes crossfade without jumps.
+    let mut node = binaural_hrir(&set, 0.0, 0.0);
+    assert!(node.hrir_set().shares_hrirs(&set));
+    let mut previous = 0.0;
+    for i in 0..4410 {
+        if i == 2000 {
+            node.set((0.5 * PI, 0.0));
+        }
+        let y = node.tick(&[sin_hz(100.0, i as f64 / DEFAULT_SR)].into())[0];
+        if i > 200 {
+            assert!((y - previous).abs() < 0.05);
+        }
+        previous = y;
+    }
+}
+
+#[cfg(feature = "profiling")]
+#[test]
+fn test_profile() {
+    let mut net = Net64::new(1, 1);
+    let light = net.chain(Box::new(pass()));
+    let heavy = net.chain(Box::new(map(|i: &Frame<f64, U1>| {
+        let mut x = i[0];
+        for _ in 0..200 {
+            x = sin(x + 1.0);
+        }
+        x
+    })));
+    let cost = net.profile(0.1);
+    assert_eq!(cost.len(), 2);
+    assert!(cost[&heavy] > cost[&light]);
+    assert!(cost[&light] >= 0.0);
+}
+
+#[test]
+fn test_net_validate() {
+    // A valid directed acyclic graph.
+    let mut net = Net64::new(1, 1);
+    let id1 = net.push(Box::new(pass()));
+    let id2 = net.push(Box::new(lowpass_hz(1000.0, 1.0)));
+    let id3 = net.push(Box::new(pass() + pass()));
+    net.pipe_input(id1);
+    net.pipe(id1, id2);
+    net.connect(id1, 0, id3, 0);
+    net.connect(id2, 0, id3, 1);
+    net.pipe_output(id3);
+    net.check();
+    assert_eq!(net.validate(true), Ok(()));
+    // Unconnected inputs are reported only when asked.
+    net.disconnect(id3, 1);
+    net.check();
+    assert_eq!(net.validate(false), Ok(()));
+    assert_eq!(
+        net.validate(true),
+        Err(NetValidationError::Unconnected(id3, 1))
+    );
+    assert!(net.node_ids().all(|id| [id1, id2, id3].contains(&id)));
+
+    // A self-loop.
+    let mut net = Net64::new(1, 1);
+    let id = net.push(Box::new(pass()));
+    net.pipe(id, id);
+    net.pipe_output(id);
+    assert!(net.has_cycle());
+    assert_eq!(
+        net.validate(false),
+        Err(NetValidationError::Cycle(vec![id]))
+    );
+
+    // A two-node cycle downstream of another node.
+    let mut net = Net64::new(1, 1);
+    let id0 = net.chain(Box::new(pass()));
+    let id1 = net.push(Box::new(pass()));
+    let id2 = net.push(Box::new(pass() + pass()));
+    net.connect(id1, 0, id2, 0);
+    net.connect(id0, 0, id2, 1);
+    net.pipe(id2, id1);
+    net.pipe_output(id2);
+    let error = net.validate(false).unwrap_err();
+    assert!(
+        error == NetValidationError::Cycle(vec![id1, id2])
+            || error == NetValidationError::Cycle(vec![id2, id1])
+    );
+    assert!(error.to_string().contains("cycle"));
+}
+
+#[test]
+fn test_net_feedback_comb() {
+    // Feedback comb filter y[n] = x[n] + g * y[n - D], where the loop delay D
+    // is the delay line length plus one sample from the feedback connection.
+    let length = 10;
+    let g = 0.5;
+    let mut net = Net64::new(1, 1);
+    let id1 = net.push(Box::new(pass() + pass()));
+    let id2 = net.push(Box::new(delay(length as f64 / DEFAULT_SR) * g));
+    net.connect_input(0, id1, 0);
+    net.connect(id1, 0, id2, 0);
+    net.connect_feedback(id2, 0, id1, 1);
+    net.pipe_output(id1);
+    net.check();
+    assert_eq!(net.validate(true), Ok(()));
+    assert!(!net.has_cycle());
+
+    let expected = |n: usize| {
+        if n % (length + 1) == 0 {
+            g.powi((n / (length + 1)) as i32)
+        } else {
+            0.0
+        }
+    };
+
+    // Tick path.
+    let mut tick_net = net.clone();
+    for n in 0..200 {
+        let x = if n == 0 { 1.0 } else { 0.0 };
+        assert!((tick_net.filter_mono(x) - expected(n)).abs() < 1.0e-12);
+    }
+
+    // Block path.
+    let mut input = Wave64::new(1, DEFAULT_SR);
+    input.resize(200);
+    input.set(0, 0, 1.0);
+    let output = input.filter(200.0 / DEFAULT_SR, &mut net);
+    for n in 0..200 {
+        assert!((output.at(0, n) - expected(n)).abs() < 1.0e-12);
+    }
+}
+
+#[test]
+fn test_net_latency_compensation() {
+    // A plain path and an oversampled path with 32 samples of latency are summed.
+    let mut net = Net64::new(1, 1);
+    let id1 = net.push(Box::new(pass()));
+    let id2 = net.push(Box::new(oversample_unit(2, Box::new(pass()))));
+    let id3 = net.push(Box::new(pass() + pass()));
+    net.pipe_input(id1);
+    net.pipe_input(id2);
+    net.connect(id1, 0, id3, 0);
+    net.connect(id2, 0, id3, 1);
+    net.pipe_output(id3);
+    assert!(!net.latency_compensation());
+    assert_eq!(net.latency(), Some(0.0));
+    let mut reference = oversample_unit(2, Box::new(pass()));
+    assert!((net.filter_mono(1.0) - 1.0 - reference.filter_mono(1.0)).abs() < 1.0e-12);
+
+    net.set_latency_compensation(true);
+    net.reset();
+    reference.reset();
+    assert_eq!(net.latency(), Some(32.0));
+    let mut block_net = net.clone();
+
+    // The impulse from the plain path arrives together with the oversampled one.
+    let mut input = Wave64::new(1, DEFAULT_SR);
+    input.resize(200);
+    input.set(0, 0, 1.0);
+    let output = input.filter(200.0 / DEFAULT_SR, &mut block_net);
+    for n in 0..200 {
+        let x = if n == 0 { 1.0 } else { 0.0 };
+        let expected = reference.filter_mono(x) + if n == 32 { 1.0 } else { 0.0 };
+        assert!((net.filter_mono(x) - expected).abs() < 1.0e-12);
+        assert!((output.at(0, n) - expected).abs() < 1.0e-12);
+    }
+
+    // Compensation is removed when disabled.
+    net.set_latency_compensation(false);
+    net.reset();
+    reference.reset();
+    assert!((net.filter_mono(1.0) - 1.0 - reference.filter_mono(1.0)).abs() < 1.0e-12);
+}
+
+#[test]
+fn test_net_snapshot() {
+    let mut net = Net64::new(0, 1);
+    let osc = net.chain(Box::new(sine_hz(110.0)));
+    let snapshot = net.snapshot();
+    let mut original = net.clone();
+    original.reset();
+
+    // Edit the structure and the units, then undo.
+    net.chain(Box::new(lowpass_hz(500.0, 1.0)));
+    net.replace(osc, Box::new(saw_hz(220.0)));
+    assert_eq!(net.size(), 2);
+    net.restore(&snapshot, 0.0);
+    net.check();
+    assert_eq!(net.size(), 1);
+    assert_eq!(net.node(osc).get_id(), original.node(osc).get_id());
+    for _ in 0..100 {
+        assert_eq!(net.get_mono(), original.get_mono());
+    }
+
+    // Restoring live crossfades smoothly to the snapshot.
+    let mut net = Net64::new(0, 1);
+    let id = net.chain(Box::new(dc(1.0)));
+    let snapshot = net.snapshot();
+    let mut backend = net.backend();
+    net.replace(id, Box::new(dc(-1.0)));
+    net.commit();
+    assert_eq!(backend.get_mono(), -1.0);
+    net.restore(&snapshot, 0.01);
+    let mut previous = -1.0;
+    for _ in 0..1000 {
+        let x = backend.get_mono();
+        assert!(x >= previous && x - previous < 0.01);
+        previous = x;
+    }
+    assert_eq!(previous, 1.0);
+}
+
+#[test]
+fn test_net_bake() {
+    // A 441 Hz sine has a period of 100 samples at 44.1 kHz.
+    let mut net = Net64::new(0, 2);
+    let id = net.push(Box::new(sine_hz(441.0) | sine_hz(882.0)));
+    net.connect_output(id, 0, 0);
+    net.connect_output(id, 1, 1);
+    net.reset();
+    let live: Vec<(f64, f64)> = (0..100).map(|_| net.get_stereo()).collect();
+
+    let original = net.bake(id, 100);
+    assert_eq!(original.outputs(), 2);
+    net.check();
+    net.reset();
+    for i in 0..300 {
+        let (x, y) = net.get_stereo();
+        assert!((x - live[i % 100].0).abs() < 1.0e-9);
+        assert!((y - live[i % 100].1).abs() < 1.0e-9);
+    }
+    // The live sine continues seamlessly over the loop boundary.
+    let mut reference = original;
+    reference.reset();
+    for i in 0..300 {
+        assert!((reference.get_stereo().0 - live[i % 100].0).abs() < 1.0e-6);
+    }
+}
+
+#[test]
+fn test_net_output_gain() {
+    let mut net = Net64::new(0, 2);
+    net.chain(Box::new(dc((1.0, 1.0))));
+    net.check();
+    assert_eq!(net.output_gain(1), 1.0);
+    assert_eq!(net.get_stereo(), (1.0, 1.0));
+
+    net.set_output_gain(1, 0.5);
+    assert_eq!(net.output_gain(1), 0.5);
+    // The change is smoothed.
+    let (x, y) = net.get_stereo();
+    assert_eq!(x, 1.0);
+    assert!(y < 1.0 && y > 0.5);
+    let mut previous = y;
+    for _ in 0..(0.1 * DEFAULT_SR) as usize {
+        let (x, y) = net.get_stereo();
+        assert_eq!(x, 1.0);
+        assert!(y <= previous && y >= 0.5);
+        previous = y;
+    }
+    assert_eq!(previous, 0.5);
+
+    // Block processing and backends apply the gain as well.
+    let mut output = Buffer::<f64>::with_channels(2);
+    net.process(64, &[], output.self_mut());
+    assert!(output.at(1)[..64].iter().all(|&y| y == 0.5));
+    let mut backend = net.backend();
+    net.set_output_gain(0, 0.0);
+    net.commit();
+    for _ in 0..(0.1 * DEFAULT_SR) as usize {
+        backend.get_stereo();
+    }
+    assert_eq!(backend.get_stereo(), (0.0, 0.5));
+}
+
+#[test]
+fn test_net_output_gain_combined() {
+    let mut net1 = Net64::new(0, 1);
+    net1.chain(Box::new(dc(1.0)));
+    net1.set_output_gain(0, 0.5);
+    let mut net2 = Net64::new(0, 1);
+    net2.chain(Box::new(dc(2.0)));
+    net2.set_output_gain(0, 0.25);
+
+    // Each side keeps its gains in stacks and branches.
+    let mut output = Buffer::<f64>::with_channels(2);
+    for mut net in [net1.clone() | net2.clone(), net1.clone() ^ net2.clone()] {
+        net.check();
+        net.reset();
+        assert_eq!((net.output_gain(0), net.output_gain(1)), (0.5, 0.25));
+        assert_eq!(net.get_stereo(), (0.5, 0.5));
+        net.process(64, &[], output.self_mut());
+        assert!(output.at(0)[..64].iter().all(|&x| x == 0.5));
+        assert!(output.at(1)[..64].iter().all(|&x| x == 0.5));
+    }
+
+    // A pipe keeps the gains of the second network.
+    let mut net3 = Net64::wrap(Box::new(pass() | pass()));
+    net3.set_output_gain(1, 2.0);
+    let mut net = (net1.clone() | net2.clone()) >> net3;
+    net.check();
+    net.reset();
+    assert_eq!(net.output_gain(0), 1.0);
+    assert_eq!(net.get_stereo(), (1.0, 4.0));
+    net.process(64, &[], output.self_mut());
+    assert!(output.at(1)[..64].iter().all(|&x| x == 4.0));
+
+    // Sums start from unity gain.
+    let mut net = net1 & net2;
+    net.reset();
+    assert_eq!(net.output_gain(0), 1.0);
+    assert_eq!(net.get_mono(), 3.0);
+}
+
+#[test]
+fn test_stabilize() {
+    let mut node = stabilize(reverb_stereo(5.0, 0.5));
+    let mut energy = 0.0;
+    // Transient.
+    for i in 0..10 {
+        let x = if i == 0 { 1.0 } else { 0.0 };
+        let (l, r) = node.filter_stereo(x, x);
+        energy += l * l + r * r;
+    }
+    // Silence. The tail decays by more than 200 dB in 4 seconds.
+    for _ in 0..(4.0 * DEFAULT_SR) as usize {
+        let (l, r) = node.filter_stereo(0.0, 0.0);
+        energy += l * l + r * r;
+    }
+    assert!(energy > 0.0);
+    for _ in 0..(DEFAULT_SR as usize) {
+        assert_eq!(node.filter_stereo(0.0, 0.0), (0.0, 0.0));
+    }
+
+    // Block processing flushes the tail as well.
+    let mut node = stabilize(reverb_stereo(5.0, 0.5));
+    node.filter_stereo(1.0, 1.0);
+    let mut input = Buffer::<f64>::with_channels(2);
+    let mut output = Buffer::<f64>::with_channels(2);
+    let blocks = 5 * DEFAULT_SR as usize / MAX_BUFFER_SIZE;
+    for block in 0..blocks {
+        node.process(MAX_BUFFER_SIZE, input.self_ref(), output.self_mut());
+        if block * MAX_BUFFER_SIZE >= 4 * DEFAULT_SR as usize {
+            assert!(output.at(0).iter().all(|x| *x == 0.0));
+            assert!(output.at(1).iter().all(|x| *x == 0.0));
+        }
+    }
+}
+
+#[test]
+fn test_stabilize_inputs() {
+    // Control inputs reach the enclosed node untouched.
+    let mut node = stabilize(lowpass());
+    let mut reference = lowpass();
+    let mut rnd = Rnd::from_u64(1);
+    for _ in 0..10000 {
+        let input = Frame::from([rnd.f64() * 2.0 - 1.0, 1000.0, 1.0]);
+        assert_eq!(node.tick(&input), reference.tick(&input));
+    }
+    // A DC input passes through the lowpass.
+    for _ in 0..1000 {
+        node.tick(&Frame::from([1.0, 1000.0, 1.0]));
+    }
+    assert!((node.tick(&Frame::from([1.0, 1000.0, 1.0]))[0] - 1.0).abs() < 1.0e-6);
+
+    // The loop stabilizer removes DC from the fed-back signal only.
+    let mut node = feedback2(delay(0.01) * 0.9, loop_stabilizer());
+    let mut reference = feedback(delay(0.01) * 0.9);
+    for _ in 0..(5.0 * DEFAULT_SR) as usize {
+        node.filter_mono(1.0);
+        reference.filter_mono(1.0);
+    }
+    assert!((node.filter_mono(1.0) - 0.9).abs() < 1.0e-3);
+    assert!((reference.filter_mono(1.0) - 9.0).abs() < 1.0e-3);
+}
+
+#[test]
+fn test_block_rate_host_block() {
+    let mut reference = sine_hz(110.0) | saw_hz(55.0);
+    let mut adapter = BlockRateAdapter64::new(Box::new(sine_hz(110.0) | saw_hz(55.0)));
+    assert_eq!(adapter.host_block(), None);
+    adapter.set_host_block(Some(48));
+    assert_eq!(adapter.host_block(), Some(48));
+
+    let mut output = Buffer::<f64>::with_channels(2);
+    for _ in 0..100 {
+        adapter.process(48, &[], output.get_mut(2));
+        for i in 0..48 {
+            let (x, y) = reference.get_stereo();
+            assert!((output.at(0)[i] - x).abs() < 1.0e-9);
+            assert!((output.at(1)[i] - y).abs() < 1.0e-9);
+        }
+    }
+
+    // Block sizes that do not match the hint still produce a continuous signal.
+    for size in [1, 17, 48, 64, 5] {
+        adapter.process(size, &[], output.get_mut(2));
+        for i in 0..size {
+            let (x, y) = reference.get_stereo();
+            assert!((output.at(0)[i] - x).abs() < 1.0e-9);
+            assert!((output.at(1)[i] - y).abs() < 1.0e-9);
+        }
+    }
+}
+
+#[test]
+fn test_block_rate_input() {
+    // A pass-through unit reproduces its input delayed by one block.
+    let mut adapter = BlockRateAdapter64::new(Box::new(pass() | pass()));
+    assert_eq!(adapter.inputs(), 2);
+    assert_eq!(adapter.block_latency(), MAX_BUFFER_SIZE);
+    assert_eq!(adapter.latency(), Some(MAX_BUFFER_SIZE as f64));
+    let mut rnd = Rnd::from_u64(1);
+    let input: Vec<(f64, f64)> = (0..2000).map(|_| (rnd.f64(), rnd.f64())).collect();
+    let delayed = |i: usize| {
+        if i < MAX_BUFFER_SIZE {
+            (0.0, 0.0)
+        } else {
+            input[i - MAX_BUFFER_SIZE]
+        }
+    };
+    let mut i = 0;
+    let mut input_buffer = Buffer::<f64>::with_channels(2);
+    let mut output = Buffer::<f64>::with_channels(2);
+    for &size in [1, 17, 64, 5, 64, 30, 3].iter().cycle() {
+        if i + size > input.len() {
+            break;
+        }
+        if size == 1 {
+            // Mix single sample processing with block processing.
+            let (x, y) = adapter.filter_stereo(input[i].0, input[i].1);
+            assert_eq!((x, y), delayed(i));
+        } else {
+            for j in 0..size {
+                input_buffer.mut_at(0)[j] = input[i + j].0;
+                input_buffer.mut_at(1)[j] = input[i + j].1;
+            }
+            adapter.process(size, input_buffer.self_ref(), output.get_mut(2));
+            for j in 0..size {
+                assert_eq!((output.at(0)[j], output.at(1)[j]), delayed(i + j));
+            }
+        }
+        i += size;
+    }
+
+    // After a reset, the first block is silent again.
+    adapter.reset();
+    assert_eq!(adapter.filter_stereo(1.0, 1.0), (0.0, 0.0));
+}
+
+#[test]
+fn test_arpeggiator() {
+    let (notes, mut arp) = arpeggiator(ArpPattern::Up, 4.0);
+    notes.hold(64.0);
+    notes.hold(60.0);
+    notes.hold(67.0);
+    assert_eq!(notes.notes(), vec![64.0, 60.0, 67.0]);
+    let step = (DEFAULT_SR / 4.0) as usize;
+    for expected in [60.0, 64.0, 67.0, 60.0, 64.0, 67.0, 60.0] {
+        for _ in 0..step {
+            assert_eq!(arp.get_mono(), midi_hz(expected));
+        }
+    }
+
+    // Releasing a note takes effect at the next step. The step counter keeps running.
+    notes.release(60.0);
+    for expected in [67.0, 64.0, 67.0] {
+        for _ in 0..step {
+            assert_eq!(arp.get_mono(), midi_hz(expected));
+        }
+    }
+
+    let (notes, mut arp) = arpeggiator(ArpPattern::UpDown, 4.0);
+    notes.set_notes(&[67.0, 64.0, 60.0]);
+    for expected in [60.0, 64.0, 67.0, 64.0, 60.0, 64.0] {
+        for _ in 0..step {
+            assert_eq!(arp.get_mono(), midi_hz(expected));
+        }
+    }
+    notes.clear();
+    for _ in 0..step {
+        assert_eq!(arp.get_mono(), 0.0);
+    }
+}
+
+#[test]
+fn test_ramp() {
+    let mut node = ramp(-1.0, 3.0, 0.5);
+    let length = (0.5 * DEFAULT_SR) as usize;
+    assert_eq!(node.get_mono(), -1.0);
+    let mut previous = -1.0;
+    for i in 1..length {
+        let x = node.get_mono();
+        assert!(x > previous && x < 3.0);
+        assert!((x - (-1.0 + 4.0 * i as f64 / length as f64)).abs() < 1.0e-9);
+        previous = x;
+    }
+    // The ramp reaches its end value at the specified time and holds.
+    for _ in 0..1000 {
+        assert_eq!(node.get_mono(), 3.0);
+    }
+    node.reset();
+    assert_eq!(node.get_mono(), -1.0);
+
+    // Block processing matches.
+    let mut node = ramp(-1.0, 3.0, 0.5);
+    let mut reference = ramp(-1.0, 3.0, 0.5);
+    let mut output = [0.0; 64];
+    for _ in 0..length / 64 + 2 {
+        node.process(64, &[], &mut [&mut output[..]]);
+        for x in output.iter() {
+            assert_eq!(*x, reference.get_mono());
+        }
+    }
+}
+
+#[test]
+fn test_declick() {
+    let length = (0.01 * DEFAULT_SR) as usize;
+
+    let mut sequencer = Sequencer64::new(true, 1);
+    sequencer.set_declick(0.01);
+    sequencer.push(0.0, 10.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+    // There is no fade before the first reset.
+    assert_eq!(sequencer.get_mono(), 1.0);
+    for _ in 0..100 {
+        sequencer.get_mono();
+    }
+    sequencer.reset();
+    assert_eq!(sequencer.get_mono(), 0.0);
+    let mut previous = 0.0;
+    for _ in 1..length {
+        let x = sequencer.get_mono();
+        assert!(x > previous && x < 1.0);
+        previous = x;
+    }
+    assert_eq!(sequencer.get_mono(), 1.0);
+
+    // A sample rate change restarts the fade in block processing.
+    sequencer.set_sample_rate(48000.0);
+    let mut output = [0.0; 64];
+    sequencer.process(64, &[], &mut [&mut output[..]]);
+    assert_eq!(output[0], 0.0);
+    assert!(output.windows(2).all(|x| x[0] < x[1]));
+
+    let (_slot, mut backend) = Slot64::new(Box::new(dc(1.0)));
+    backend.set_declick(0.01);
+    assert_eq!(backend.get_mono(), 1.0);
+    backend.reset();
+    assert_eq!(backend.get_mono(), 0.0);
+    let mut previous = 0.0;
+    for _ in 1..length {
+        let x = backend.get_mono();
+        assert!(x > previous && x < 1.0);
+        previous = x;
+    }
+    assert_eq!(backend.get_mono(), 1.0);
+    backend.reset();
+    backend.process(64, &[], &mut [&mut output[..]]);
+    assert_eq!(output[0], 0.0);
+    assert!(output.windows(2).all(|x| x[0] < x[1]));
+}
+
+#[test]
+fn test_is_effect() {
+    assert!(reverb_stereo(10.0, 2.0).is_effect());
+    assert!(lowpass_hz(1000.0, 1.0).is_effect());
+    assert!(!sine_hz(440.0).is_effect());
+    assert!(!(pass() >> split::<U2>()).is_effect());
+    assert!(!sink().is_effect());
+    let net = Net64::wrap(Box::new(reverb_stereo(10.0, 2.0)));
+    assert!(net.is_effect());
+}
+
+#[test]
+fn test_tune() {
+    let mut node = tune(440.0);
+    assert!((node.filter_mono(69.0) - 440.0).abs() < 1.0e-9);
+    assert!((node.filter_mono(81.0) - 880.0).abs() < 1.0e-9);
+    assert!((node.filter_mono(60.0) - midi_hz(60.0)).abs() < 1.0e-9);
+
+    // Alternate reference pitch.
+    node.set(415.0);
+    assert!((node.filter_mono(69.0) - 415.0).abs() < 1.0e-9);
+    assert!((node.filter_mono(57.0) - 207.5).abs() < 1.0e-9);
+
+    // Quantization rounds to the nearest semitone.
+    let mut node = tune(440.0);
+    node.set_quantize(true);
+    assert!((node.filter_mono(69.4) - 440.0).abs() < 1.0e-9);
+    assert!((node.filter_mono(80.6) - 880.0).abs() < 1.0e-9);
+
+    // Stretched octaves are wider than 2:1.
+    let mut node = tune(440.0);
+    node.set_stretch(10.0);
+    assert!((node.stretch() - 10.0).abs() < 1.0e-9);
+    assert!((node.filter_mono(81.0) - 880.0 * exp2(10.0 / 1200.0)).abs() < 1.0e-9);
+}
+
+#[test]
+fn test_tuning() {
+    // Equal temperament agrees with the free functions.
+    let equal = Tuning::equal_temperament();
+    for note in [0.0, 21.0, 60.0, 69.0, 69.5, 100.25, 127.0] {
+        assert!((equal.midi_hz(note) / midi_hz(note) - 1.0).abs() < 1.0e-12);
+        assert!((hz_midi(midi_hz(note)) - note).abs() < 1.0e-9);
+    }
+
+    // Just intonation has pure intervals from the root.
+    let just = Tuning::just_intonation();
+    let c4 = just.midi_hz(60.0);
+    assert!((c4 - midi_hz(60.0)).abs() < 1.0e-9);
+    assert!((just.midi_hz(67.0) / c4 - 1.5).abs() < 1.0e-12);
+    assert!((just.midi_hz(64.0) / c4 - 1.25).abs() < 1.0e-12);
+    assert!((just.midi_hz(72.0) / c4 - 2.0).abs() < 1.0e-12);
+    // The pattern repeats in other octaves.
+    assert!((just.midi_hz(43.0) / just.midi_hz(36.0) - 1.5).abs() < 1.0e-12);
+
+    // Conversion back to notes is the inverse.
+    for note in [10.0, 35.5, 59.9, 60.0, 61.3, 71.99, 88.0] {
+        assert!((just.hz_midi(just.midi_hz(note)) - note).abs() < 1.0e-9);
+    }
+
+    // Custom tables may have any number of notes.
+    let ratios: Vec<f64> = (0..19).map(|i| exp2(i as f64 / 19.0)).collect();
+    let mut tet19 = Tuning::new(&ratios, 2.0, 60.0, 261.0);
+    assert!((tet19.midi_hz(79.0) - 522.0).abs() < 1.0e-9);
+    tet19.set_reference(69.0, 440.0);
+    assert!((tet19.midi_hz(50.0) - 220.0).abs() < 1.0e-9);
+}
+
+#[test]
+fn test_master() {
+    // Neutral settings pass the signal through.
+    let mut node = master(1.0, 0.0, 1.0);
+    assert_eq!(node.filter_stereo(0.3, -0.7), (0.3, -0.7));
+
+    // Zero width folds the image to mono.
+    let mut node = master(1.0, 0.0, 0.0);
+    for i in 0..100 {
+        let (l, r) = node.filter_stereo(sin(i as f64), cos(i as f64 * 0.7));
+        assert_eq!(l, r);
+    }
+
+    // Full left balance mutes the right channel.
+    let mut node = master(1.0, -1.0, 1.0);
+    let (l, r) = node.filter_stereo(0.5, 0.5);
+    assert_eq!((l, r), (0.5, 0.0));
+
+    // Gain changes are smoothed and block processing matches.
+    let mut node = master(1.0, 0.25, 1.5);
+    let mut reference = node.clone();
+    node.set((0.5, 0.25, 1.5));
+    reference.set((0.5, 0.25, 1.5));
+    let (l, _) = node.filter_stereo(1.0, 1.0);
+    assert!(l > 0.5 * 0.75 && l < 0.75);
+    reference.filter_stereo(1.0, 1.0);
+    let input = [[0.2; 64], [-0.4; 64]];
+    let mut output = [[0.0; 64]; 2];
+    let (left, right) = output.split_at_mut(1);
+    node.process(
+        64,
+        &[&input[0][..], &input[1][..]],
+        &mut [&mut left[0][..], &mut right[0][..]],
+    );
+    for (left, right) in output[0].iter().zip(output[1].iter()) {
+        let (l, r) = reference.filter_stereo(0.2, -0.4);
+        assert!((l - left).abs() < 1.0e-12 && (r - right).abs() < 1.0e-12);
+    }
+}
+
+#[test]
+fn test_midside() {
+    let mut rnd = Rnd::from_u64(1);
+    let mut encoder = midside();
+    let mut roundtrip = midside() >> sidemid();
+    let mut unity = width(1.0);
+    let mut mono = width(0.0);
+    let mut wide = width(2.0);
+    for _ in 0..1000 {
+        let (l, r) = (rnd.f64() * 2.0 - 1.0, rnd.f64() * 2.0 - 1.0);
+        let (m, s) = encoder.filter_stereo(l, r);
+        assert_eq!((m, s), ((l + r) * 0.5, (l - r) * 0.5));
+        // Decoding reconstructs the input.
+        let (x, y) = roundtrip.filter_stereo(l, r);
+        assert!((x - l).abs() < 1.0e-15 && (y - r).abs() < 1.0e-15);
+        // Unit width is an exact identity.
+        assert_eq!(unity.filter_stereo(l, r), (l, r));
+        let (x, y) = mono.filter_stereo(l, r);
+        assert!((x - m).abs() < 1.0e-15 && (y - m).abs() < 1.0e-15);
+        // Width scales the side channel only.
+        let (x, y) = wide.filter_stereo(l, r);
+        assert!(((x + y) * 0.5 - m).abs() < 1.0e-15);
+        assert!(((x - y) * 0.5 - 2.0 * s).abs() < 1.0e-15);
+    }
+
+    // Responses are linear.
+    let mut side = (pass() | zero()) >> midside() >> (sink() | pass());
+    assert!((side.response_db(0, 1000.0).unwrap() - amp_db(0.5)).abs() < 1.0e-9);
+    let mut roundtrip = (pass() | zero()) >> midside() >> sidemid() >> (pass() | sink());
+    assert!(roundtrip.response_db(0, 1000.0).unwrap().abs() < 1.0e-9);
+    let mut wide = (pass() | zero()) >> width(2.0) >> (sink() | pass());
+    assert!((wide.response_db(0, 1000.0).unwrap() - amp_db(0.5)).abs() < 1.0e-9);
+}
+
+#[test]
+fn test_big_block() {
+    // A large multichannel block is rendered identically to ticking.
+    let node = lowpass_hz(1000.0, 1.0) | highpass_hz(500.0, 2.0) | (tick() >> lowpole_hz(300.0));
+    let mut reference = node.clone();
+    let mut big = BigBlockAdapter64::new(Box::new(node));
+    let length = 100_000;
+    let mut rnd = Rnd::from_u64(1);
+    let input: Vec<Vec<f64>> = (0..3)
+        .map(|_| (0..length).map(|_| rnd.f64() - 0.5).collect())
+        .collect();
+    let mut output = vec![vec![0.0; length]; 3];
+    let input_refs: Vec<&[f64]> = input.iter().map(|x| &x[..]).collect();
+    let mut output_refs: Vec<&mut [f64]> = output.iter_mut().map(|x| &mut x[..]).collect();
+    big.process(length, &input_refs, &mut output_refs);
+    for i in 0..length {
+        let y = reference.tick(&Frame::from([input[0][i], input[1][i], input[2][i]]));
+        for channel in 0..3 {
+            assert!((y[channel] - output[channel][i]).abs() < 1.0e-12);
+        }
+    }
+}
+
+#[test]
+fn test_is_allocated() {
+    let mut adapter = BigBlockAdapter64::new(Box::new(sine_hz(110.0) >> lowpass_hz(1000.0, 1.0)));
+    assert!(!adapter.is_allocated());
+    adapter.allocate();
+    assert!(adapter.is_allocated());
+    // Allocating again does not reallocate.
+    let footprint = adapter.total_footprint();
+    adapter.allocate();
+    assert!(adapter.is_allocated());
+    assert_eq!(adapter.total_footprint(), footprint);
+
+    let mut net = Net64::new(0, 1);
+    let id = net.chain(Box::new(saw_hz(110.0) >> (pass() & tick())));
+    assert!(!net.is_allocated());
+    net.allocate();
+    assert!(net.is_allocated());
+    let footprint = net.total_footprint();
+    net.allocate();
+    assert_eq!(net.total_footprint(), footprint);
+    // Structural edits invalidate preallocation until the next call.
+    net.chain(Box::new(lowpole_hz(1000.0)));
+    assert!(!net.is_allocated());
+    net.allocate();
+    assert!(net.is_allocated());
+    net.remove(id);
+    net.allocate();
+    assert!(net.is_allocated());
+
+    // Units without buffers are always allocated.
+    assert!(sine().is_allocated());
+    // Pipes allocate their intermediate buffers on demand.
+    let mut unit = sine_hz(440.0);
+    assert!(!unit.is_allocated());
+    unit.allocate();
+    assert!(unit.is_allocated());
+}
+
+#[test]
+fn test_resampled() {
+    let mut node = Resampled64::new(22050.0, Box::new(pass()));
+    node.set_sample_rate(44100.0);
+    assert_eq!(node.inner_rate(), 22050.0);
+    let latency = node.latency().unwrap();
+    assert!(latency > 0.0);
+
+    // A 1 kHz tone passes through intact, delayed by the reported latency.
+    for n in 0..4000 {
+        let t = n as f64 / 44100.0;
+        let y = node.filter_mono(sin_hz(1000.0, t));
+        if n >= 1000 {
+            let expected = sin_hz(1000.0, t - latency / 44100.0);
+            assert!((y - expected).abs() < 1.0e-3);
+        }
+    }
+
+    // Tones above the internal Nyquist frequency are removed.
+    node.reset();
+    let mut peak: f64 = 0.0;
+    for n in 0..4000 {
+        let y = node.filter_mono(sin_hz(15000.0, n as f64 / 44100.0));
+        if n >= 1000 {
+            peak = peak.max(y.abs());
+        }
+    }
+    assert!(peak < 1.0e-2);
+}
+
+#[test]
+fn test_ambisonics() {
+    // A source straight ahead puts energy in W and X only.
+    let mut encoder = ambi_encode(0.0, 0.0);
+    let output = encoder.tick(&Frame::from([1.0]));
+    assert!((output[0] - sqrt(0.5)).abs() < 1.0e-12);
+    assert!((output[1] - 1.0).abs() < 1.0e-12);
+    assert!(output[2].abs() < 1.0e-12 && output[3].abs() < 1.0e-12);
+
+    // Sources to the right have negative Y, sources above have positive Z.
+    let mut encoder = ambi_encode(0.5 * PI, 0.0);
+    let output = encoder.tick(&Frame::from([1.0]));
+    assert!(output[1].abs() < 1.0e-12 && (output[2] + 1.0).abs() < 1.0e-12);
+    encoder.set((0.0, 0.5 * PI));
+    let output = encoder.tick(&Frame::from([1.0]));
+    assert!((output[3] - 1.0).abs() < 1.0e-12);
+
+    // Encoding gains propagate through routing.
+    let response = encoder.response(3, 1000.0).unwrap();
+    assert!((response.re - 1.0).abs() < 1.0e-12);
+
+    // Decoding to a square layout favors the front speakers and preserves total gain.
+    let layout = [
+        (-0.25 * PI, 0.0),
+        (0.25 * PI, 0.0),
+        (0.75 * PI, 0.0),
+        (-0.75 * PI, 0.0),
+    ];
+    let mut chain = ambi_encode(0.0, 0.0) >> ambi_decode::<U4>(&layout);
+    let output = chain.tick(&Frame::from([1.0]));
+    assert!((output[0] - output[1]).abs() < 1.0e-12);
+    assert!((output[2] - output[3]).abs() < 1.0e-12);
+    assert!(output[0] > 0.5 && output[2] < 0.0);
+    assert!((output.iter().sum::<f64>() - 1.0).abs() < 1.0e-12);
+    let response = chain.response(0, 1000.0).unwrap();
+    assert!((response.re - output[0]).abs() < 1.0e-12);
+}
+
+#[test]
+fn test_max_voices() {
+    let length = (0.05 * DEFAULT_SR) as usize;
+    for policy in [StealPolicy::Oldest, StealPolicy::Quietest] {
+        let mut sequencer = Sequencer64::new(false, 1);
+        sequencer.set_max_voices(2, policy);
+        assert_eq!(sequencer.max_voices(), Some((2, policy)));
+        // The first event is the oldest and the second is the quietest.
+        sequencer.push(0.0, 10.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(4.0)));
+        sequencer.push(0.01, 10.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+        sequencer.push(0.02, 10.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(2.0)));
+        let output: Vec<f64> = (0..length).map(|_| sequencer.get_mono()).collect();
+        let survivor = if policy == StealPolicy::Oldest {
+            1.0
+        } else {
+            4.0
+        };
+        assert_eq!(output[length - 1], survivor + 2.0);
+        // The stolen event is faded out, not cut.
+        assert!(output.iter().any(|x| *x > survivor + 2.0 && *x < 7.0));
+    }
+
+    // Without a limit, all events play.
+    let mut sequencer = Sequencer64::new(false, 1);
+    for i in 0..4 {
+        sequencer.push(0.0, 10.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(i as f64)));
+    }
+    assert_eq!(sequencer.get_mono(), 6.0);
+
+    // Block processing respects the limit.
+    sequencer.reset();
+    sequencer.set_max_voices(1, StealPolicy::Oldest);
+    sequencer.push(0.0, 10.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+    sequencer.push(0.01, 10.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(2.0)));
+    let mut output = [0.0; 64];
+    for _ in 0..length / 64 {
+        sequencer.process(64, &[], &mut [&mut output[..]]);
+    }
+    assert_eq!(output[63], 2.0);
+}
+
+#[test]
+fn test_net_share_tail() {
+    // Three voices mixed together, each ready to be sent through the same filter.
+    // Voices are played back from a wave because node hashes change when nodes are added.
+    let wave = std::sync::Arc::new(Wave64::render(
+        DEFAULT_SR,
+        0.1,
+        &mut (sine_hz(220.0) | sine_hz(330.0) * 0.5 | sine_hz(4400.0) * 0.25),
+    ));
+    let voice1 = || wave64(&wave, 0, None);
+    let voice2 = || wave64(&wave, 1, None);
+    let voice3 = || wave64(&wave, 2, None);
+    let mut net = Net64::new(0, 1);
+    let id1 = net.push(Box::new(voice1()));
+    let id2 = net.push(Box::new(voice2()));
+    let id3 = net.push(Box::new(voice3()));
+    let mix = net.push(Box::new(pass() + pass() + pass()));
+    net.connect(id1, 0, mix, 0);
+    net.connect(id2, 0, mix, 1);
+    net.connect(id3, 0, mix, 2);
+    net.pipe_output(mix);
+
+    let tail = net.share_tail(&[id1, id2, id3], Box::new(lowpass_hz(1000.0, 1.0)));
+    assert_eq!(net.node(tail).inputs(), 1);
+    // The tail and two adders were added.
+    assert_eq!(net.size(), 7);
+    net.check();
+    net.reset();
+
+    let mut reference = (voice1() + voice2() + voice3()) >> lowpass_hz(1000.0, 1.0);
+    for _ in 0..1000 {
+        let x = net.get_mono();
+        let y = reference.get_mono();
+        assert!((x - y).abs() < 1.0e-9);
+    }
+}
+
+#[test]
+fn test_net_report_latency() {
+    // A 100-sample delay on the output path.
+    let mut net = Net64::new(1, 1);
+    net.chain(Box::new(lowpass_hz(1000.0, 1.0)));
+    net.chain(Box::new(delay(100.0 / DEFAULT_SR)));
+    assert_eq!(net.report_latency(), 100.0);
+
+    // The longest output determines the overall latency.
+    let mut net = Net64::new(1, 2);
+    let dry = net.push(Box::new(pass()));
+    let wet = net.push(Box::new(delay(100.0 / DEFAULT_SR) >> tick()));
+    net.connect_input(0, dry, 0);
+    net.connect_input(0, wet, 0);
+    net.connect_output(dry, 0, 0);
+    net.connect_output(wet, 0, 1);
+    assert_eq!(net.report_latency(), 101.0);
+    net.disconnect_output(1);
+    assert_eq!(net.report_latency(), 0.0);
+
+    // Generators are sources of latency as well.
+    let mut net = Net64::new(0, 1);
+    net.chain(Box::new(sine_hz(440.0) >> delay(100.0 / DEFAULT_SR)));
+    assert_eq!(net.report_latency(), 100.0);
+}
+
+#[test]
+fn test_custom_unit() {
+    // Running sum of the left channel and a channel swap.
+    let mut sum = 0.0;
+    let mut unit = custom_unit(2, 3, move |input: &[f64], output: &mut [f64]| {
+        sum += input[0];
+        output[0] = sum;
+        output[1] = input[1];
+        output[2] = input[0];
+    });
+    assert_eq!(unit.inputs(), 2);
+    assert_eq!(unit.outputs(), 3);
+    let mut output = [0.0; 3];
+    unit.tick(&[1.0, 2.0], &mut output);
+    assert_eq!(output, [1.0, 2.0, 1.0]);
+    unit.tick(&[0.5, 3.0], &mut output);
+    assert_eq!(output, [1.5, 3.0, 0.5]);
+
+    // Reset restores captured state.
+    unit.reset();
+    unit.tick(&[1.0, 2.0], &mut output);
+    assert_eq!(output, [1.0, 2.0, 1.0]);
+
+    // Block processing matches ticking.
+    unit.reset();
+    let mut reference = unit.clone();
+    let mut rnd = Rnd::from_u64(1);
+    let mut input = Buffer::<f64>::with_channels(2);
+    for i in 0..MAX_BUFFER_SIZE {
+        input.mut_at(0)[i] = rnd.f64();
+        input.mut_at(1)[i] = rnd.f64();
+    }
+    let mut block = Buffer::<f64>::with_channels(3);
+    unit.process(MAX_BUFFER_SIZE, input.self_ref(), block.get_mut(3));
+    for i in 0..MAX_BUFFER_SIZE {
+        reference.tick(&[input.at(0)[i], input.at(1)[i]], &mut output);
+        for (channel, y) in output.iter().enumerate() {
+            assert_eq!(block.at(channel)[i], *y);
+        }
+    }
+
+    // Custom units can be placed in networks.
+    let mut net = Net64::new(2, 3);
+    let id = net.push(unit);
+    net.pipe_input(id);
+    net.pipe_output(id);
+    net.reset();
+    net.tick(&[1.0, 2.0], &mut output);
+    assert_eq!(output, [1.0, 2.0, 1.0]);
+}
+
+#[test]
+fn test_dry_wet() {
+    let mut rnd = Rnd::from_u64(1);
+    let input: Vec<f64> = (0..1000).map(|_| rnd.f64() * 2.0 - 1.0).collect();
+
+    // Amount 0 outputs the dry signal exactly and amount 1 the wet unit exactly.
+    let mut dry = dry_wet(0.0, Box::new(lowpass_hz(1000.0, 1.0)));
+    let mut wet = dry_wet(1.0, Box::new(lowpass_hz(1000.0, 1.0)));
+    let mut reference = lowpass_hz(1000.0, 1.0);
+    for x in input.iter() {
+        assert_eq!(dry.filter_mono(*x), *x);
+        assert_eq!(wet.filter_mono(*x), reference.filter_mono(*x));
+    }
+
+    // The dry path is delayed to match the latency of the wet unit.
+    let mut unit = dry_wet(0.0, Box::new(oversample_unit(2, Box::new(pass()))));
+    assert_eq!(unit.dry_latency(), 32);
+    assert_eq!(unit.latency(), Some(32.0));
+    for (i, x) in input.iter().enumerate() {
+        let y = unit.filter_mono(*x);
+        assert_eq!(y, if i >= 32 { input[i - 32] } else { 0.0 });
+    }
+    let mut unit = dry_wet(0.5, Box::new(oversample_unit(2, Box::new(pass()))));
+    assert_eq!(unit.latency(), Some(32.0));
+
+    // The dry delay follows the latency of the unit when the sample rate changes.
+    let mut unit = dry_wet(0.0, Box::new(limiter((0.01, 0.1))));
+    assert_eq!(unit.dry_latency(), 441);
+    unit.set_sample_rate(2.0 * DEFAULT_SR);
+    assert_eq!(unit.dry_latency(), 882);
+    assert_eq!(unit.latency(), Some(882.0));
+    for (i, x) in input.iter().enumerate() {
+        let y = unit.filter_mono(*x);
+        assert_eq!(y, if i >= 882 { input[i - 882] } else { 0.0 });
+    }
+
+    // A shared amount can be automated.
+    let amount = shared(0.0);
+    let mut unit = dry_wet_var(&amount, Box::new(mul(2.0)));
+    assert_eq!(unit.filter_mono(1.0), 1.0);
+    amount.set_value(1.0);
+    assert_eq!(unit.filter_mono(1.0), 2.0);
+    amount.set_value(0.25);
+    assert_eq!(unit.filter_mono(1.0), 1.25);
+
+    // The amount can be read from the last input.
+    let mut unit = dry_wet_input(Box::new(mul(2.0)));
+    assert_eq!(unit.inputs(), 2);
+    assert_eq!(unit.outputs(), 1);
+    let mut output = [0.0];
+    unit.tick(&[1.0, 0.0], &mut output);
+    assert_eq!(output[0], 1.0);
+    unit.tick(&[1.0, 1.0], &mut output);
+    assert_eq!(output[0], 2.0);
+    unit.tick(&[1.0, 0.5], &mut output);
+    assert_eq!(output[0], 1.5);
+
+    // Block processing matches ticking.
+    let mut unit = dry_wet_input(Box::new(oversample_unit(2, Box::new(pass()))));
+    let mut reference = unit.clone();
+    let mut block = Buffer::<f64>::with_channels(2);
+    for (i, x) in input.iter().enumerate().take(MAX_BUFFER_SIZE) {
+        block.mut_at(0)[i] = *x;
+        block.mut_at(1)[i] = rnd.f64();
+    }
+    let mut output = Buffer::<f64>::with_channels(1);
+    unit.process(MAX_BUFFER_SIZE, block.self_ref(), output.get_mut(1));
+    for i in 0..MAX_BUFFER_SIZE {
+        let mut y = [0.0];
+        reference.tick(&[block.at(0)[i], block.at(1)[i]], &mut y);
+        assert_eq!(output.at(0)[i], y[0]);
+    }
+}
+
+#[test]
+fn test_process_interleaved() {
+    let node = lowpass_hz(1000.0, 1.0) | (highpass_hz(500.0, 2.0) ^ mul(0.5));
+    let mut reference = node.clone();
+    let mut unit = node;
+    let length = 1000;
+    let mut rnd = Rnd::from_u64(1);
+    let input: Vec<f64> = (0..length * 2).map(|_| rnd.f64() - 0.5).collect();
+    let mut output = vec![0.0; length * 3];
+
+    // Interleaved calls of arbitrary size.
+    let mut i = 0;
+    while i < length {
+        let n = min(length - i, rnd.u64() as usize % 200);
+        unit.process_interleaved(
+            n,
+            &input[i * 2..(i + n) * 2],
+            &mut output[i * 3..(i + n) * 3],
+        );
+        i += n;
+    }
+
+    // Per-channel block processing.
+    let mut left = vec![0.0; MAX_BUFFER_SIZE];
+    let mut right = vec![0.0; MAX_BUFFER_SIZE];
+    let mut result = vec![vec![0.0; MAX_BUFFER_SIZE]; 3];
+    for i in (0..length).step_by(MAX_BUFFER_SIZE) {
+        let n = min(length - i, MAX_BUFFER_SIZE);
+        for (j, frame) in input[i * 2..(i + n) * 2].chunks(2).enumerate() {
+            left[j] = frame[0];
+            right[j] = frame[1];
+        }
+        let mut result_refs: Vec<&mut [f64]> = result.iter_mut().map(|x| &mut x[..]).collect();
+        reference.process(n, &[&left[..], &right[..]], &mut result_refs);
+        for (channel, buffer) in result.iter().enumerate() {
+            for (j, y) in buffer[..n].iter().enumerate() {
+                assert!((y - output[(i + j) * 3 + channel]).abs() < 1.0e-12);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "files")]
+#[test]
+fn test_wav_roundtrip() {
+    let mut node = sine_hz(1000.0) * 0.5 | sine_hz(2500.0) * 0.25;
+    let wave = Wave64::render(DEFAULT_SR, 0.5, &mut node);
+    let path = std::env::temp_dir().join("fundsp_test_wav_roundtrip.wav");
+    wave.save_wav16(&path).unwrap();
+    let loaded = Wave64::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.channels(), 2);
+    assert_eq!(loaded.len(), wave.len());
+    assert_eq!(loaded.sample_rate(), DEFAULT_SR);
+
+    // Samples are quantized to 16 bits.
+    for channel in 0..2 {
+        for i in 0..wave.len() {
+            assert!((loaded.at(channel, i) - wave.at(channel, i)).abs() < 1.0e-4);
+        }
+    }
+
+    // The spectral peak of each channel is at the frequency of its sine.
+    let peak_frequency = |channel: usize| {
+        let mut peak = (0.0, 0.0);
+        for f in (100..5000).step_by(10) {
+            let f = f as f64;
+            let (mut s, mut c) = (0.0, 0.0);
+            for i in 0..4410 {
+                let phase = i as f64 * f / DEFAULT_SR * TAU;
+                s += loaded.at(channel, i) * sin(phase);
+                c += loaded.at(channel, i) * cos(phase);
+            }
+            let energy = s * s + c * c;
+            if energy > peak.0 {
+                peak = (energy, f);
+            }
+        }
+        peak.1
+    };
+    assert_eq!(peak_frequency(0), 1000.0);
+    assert_eq!(peak_frequency(1), 2500.0);
+}
+
+/// Mono 24-bit PCM WAV file with the given samples.
+fn wav24(sample_rate: u32, samples: &[i32]) -> Vec<u8> {
+    let data = samples.len() as u32 * 3;
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(data + 36).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * 3).to_le_bytes());
+    bytes.extend_from_slice(&3u16.to_le_bytes());
+    bytes.extend_from_slice(&24u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes()[..3]);
+    }
+    bytes
+}
+
+#[test]
+fn test_wave_player() {
+    let wave = Wave64::render(DEFAULT_SR, 0.1, &mut (sine_hz(440.0) | saw_hz(110.0) * 0.5));
+
+    // 32-bit float data is read exactly.
+    let mut bytes = Vec::new();
+    wave.write_wav32(&mut bytes).unwrap();
+    let loaded = Wave64::read_wav(&mut &bytes[..]).unwrap();
+    assert_eq!(loaded.channels(), 2);
+    assert_eq!(loaded.len(), wave.len());
+    assert_eq!(loaded.sample_rate(), DEFAULT_SR);
+    for channel in 0..2 {
+        for i in 0..wave.len() {
+            assert_eq!(loaded.at(channel, i), wave.at(channel, i) as f32 as f64);
+        }
+    }
+
+    // 16-bit data is normalized.
+    let path = std::env::temp_dir().join("fundsp_test_wave_player.wav");
+    wave.save_wav16(&path).unwrap();
+    let loaded = Wave64::load_wav(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    for channel in 0..2 {
+        for i in 0..wave.len() {
+            assert!((loaded.at(channel, i) - wave.at(channel, i)).abs() < 1.0e-4);
+        }
+    }
+
+    // 24-bit data.
+    let bytes = wav24(48000, &[0, 0x400000, -0x800000, 0x7fffff]);
+    let wave24 = Wave64::read_wav(&mut &bytes[..]).unwrap();
+    assert_eq!(wave24.sample_rate(), 48000.0);
+    assert_eq!(wave24.len(), 4);
+    assert_eq!(wave24.at(0, 0), 0.0);
+    assert_eq!(wave24.at(0, 1), 0.5);
+    assert_eq!(wave24.at(0, 2), -1.0);
+    assert!(wave24.at(0, 3) < 1.0 && wave24.at(0, 3) > 0.9999);
+    assert!(Wave64::read_wav(&mut &bytes[..20]).is_err());
+
+    // At a matching sample rate, the player reproduces the wave sample-accurately.
+    let loaded = std::sync::Arc::new(loaded);
+    let mut player = waveplayer(&loaded, 1, 0, loaded.len(), false);
+    for i in 0..loaded.len() {
+        assert_eq!(player.get_mono(), loaded.at(1, i));
+    }
+    assert_eq!(player.get_mono(), 0.0);
+
+    // Looping jumps back to the start point, and reset rewinds there.
+    let mut player = waveplayer(&loaded, 0, 100, 200, true);
+    for i in 0..250 {
+        assert_eq!(player.get_mono(), loaded.at(0, 100 + i % 100));
+    }
+    player.reset();
+    assert_eq!(player.get_mono(), loaded.at(0, 100));
+
+    // At a different sample rate, the wave is interpolated linearly.
+    let ramp: Vec<f64> = (0..10).map(|i| i as f64).collect();
+    let ramp = std::sync::Arc::new(Wave64::from_samples(22050.0, &ramp));
+    let mut player = waveplayer(&ramp, 0, 0, ramp.len(), false);
+    player.set_sample_rate(44100.0);
+    for i in 0..18 {
+        assert_eq!(player.get_mono(), i as f64 * 0.5);
+    }
+}
+
+#[test]
+fn test_sequencer_render() {
+    let mut sequencer = Sequencer64::new(false, 2);
+    sequencer.push(0.25, 0.5, Fade::Smooth, 0.0, 0.0, Box::new(dc((1.0, 2.0))));
+    // An event that never ends is cut off at the end of the render.
+    sequencer.push(
+        0.75,
+        f64::INFINITY,
+        Fade::Smooth,
+        0.0,
+        0.0,
+        Box::new(dc((0.5, 0.5))),
+    );
+    let wave = sequencer.render(1.0, 44100.0);
+    assert_eq!(wave.channels(), 2);
+    assert_eq!(wave.length(), 44100);
+    assert_eq!(wave.sample_rate(), 44100.0);
+    for i in 0..wave.length() {
+        let expected = if (11025..22050).contains(&i) {
+            (1.0, 2.0)
+        } else if i >= 33075 {
+            (0.5, 0.5)
+        } else {
+            (0.0, 0.0)
+        };
+        assert_eq!((wave.at(0, i), wave.at(1, i)), expected);
+    }
+    assert!((sequencer.time() - 1.0).abs() < 1.0e-9);
+}
+
+#[test]
+fn test_sequencer_events() {
+    let mut sequencer = Sequencer64::new(false, 1);
+    let a = sequencer.push(0.0, 0.5, Fade::Smooth, 0.1, 0.2, Box::new(dc(1.0)));
+    let b = sequencer.push(0.25, 1.0, Fade::Power, 0.0, 0.1, Box::new(dc(1.0)));
+    let c = sequencer.push_duration(2.0, 1.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+    let mut events: Vec<EventInfo64> = sequencer.active_events().collect();
+    events.sort_by(|x, y| x.start_time.total_cmp(&y.start_time));
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].id, a);
+    assert_eq!(events[0].fade_in, 0.1);
+    assert_eq!(events[0].fade_out, 0.2);
+    assert_eq!((events[1].start_time, events[1].end_time), (0.25, 1.0));
+    assert_eq!(events[1].fade_ease, Fade::Power);
+    assert_eq!((events[2].start_time, events[2].end_time), (2.0, 3.0));
+
+    // Edits are reflected and ended events are no longer reported.
+    sequencer.edit(c, 2.5, 0.1);
+    assert_eq!(sequencer.event(c).map(|x| x.end_time), Some(2.5));
+    sequencer.render(0.75, 44100.0);
+    assert!(sequencer.event(a).is_none());
+    assert!(sequencer.event(b).is_some());
+    assert_eq!(sequencer.active_events().count(), 2);
+
+    // Frontends keep track of events they have pushed.
+    let mut sequencer = Sequencer64::new(false, 1);
+    let a = sequencer.push(0.0, 0.5, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+    let mut backend = sequencer.backend();
+    let b = sequencer.push(1.0, 2.0, Fade::Smooth, 0.0, 0.5, Box::new(dc(1.0)));
+    assert_eq!(sequencer.active_events().count(), 2);
+    for _ in 0..441 {
+        backend.get_mono();
+    }
+    let c = sequencer.push_relative(0.5, 1.0, Fade::Smooth, 0.0, 0.0, Box::new(dc(1.0)));
+    let info = sequencer.event(c).unwrap();
+    assert!((info.start_time - 0.51).abs() < 1.0e-9);
+    assert!((info.end_time - 1.01).abs() < 1.0e-9);
+    sequencer.edit(b, 1.5, 0.25);
+    assert_eq!(sequencer.event(b).map(|x| x.end_time), Some(1.5));
+    for _ in 0..30870 {
+        backend.get_mono();
+    }
+    assert!(sequencer.event(a).is_none());
+    assert_eq!(sequencer.active_events().count(), 2);
+    for _ in 0..44100 {
+        backend.get_mono();
+    }
+    assert_eq!(sequencer.active_events().count(), 0);
+}
+
+#[test]
+fn test_snoop_meter() {
+    let (mut snoop, mut backend) = snoop(1024);
+    assert_eq!(snoop.rms(100), 0.0);
+    assert_eq!(snoop.peak(), 0.0);
+
+    // A sine wave with a period of 100 samples.
+    for i in 0..2048 {
+        backend.filter_mono(0.5 * sin_hz(441.0, i as f64 / DEFAULT_SR));
+    }
+    snoop.update();
+    assert!((snoop.rms(1000) - 0.5 / sqrt(2.0)).abs() < 1.0e-9);
+    // The window is limited to the buffered samples.
+    assert_eq!(snoop.rms(1_000_000), snoop.rms(1024));
+    assert!((snoop.peak() - 0.5).abs() < 1.0e-9);
+
+    // The held peak decays per sample.
+    let held = snoop.peak_hold(0.999);
+    assert!(held <= 0.5 && held > 0.45);
+    for _ in 0..640 {
+        backend.filter_mono(0.0);
+    }
+    snoop.update();
+    assert!((snoop.peak_hold(0.999) - held * pow(0.999, 640.0)).abs() < 1.0e-12);
+    assert_eq!(snoop.rms(640), 0.0);
+}
+
+#[test]
+fn test_delay_var() {
+    let mut rnd = Rnd::from_u64(1);
+    let input: Vec<f64> = (0..1000).map(|_| rnd.f64() - 0.5).collect();
+
+    // A static delay of N samples reproduces the input delayed by N samples.
+    for n in [1, 2, 10, 441] {
+        let mut node = delay_var(0.01);
+        let time = n as f64 / DEFAULT_SR;
+        for (i, x) in input.iter().enumerate() {
+            let y = node.tick(&[*x, time].into())[0];
+            let expected = if i >= n { input[i - n] } else { 0.0 };
+            assert!((y - expected).abs() < 1.0e-9);
+        }
+    }
+
+    // Delay time is clamped to the maximum.
+    let mut node = delay_var(100.0 / DEFAULT_SR);
+    for (i, x) in input.iter().enumerate() {
+        let y = node.tick(&[*x, 1.0].into())[0];
+        let expected = if i >= 100 { input[i - 100] } else { 0.0 };
+        assert!((y - expected).abs() < 1.0e-9);
+    }
+}
+
+#[test]
+fn test_pluck_var() {
+    // Deviation in cents of the spectral peak of `x` from `f` Hz, with a precision of 0.5 cents.
+    let detune = |x: &[f64], f: f64| -> f64 {
+        let n = x.len() as f64;
+        let mut best = (0.0, 0.0);
+        for k in -40..=40 {
+            let cents = k as f64 * 0.5;
+            let w = f * exp2(cents / 1200.0) * TAU / DEFAULT_SR;
+            let (mut re, mut im) = (0.0, 0.0);
+            for (i, y) in x.iter().enumerate() {
+                let y = y * (0.5 - 0.5 * cos(TAU * i as f64 / n));
+                re += y * cos(w * i as f64);
+                im -= y * sin(w * i as f64);
+            }
+            let m = re * re + im * im;
+            if m > best.0 {
+                best = (m, cents);
+            }
+        }
+        best.1
+    };
+
+    for f in [110.0, 261.63, 440.0, 987.0] {
+        let mut node = dc((f, 0.5, 1.0)) >> pluck_var(50.0, 0.5);
+        let x: Vec<f64> = (0..8820).map(|_| node.get_mono()).collect();
+        assert!(x.iter().any(|&y| y != 0.0));
+        assert!(detune(&x, f).abs() <= 3.0);
+    }
+
+    // The string is retuned continuously and silent until plucked.
+    let mut node = pluck_var(50.0, 0.5);
+    assert_eq!(node.tick(&[220.0, 0.5, 0.0].into())[0], 0.0);
+    for _ in 0..2000 {
+        node.tick(&[220.0, 0.5, 1.0].into());
+    }
+    let x: Vec<f64> = (0..8820)
+        .map(|_| node.tick(&[330.0, 0.5, 1.0].into())[0])
+        .collect();
+    assert!(detune(&x, 330.0).abs() <= 3.0);
+}
+
+#[test]
+fn test_net_crossfade() {
+    let mut net = Net64::new(0, 1);
+    let osc = net.chain(Box::new(sine_hz(110.0)));
+    let gain = net.chain(Box::new(mul(0.5)));
+    assert!(net.node_ids().eq([osc, gain]));
+    // The reference keeps running the original chain.
+    let mut reference = net.clone();
+    let mut previous = 0.0;
+    for _ in 0..100 {
+        previous = net.get_mono();
+        assert_eq!(previous, reference.get_mono());
+    }
+
+    // Replacing the gain node keeps the oscillator running.
+    net.replace(gain, Box::new(mul(0.5)));
+    for _ in 0..100 {
+        previous = net.get_mono();
+        assert_eq!(previous, reference.get_mono());
+    }
+
+    // Crossfading is continuous and ends with the new unit.
+    let footprint = net.total_footprint();
+    net.crossfade(gain, Fade::Smooth, 0.01, Box::new(mul(2.0)));
+    for i in 0..1000 {
+        let x = net.get_mono();
+        let y = reference.get_mono();
+        assert!((x - previous).abs() < 0.05);
+        if i >= 441 {
+            assert_eq!(x, 4.0 * y);
+        }
+        previous = x;
+    }
+    // The faded out unit is dropped once the crossfade is complete.
+    assert_eq!(net.total_footprint(), footprint);
+
+    // Block processing crossfades as well.
+    net.crossfade(gain, Fade::Power, 0.01, Box::new(mul(0.5)));
+    let mut output = Buffer::<f64>::with_channels(1);
+    for block in 0..16 {
+        net.process(64, &[], output.self_mut());
+        for (i, &x) in output.at(0)[..64].iter().enumerate() {
+            let y = reference.get_mono();
+            assert!((x - previous).abs() < 0.05);
+            // Block processing of the oscillator may differ in rounding from ticking.
+            if block * 64 + i >= 441 {
+                assert!((x - y).abs() < 1.0e-12);
+            }
+            previous = x;
+        }
+    }
+    assert_eq!(net.size(), 2);
+    net.check();
+
+    // With a backend, the crossfade starts from the running unit.
+    let mut net = Net64::new(0, 1);
+    net.chain(Box::new(sine_hz(110.0)));
+    let filter = net.chain(Box::new(lowpass_hz(1000.0, 1.0)));
+    let mut backend = net.backend();
+    let mut previous = 0.0;
+    for _ in 0..1000 {
+        previous = backend.get_mono();
+    }
+    net.crossfade(
+        filter,
+        Fade::Smooth,
+        0.01,
+        Box::new(lowpass_hz(2000.0, 1.0)),
+    );
+    net.commit();
+    for _ in 0..1000 {
+        let x = backend.get_mono();
+        assert!((x - previous).abs() < 0.05);
+        previous = x;
+    }
+}
@@ -68,3 +68,295 @@ fn test_dynamics() {
         assert_eq!(x2, s1.value());
     }
 }
+
+#[test]
+fn test_mixer() {
+    let mut mix = mixer::<U3>();
+    let control = mix.clone();
+    mix.reset();
+    let input: Frame<f64, U3> = [1.0, 2.0, 4.0].into();
+    // Run long enough for the smoothed gains to settle.
+    let settle = |mix: &mut An<Mixer<U3, f64>>| {
+        let mut value = 0.0;
+        for _ in 0..8820 {
+            value = mix.tick(&input)[0];
+        }
+        value
+    };
+    assert!((settle(&mut mix) - 7.0).abs() < 1.0e-6);
+
+    // Soloing one input silences the others.
+    control.set_solo(1, true);
+    assert!((settle(&mut mix) - 2.0).abs() < 1.0e-6);
+    // Mute overrides solo.
+    control.set_mute(1, true);
+    assert!(settle(&mut mix).abs() < 1.0e-6);
+    control.set_mute(1, false);
+
+    // Unsoloing restores the others.
+    control.set_solo(1, false);
+    assert!((settle(&mut mix) - 7.0).abs() < 1.0e-6);
+
+    // Transitions are smoothed.
+    control.set_mute(2, true);
+    let first = mix.tick(&input)[0];
+    assert!(first > 6.0 && first < 7.0);
+    assert!((settle(&mut mix) - 3.0).abs() < 1.0e-6);
+
+    // Block processing agrees with the settled gains.
+    control.set_gain(0, 0.5);
+    let mut block = mix.clone();
+    let x0 = [1.0; 64];
+    let x1 = [2.0; 64];
+    let x2 = [4.0; 64];
+    let mut y = [0.0; 64];
+    for _ in 0..200 {
+        block.process(64, &[&x0[..], &x1[..], &x2[..]], &mut [&mut y[..]]);
+    }
+    assert!((y[63] - 2.5).abs() < 1.0e-6);
+}
+
+#[test]
+fn test_join_power() {
+    fn rms(unit: &mut dyn AudioUnit64) -> f64 {
+        let n = 44100;
+        let mut sum = 0.0;
+        for _ in 0..n {
+            let x = unit.get_mono();
+            sum += x * x;
+        }
+        (sum / n as f64).sqrt()
+    }
+
+    // Independent noises keep their level as more are summed.
+    let level1 = rms(&mut (noise() >> join_power::<U1>()));
+    let level2 = rms(&mut ((noise() | noise()) >> join_power::<U2>()));
+    let level4 = rms(&mut ((noise() | noise() | noise() | noise()) >> join_power::<U4>()));
+    let mut eight = (noise() | noise() | noise() | noise() | noise() | noise() | noise() | noise())
+        >> join_power::<U8>();
+    let level8 = rms(&mut eight);
+    for level in [level2, level4, level8] {
+        assert!((amp_db(level) - amp_db(level1)).abs() < 0.5);
+    }
+
+    // Identical inputs gain sqrt(N), and the reported response agrees.
+    let mut identical = split::<U4>() >> join_power::<U4>();
+    assert!((identical.filter_mono(1.0) - 2.0).abs() < 1.0e-9);
+    let response = identical.response(0, 1000.0).unwrap();
+    assert!((response.norm() - 2.0).abs() < 1.0e-9);
+}
+
+#[test]
+fn test_overs() {
+    let (meter, mut node) = overs(0.9);
+    assert_eq!(meter.count(), 0);
+    assert_eq!(meter.since_last(), None);
+
+    // Five peaks exceed the threshold, one sits exactly at it.
+    let mut input = vec![0.5; 1000];
+    for &i in [10, 200, 201, 500, 900].iter() {
+        input[i] = if i % 2 == 0 { 1.2 } else { -1.0 };
+    }
+    input[700] = 0.9;
+    for &x in input.iter() {
+        assert_eq!(node.filter_mono(x), x);
+    }
+    assert_eq!(meter.count(), 5);
+    let since = meter.since_last().unwrap();
+    assert!((since - 99.0 / DEFAULT_SR).abs() < 1.0e-12);
+
+    // Block processing counts the same.
+    node.reset();
+    assert_eq!(meter.count(), 0);
+    let mut output = vec![0.0; 1000];
+    for (x, y) in input.chunks(64).zip(output.chunks_mut(64)) {
+        node.process(x.len(), &[x], &mut [y]);
+    }
+    assert_eq!(output, input);
+    assert_eq!(meter.count(), 5);
+}
+
+#[test]
+fn test_compressor_gain() {
+    let (threshold, ratio, knee) = (-20.0, 4.0, 10.0);
+    let hard = |level: f64| compressor_gain(level, threshold, ratio, 0.0);
+    let soft = |level: f64| compressor_gain(level, threshold, ratio, knee);
+
+    // Below the knee both curves leave the signal alone.
+    assert_eq!(hard(-40.0), 0.0);
+    assert_eq!(soft(-40.0), 0.0);
+    // Above the knee both follow the ratio.
+    assert!((hard(0.0) - -15.0).abs() < 1.0e-12);
+    assert!((soft(0.0) - -15.0).abs() < 1.0e-12);
+
+    // The soft knee meets the hard knee curve at threshold +- knee / 2.
+    let lower = threshold - knee / 2.0;
+    let upper = threshold + knee / 2.0;
+    assert!((soft(lower) - hard(lower)).abs() < 1.0e-12);
+    assert!((soft(upper) - hard(upper)).abs() < 1.0e-12);
+    assert!((soft(upper) - (1.0 / ratio - 1.0) * knee / 2.0).abs() < 1.0e-12);
+
+    // Inside the knee the soft curve starts compressing earlier and stays below the hard curve.
+    assert_eq!(hard(threshold), 0.0);
+    assert!((soft(threshold) - (1.0 / ratio - 1.0) * knee / 8.0).abs() < 1.0e-12);
+    let mut level = lower;
+    while level <= upper {
+        assert!(soft(level) <= hard(level) + 1.0e-12);
+        assert!(soft(level + 0.5) <= soft(level));
+        level += 0.5;
+    }
+}
+
+#[test]
+fn test_limiter_lookahead() {
+    let mut node = limiter((0.01, 0.1));
+    let lookahead = node.lookahead_samples();
+    assert_eq!(lookahead, 441);
+    assert_eq!(node.latency(), Some(lookahead as f64));
+
+    // A sharp transient after silence must be limited without overshoot.
+    let length = 4 * lookahead;
+    let onset = 2 * lookahead;
+    let mut peak: f64 = 0.0;
+    let mut first = None;
+    for i in 0..length {
+        let x = if i >= onset && i < onset + 4 {
+            10.0
+        } else {
+            0.0
+        };
+        let y = node.filter_mono(x);
+        assert!(y.abs() <= 1.0);
+        if y != 0.0 && first.is_none() {
+            first = Some(i);
+        }
+        peak = peak.max(y.abs());
+    }
+    // The transient arrives after the look-ahead delay, reduced but audible.
+    assert_eq!(first, Some(onset + lookahead));
+    assert!(peak > 0.5);
+}
+
+#[test]
+fn test_limiter_stereo() {
+    let mut node = limiter_stereo((0.005, 0.05));
+    // The look-ahead follows the sample rate.
+    node.set_sample_rate(96000.0);
+    assert_eq!(node.lookahead_samples(), 480);
+    assert_eq!(node.latency(), Some(480.0));
+    node.allocate();
+    assert!(node.is_allocated());
+
+    // Channels are linked: the louder channel sets the gain of both.
+    let mut rnd = Rnd::from_u64(1);
+    for i in 0..20000 {
+        let level = if i % 5000 < 2500 { 0.5 } else { 8.0 };
+        let x = (rnd.f64() - 0.5) * level;
+        let (l, r) = node.filter_stereo(x, x * 0.25);
+        assert!(l.abs() <= 1.0 && r.abs() <= 1.0);
+        assert!((l * 0.25 - r).abs() < 1.0e-12);
+    }
+}
+
+#[test]
+fn test_clipper() {
+    let mut hard = clipper(0.5);
+    assert_eq!(hard.filter_mono(0.25), 0.25);
+    assert_eq!(hard.filter_mono(-0.5), -0.5);
+    assert_eq!(hard.filter_mono(0.8), 0.5);
+    assert_eq!(hard.filter_mono(-3.0), -0.5);
+    assert!(hard.response(0, 1000.0).is_none());
+    hard.set(0.8);
+    assert_eq!(hard.filter_mono(1.0), 0.8);
+
+    let mut soft = clipper_soft(1.0, 0.2);
+    assert_eq!(soft.filter_mono(0.7), 0.7);
+    assert_eq!(soft.filter_mono(-0.8), -0.8);
+    assert_eq!(soft.filter_mono(1.5), 1.0);
+    assert_eq!(soft.filter_mono(-1.2), -1.0);
+    // The knee is monotonic and stays below the threshold.
+    let mut previous = 0.8;
+    for i in 1..40 {
+        let y = soft.filter_mono(0.8 + i as f64 * 0.01);
+        assert!(y > previous && y < 1.0);
+        previous = y;
+    }
+}
+
+#[test]
+fn test_adaptive_window() {
+    // With a low target level, `tanh` is nearly linear and the output gain
+    // is the target level divided by the estimated RMS level.
+    let level = 1.0e-3;
+    let mut node = shape(Shape::AdaptiveTanhWindow(10.0, level));
+    node.set_sample_rate(44100.0);
+    node.reset();
+    for _ in 0..44100 {
+        node.filter_mono(1.0);
+    }
+    // The estimate moves 63% of the way to the new level in one window of 441 samples.
+    let x = 0.25;
+    let estimate = |y: f64| squared(level * x / y);
+    let distance = |y: f64| (estimate(y) - x * x) / (1.0 - x * x);
+    for i in 1..=882 {
+        let y = node.filter_mono(x);
+        if i == 220 {
+            assert!(distance(y) > 0.5);
+        } else if i == 441 {
+            assert!((distance(y) - exp(-1.0)).abs() < 0.01);
+        } else if i == 882 {
+            assert!((distance(y) - exp(-2.0)).abs() < 0.01);
+        }
+    }
+
+    // The window is equivalent to a halfway timescale of ln(2) windows.
+    let mut window = shape(Shape::AdaptiveTanhWindow(10.0, 0.5));
+    let mut timescale = shape(Shape::AdaptiveTanh(0.01 * LN_2, 0.5));
+    let mut rnd = Rnd::from_u64(1);
+    for _ in 0..1000 {
+        let x = rnd.f64() - 0.5;
+        assert!((window.filter_mono(x) - timescale.filter_mono(x)).abs() < 1.0e-6);
+    }
+}
+
+#[test]
+fn test_compressor() {
+    let (threshold, ratio) = (-20.0, 4.0);
+    let mut node = compressor_meter(threshold, ratio, 0.005, 0.05);
+    // A full scale sine wave has an RMS level of about -3 dB.
+    let level = amp_db(sqrt(0.5));
+    let expected = (threshold - level) * (1.0 - 1.0 / ratio);
+    let mut gain = 0.0;
+    for i in 0..44100 {
+        let x = sin_hz(1000.0, i as f64 / DEFAULT_SR);
+        let output = node.tick(&[x].into());
+        let (y, g) = (output[0], output[1]);
+        assert!(g <= 0.0);
+        assert!((y - x * db_amp(g)).abs() < 1.0e-12);
+        gain = g;
+    }
+    // Steady state gain reduction follows the ratio.
+    assert!((gain - expected).abs() < 0.1);
+    assert!((node.gain_db() - gain).abs() < 1.0e-12);
+
+    // Signals below threshold are left alone.
+    node.reset();
+    assert_eq!(node.gain_db(), 0.0);
+    for i in 0..44100 {
+        let x = 0.01 * sin_hz(1000.0, i as f64 / DEFAULT_SR);
+        let output = node.tick(&[x].into());
+        let (y, g) = (output[0], output[1]);
+        assert_eq!(g, 0.0);
+        assert_eq!(y, x);
+    }
+
+    // Stereo detection is linked: the louder channel sets the gain of both.
+    let mut node = compressor_stereo(threshold, ratio, 0.005, 0.05);
+    let mut rnd = Rnd::from_u64(1);
+    for _ in 0..20000 {
+        let x = rnd.f64() - 0.5;
+        let (l, r) = node.filter_stereo(x, x * 0.1);
+        assert!((l * 0.1 - r).abs() < 1.0e-12);
+    }
+    assert!(node.gain_db() < 0.0);
+}